@@ -0,0 +1,5 @@
+// Rebuild when migrations change so `sqlx::migrate!()` embeds new files.
+fn main() {
+    println!("cargo:rerun-if-changed=migrations");
+    println!("cargo:rerun-if-changed=migrations_sqlite");
+}
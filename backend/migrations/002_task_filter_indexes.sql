@@ -0,0 +1,2 @@
+-- Support range filters on updated_at alongside the existing idx_tasks_* indexes
+CREATE INDEX idx_tasks_updated_at ON tasks(updated_at);
@@ -0,0 +1,27 @@
+-- Full-text search over task titles and descriptions
+ALTER TABLE tasks ADD COLUMN search_vector tsvector;
+
+-- Titles rank above descriptions
+CREATE OR REPLACE FUNCTION update_tasks_search_vector()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.search_vector =
+        setweight(to_tsvector('english', COALESCE(NEW.title, '')), 'A') ||
+        setweight(to_tsvector('english', COALESCE(NEW.description, '')), 'B');
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER update_tasks_search_vector
+    BEFORE INSERT OR UPDATE OF title, description ON tasks
+    FOR EACH ROW
+    EXECUTE FUNCTION update_tasks_search_vector();
+
+-- Backfill existing rows without touching their updated_at timestamps
+ALTER TABLE tasks DISABLE TRIGGER update_tasks_updated_at;
+UPDATE tasks SET search_vector =
+    setweight(to_tsvector('english', COALESCE(title, '')), 'A') ||
+    setweight(to_tsvector('english', COALESCE(description, '')), 'B');
+ALTER TABLE tasks ENABLE TRIGGER update_tasks_updated_at;
+
+CREATE INDEX idx_tasks_search_vector ON tasks USING GIN (search_vector);
@@ -0,0 +1,3 @@
+-- Optimistic concurrency: every update bumps the version, and writes carrying
+-- an If-Match for an older version are rejected
+ALTER TABLE tasks ADD COLUMN version BIGINT NOT NULL DEFAULT 1;
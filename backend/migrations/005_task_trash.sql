@@ -0,0 +1,5 @@
+-- Soft delete: trashed tasks keep their row until restored or purged
+ALTER TABLE tasks ADD COLUMN deleted_at TIMESTAMPTZ;
+
+-- Serves the trash listing and the auto-purge sweep
+CREATE INDEX idx_tasks_deleted_at ON tasks(deleted_at) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,16 @@
+-- Change history: one row per created, deleted or restored task and per field
+-- changed by an update, written in the same transaction as the change
+CREATE TYPE task_event_kind AS ENUM ('Created', 'Updated', 'Deleted', 'Restored');
+
+CREATE TABLE task_events (
+    id BIGSERIAL PRIMARY KEY,
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    kind task_event_kind NOT NULL,
+    field VARCHAR(50),
+    old_value TEXT,
+    new_value TEXT,
+    actor VARCHAR(255),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_task_events_task_id ON task_events(task_id, id);
@@ -0,0 +1,10 @@
+-- Team members tasks can be assigned to; deleting one unassigns their tasks
+CREATE TABLE members (
+    id UUID PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE tasks ADD COLUMN assignee_id UUID REFERENCES members(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_tasks_assignee_id ON tasks(assignee_id);
@@ -0,0 +1,14 @@
+-- Projects group tasks into separate boards; existing tasks move into a
+-- default project that cannot be deleted
+CREATE TABLE projects (
+    id UUID PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO projects (id, name) VALUES ('00000000-0000-0000-0000-000000000001', 'Default');
+
+ALTER TABLE tasks ADD COLUMN project_id UUID NOT NULL
+    DEFAULT '00000000-0000-0000-0000-000000000001' REFERENCES projects(id);
+
+CREATE INDEX idx_tasks_project_id ON tasks(project_id);
@@ -0,0 +1,5 @@
+-- Subtasks point at their parent; purging a parent makes its subtasks
+-- top-level tasks
+ALTER TABLE tasks ADD COLUMN parent_id UUID REFERENCES tasks(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_tasks_parent_id ON tasks(parent_id);
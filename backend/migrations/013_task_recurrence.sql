@@ -0,0 +1,3 @@
+-- Recurrence rule of a repeating task, carried on from each occurrence to
+-- the next
+ALTER TABLE tasks ADD COLUMN recurrence TEXT;
@@ -0,0 +1,21 @@
+-- Manual order of the cards in each board column. Ranks compare as plain
+-- bytes, so the column skips locale-aware collation.
+ALTER TABLE tasks ADD COLUMN rank TEXT COLLATE "C" NOT NULL DEFAULT '';
+
+-- Existing cards keep the order the board showed them in: highest priority
+-- first, then newest. Backfilled without touching updated_at.
+ALTER TABLE tasks DISABLE TRIGGER update_tasks_updated_at;
+UPDATE tasks SET rank = ranked.rank
+FROM (
+    SELECT id, lpad(row_number() OVER (
+        PARTITION BY project_id, status
+        ORDER BY priority DESC, created_at DESC
+    )::text, 8, '0') || 'V' AS rank
+    FROM tasks
+) AS ranked
+WHERE tasks.id = ranked.id;
+ALTER TABLE tasks ENABLE TRIGGER update_tasks_updated_at;
+
+ALTER TABLE tasks ALTER COLUMN rank DROP DEFAULT;
+
+CREATE INDEX idx_tasks_column_rank ON tasks(project_id, status, rank);
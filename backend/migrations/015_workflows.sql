@@ -0,0 +1,31 @@
+-- Per-project workflows replace the fixed task_status enum. Each project
+-- lists its own states in board order; the category tells the server what a
+-- state means for blockers, subtasks and recurrence.
+CREATE TYPE status_category AS ENUM ('Todo', 'Doing', 'Done');
+
+CREATE TABLE workflow_states (
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    category status_category NOT NULL,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (project_id, name)
+);
+
+-- Every existing project starts with the statuses tasks already use
+INSERT INTO workflow_states (project_id, name, category, position)
+SELECT projects.id, states.name, states.category::status_category, states.position
+FROM projects CROSS JOIN (VALUES
+    ('Todo', 'Todo', 0),
+    ('InProgress', 'Doing', 1),
+    ('Completed', 'Done', 2),
+    ('Backlog', 'Todo', 3)
+) AS states(name, category, position);
+
+ALTER TABLE tasks ALTER COLUMN status DROP DEFAULT;
+ALTER TABLE tasks ALTER COLUMN status TYPE VARCHAR(50) USING status::text;
+
+-- A task's status must be a state of its own project's workflow
+ALTER TABLE tasks ADD CONSTRAINT tasks_workflow_state_fkey
+    FOREIGN KEY (project_id, status) REFERENCES workflow_states(project_id, name);
+
+DROP TYPE task_status;
@@ -0,0 +1,2 @@
+-- Work-in-progress limit of a board column; NULL for no limit
+ALTER TABLE workflow_states ADD COLUMN wip_limit INTEGER CHECK (wip_limit > 0);
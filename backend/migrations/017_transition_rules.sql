@@ -0,0 +1,12 @@
+-- The states a task may move to from each state, NULL for any, and the
+-- fields a task needs before it can enter a state
+ALTER TABLE workflow_states ADD COLUMN next_states TEXT[];
+ALTER TABLE workflow_states ADD COLUMN required_fields TEXT[] NOT NULL DEFAULT '{}';
+
+-- The fields a task needs before it can take a priority
+CREATE TABLE priority_rules (
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    priority task_priority NOT NULL,
+    required_fields TEXT[] NOT NULL,
+    PRIMARY KEY (project_id, priority)
+);
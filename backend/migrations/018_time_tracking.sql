@@ -0,0 +1,18 @@
+-- Effort estimates and the time members spend on tasks. An entry without an
+-- end is a running timer, and each member has at most one running. Entries
+-- go when their task is purged and outlive the member who recorded them.
+ALTER TABLE tasks ADD COLUMN estimate_minutes INTEGER CHECK (estimate_minutes >= 0);
+
+CREATE TABLE time_entries (
+    id UUID PRIMARY KEY,
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    member_id UUID REFERENCES members(id) ON DELETE SET NULL,
+    started_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    ended_at TIMESTAMP WITH TIME ZONE CHECK (ended_at >= started_at),
+    note TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_time_entries_task_id ON time_entries(task_id, started_at);
+
+CREATE UNIQUE INDEX idx_time_entries_running ON time_entries(member_id) WHERE ended_at IS NULL;
@@ -0,0 +1,16 @@
+-- Files attached to tasks. The content lives in the blob store under its
+-- SHA-256, shared by every attachment with the same bytes; rows go when
+-- their task is purged, and the purge reports the blobs left unused.
+CREATE TABLE attachments (
+    id UUID PRIMARY KEY,
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    filename VARCHAR(255) NOT NULL,
+    content_type VARCHAR(255) NOT NULL,
+    size_bytes BIGINT NOT NULL CHECK (size_bytes >= 0),
+    sha256 CHAR(64) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_attachments_task_id ON attachments(task_id, created_at);
+
+CREATE INDEX idx_attachments_sha256 ON attachments(sha256);
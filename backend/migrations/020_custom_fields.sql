@@ -0,0 +1,21 @@
+-- Extra fields each project defines for its tasks. Values live on the task
+-- as a JSON object keyed by field id and are checked against the field's
+-- type on every write; deleting a field removes its values.
+CREATE TYPE custom_field_type AS ENUM (
+    'Text', 'Number', 'Date', 'SingleSelect', 'MultiSelect', 'Checkbox'
+);
+
+CREATE TABLE custom_fields (
+    id UUID PRIMARY KEY,
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    field_type custom_field_type NOT NULL,
+    options TEXT[] NOT NULL DEFAULT '{}',
+    position INTEGER NOT NULL,
+    UNIQUE (project_id, name)
+);
+
+ALTER TABLE tasks ADD COLUMN custom_fields JSONB NOT NULL DEFAULT '{}';
+
+-- Serves containment filters on any field
+CREATE INDEX idx_tasks_custom_fields ON tasks USING GIN (custom_fields);
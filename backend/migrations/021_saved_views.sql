@@ -0,0 +1,15 @@
+-- Named board setups shared by everyone. The filter is kept as the JSON the
+-- API takes, and a view of a project's board goes with the project.
+CREATE TYPE view_grouping AS ENUM ('None', 'Priority', 'Assignee');
+
+CREATE TABLE saved_views (
+    id UUID PRIMARY KEY,
+    name VARCHAR(100) NOT NULL UNIQUE,
+    project_id UUID REFERENCES projects(id) ON DELETE CASCADE,
+    filter JSONB NOT NULL,
+    sort TEXT,
+    grouping view_grouping NOT NULL DEFAULT 'None',
+    columns TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
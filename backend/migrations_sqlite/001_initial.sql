@@ -0,0 +1,22 @@
+-- Initial SQLite schema for RustTracker, equivalent to the PostgreSQL
+-- migrations. UUIDs are stored as 16-byte blobs, timestamps as RFC 3339
+-- text and enums as their variant names.
+CREATE TABLE tasks (
+    id BLOB PRIMARY KEY NOT NULL,
+    title TEXT NOT NULL CHECK (length(title) <= 255),
+    description TEXT,
+    status TEXT NOT NULL DEFAULT 'Todo'
+        CHECK (status IN ('Todo', 'InProgress', 'Completed', 'Backlog')),
+    priority TEXT NOT NULL DEFAULT 'Medium'
+        CHECK (priority IN ('Low', 'Medium', 'High', 'Urgent')),
+    due_date TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- Create indexes for better query performance
+CREATE INDEX idx_tasks_status ON tasks(status);
+CREATE INDEX idx_tasks_priority ON tasks(priority);
+CREATE INDEX idx_tasks_due_date ON tasks(due_date);
+CREATE INDEX idx_tasks_created_at ON tasks(created_at);
+CREATE INDEX idx_tasks_updated_at ON tasks(updated_at);
@@ -0,0 +1,25 @@
+-- Full-text search over task titles and descriptions using FTS5.
+-- The index keeps its own copy of the text keyed by task id, because the
+-- implicit rowid of `tasks` may change on VACUUM. Words are not stemmed:
+-- the Porter stemmer rewrites prefixes (`deploy` becomes `deploi`), which
+-- would break `word*` queries.
+CREATE VIRTUAL TABLE tasks_fts USING fts5(
+    id UNINDEXED,
+    title,
+    description,
+    tokenize = 'unicode61'
+);
+
+CREATE TRIGGER tasks_fts_insert AFTER INSERT ON tasks BEGIN
+    INSERT INTO tasks_fts (id, title, description)
+    VALUES (new.id, new.title, new.description);
+END;
+
+CREATE TRIGGER tasks_fts_update AFTER UPDATE OF title, description ON tasks BEGIN
+    UPDATE tasks_fts SET title = new.title, description = new.description
+    WHERE id = old.id;
+END;
+
+CREATE TRIGGER tasks_fts_delete AFTER DELETE ON tasks BEGIN
+    DELETE FROM tasks_fts WHERE id = old.id;
+END;
@@ -0,0 +1,2 @@
+-- Optimistic concurrency, as in the PostgreSQL 004_task_version migration
+ALTER TABLE tasks ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,4 @@
+-- Soft delete, as in the PostgreSQL 005_task_trash migration
+ALTER TABLE tasks ADD COLUMN deleted_at TEXT;
+
+CREATE INDEX idx_tasks_deleted_at ON tasks(deleted_at) WHERE deleted_at IS NOT NULL;
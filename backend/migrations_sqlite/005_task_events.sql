@@ -0,0 +1,13 @@
+-- Change history, as in the PostgreSQL 006_task_events migration
+CREATE TABLE task_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id BLOB NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK (kind IN ('Created', 'Updated', 'Deleted', 'Restored')),
+    field TEXT,
+    old_value TEXT,
+    new_value TEXT,
+    actor TEXT CHECK (length(actor) <= 255),
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_task_events_task_id ON task_events(task_id, id);
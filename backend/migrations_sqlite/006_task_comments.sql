@@ -0,0 +1,11 @@
+-- Discussion threads, as in the PostgreSQL 007_task_comments migration
+CREATE TABLE comments (
+    id BLOB PRIMARY KEY,
+    task_id BLOB NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    body TEXT NOT NULL,
+    author TEXT CHECK (length(author) <= 255),
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_comments_task_id ON comments(task_id, created_at);
@@ -0,0 +1,15 @@
+-- Labels, as in the PostgreSQL 008_task_tags migration
+CREATE TABLE tags (
+    id BLOB PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE CHECK (length(name) <= 50),
+    color TEXT NOT NULL CHECK (length(color) <= 7),
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE task_tags (
+    task_id BLOB NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    tag_id BLOB NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (task_id, tag_id)
+);
+
+CREATE INDEX idx_task_tags_tag_id ON task_tags(tag_id);
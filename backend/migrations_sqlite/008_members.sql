@@ -0,0 +1,10 @@
+-- Team members, as in the PostgreSQL 009_members migration
+CREATE TABLE members (
+    id BLOB PRIMARY KEY,
+    name TEXT NOT NULL CHECK (length(name) <= 100),
+    created_at TEXT NOT NULL
+);
+
+ALTER TABLE tasks ADD COLUMN assignee_id BLOB REFERENCES members(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_tasks_assignee_id ON tasks(assignee_id);
@@ -0,0 +1,16 @@
+-- Projects, as in the PostgreSQL 010_projects migration. SQLite cannot add a
+-- NOT NULL foreign key column, so existing tasks are moved by an UPDATE.
+CREATE TABLE projects (
+    id BLOB PRIMARY KEY,
+    name TEXT NOT NULL CHECK (length(name) <= 100),
+    created_at TEXT NOT NULL
+);
+
+INSERT INTO projects (id, name, created_at)
+VALUES (X'00000000000000000000000000000001', 'Default', strftime('%Y-%m-%dT%H:%M:%fZ', 'now'));
+
+ALTER TABLE tasks ADD COLUMN project_id BLOB REFERENCES projects(id);
+
+UPDATE tasks SET project_id = X'00000000000000000000000000000001';
+
+CREATE INDEX idx_tasks_project_id ON tasks(project_id);
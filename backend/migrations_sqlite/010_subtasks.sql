@@ -0,0 +1,4 @@
+-- Subtasks, as in the PostgreSQL 011_subtasks migration
+ALTER TABLE tasks ADD COLUMN parent_id BLOB REFERENCES tasks(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_tasks_parent_id ON tasks(parent_id);
@@ -0,0 +1,9 @@
+-- Dependencies, as in the PostgreSQL 012_task_dependencies migration
+CREATE TABLE task_dependencies (
+    blocker_id BLOB NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    blocked_id BLOB NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    PRIMARY KEY (blocker_id, blocked_id),
+    CHECK (blocker_id <> blocked_id)
+);
+
+CREATE INDEX idx_task_dependencies_blocked_id ON task_dependencies(blocked_id);
@@ -0,0 +1,2 @@
+-- Recurrence, as in the PostgreSQL 013_task_recurrence migration
+ALTER TABLE tasks ADD COLUMN recurrence TEXT;
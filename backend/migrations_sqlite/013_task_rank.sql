@@ -0,0 +1,16 @@
+-- Card order, as in the PostgreSQL 014_task_rank migration. SQLite's
+-- default collation already compares ranks as plain bytes.
+ALTER TABLE tasks ADD COLUMN rank TEXT NOT NULL DEFAULT '';
+
+UPDATE tasks SET rank = ranked.rank
+FROM (
+    SELECT id, printf('%08dV', row_number() OVER (
+        PARTITION BY project_id, status
+        ORDER BY CASE priority WHEN 'Low' THEN 0 WHEN 'Medium' THEN 1 WHEN 'High' THEN 2 ELSE 3 END DESC,
+            created_at DESC
+    )) AS rank
+    FROM tasks
+) AS ranked
+WHERE tasks.id = ranked.id;
+
+CREATE INDEX idx_tasks_column_rank ON tasks(project_id, status, rank);
@@ -0,0 +1,73 @@
+-- Workflows, as in the PostgreSQL 015_workflows migration. SQLite cannot drop
+-- the CHECK on tasks.status or add a foreign key to an existing table, so
+-- the table is rebuilt; migrations run with foreign keys off.
+CREATE TABLE workflow_states (
+    project_id BLOB NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    name TEXT NOT NULL CHECK (length(name) <= 50),
+    category TEXT NOT NULL CHECK (category IN ('Todo', 'Doing', 'Done')),
+    position INTEGER NOT NULL,
+    PRIMARY KEY (project_id, name)
+);
+
+INSERT INTO workflow_states (project_id, name, category, position)
+SELECT projects.id, states.name, states.category, states.position
+FROM projects CROSS JOIN (
+    SELECT 'Todo' AS name, 'Todo' AS category, 0 AS position
+    UNION ALL SELECT 'InProgress', 'Doing', 1
+    UNION ALL SELECT 'Completed', 'Done', 2
+    UNION ALL SELECT 'Backlog', 'Todo', 3
+) AS states;
+
+CREATE TABLE tasks_new (
+    id BLOB PRIMARY KEY NOT NULL,
+    title TEXT NOT NULL CHECK (length(title) <= 255),
+    description TEXT,
+    status TEXT NOT NULL CHECK (length(status) <= 50),
+    priority TEXT NOT NULL DEFAULT 'Medium'
+        CHECK (priority IN ('Low', 'Medium', 'High', 'Urgent')),
+    due_date TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    version INTEGER NOT NULL DEFAULT 1,
+    deleted_at TEXT,
+    assignee_id BLOB REFERENCES members(id) ON DELETE SET NULL,
+    project_id BLOB REFERENCES projects(id),
+    parent_id BLOB REFERENCES tasks(id) ON DELETE SET NULL,
+    recurrence TEXT,
+    rank TEXT NOT NULL DEFAULT '',
+    FOREIGN KEY (project_id, status) REFERENCES workflow_states(project_id, name)
+);
+
+INSERT INTO tasks_new (id, title, description, status, priority, due_date, created_at,
+    updated_at, version, deleted_at, assignee_id, project_id, parent_id, recurrence, rank)
+SELECT id, title, description, status, priority, due_date, created_at,
+    updated_at, version, deleted_at, assignee_id, project_id, parent_id, recurrence, rank
+FROM tasks;
+
+DROP TABLE tasks;
+ALTER TABLE tasks_new RENAME TO tasks;
+
+CREATE INDEX idx_tasks_status ON tasks(status);
+CREATE INDEX idx_tasks_priority ON tasks(priority);
+CREATE INDEX idx_tasks_due_date ON tasks(due_date);
+CREATE INDEX idx_tasks_created_at ON tasks(created_at);
+CREATE INDEX idx_tasks_updated_at ON tasks(updated_at);
+CREATE INDEX idx_tasks_deleted_at ON tasks(deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX idx_tasks_assignee_id ON tasks(assignee_id);
+CREATE INDEX idx_tasks_project_id ON tasks(project_id);
+CREATE INDEX idx_tasks_parent_id ON tasks(parent_id);
+CREATE INDEX idx_tasks_column_rank ON tasks(project_id, status, rank);
+
+CREATE TRIGGER tasks_fts_insert AFTER INSERT ON tasks BEGIN
+    INSERT INTO tasks_fts (id, title, description)
+    VALUES (new.id, new.title, new.description);
+END;
+
+CREATE TRIGGER tasks_fts_update AFTER UPDATE OF title, description ON tasks BEGIN
+    UPDATE tasks_fts SET title = new.title, description = new.description
+    WHERE id = old.id;
+END;
+
+CREATE TRIGGER tasks_fts_delete AFTER DELETE ON tasks BEGIN
+    DELETE FROM tasks_fts WHERE id = old.id;
+END;
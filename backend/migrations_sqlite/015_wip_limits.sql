@@ -0,0 +1,2 @@
+-- WIP limits, as in the PostgreSQL 016_wip_limits migration
+ALTER TABLE workflow_states ADD COLUMN wip_limit INTEGER CHECK (wip_limit > 0);
@@ -0,0 +1,12 @@
+-- Transition rules, as in the PostgreSQL 017_transition_rules migration.
+-- SQLite has no arrays, so state names and fields are stored comma-separated,
+-- which neither can contain; a NULL next_states allows any move.
+ALTER TABLE workflow_states ADD COLUMN next_states TEXT;
+ALTER TABLE workflow_states ADD COLUMN required_fields TEXT NOT NULL DEFAULT '';
+
+CREATE TABLE priority_rules (
+    project_id BLOB NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    priority TEXT NOT NULL CHECK (priority IN ('Low', 'Medium', 'High', 'Urgent')),
+    required_fields TEXT NOT NULL,
+    PRIMARY KEY (project_id, priority)
+);
@@ -0,0 +1,16 @@
+-- Time tracking, as in the PostgreSQL 018_time_tracking migration
+ALTER TABLE tasks ADD COLUMN estimate_minutes INTEGER CHECK (estimate_minutes >= 0);
+
+CREATE TABLE time_entries (
+    id BLOB PRIMARY KEY,
+    task_id BLOB NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    member_id BLOB REFERENCES members(id) ON DELETE SET NULL,
+    started_at TEXT NOT NULL,
+    ended_at TEXT CHECK (julianday(ended_at) >= julianday(started_at)),
+    note TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_time_entries_task_id ON time_entries(task_id, started_at);
+
+CREATE UNIQUE INDEX idx_time_entries_running ON time_entries(member_id) WHERE ended_at IS NULL;
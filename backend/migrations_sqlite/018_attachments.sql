@@ -0,0 +1,14 @@
+-- Attachments, as in the PostgreSQL 019_attachments migration
+CREATE TABLE attachments (
+    id BLOB PRIMARY KEY,
+    task_id BLOB NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    filename TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size_bytes INTEGER NOT NULL CHECK (size_bytes >= 0),
+    sha256 TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_attachments_task_id ON attachments(task_id, created_at);
+
+CREATE INDEX idx_attachments_sha256 ON attachments(sha256);
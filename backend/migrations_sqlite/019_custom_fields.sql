@@ -0,0 +1,14 @@
+-- Custom fields, as in the PostgreSQL 020_custom_fields migration. Options
+-- are a JSON array, as they may contain commas.
+CREATE TABLE custom_fields (
+    id BLOB PRIMARY KEY,
+    project_id BLOB NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    name TEXT NOT NULL CHECK (length(name) <= 50),
+    field_type TEXT NOT NULL CHECK (field_type IN
+        ('Text', 'Number', 'Date', 'SingleSelect', 'MultiSelect', 'Checkbox')),
+    options TEXT NOT NULL DEFAULT '[]',
+    position INTEGER NOT NULL,
+    UNIQUE (project_id, name)
+);
+
+ALTER TABLE tasks ADD COLUMN custom_fields TEXT NOT NULL DEFAULT '{}';
@@ -0,0 +1,13 @@
+-- Saved views, as in the PostgreSQL 021_saved_views migration. Columns are
+-- a JSON array, as custom field options are.
+CREATE TABLE saved_views (
+    id BLOB PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE CHECK (length(name) <= 100),
+    project_id BLOB REFERENCES projects(id) ON DELETE CASCADE,
+    filter TEXT NOT NULL,
+    sort TEXT,
+    grouping TEXT NOT NULL DEFAULT 'None' CHECK (grouping IN ('None', 'Priority', 'Assignee')),
+    columns TEXT NOT NULL DEFAULT '[]',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
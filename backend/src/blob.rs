@@ -0,0 +1,125 @@
+//! Storage for attachment content, kept apart from task storage so any
+//! repository can sit next to any blob store.
+//!
+//! Blobs are content-addressed: each is stored under the hex SHA-256 of its
+//! bytes, so uploading the same file twice stores it once. Which attachments
+//! use a blob is known only to the repository, which reports the blobs a
+//! purge leaves unused for the caller to remove.
+
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use async_trait::async_trait;
+use infer::MatcherType;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::error::AppError;
+
+/// Default largest attachment, in bytes: 10 MiB.
+pub const DEFAULT_MAX_ATTACHMENT_BYTES: usize = 10 * 1024 * 1024;
+
+/// The key a blob with `bytes` is stored under.
+pub fn content_key(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// The MIME type of `bytes`, judged from their content alone. Text of any
+/// kind, markup included, is served as plain text, so an uploaded page or
+/// script is never rendered by the browser.
+pub fn sniff_content_type(bytes: &[u8]) -> String {
+    match infer::get(bytes) {
+        Some(kind) if kind.matcher_type() != MatcherType::Text => kind.mime_type().to_string(),
+        _ if std::str::from_utf8(bytes).is_ok() => "text/plain; charset=utf-8".to_string(),
+        _ => "application/octet-stream".to_string(),
+    }
+}
+
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    /// Stores `bytes` under `key`, which must be [`content_key`] of them.
+    /// Storing a blob that already exists leaves it as it is.
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<(), AppError>;
+
+    /// Returns the blob stored under `key`.
+    async fn get(&self, key: &str) -> Result<Vec<u8>, AppError>;
+
+    /// Removes the blob stored under `key`; a missing blob is not an error.
+    async fn delete(&self, key: &str) -> Result<(), AppError>;
+}
+
+/// Keeps blob removals apart from uploads. An upload holds it shared from
+/// recording its attachment until its blob is stored, and a removal holds it
+/// exclusively from finding blobs unused until they are gone, so a blob is
+/// never removed from under an attachment that has just started using it.
+pub type BlobLock = tokio::sync::RwLock<()>;
+
+/// Removes blobs a purge left unused. Failures are only logged: the tasks
+/// are gone either way, and a blob left behind wastes space but is never
+/// served again.
+pub async fn remove_blobs(store: &dyn BlobStore, keys: &[String]) {
+    for key in keys {
+        if let Err(err) = store.delete(key).await {
+            tracing::error!("Failed to remove blob {key}: {err}");
+        }
+    }
+}
+
+/// Blobs as files under a root directory, sharded by the first two
+/// characters of their key.
+pub struct LocalBlobStore {
+    root: PathBuf,
+}
+
+impl LocalBlobStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, AppError> {
+        // Keys come from our own hashing, but they end up in a path
+        if key.len() < 3 || !key.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(AppError::AttachmentNotFound);
+        }
+        Ok(self.root.join(&key[..2]).join(key))
+    }
+}
+
+#[async_trait]
+impl BlobStore for LocalBlobStore {
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<(), AppError> {
+        let path = self.path(key)?;
+        if tokio::fs::try_exists(&path).await? {
+            return Ok(());
+        }
+        let dir = path.parent().unwrap_or(Path::new("."));
+        tokio::fs::create_dir_all(dir).await?;
+
+        // Written aside and renamed into place, so readers never see half a file
+        let partial = dir.join(format!(".{key}.{}", Uuid::new_v4()));
+        tokio::fs::write(&partial, bytes).await?;
+        if let Err(err) = tokio::fs::rename(&partial, &path).await {
+            let _ = tokio::fs::remove_file(&partial).await;
+            return Err(err.into());
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, AppError> {
+        match tokio::fs::read(self.path(key)?).await {
+            Ok(bytes) => Ok(bytes),
+            Err(err) if err.kind() == ErrorKind::NotFound => Err(AppError::AttachmentNotFound),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
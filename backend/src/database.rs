@@ -1,9 +1,100 @@
-use chrono::Utc;
-use common::{CreateTaskRequest, Task, TaskFilter, TaskStatus, UpdateTaskRequest};
-use sqlx::{PgPool, Row};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::{collections::BTreeSet, slice};
+
+use common::{
+    custom_fields::{CustomField, CustomFieldRequest, CustomFieldValues},
+    query::{Condition, DateField},
+    rank,
+    saved_views::{SavedView, SavedViewRequest},
+    transition::{PriorityRule, TaskField},
+    Attachment, Comment, CommentRequest, CreateTaskRequest, Member, MemberRequest, MoveTaskRequest,
+    Page, Patch, Project, ProjectRequest, SearchMatch, SortDirection, Tag, TagRequest, Task,
+    TaskDependencies, TaskEvent, TaskEventKind, TaskFilter, TaskSort, TaskSortField, TaskStatus,
+    TimeEntry, TimeEntryRequest, UpdateTaskRequest, Workflow, WorkflowRequest, WorkflowState,
+    DEFAULT_PROJECT_ID, MAX_SUBTASK_DEPTH,
+};
+use sqlx::{
+    postgres::PgRow, types::Json, Executor, PgConnection, PgPool, Postgres, QueryBuilder, Row,
+};
 use uuid::Uuid;
 
-use crate::error::AppError;
+use crate::{
+    error::AppError,
+    history::{self, NewTaskEvent},
+    pagination::{Cursor, SortKey},
+    repository::{
+        check_blockers, check_custom_fields, check_dependency, check_field_change, check_move,
+        check_nesting, check_new_task, check_parent_project, check_project_deletable,
+        check_project_move, check_recurrence, check_states_kept, check_tags_exist,
+        check_transition, check_wip_limit, done_state, field_name_conflict, is_completing,
+        move_update, neighbour_moved, next_occurrence, open_subtasks, plan_recurrence,
+        project_in_use, settle_custom_fields, tag_name_conflict, target_state,
+        timer_already_running, timer_not_running, unknown_member, unknown_parent, unknown_project,
+        view_write_failed, wip_limited, NewAttachment, Purged, SubtaskCompletion, TaskRepository,
+        WriteContext,
+    },
+    search,
+};
+
+const TASK_COLUMNS: &str = "id, title, description, status, priority, due_date, created_at, \
+     updated_at, version, deleted_at, assignee_id, project_id, parent_id, recurrence, rank, \
+     estimate_minutes, custom_fields, \
+     (SELECT category FROM workflow_states \
+      WHERE workflow_states.project_id = tasks.project_id \
+      AND workflow_states.name = tasks.status) AS status_category, \
+     (SELECT COUNT(*) FROM comments WHERE comments.task_id = tasks.id) AS comment_count, \
+     (SELECT COUNT(*) FROM tasks AS subtasks \
+      WHERE subtasks.parent_id = tasks.id AND subtasks.deleted_at IS NULL) AS subtask_count, \
+     (SELECT COUNT(*) FROM tasks AS subtasks \
+      WHERE subtasks.parent_id = tasks.id AND subtasks.deleted_at IS NULL \
+      AND subtasks.status IN (SELECT name FROM workflow_states \
+       WHERE workflow_states.project_id = subtasks.project_id \
+       AND category = 'Done')) AS completed_subtask_count, \
+     EXISTS (SELECT 1 FROM task_dependencies \
+      JOIN tasks AS blockers ON blockers.id = task_dependencies.blocker_id \
+      WHERE task_dependencies.blocked_id = tasks.id AND blockers.deleted_at IS NULL \
+      AND blockers.status NOT IN (SELECT name FROM workflow_states \
+       WHERE workflow_states.project_id = blockers.project_id \
+       AND category = 'Done')) AS is_blocked, \
+     (SELECT ROUND(COALESCE(SUM(EXTRACT(EPOCH FROM ended_at - started_at)), 0))::BIGINT / 60 \
+      FROM time_entries WHERE time_entries.task_id = tasks.id \
+      AND ended_at IS NOT NULL) AS logged_minutes, \
+     (SELECT MIN(started_at) FROM time_entries \
+      WHERE time_entries.task_id = tasks.id AND ended_at IS NULL) AS timer_started_at";
+
+/// Whether `$1` already blocks `$2`, directly or through other tasks, in
+/// which case `$2` blocking `$1` would close a cycle. Trashed tasks count,
+/// as they can be restored.
+const BLOCKS_TRANSITIVELY: &str = r#"
+    WITH RECURSIVE downstream(id) AS (
+        SELECT blocked_id FROM task_dependencies WHERE blocker_id = $1
+        UNION
+        SELECT task_dependencies.blocked_id
+        FROM task_dependencies JOIN downstream ON task_dependencies.blocker_id = downstream.id
+    )
+    SELECT EXISTS (SELECT 1 FROM downstream WHERE id = $2)
+"#;
+
+const COMMENT_COLUMNS: &str = "id, task_id, body, author, created_at, updated_at";
+
+const TIME_ENTRY_COLUMNS: &str = "id, task_id, member_id, started_at, ended_at, note";
+
+const ATTACHMENT_COLUMNS: &str =
+    "id, task_id, filename, content_type, size_bytes, sha256, created_at";
+
+const TAG_COLUMNS: &str = "id, name, color";
+
+const MEMBER_COLUMNS: &str = "id, name";
+
+const PROJECT_COLUMNS: &str = "id, name";
+
+const STATE_COLUMNS: &str = "name, category, wip_limit, next_states, required_fields";
+
+const CUSTOM_FIELD_COLUMNS: &str = "id, project_id, name, field_type, options";
+
+const SAVED_VIEW_COLUMNS: &str =
+    "id, name, filter, sort, grouping, columns, created_at, updated_at";
 
 #[derive(Clone)]
 pub struct Database {
@@ -15,171 +106,2631 @@ impl Database {
         Self { pool }
     }
 
-    pub async fn create_task(&self, request: CreateTaskRequest) -> Result<Task, AppError> {
-        let id = Uuid::new_v4();
+    pub async fn create_task(
+        &self,
+        mut request: CreateTaskRequest,
+        context: &WriteContext,
+    ) -> Result<Task, AppError> {
         let now = Utc::now();
-        let status = TaskStatus::Todo;
+        let project_id = request.project_id.unwrap_or(DEFAULT_PROJECT_ID);
 
-        let row = sqlx::query(
-            r#"
-            INSERT INTO tasks (id, title, description, status, priority, due_date, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-            RETURNING id, title, description, status, priority, due_date, created_at, updated_at
-            "#,
+        let mut tx = self.pool.begin().await?;
+        lock_project(&mut tx, project_id).await?;
+        if let Some(assignee_id) = request.assignee_id {
+            lock_member(&mut tx, assignee_id).await?;
+        }
+        if let Some(parent_id) = request.parent_id {
+            check_parent(&mut tx, None, parent_id, project_id).await?;
+        }
+        check_recurrence(request.recurrence.as_deref(), request.due_date)?;
+        check_new_task(&request, &workflow_of(&mut tx, project_id).await?)?;
+        let fields = custom_fields_of(&mut tx, project_id).await?;
+        check_custom_fields(&mut request.custom_fields, &fields)?;
+
+        let task = insert_task(&mut tx, &request, context, now).await?;
+        tx.commit().await?;
+
+        Ok(task)
+    }
+
+    /// Returns one page of tasks matching `filter`, ordered by `sort` and
+    /// starting after `after` when given.
+    pub async fn get_task_page(
+        &self,
+        filter: &TaskFilter,
+        sort: TaskSort,
+        after: Option<&Cursor>,
+        limit: u32,
+    ) -> Result<Page<Task>, AppError> {
+        let (mut query, mut conditions) = select_tasks(filter);
+
+        if let Some(cursor) = after {
+            conditions.next(&mut query);
+            push_keyset(&mut query, sort, cursor);
+        }
+
+        push_order_by(&mut query, sort);
+        query.push(" LIMIT ").push_bind(i64::from(limit) + 1);
+
+        let mut rows = query.build().fetch_all(&self.pool).await?;
+
+        // Counting on every page would scan the whole result each time, so
+        // later pages reuse the first page's total
+        let total = match after {
+            Some(cursor) => cursor.total,
+            None => {
+                let mut count = QueryBuilder::<Postgres>::new("SELECT COUNT(*)");
+                push_from(&mut count, filter);
+                push_filter(&mut count, &mut Conditions::default(), filter);
+                let total: i64 = count.build_query_scalar().fetch_one(&self.pool).await?;
+                u64::try_from(total).unwrap_or_default()
+            }
+        };
+
+        let next_cursor = if rows.len() > limit as usize {
+            rows.truncate(limit as usize);
+            rows.last().map(|row| {
+                let key = sort_key_from_row(row, sort.field);
+                Cursor::new(sort, key, row.get("id"), total).encode()
+            })
+        } else {
+            None
+        };
+
+        let mut items: Vec<Task> = rows.iter().map(task_from_row).collect();
+        attach_tags(&self.pool, &mut items).await?;
+
+        Ok(Page {
+            items,
+            next_cursor,
+            total_estimate: total,
+        })
+    }
+
+    pub async fn get_task_by_id(&self, id: Uuid) -> Result<Task, AppError> {
+        let row = sqlx::query(&format!(
+            "SELECT {TASK_COLUMNS} FROM tasks WHERE id = $1 AND deleted_at IS NULL"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(AppError::TaskNotFound)?;
+
+        let mut task = task_from_row(&row);
+        attach_tags(&self.pool, slice::from_mut(&mut task)).await?;
+        Ok(task)
+    }
+
+    /// Applies the fields present in `request`, leaving the others as they
+    /// are, and increments the version.
+    pub async fn update_task(
+        &self,
+        id: Uuid,
+        request: UpdateTaskRequest,
+        context: &WriteContext,
+    ) -> Result<Task, AppError> {
+        let mut tx = self.pool.begin().await?;
+        let task = update_in(&mut tx, id, request, None, context).await?;
+        tx.commit().await?;
+
+        Ok(task)
+    }
+
+    /// Moves the task into a column and between two of its cards.
+    pub async fn move_task(
+        &self,
+        id: Uuid,
+        request: MoveTaskRequest,
+        context: &WriteContext,
+    ) -> Result<Task, AppError> {
+        check_move(id, &request)?;
+
+        let mut tx = self.pool.begin().await?;
+        let task = update_in(&mut tx, id, move_update(&request), Some(&request), context).await?;
+        tx.commit().await?;
+
+        Ok(task)
+    }
+
+    /// Moves the task to the trash.
+    pub async fn delete_task(&self, id: Uuid, context: &WriteContext) -> Result<(), AppError> {
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+        let task = lock_live_task(&mut tx, id).await?;
+        context.check_version(&task)?;
+
+        sqlx::query("UPDATE tasks SET deleted_at = $2 WHERE id = $1")
+            .bind(id)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            "UPDATE time_entries SET ended_at = $2 WHERE task_id = $1 AND ended_at IS NULL",
         )
         .bind(id)
-        .bind(&request.title)
-        .bind(&request.description)
-        .bind(status)
-        .bind(request.priority)
-        .bind(request.due_date)
         .bind(now)
-        .bind(now)
-        .fetch_one(&self.pool)
+        .execute(&mut *tx)
         .await?;
 
-        Ok(Task {
-            id: row.get("id"),
-            title: row.get("title"),
-            description: row.get("description"),
-            status: row.get("status"),
-            priority: row.get("priority"),
-            due_date: row.get("due_date"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-        })
+        let events = vec![NewTaskEvent::task(TaskEventKind::Deleted)];
+        insert_events(&mut tx, id, events, context, now).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Returns trashed tasks, most recently deleted first.
+    pub async fn get_trash(&self) -> Result<Vec<Task>, AppError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {TASK_COLUMNS} FROM tasks WHERE deleted_at IS NOT NULL \
+             ORDER BY deleted_at DESC, id DESC"
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut tasks: Vec<Task> = rows.iter().map(task_from_row).collect();
+        attach_tags(&self.pool, &mut tasks).await?;
+        Ok(tasks)
+    }
+
+    pub async fn restore_task(&self, id: Uuid, context: &WriteContext) -> Result<Task, AppError> {
+        let mut tx = self.pool.begin().await?;
+        let row = sqlx::query(&format!(
+            "UPDATE tasks SET deleted_at = NULL WHERE id = $1 AND deleted_at IS NOT NULL \
+             RETURNING {TASK_COLUMNS}"
+        ))
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AppError::TaskNotFound)?;
+
+        let mut task = task_from_row(&row);
+        attach_tags(&mut *tx, slice::from_mut(&mut task)).await?;
+
+        let events = vec![NewTaskEvent::task(TaskEventKind::Restored)];
+        insert_events(&mut tx, id, events, context, Utc::now()).await?;
+        tx.commit().await?;
+
+        Ok(task)
     }
 
-    pub async fn get_tasks(&self, filter: Option<TaskFilter>) -> Result<Vec<Task>, AppError> {
+    /// Permanently deletes a trashed task.
+    pub async fn purge_task(&self, id: Uuid) -> Result<Purged, AppError> {
+        let mut tx = self.pool.begin().await?;
+        let ids: Vec<Uuid> = sqlx::query_scalar(
+            "SELECT id FROM tasks WHERE id = $1 AND deleted_at IS NOT NULL FOR UPDATE",
+        )
+        .bind(id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        if ids.is_empty() {
+            return Err(AppError::TaskNotFound);
+        }
+
+        let purged = purge_tasks(&mut tx, &ids).await?;
+        tx.commit().await?;
+        Ok(purged)
+    }
+
+    /// Permanently deletes tasks trashed before `before`.
+    pub async fn purge_trash(&self, before: DateTime<Utc>) -> Result<Purged, AppError> {
+        let mut tx = self.pool.begin().await?;
+        let ids: Vec<Uuid> =
+            sqlx::query_scalar("SELECT id FROM tasks WHERE deleted_at < $1 FOR UPDATE")
+                .bind(before)
+                .fetch_all(&mut *tx)
+                .await?;
+
+        let purged = purge_tasks(&mut tx, &ids).await?;
+        tx.commit().await?;
+        Ok(purged)
+    }
+
+    /// Returns the change history of a live task, oldest first.
+    pub async fn get_task_history(&self, id: Uuid) -> Result<Vec<TaskEvent>, AppError> {
+        self.get_task_by_id(id).await?;
+
         let rows = sqlx::query(
             r#"
-            SELECT id, title, description, status, priority, due_date, created_at, updated_at
-            FROM tasks
-            ORDER BY created_at DESC
+            SELECT id, task_id, kind, field, old_value, new_value, actor, created_at
+            FROM task_events WHERE task_id = $1
+            ORDER BY id
             "#,
         )
+        .bind(id)
         .fetch_all(&self.pool)
         .await?;
 
-        let tasks: Vec<Task> = rows
-            .into_iter()
-            .map(|row| Task {
+        Ok(rows
+            .iter()
+            .map(|row| TaskEvent {
                 id: row.get("id"),
-                title: row.get("title"),
-                description: row.get("description"),
-                status: row.get("status"),
-                priority: row.get("priority"),
-                due_date: row.get("due_date"),
+                task_id: row.get("task_id"),
+                kind: row.get("kind"),
+                field: row.get("field"),
+                old_value: row.get("old_value"),
+                new_value: row.get("new_value"),
+                actor: row.get("actor"),
                 created_at: row.get("created_at"),
-                updated_at: row.get("updated_at"),
             })
-            .collect();
+            .collect())
+    }
 
-        // Apply filters in Rust for now
-        let filtered_tasks = if let Some(filter) = filter {
-            tasks
-                .into_iter()
-                .filter(|task| {
-                    let status_match = filter.status.is_none_or(|s| task.status == s);
-                    let priority_match = filter.priority.is_none_or(|p| task.priority == p);
-                    let due_before_match = filter
-                        .due_before
-                        .is_none_or(|d| task.due_date.is_some_and(|due| due < d));
-                    let due_after_match = filter
-                        .due_after
-                        .is_none_or(|d| task.due_date.is_some_and(|due| due > d));
-
-                    status_match && priority_match && due_before_match && due_after_match
-                })
-                .collect()
-        } else {
-            tasks
-        };
+    /// Returns the live direct subtasks of a live task, oldest first.
+    pub async fn get_children(&self, id: Uuid) -> Result<Vec<Task>, AppError> {
+        self.get_task_by_id(id).await?;
+
+        let rows = sqlx::query(&format!(
+            "SELECT {TASK_COLUMNS} FROM tasks WHERE parent_id = $1 AND deleted_at IS NULL \
+             ORDER BY created_at, id"
+        ))
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await?;
 
-        Ok(filtered_tasks)
+        let mut tasks: Vec<Task> = rows.iter().map(task_from_row).collect();
+        attach_tags(&self.pool, &mut tasks).await?;
+        Ok(tasks)
     }
 
-    pub async fn get_task_by_id(&self, id: Uuid) -> Result<Task, AppError> {
-        let row = sqlx::query(
-            r#"
-            SELECT id, title, description, status, priority, due_date, created_at, updated_at
-            FROM tasks WHERE id = $1
-            "#,
+    /// Returns the live tasks blocking and blocked by a live task.
+    pub async fn get_dependencies(&self, id: Uuid) -> Result<TaskDependencies, AppError> {
+        self.get_task_by_id(id).await?;
+
+        Ok(TaskDependencies {
+            blocked_by: self.linked_tasks(id, "blocker_id", "blocked_id").await?,
+            blocks: self.linked_tasks(id, "blocked_id", "blocker_id").await?,
+        })
+    }
+
+    /// The live tasks in column `select` of the dependencies whose column
+    /// `by` is `id`, oldest first.
+    async fn linked_tasks(&self, id: Uuid, select: &str, by: &str) -> Result<Vec<Task>, AppError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {TASK_COLUMNS} FROM tasks WHERE deleted_at IS NULL AND id IN \
+             (SELECT {select} FROM task_dependencies WHERE {by} = $1) \
+             ORDER BY created_at, id"
+        ))
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut tasks: Vec<Task> = rows.iter().map(task_from_row).collect();
+        attach_tags(&self.pool, &mut tasks).await?;
+        Ok(tasks)
+    }
+
+    /// Makes `blocker_id` block `id`, refusing edges that would close a
+    /// cycle.
+    pub async fn add_dependency(&self, id: Uuid, blocker_id: Uuid) -> Result<(), AppError> {
+        check_dependency(id, blocker_id)?;
+
+        let mut tx = self.pool.begin().await?;
+        // One writer at a time, so two edges added together cannot close a
+        // cycle that neither sees on its own
+        sqlx::query("LOCK TABLE task_dependencies IN SHARE ROW EXCLUSIVE MODE")
+            .execute(&mut *tx)
+            .await?;
+        for task_id in [id, blocker_id] {
+            sqlx::query("SELECT 1 FROM tasks WHERE id = $1 AND deleted_at IS NULL FOR SHARE")
+                .bind(task_id)
+                .fetch_optional(&mut *tx)
+                .await?
+                .ok_or(AppError::TaskNotFound)?;
+        }
+
+        let cycle: bool = sqlx::query_scalar(BLOCKS_TRANSITIVELY)
+            .bind(id)
+            .bind(blocker_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        if cycle {
+            return Err(AppError::DependencyCycle);
+        }
+
+        sqlx::query(
+            "INSERT INTO task_dependencies (blocker_id, blocked_id) VALUES ($1, $2) \
+             ON CONFLICT DO NOTHING",
         )
+        .bind(blocker_id)
         .bind(id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn remove_dependency(&self, id: Uuid, blocker_id: Uuid) -> Result<(), AppError> {
+        self.get_task_by_id(id).await?;
+
+        let result =
+            sqlx::query("DELETE FROM task_dependencies WHERE blocker_id = $1 AND blocked_id = $2")
+                .bind(blocker_id)
+                .bind(id)
+                .execute(&self.pool)
+                .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::DependencyNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the comments on a live task, oldest first.
+    pub async fn get_comments(&self, task_id: Uuid) -> Result<Vec<Comment>, AppError> {
+        self.get_task_by_id(task_id).await?;
+
+        let rows = sqlx::query(&format!(
+            "SELECT {COMMENT_COLUMNS} FROM comments WHERE task_id = $1 ORDER BY created_at, id"
+        ))
+        .bind(task_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(comment_from_row).collect())
+    }
+
+    pub async fn create_comment(
+        &self,
+        task_id: Uuid,
+        request: CommentRequest,
+        author: Option<String>,
+    ) -> Result<Comment, AppError> {
+        let now = Utc::now();
+
+        // Inserting only while the task is live keeps comments off the trash
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO comments (id, task_id, body, author, created_at, updated_at)
+            SELECT $1, $2, $3, $4, $5, $5
+            WHERE EXISTS (SELECT 1 FROM tasks WHERE id = $2 AND deleted_at IS NULL)
+            RETURNING {COMMENT_COLUMNS}
+            "#
+        ))
+        .bind(Uuid::new_v4())
+        .bind(task_id)
+        .bind(request.body)
+        .bind(author)
+        .bind(now)
         .fetch_optional(&self.pool)
         .await?;
 
         match row {
-            Some(row) => Ok(Task {
-                id: row.get("id"),
-                title: row.get("title"),
-                description: row.get("description"),
-                status: row.get("status"),
-                priority: row.get("priority"),
-                due_date: row.get("due_date"),
-                created_at: row.get("created_at"),
-                updated_at: row.get("updated_at"),
-            }),
+            Some(row) => Ok(comment_from_row(&row)),
             None => Err(AppError::TaskNotFound),
         }
     }
 
-    pub async fn update_task(
+    /// Replaces the body of a comment on a live task.
+    pub async fn update_comment(
         &self,
+        task_id: Uuid,
         id: Uuid,
-        request: UpdateTaskRequest,
-    ) -> Result<Task, AppError> {
-        // First check if task exists
-        let _existing_task = self.get_task_by_id(id).await?;
+        request: CommentRequest,
+    ) -> Result<Comment, AppError> {
+        self.get_task_by_id(task_id).await?;
+
+        let row = sqlx::query(&format!(
+            "UPDATE comments SET body = $3, updated_at = $4 WHERE id = $2 AND task_id = $1 \
+             RETURNING {COMMENT_COLUMNS}"
+        ))
+        .bind(task_id)
+        .bind(id)
+        .bind(request.body)
+        .bind(Utc::now())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(comment_from_row(&row)),
+            None => Err(AppError::CommentNotFound),
+        }
+    }
+
+    pub async fn delete_comment(&self, task_id: Uuid, id: Uuid) -> Result<(), AppError> {
+        self.get_task_by_id(task_id).await?;
+
+        let result = sqlx::query("DELETE FROM comments WHERE id = $2 AND task_id = $1")
+            .bind(task_id)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::CommentNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the time entries of a live task, earliest start first.
+    pub async fn get_time_entries(&self, task_id: Uuid) -> Result<Vec<TimeEntry>, AppError> {
+        self.get_task_by_id(task_id).await?;
+
+        let rows = sqlx::query(&format!(
+            "SELECT {TIME_ENTRY_COLUMNS} FROM time_entries WHERE task_id = $1 \
+             ORDER BY started_at, id"
+        ))
+        .bind(task_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(time_entry_from_row).collect())
+    }
+
+    /// Starts a member's timer on a live task, stopping the one they have
+    /// running elsewhere.
+    pub async fn start_timer(&self, task_id: Uuid, member_id: Uuid) -> Result<TimeEntry, AppError> {
         let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+        lock_live_task(&mut tx, task_id).await?;
+        lock_timer(&mut tx, member_id).await?;
 
-        // Simple update approach using COALESCE
-        let row = sqlx::query(
-            r#"
-            UPDATE tasks 
-            SET title = COALESCE($2, title),
-                description = COALESCE($3, description),
-                status = COALESCE($4, status),
-                priority = COALESCE($5, priority),
-                due_date = COALESCE($6, due_date),
-                updated_at = $7
-            WHERE id = $1
-            RETURNING id, title, description, status, priority, due_date, created_at, updated_at
-            "#,
+        let running: Option<Uuid> = sqlx::query_scalar(
+            "SELECT task_id FROM time_entries WHERE member_id = $1 AND ended_at IS NULL",
         )
-        .bind(id)
-        .bind(&request.title)
-        .bind(&request.description)
-        .bind(request.status)
-        .bind(request.priority)
-        .bind(request.due_date)
+        .bind(member_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        if running == Some(task_id) {
+            return Err(timer_already_running());
+        }
+        sqlx::query(
+            "UPDATE time_entries SET ended_at = $2 WHERE member_id = $1 AND ended_at IS NULL",
+        )
+        .bind(member_id)
         .bind(now)
-        .fetch_one(&self.pool)
+        .execute(&mut *tx)
         .await?;
 
-        Ok(Task {
-            id: row.get("id"),
-            title: row.get("title"),
-            description: row.get("description"),
-            status: row.get("status"),
-            priority: row.get("priority"),
-            due_date: row.get("due_date"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-        })
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO time_entries (id, task_id, member_id, started_at, created_at)
+            VALUES ($1, $2, $3, $4, $4)
+            RETURNING {TIME_ENTRY_COLUMNS}
+            "#
+        ))
+        .bind(Uuid::new_v4())
+        .bind(task_id)
+        .bind(member_id)
+        .bind(now)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(time_entry_from_row(&row))
+    }
+
+    pub async fn stop_timer(&self, task_id: Uuid, member_id: Uuid) -> Result<TimeEntry, AppError> {
+        self.get_task_by_id(task_id).await?;
+
+        let row = sqlx::query(&format!(
+            "UPDATE time_entries SET ended_at = $3 \
+             WHERE task_id = $1 AND member_id = $2 AND ended_at IS NULL \
+             RETURNING {TIME_ENTRY_COLUMNS}"
+        ))
+        .bind(task_id)
+        .bind(member_id)
+        .bind(Utc::now())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(time_entry_from_row(&row)),
+            None => Err(timer_not_running()),
+        }
+    }
+
+    pub async fn create_time_entry(
+        &self,
+        task_id: Uuid,
+        request: TimeEntryRequest,
+    ) -> Result<TimeEntry, AppError> {
+        let mut tx = self.pool.begin().await?;
+        lock_live_task(&mut tx, task_id).await?;
+        lock_member(&mut tx, request.member_id).await?;
+
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO time_entries (id, task_id, member_id, started_at, ended_at, note, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING {TIME_ENTRY_COLUMNS}
+            "#
+        ))
+        .bind(Uuid::new_v4())
+        .bind(task_id)
+        .bind(request.member_id)
+        .bind(request.started_at)
+        .bind(request.ended_at)
+        .bind(request.note)
+        .bind(Utc::now())
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(time_entry_from_row(&row))
     }
 
-    pub async fn delete_task(&self, id: Uuid) -> Result<(), AppError> {
-        let result = sqlx::query("DELETE FROM tasks WHERE id = $1")
+    /// Replaces the member, times and note of an entry on a live task.
+    pub async fn update_time_entry(
+        &self,
+        task_id: Uuid,
+        id: Uuid,
+        request: TimeEntryRequest,
+    ) -> Result<TimeEntry, AppError> {
+        let mut tx = self.pool.begin().await?;
+        lock_live_task(&mut tx, task_id).await?;
+        lock_member(&mut tx, request.member_id).await?;
+
+        let row = sqlx::query(&format!(
+            "UPDATE time_entries SET member_id = $3, started_at = $4, ended_at = $5, note = $6 \
+             WHERE id = $2 AND task_id = $1 RETURNING {TIME_ENTRY_COLUMNS}"
+        ))
+        .bind(task_id)
+        .bind(id)
+        .bind(request.member_id)
+        .bind(request.started_at)
+        .bind(request.ended_at)
+        .bind(request.note)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AppError::TimeEntryNotFound)?;
+        tx.commit().await?;
+
+        Ok(time_entry_from_row(&row))
+    }
+
+    pub async fn delete_time_entry(&self, task_id: Uuid, id: Uuid) -> Result<(), AppError> {
+        self.get_task_by_id(task_id).await?;
+
+        let result = sqlx::query("DELETE FROM time_entries WHERE id = $2 AND task_id = $1")
+            .bind(task_id)
             .bind(id)
             .execute(&self.pool)
             .await?;
 
         if result.rows_affected() == 0 {
-            return Err(AppError::TaskNotFound);
+            return Err(AppError::TimeEntryNotFound);
         }
 
         Ok(())
     }
+
+    /// Returns the attachments of a live task, oldest first.
+    pub async fn get_attachments(&self, task_id: Uuid) -> Result<Vec<Attachment>, AppError> {
+        self.get_task_by_id(task_id).await?;
+
+        let rows = sqlx::query(&format!(
+            "SELECT {ATTACHMENT_COLUMNS} FROM attachments WHERE task_id = $1 \
+             ORDER BY created_at, id"
+        ))
+        .bind(task_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(attachment_from_row).collect())
+    }
+
+    pub async fn get_attachment(&self, task_id: Uuid, id: Uuid) -> Result<Attachment, AppError> {
+        self.get_task_by_id(task_id).await?;
+
+        let row = sqlx::query(&format!(
+            "SELECT {ATTACHMENT_COLUMNS} FROM attachments WHERE id = $2 AND task_id = $1"
+        ))
+        .bind(task_id)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(AppError::AttachmentNotFound)?;
+
+        Ok(attachment_from_row(&row))
+    }
+
+    pub async fn create_attachment(
+        &self,
+        task_id: Uuid,
+        attachment: NewAttachment,
+    ) -> Result<Attachment, AppError> {
+        let mut tx = self.pool.begin().await?;
+        lock_live_task(&mut tx, task_id).await?;
+
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO attachments (id, task_id, filename, content_type, size_bytes, sha256, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING {ATTACHMENT_COLUMNS}
+            "#
+        ))
+        .bind(Uuid::new_v4())
+        .bind(task_id)
+        .bind(attachment.filename)
+        .bind(attachment.content_type)
+        .bind(attachment.size_bytes)
+        .bind(attachment.sha256)
+        .bind(Utc::now())
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(attachment_from_row(&row))
+    }
+
+    /// Deletes an attachment of a live task, returning its blob if no
+    /// attachment uses it any more.
+    pub async fn delete_attachment(
+        &self,
+        task_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<String>, AppError> {
+        let mut tx = self.pool.begin().await?;
+        lock_live_task(&mut tx, task_id).await?;
+
+        let sha256: String = sqlx::query_scalar(
+            "DELETE FROM attachments WHERE id = $2 AND task_id = $1 RETURNING sha256",
+        )
+        .bind(task_id)
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AppError::AttachmentNotFound)?;
+        let mut unused = unused_blobs(&mut tx, vec![sha256]).await?;
+        tx.commit().await?;
+
+        Ok(unused.pop())
+    }
+
+    /// Returns every tag, ordered by name.
+    pub async fn get_tags(&self) -> Result<Vec<Tag>, AppError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {TAG_COLUMNS} FROM tags ORDER BY name COLLATE \"C\""
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(tag_from_row).collect())
+    }
+
+    pub async fn create_tag(&self, request: TagRequest) -> Result<Tag, AppError> {
+        let row = sqlx::query(&format!(
+            "INSERT INTO tags (id, name, color) VALUES ($1, $2, $3) RETURNING {TAG_COLUMNS}"
+        ))
+        .bind(Uuid::new_v4())
+        .bind(&request.name)
+        .bind(&request.color)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|err| tag_name_conflict(err, &request.name))?;
+
+        Ok(tag_from_row(&row))
+    }
+
+    pub async fn update_tag(&self, id: Uuid, request: TagRequest) -> Result<Tag, AppError> {
+        let row = sqlx::query(&format!(
+            "UPDATE tags SET name = $2, color = $3 WHERE id = $1 RETURNING {TAG_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(&request.name)
+        .bind(&request.color)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err| tag_name_conflict(err, &request.name))?;
+
+        match row {
+            Some(row) => Ok(tag_from_row(&row)),
+            None => Err(AppError::TagNotFound),
+        }
+    }
+
+    pub async fn delete_tag(&self, id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query("DELETE FROM tags WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::TagNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Returns every member, ordered by name.
+    pub async fn get_members(&self) -> Result<Vec<Member>, AppError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {MEMBER_COLUMNS} FROM members ORDER BY name COLLATE \"C\", id"
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(member_from_row).collect())
+    }
+
+    pub async fn create_member(&self, request: MemberRequest) -> Result<Member, AppError> {
+        let row = sqlx::query(&format!(
+            "INSERT INTO members (id, name) VALUES ($1, $2) RETURNING {MEMBER_COLUMNS}"
+        ))
+        .bind(Uuid::new_v4())
+        .bind(request.name)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(member_from_row(&row))
+    }
+
+    pub async fn update_member(
+        &self,
+        id: Uuid,
+        request: MemberRequest,
+    ) -> Result<Member, AppError> {
+        let row = sqlx::query(&format!(
+            "UPDATE members SET name = $2 WHERE id = $1 RETURNING {MEMBER_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(request.name)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(member_from_row(&row)),
+            None => Err(AppError::MemberNotFound),
+        }
+    }
+
+    /// Deletes a member; the foreign keys unassign their tasks and keep
+    /// their time entries without them, once a running timer is stopped.
+    pub async fn delete_member(&self, id: Uuid) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "UPDATE time_entries SET ended_at = $2 WHERE member_id = $1 AND ended_at IS NULL",
+        )
+        .bind(id)
+        .bind(Utc::now())
+        .execute(&mut *tx)
+        .await?;
+        let result = sqlx::query("DELETE FROM members WHERE id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::MemberNotFound);
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Returns every project, ordered by name.
+    pub async fn get_projects(&self) -> Result<Vec<Project>, AppError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {PROJECT_COLUMNS} FROM projects ORDER BY name COLLATE \"C\", id"
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(project_from_row).collect())
+    }
+
+    pub async fn get_project(&self, id: Uuid) -> Result<Project, AppError> {
+        let row = sqlx::query(&format!(
+            "SELECT {PROJECT_COLUMNS} FROM projects WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(project_from_row(&row)),
+            None => Err(AppError::ProjectNotFound),
+        }
+    }
+
+    /// Creates a project with the default workflow.
+    pub async fn create_project(&self, request: ProjectRequest) -> Result<Project, AppError> {
+        let mut tx = self.pool.begin().await?;
+        let row = sqlx::query(&format!(
+            "INSERT INTO projects (id, name) VALUES ($1, $2) RETURNING {PROJECT_COLUMNS}"
+        ))
+        .bind(Uuid::new_v4())
+        .bind(request.name)
+        .fetch_one(&mut *tx)
+        .await?;
+        let project = project_from_row(&row);
+
+        upsert_states(&mut tx, project.id, &common::default_workflow()).await?;
+        tx.commit().await?;
+
+        Ok(project)
+    }
+
+    pub async fn update_project(
+        &self,
+        id: Uuid,
+        request: ProjectRequest,
+    ) -> Result<Project, AppError> {
+        let row = sqlx::query(&format!(
+            "UPDATE projects SET name = $2 WHERE id = $1 RETURNING {PROJECT_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(request.name)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(project_from_row(&row)),
+            None => Err(AppError::ProjectNotFound),
+        }
+    }
+
+    /// Deletes an empty project; the foreign key refuses one with tasks.
+    pub async fn delete_project(&self, id: Uuid) -> Result<(), AppError> {
+        check_project_deletable(id)?;
+        let result = sqlx::query("DELETE FROM projects WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(project_in_use)?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::ProjectNotFound);
+        }
+
+        Ok(())
+    }
+
+    pub async fn count_in_state(
+        &self,
+        project_id: Uuid,
+        status: &TaskStatus,
+    ) -> Result<i64, AppError> {
+        let mut conn = self.pool.acquire().await?;
+        Ok(column_size(&mut conn, project_id, status).await?)
+    }
+
+    /// Returns the states of a project's workflow in board order.
+    pub async fn get_workflow(&self, project_id: Uuid) -> Result<Workflow, AppError> {
+        self.get_project(project_id).await?;
+
+        let mut conn = self.pool.acquire().await?;
+        Ok(workflow_of(&mut conn, project_id).await?)
+    }
+
+    /// Replaces the states of a project's workflow, refusing to drop a state
+    /// that tasks are still in.
+    pub async fn update_workflow(
+        &self,
+        project_id: Uuid,
+        request: WorkflowRequest,
+    ) -> Result<Workflow, AppError> {
+        let mut tx = self.pool.begin().await?;
+        // Locking the project keeps tasks from moving into it meanwhile
+        sqlx::query("SELECT 1 FROM projects WHERE id = $1 FOR UPDATE")
+            .bind(project_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(AppError::ProjectNotFound)?;
+
+        let in_use: Vec<TaskStatus> =
+            sqlx::query_scalar("SELECT DISTINCT status FROM tasks WHERE project_id = $1")
+                .bind(project_id)
+                .fetch_all(&mut *tx)
+                .await?;
+        check_states_kept(&in_use, &request)?;
+
+        let names: Vec<&str> = request
+            .states
+            .iter()
+            .map(|state| state.name.as_str())
+            .collect();
+        sqlx::query("DELETE FROM workflow_states WHERE project_id = $1 AND name <> ALL($2)")
+            .bind(project_id)
+            .bind(&names)
+            .execute(&mut *tx)
+            .await?;
+        upsert_states(&mut tx, project_id, &request.states).await?;
+        replace_priority_rules(&mut tx, project_id, &request.priority_rules).await?;
+        let workflow = workflow_of(&mut tx, project_id).await?;
+        tx.commit().await?;
+
+        Ok(workflow)
+    }
+
+    /// Returns a project's custom fields in the order they were added.
+    pub async fn get_custom_fields(&self, project_id: Uuid) -> Result<Vec<CustomField>, AppError> {
+        self.get_project(project_id).await?;
+
+        let mut conn = self.pool.acquire().await?;
+        Ok(custom_fields_of(&mut conn, project_id).await?)
+    }
+
+    pub async fn get_custom_field(&self, id: Uuid) -> Result<CustomField, AppError> {
+        let row = sqlx::query(&format!(
+            "SELECT {CUSTOM_FIELD_COLUMNS} FROM custom_fields WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(custom_field_from_row(&row)),
+            None => Err(AppError::CustomFieldNotFound),
+        }
+    }
+
+    /// Adds a custom field after the project's last one.
+    pub async fn create_custom_field(
+        &self,
+        project_id: Uuid,
+        request: CustomFieldRequest,
+    ) -> Result<CustomField, AppError> {
+        self.get_project(project_id).await?;
+
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO custom_fields (id, project_id, name, field_type, options, position)
+            SELECT $1, $2, $3, $4, $5, COALESCE(MAX(position) + 1, 0)
+            FROM custom_fields WHERE project_id = $2
+            RETURNING {CUSTOM_FIELD_COLUMNS}
+            "#
+        ))
+        .bind(Uuid::new_v4())
+        .bind(project_id)
+        .bind(&request.name)
+        .bind(request.field_type)
+        .bind(&request.options)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|err| field_name_conflict(err, &request.name))?;
+
+        Ok(custom_field_from_row(&row))
+    }
+
+    /// Renames a custom field or changes its options, refusing to remove an
+    /// option that tasks still use.
+    pub async fn update_custom_field(
+        &self,
+        project_id: Uuid,
+        id: Uuid,
+        request: CustomFieldRequest,
+    ) -> Result<CustomField, AppError> {
+        let mut tx = self.pool.begin().await?;
+        // Locking the field keeps task writes from using it meanwhile
+        let field = sqlx::query(&format!(
+            "SELECT {CUSTOM_FIELD_COLUMNS} FROM custom_fields \
+             WHERE id = $1 AND project_id = $2 FOR UPDATE"
+        ))
+        .bind(id)
+        .bind(project_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .map(|row| custom_field_from_row(&row))
+        .ok_or(AppError::CustomFieldNotFound)?;
+
+        let in_use: Vec<Json<serde_json::Value>> = sqlx::query_scalar(
+            "SELECT custom_fields -> $1 FROM tasks WHERE project_id = $2 AND custom_fields ? $1",
+        )
+        .bind(id.to_string())
+        .bind(project_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        let in_use: Vec<serde_json::Value> = in_use.into_iter().map(|value| value.0).collect();
+        check_field_change(&field, &request, &in_use)?;
+
+        let row = sqlx::query(&format!(
+            "UPDATE custom_fields SET name = $2, options = $3 WHERE id = $1 \
+             RETURNING {CUSTOM_FIELD_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(&request.name)
+        .bind(&request.options)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|err| field_name_conflict(err, &request.name))?;
+        tx.commit().await?;
+
+        Ok(custom_field_from_row(&row))
+    }
+
+    /// Deletes a custom field and strips its value from every task.
+    pub async fn delete_custom_field(&self, project_id: Uuid, id: Uuid) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+        let result = sqlx::query("DELETE FROM custom_fields WHERE id = $1 AND project_id = $2")
+            .bind(id)
+            .bind(project_id)
+            .execute(&mut *tx)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::CustomFieldNotFound);
+        }
+
+        sqlx::query(
+            "UPDATE tasks SET custom_fields = custom_fields - $1 \
+             WHERE project_id = $2 AND custom_fields ? $1",
+        )
+        .bind(id.to_string())
+        .bind(project_id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Returns every saved view, ordered by name.
+    pub async fn get_saved_views(&self) -> Result<Vec<SavedView>, AppError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {SAVED_VIEW_COLUMNS} FROM saved_views ORDER BY name COLLATE \"C\""
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(saved_view_from_row).collect())
+    }
+
+    pub async fn get_saved_view(&self, id: Uuid) -> Result<SavedView, AppError> {
+        let row = sqlx::query(&format!(
+            "SELECT {SAVED_VIEW_COLUMNS} FROM saved_views WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(saved_view_from_row(&row)),
+            None => Err(AppError::SavedViewNotFound),
+        }
+    }
+
+    pub async fn create_saved_view(
+        &self,
+        request: SavedViewRequest,
+    ) -> Result<SavedView, AppError> {
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO saved_views (id, name, project_id, filter, sort, grouping, columns)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING {SAVED_VIEW_COLUMNS}
+            "#
+        ))
+        .bind(Uuid::new_v4())
+        .bind(&request.name)
+        .bind(request.filter.project)
+        .bind(Json(&request.filter))
+        .bind(request.sort.map(|sort| sort.to_string()))
+        .bind(request.grouping)
+        .bind(column_names(&request.columns))
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|err| view_write_failed(err, &request))?;
+
+        Ok(saved_view_from_row(&row))
+    }
+
+    pub async fn update_saved_view(
+        &self,
+        id: Uuid,
+        request: SavedViewRequest,
+    ) -> Result<SavedView, AppError> {
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE saved_views
+            SET name = $2, project_id = $3, filter = $4, sort = $5, grouping = $6,
+                columns = $7, updated_at = NOW()
+            WHERE id = $1
+            RETURNING {SAVED_VIEW_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(&request.name)
+        .bind(request.filter.project)
+        .bind(Json(&request.filter))
+        .bind(request.sort.map(|sort| sort.to_string()))
+        .bind(request.grouping)
+        .bind(column_names(&request.columns))
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err| view_write_failed(err, &request))?;
+
+        match row {
+            Some(row) => Ok(saved_view_from_row(&row)),
+            None => Err(AppError::SavedViewNotFound),
+        }
+    }
+
+    pub async fn delete_saved_view(&self, id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query("DELETE FROM saved_views WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::SavedViewNotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TaskRepository for Database {
+    async fn create_task(
+        &self,
+        request: CreateTaskRequest,
+        context: &WriteContext,
+    ) -> Result<Task, AppError> {
+        Database::create_task(self, request, context).await
+    }
+
+    async fn get_task(&self, id: Uuid) -> Result<Task, AppError> {
+        self.get_task_by_id(id).await
+    }
+
+    async fn list_tasks(
+        &self,
+        filter: &TaskFilter,
+        sort: TaskSort,
+        after: Option<&Cursor>,
+        limit: u32,
+    ) -> Result<Page<Task>, AppError> {
+        self.get_task_page(filter, sort, after, limit).await
+    }
+
+    async fn update_task(
+        &self,
+        id: Uuid,
+        request: UpdateTaskRequest,
+        context: &WriteContext,
+    ) -> Result<Task, AppError> {
+        Database::update_task(self, id, request, context).await
+    }
+
+    async fn move_task(
+        &self,
+        id: Uuid,
+        request: MoveTaskRequest,
+        context: &WriteContext,
+    ) -> Result<Task, AppError> {
+        Database::move_task(self, id, request, context).await
+    }
+
+    async fn delete_task(&self, id: Uuid, context: &WriteContext) -> Result<(), AppError> {
+        Database::delete_task(self, id, context).await
+    }
+
+    async fn list_trash(&self) -> Result<Vec<Task>, AppError> {
+        self.get_trash().await
+    }
+
+    async fn restore_task(&self, id: Uuid, context: &WriteContext) -> Result<Task, AppError> {
+        Database::restore_task(self, id, context).await
+    }
+
+    async fn purge_task(&self, id: Uuid) -> Result<Purged, AppError> {
+        Database::purge_task(self, id).await
+    }
+
+    async fn purge_trash(&self, before: DateTime<Utc>) -> Result<Purged, AppError> {
+        Database::purge_trash(self, before).await
+    }
+
+    async fn task_history(&self, id: Uuid) -> Result<Vec<TaskEvent>, AppError> {
+        self.get_task_history(id).await
+    }
+
+    async fn list_children(&self, id: Uuid) -> Result<Vec<Task>, AppError> {
+        self.get_children(id).await
+    }
+
+    async fn list_dependencies(&self, id: Uuid) -> Result<TaskDependencies, AppError> {
+        self.get_dependencies(id).await
+    }
+
+    async fn add_dependency(&self, id: Uuid, blocker_id: Uuid) -> Result<(), AppError> {
+        Database::add_dependency(self, id, blocker_id).await
+    }
+
+    async fn remove_dependency(&self, id: Uuid, blocker_id: Uuid) -> Result<(), AppError> {
+        Database::remove_dependency(self, id, blocker_id).await
+    }
+
+    async fn list_comments(&self, task_id: Uuid) -> Result<Vec<Comment>, AppError> {
+        self.get_comments(task_id).await
+    }
+
+    async fn create_comment(
+        &self,
+        task_id: Uuid,
+        request: CommentRequest,
+        author: Option<String>,
+    ) -> Result<Comment, AppError> {
+        Database::create_comment(self, task_id, request, author).await
+    }
+
+    async fn update_comment(
+        &self,
+        task_id: Uuid,
+        id: Uuid,
+        request: CommentRequest,
+    ) -> Result<Comment, AppError> {
+        Database::update_comment(self, task_id, id, request).await
+    }
+
+    async fn delete_comment(&self, task_id: Uuid, id: Uuid) -> Result<(), AppError> {
+        Database::delete_comment(self, task_id, id).await
+    }
+
+    async fn list_time_entries(&self, task_id: Uuid) -> Result<Vec<TimeEntry>, AppError> {
+        self.get_time_entries(task_id).await
+    }
+
+    async fn start_timer(&self, task_id: Uuid, member_id: Uuid) -> Result<TimeEntry, AppError> {
+        Database::start_timer(self, task_id, member_id).await
+    }
+
+    async fn stop_timer(&self, task_id: Uuid, member_id: Uuid) -> Result<TimeEntry, AppError> {
+        Database::stop_timer(self, task_id, member_id).await
+    }
+
+    async fn create_time_entry(
+        &self,
+        task_id: Uuid,
+        request: TimeEntryRequest,
+    ) -> Result<TimeEntry, AppError> {
+        Database::create_time_entry(self, task_id, request).await
+    }
+
+    async fn update_time_entry(
+        &self,
+        task_id: Uuid,
+        id: Uuid,
+        request: TimeEntryRequest,
+    ) -> Result<TimeEntry, AppError> {
+        Database::update_time_entry(self, task_id, id, request).await
+    }
+
+    async fn delete_time_entry(&self, task_id: Uuid, id: Uuid) -> Result<(), AppError> {
+        Database::delete_time_entry(self, task_id, id).await
+    }
+
+    async fn list_attachments(&self, task_id: Uuid) -> Result<Vec<Attachment>, AppError> {
+        self.get_attachments(task_id).await
+    }
+
+    async fn get_attachment(&self, task_id: Uuid, id: Uuid) -> Result<Attachment, AppError> {
+        Database::get_attachment(self, task_id, id).await
+    }
+
+    async fn create_attachment(
+        &self,
+        task_id: Uuid,
+        attachment: NewAttachment,
+    ) -> Result<Attachment, AppError> {
+        Database::create_attachment(self, task_id, attachment).await
+    }
+
+    async fn delete_attachment(&self, task_id: Uuid, id: Uuid) -> Result<Option<String>, AppError> {
+        Database::delete_attachment(self, task_id, id).await
+    }
+
+    async fn list_tags(&self) -> Result<Vec<Tag>, AppError> {
+        self.get_tags().await
+    }
+
+    async fn create_tag(&self, request: TagRequest) -> Result<Tag, AppError> {
+        Database::create_tag(self, request).await
+    }
+
+    async fn update_tag(&self, id: Uuid, request: TagRequest) -> Result<Tag, AppError> {
+        Database::update_tag(self, id, request).await
+    }
+
+    async fn delete_tag(&self, id: Uuid) -> Result<(), AppError> {
+        Database::delete_tag(self, id).await
+    }
+
+    async fn list_members(&self) -> Result<Vec<Member>, AppError> {
+        self.get_members().await
+    }
+
+    async fn create_member(&self, request: MemberRequest) -> Result<Member, AppError> {
+        Database::create_member(self, request).await
+    }
+
+    async fn update_member(&self, id: Uuid, request: MemberRequest) -> Result<Member, AppError> {
+        Database::update_member(self, id, request).await
+    }
+
+    async fn delete_member(&self, id: Uuid) -> Result<(), AppError> {
+        Database::delete_member(self, id).await
+    }
+
+    async fn list_projects(&self) -> Result<Vec<Project>, AppError> {
+        self.get_projects().await
+    }
+
+    async fn get_project(&self, id: Uuid) -> Result<Project, AppError> {
+        Database::get_project(self, id).await
+    }
+
+    async fn create_project(&self, request: ProjectRequest) -> Result<Project, AppError> {
+        Database::create_project(self, request).await
+    }
+
+    async fn update_project(&self, id: Uuid, request: ProjectRequest) -> Result<Project, AppError> {
+        Database::update_project(self, id, request).await
+    }
+
+    async fn delete_project(&self, id: Uuid) -> Result<(), AppError> {
+        Database::delete_project(self, id).await
+    }
+
+    async fn count_in_state(&self, project_id: Uuid, status: &TaskStatus) -> Result<i64, AppError> {
+        Database::count_in_state(self, project_id, status).await
+    }
+
+    async fn get_workflow(&self, project_id: Uuid) -> Result<Workflow, AppError> {
+        Database::get_workflow(self, project_id).await
+    }
+
+    async fn update_workflow(
+        &self,
+        project_id: Uuid,
+        request: WorkflowRequest,
+    ) -> Result<Workflow, AppError> {
+        Database::update_workflow(self, project_id, request).await
+    }
+
+    async fn list_custom_fields(&self, project_id: Uuid) -> Result<Vec<CustomField>, AppError> {
+        Database::get_custom_fields(self, project_id).await
+    }
+
+    async fn get_custom_field(&self, id: Uuid) -> Result<CustomField, AppError> {
+        Database::get_custom_field(self, id).await
+    }
+
+    async fn create_custom_field(
+        &self,
+        project_id: Uuid,
+        request: CustomFieldRequest,
+    ) -> Result<CustomField, AppError> {
+        Database::create_custom_field(self, project_id, request).await
+    }
+
+    async fn update_custom_field(
+        &self,
+        project_id: Uuid,
+        id: Uuid,
+        request: CustomFieldRequest,
+    ) -> Result<CustomField, AppError> {
+        Database::update_custom_field(self, project_id, id, request).await
+    }
+
+    async fn delete_custom_field(&self, project_id: Uuid, id: Uuid) -> Result<(), AppError> {
+        Database::delete_custom_field(self, project_id, id).await
+    }
+
+    async fn list_saved_views(&self) -> Result<Vec<SavedView>, AppError> {
+        Database::get_saved_views(self).await
+    }
+
+    async fn get_saved_view(&self, id: Uuid) -> Result<SavedView, AppError> {
+        Database::get_saved_view(self, id).await
+    }
+
+    async fn create_saved_view(&self, request: SavedViewRequest) -> Result<SavedView, AppError> {
+        Database::create_saved_view(self, request).await
+    }
+
+    async fn update_saved_view(
+        &self,
+        id: Uuid,
+        request: SavedViewRequest,
+    ) -> Result<SavedView, AppError> {
+        Database::update_saved_view(self, id, request).await
+    }
+
+    async fn delete_saved_view(&self, id: Uuid) -> Result<(), AppError> {
+        Database::delete_saved_view(self, id).await
+    }
+}
+
+/// Applies an update to a live task inside a transaction. With `placement`,
+/// the task goes between the cards it names; otherwise a task that changes
+/// column goes to the bottom of its new one.
+async fn update_in(
+    conn: &mut PgConnection,
+    id: Uuid,
+    mut request: UpdateTaskRequest,
+    placement: Option<&MoveTaskRequest>,
+    context: &WriteContext,
+) -> Result<Task, AppError> {
+    let now = Utc::now();
+    let before = lock_live_task(conn, id).await?;
+    context.check_version(&before)?;
+    if let Patch::Value(assignee_id) = request.assignee_id {
+        lock_member(conn, assignee_id).await?;
+    }
+    if let Patch::Value(project_id) = request.project_id {
+        lock_project(conn, project_id).await?;
+    }
+
+    let project_id = match request.project_id {
+        Patch::Value(project_id) => project_id,
+        _ => before.project_id,
+    };
+    let moved = project_id != before.project_id;
+    if moved {
+        check_project_move(has_subtasks(conn, id).await?)?;
+    }
+    let parent_change = request.parent_id.clone().into_change();
+    if let Some(parent_id) = parent_change.unwrap_or(before.parent_id) {
+        if moved || parent_change.is_some() {
+            check_parent(conn, Some(id), parent_id, project_id).await?;
+        }
+    }
+    let workflow = workflow_of(conn, project_id).await?;
+    let target = target_state(&before, &mut request, &workflow)?;
+    settle_custom_fields(
+        &before,
+        &mut request,
+        &custom_fields_of(conn, project_id).await?,
+    )?;
+    check_transition(&before, &request, &workflow, &target)?;
+    check_blockers(&before, &target, context)?;
+    if wip_limited(&before, project_id, &target, context) {
+        // Held until commit, so two moves cannot both take the last place
+        lock_column(conn, project_id, &target.name).await?;
+        check_wip_limit(&target, column_size(conn, project_id, &target.name).await?)?;
+    }
+    let completing = is_completing(&before, &target);
+    if completing {
+        complete_subtasks(conn, id, &done_state(&workflow).name, context, now).await?;
+    }
+    let next = plan_recurrence(&before, &mut request, completing)?;
+    let rank = match placement {
+        Some(placement) => Some(place_task(conn, id, project_id, placement).await?),
+        None if moved || target.name != before.status => {
+            Some(bottom_rank(conn, project_id, &target.name, None).await?)
+        }
+        None => None,
+    };
+
+    let mut query =
+        QueryBuilder::<Postgres>::new("UPDATE tasks SET version = version + 1, updated_at = ");
+    query.push_bind(now);
+    push_assignment(&mut query, "title", request.title);
+    push_assignment(&mut query, "description", request.description);
+    push_assignment(&mut query, "status", request.status);
+    push_assignment(&mut query, "priority", request.priority);
+    push_assignment(&mut query, "due_date", request.due_date);
+    push_assignment(&mut query, "assignee_id", request.assignee_id);
+    push_assignment(&mut query, "project_id", request.project_id);
+    push_assignment(&mut query, "parent_id", request.parent_id);
+    push_assignment(&mut query, "recurrence", request.recurrence);
+    push_assignment(
+        &mut query,
+        "estimate_minutes",
+        request.estimate_minutes.map(|minutes| minutes as i32),
+    );
+    push_assignment(&mut query, "custom_fields", request.custom_fields.map(Json));
+    push_assignment(&mut query, "rank", rank.map_or(Patch::Absent, Patch::Value));
+    query.push(" WHERE id = ").push_bind(id);
+    query.push(format_args!(" RETURNING {TASK_COLUMNS}"));
+    let mut task = task_from_row(&query.build().fetch_one(&mut *conn).await?);
+
+    if let Some(names) = request.tags.into_change() {
+        set_task_tags(conn, id, &names.unwrap_or_default()).await?;
+    }
+    attach_tags(&mut *conn, slice::from_mut(&mut task)).await?;
+
+    let events = history::field_changes(&before, &task);
+    insert_events(conn, id, events, context, now).await?;
+    if let Some((due_date, recurrence)) = next {
+        let request = next_occurrence(&task, due_date, recurrence);
+        insert_task(conn, &request, context, now).await?;
+    }
+
+    Ok(task)
+}
+
+/// Inserts a checked task with its tags and creation event.
+async fn insert_task(
+    conn: &mut PgConnection,
+    request: &CreateTaskRequest,
+    context: &WriteContext,
+    now: DateTime<Utc>,
+) -> Result<Task, AppError> {
+    let id = Uuid::new_v4();
+    let project_id = request.project_id.unwrap_or(DEFAULT_PROJECT_ID);
+    let status: TaskStatus = sqlx::query_scalar(
+        "SELECT name FROM workflow_states WHERE project_id = $1 ORDER BY position LIMIT 1",
+    )
+    .bind(project_id)
+    .fetch_one(&mut *conn)
+    .await?;
+    let rank = bottom_rank(conn, project_id, &status, None).await?;
+    let row = sqlx::query(&format!(
+        r#"
+        INSERT INTO tasks (id, title, description, status, priority, due_date, assignee_id, project_id, parent_id, recurrence, rank, estimate_minutes, custom_fields, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+        RETURNING {TASK_COLUMNS}
+        "#
+    ))
+    .bind(id)
+    .bind(&request.title)
+    .bind(&request.description)
+    .bind(status)
+    .bind(request.priority)
+    .bind(request.due_date)
+    .bind(request.assignee_id)
+    .bind(project_id)
+    .bind(request.parent_id)
+    .bind(&request.recurrence)
+    .bind(rank)
+    .bind(request.estimate_minutes.map(|minutes| minutes as i32))
+    .bind(Json(&request.custom_fields))
+    .bind(now)
+    .bind(now)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let mut task = task_from_row(&row);
+    if !request.tags.is_empty() {
+        set_task_tags(conn, id, &request.tags).await?;
+        attach_tags(&mut *conn, slice::from_mut(&mut task)).await?;
+    }
+
+    let events = vec![NewTaskEvent::task(TaskEventKind::Created)];
+    insert_events(conn, id, events, context, now).await?;
+    Ok(task)
+}
+
+/// Reads a live task and locks its row until the transaction ends, so that
+/// the version check and history see the state being replaced.
+async fn lock_live_task(conn: &mut PgConnection, id: Uuid) -> Result<Task, AppError> {
+    let row = sqlx::query(&format!(
+        "SELECT {TASK_COLUMNS} FROM tasks WHERE id = $1 AND deleted_at IS NULL FOR UPDATE"
+    ))
+    .bind(id)
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or(AppError::TaskNotFound)?;
+
+    let mut task = task_from_row(&row);
+    attach_tags(conn, slice::from_mut(&mut task)).await?;
+    Ok(task)
+}
+
+/// Deletes the tasks `ids`, locked by the caller, with their attachments.
+/// Attachments go first so the blobs they used can be checked afterwards.
+async fn purge_tasks(conn: &mut PgConnection, ids: &[Uuid]) -> Result<Purged, AppError> {
+    let sha256s: Vec<String> =
+        sqlx::query_scalar("DELETE FROM attachments WHERE task_id = ANY($1) RETURNING sha256")
+            .bind(ids)
+            .fetch_all(&mut *conn)
+            .await?;
+    let result = sqlx::query("DELETE FROM tasks WHERE id = ANY($1)")
+        .bind(ids)
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(Purged {
+        tasks: result.rows_affected(),
+        blobs: unused_blobs(conn, sha256s).await?,
+    })
+}
+
+/// The blobs of `sha256s` no attachment uses, each once and in order.
+async fn unused_blobs(
+    conn: &mut PgConnection,
+    mut sha256s: Vec<String>,
+) -> Result<Vec<String>, AppError> {
+    sha256s.sort();
+    sha256s.dedup();
+    let used: Vec<String> =
+        sqlx::query_scalar("SELECT DISTINCT sha256 FROM attachments WHERE sha256 = ANY($1)")
+            .bind(&sha256s)
+            .fetch_all(conn)
+            .await?;
+    sha256s.retain(|sha256| !used.contains(sha256));
+    Ok(sha256s)
+}
+
+/// Checks that `id` is a member and keeps them from being deleted until the
+/// transaction ends, so a task is never assigned to someone just removed.
+async fn lock_member(conn: &mut PgConnection, id: Uuid) -> Result<(), AppError> {
+    sqlx::query("SELECT 1 FROM members WHERE id = $1 FOR SHARE")
+        .bind(id)
+        .fetch_optional(conn)
+        .await?
+        .map(|_| ())
+        .ok_or_else(|| unknown_member(id))
+}
+
+/// Checks that `id` is a member and holds their timer until the transaction
+/// ends, so two starts cannot both find it stopped.
+async fn lock_timer(conn: &mut PgConnection, id: Uuid) -> Result<(), AppError> {
+    sqlx::query("SELECT 1 FROM members WHERE id = $1 FOR UPDATE")
+        .bind(id)
+        .fetch_optional(conn)
+        .await?
+        .map(|_| ())
+        .ok_or_else(|| unknown_member(id))
+}
+
+/// Checks that a task (`None` for a new one) can go under `parent_id` in
+/// `project_id`, and keeps the parent from changing until the transaction
+/// ends.
+async fn check_parent(
+    conn: &mut PgConnection,
+    task_id: Option<Uuid>,
+    parent_id: Uuid,
+    project_id: Uuid,
+) -> Result<(), AppError> {
+    let parent_project_id: Uuid = sqlx::query_scalar(
+        "SELECT project_id FROM tasks WHERE id = $1 AND deleted_at IS NULL FOR SHARE",
+    )
+    .bind(parent_id)
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| unknown_parent(parent_id))?;
+    check_parent_project(parent_project_id, project_id)?;
+
+    // Walks up from the parent; the bound keeps a bad row from looping
+    let ancestry: Vec<Uuid> = sqlx::query_scalar(
+        r#"
+        WITH RECURSIVE ancestry(id, parent_id, depth) AS (
+            SELECT id, parent_id, 0 FROM tasks WHERE id = $1
+            UNION ALL
+            SELECT tasks.id, tasks.parent_id, ancestry.depth + 1
+            FROM tasks JOIN ancestry ON tasks.id = ancestry.parent_id
+            WHERE ancestry.depth < $2
+        )
+        SELECT id FROM ancestry ORDER BY depth
+        "#,
+    )
+    .bind(parent_id)
+    .bind(MAX_SUBTASK_DEPTH as i32)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let height = match task_id {
+        Some(task_id) => {
+            let height: i32 = sqlx::query_scalar(
+                r#"
+                WITH RECURSIVE subtree(id, depth) AS (
+                    SELECT id, 1 FROM tasks WHERE parent_id = $1
+                    UNION ALL
+                    SELECT tasks.id, subtree.depth + 1
+                    FROM tasks JOIN subtree ON tasks.parent_id = subtree.id
+                    WHERE subtree.depth <= $2
+                )
+                SELECT COALESCE(MAX(depth), 0) FROM subtree
+                "#,
+            )
+            .bind(task_id)
+            .bind(MAX_SUBTASK_DEPTH as i32)
+            .fetch_one(&mut *conn)
+            .await?;
+            height as usize
+        }
+        None => 0,
+    };
+
+    check_nesting(task_id, &ancestry, height)
+}
+
+/// Whether the task has subtasks, trashed ones included.
+async fn has_subtasks(conn: &mut PgConnection, id: Uuid) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM tasks WHERE parent_id = $1)")
+        .bind(id)
+        .fetch_one(conn)
+        .await
+}
+
+/// Refuses to complete a task with open live subtasks, or moves them to
+/// `done` too, as `context` says.
+async fn complete_subtasks(
+    conn: &mut PgConnection,
+    id: Uuid,
+    done: &TaskStatus,
+    context: &WriteContext,
+    now: DateTime<Utc>,
+) -> Result<(), AppError> {
+    let rows = sqlx::query(&format!(
+        r#"
+        WITH RECURSIVE subtree(id) AS (
+            SELECT id FROM tasks WHERE parent_id = $1 AND deleted_at IS NULL
+            UNION ALL
+            SELECT tasks.id FROM tasks JOIN subtree ON tasks.parent_id = subtree.id
+            WHERE tasks.deleted_at IS NULL
+        )
+        SELECT {TASK_COLUMNS} FROM tasks
+        WHERE id IN (SELECT id FROM subtree) AND status NOT IN (
+            SELECT name FROM workflow_states
+            WHERE workflow_states.project_id = tasks.project_id AND category = 'Done'
+        )
+        FOR UPDATE
+        "#
+    ))
+    .bind(id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    if rows.is_empty() {
+        return Ok(());
+    }
+    if context.subtask_completion == SubtaskCompletion::Block {
+        return Err(open_subtasks());
+    }
+
+    for row in rows {
+        let before = task_from_row(&row);
+        let rank = bottom_rank(conn, before.project_id, done, None).await?;
+        let row = sqlx::query(&format!(
+            "UPDATE tasks SET status = $2, rank = $3, version = version + 1, updated_at = $4 \
+             WHERE id = $1 RETURNING {TASK_COLUMNS}"
+        ))
+        .bind(before.id)
+        .bind(done)
+        .bind(rank)
+        .bind(now)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        let events = history::field_changes(&before, &task_from_row(&row));
+        insert_events(conn, before.id, events, context, now).await?;
+    }
+
+    Ok(())
+}
+
+/// Keeps other transactions from ranking cards into a board column until
+/// this one ends, so that two cards dropped into the same gap at once are
+/// not given the same rank. Take it after any row locks.
+async fn lock_column(
+    conn: &mut PgConnection,
+    project_id: Uuid,
+    status: &TaskStatus,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtextextended($1, 0))")
+        .bind(format!("{project_id}/{status}"))
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+/// Number of live tasks in a column.
+async fn column_size(
+    conn: &mut PgConnection,
+    project_id: Uuid,
+    status: &TaskStatus,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT COUNT(*) FROM tasks \
+         WHERE project_id = $1 AND status = $2 AND deleted_at IS NULL",
+    )
+    .bind(project_id)
+    .bind(status)
+    .fetch_one(conn)
+    .await
+}
+
+/// Rank below every card of a column, trashed ones included so that they
+/// come back in place when restored, leaving out task `except`.
+async fn bottom_rank(
+    conn: &mut PgConnection,
+    project_id: Uuid,
+    status: &TaskStatus,
+    except: Option<Uuid>,
+) -> Result<String, sqlx::Error> {
+    lock_column(conn, project_id, status).await?;
+    let last: Option<String> = sqlx::query_scalar(
+        "SELECT MAX(rank) FROM tasks \
+         WHERE project_id = $1 AND status = $2 AND id IS DISTINCT FROM $3",
+    )
+    .bind(project_id)
+    .bind(status)
+    .bind(except)
+    .fetch_one(conn)
+    .await?;
+
+    Ok(rank::between(last.as_deref(), None))
+}
+
+/// Rank that puts task `id` where `placement` says in its column of
+/// `project_id`.
+async fn place_task(
+    conn: &mut PgConnection,
+    id: Uuid,
+    project_id: Uuid,
+    placement: &MoveTaskRequest,
+) -> Result<String, AppError> {
+    let status = &placement.status;
+    let (after, before) = match (placement.after_id, placement.before_id) {
+        (Some(after_id), _) => {
+            lock_column(conn, project_id, status).await?;
+            let after = neighbour_rank(conn, after_id, project_id, status).await?;
+            let before = next_rank(conn, id, project_id, status, &after, true).await?;
+            (Some(after), before)
+        }
+        (None, Some(before_id)) => {
+            lock_column(conn, project_id, status).await?;
+            let before = neighbour_rank(conn, before_id, project_id, status).await?;
+            let after = next_rank(conn, id, project_id, status, &before, false).await?;
+            (after, Some(before))
+        }
+        (None, None) => return Ok(bottom_rank(conn, project_id, status, Some(id)).await?),
+    };
+
+    Ok(rank::between(after.as_deref(), before.as_deref()))
+}
+
+/// Rank of a card a move names, which must still be live in the column.
+async fn neighbour_rank(
+    conn: &mut PgConnection,
+    id: Uuid,
+    project_id: Uuid,
+    status: &TaskStatus,
+) -> Result<String, AppError> {
+    sqlx::query_scalar(
+        "SELECT rank FROM tasks \
+         WHERE id = $1 AND project_id = $2 AND status = $3 AND deleted_at IS NULL",
+    )
+    .bind(id)
+    .bind(project_id)
+    .bind(status)
+    .fetch_optional(conn)
+    .await?
+    .ok_or_else(neighbour_moved)
+}
+
+/// Rank of the card next to `rank` in a column, leaving out task `id`: the
+/// one below it when `below`, otherwise the one above.
+async fn next_rank(
+    conn: &mut PgConnection,
+    id: Uuid,
+    project_id: Uuid,
+    status: &TaskStatus,
+    rank: &str,
+    below: bool,
+) -> Result<Option<String>, sqlx::Error> {
+    let query = if below {
+        "SELECT MIN(rank) FROM tasks \
+         WHERE project_id = $1 AND status = $2 AND id <> $3 AND rank > $4"
+    } else {
+        "SELECT MAX(rank) FROM tasks \
+         WHERE project_id = $1 AND status = $2 AND id <> $3 AND rank < $4"
+    };
+    sqlx::query_scalar(query)
+        .bind(project_id)
+        .bind(status)
+        .bind(id)
+        .bind(rank)
+        .fetch_one(conn)
+        .await
+}
+
+/// The states of a project's workflow in board order.
+async fn workflow_of(conn: &mut PgConnection, project_id: Uuid) -> Result<Workflow, sqlx::Error> {
+    let rows = sqlx::query(&format!(
+        "SELECT {STATE_COLUMNS} FROM workflow_states WHERE project_id = $1 ORDER BY position"
+    ))
+    .bind(project_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let rules = sqlx::query(
+        "SELECT priority, required_fields FROM priority_rules WHERE project_id = $1 \
+         ORDER BY priority",
+    )
+    .bind(project_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(Workflow {
+        project_id,
+        states: rows.iter().map(state_from_row).collect(),
+        priority_rules: rules
+            .iter()
+            .map(|row| PriorityRule {
+                priority: row.get("priority"),
+                requires: fields_from(row.get("required_fields")),
+            })
+            .collect(),
+    })
+}
+
+/// A project's custom fields in the order they were added, locked against
+/// changes until the transaction ends.
+async fn custom_fields_of(
+    conn: &mut PgConnection,
+    project_id: Uuid,
+) -> Result<Vec<CustomField>, sqlx::Error> {
+    let rows = sqlx::query(&format!(
+        "SELECT {CUSTOM_FIELD_COLUMNS} FROM custom_fields WHERE project_id = $1 \
+         ORDER BY position, id FOR SHARE"
+    ))
+    .bind(project_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(rows.iter().map(custom_field_from_row).collect())
+}
+
+/// Replaces the priority rules of a project's workflow.
+async fn replace_priority_rules(
+    conn: &mut PgConnection,
+    project_id: Uuid,
+    rules: &[PriorityRule],
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM priority_rules WHERE project_id = $1")
+        .bind(project_id)
+        .execute(&mut *conn)
+        .await?;
+    if rules.is_empty() {
+        return Ok(());
+    }
+
+    let mut query = QueryBuilder::<Postgres>::new(
+        "INSERT INTO priority_rules (project_id, priority, required_fields) ",
+    );
+    query.push_values(rules, |mut values, rule| {
+        values
+            .push_bind(project_id)
+            .push_bind(rule.priority)
+            .push_bind(field_names(&rule.requires));
+    });
+    query.build().execute(conn).await?;
+
+    Ok(())
+}
+
+/// Adds `states` to a project's workflow in the order given, updating the
+/// category, WIP limit, rules and position of the ones it has.
+async fn upsert_states(
+    conn: &mut PgConnection,
+    project_id: Uuid,
+    states: &[WorkflowState],
+) -> Result<(), sqlx::Error> {
+    let mut query = QueryBuilder::<Postgres>::new(
+        "INSERT INTO workflow_states \
+         (project_id, name, category, wip_limit, next_states, required_fields, position) ",
+    );
+    query.push_values(
+        states.iter().zip(0i32..),
+        |mut values, (state, position)| {
+            values
+                .push_bind(project_id)
+                .push_bind(state.name.clone())
+                .push_bind(state.category)
+                .push_bind(state.wip_limit.map(|limit| limit as i32))
+                .push_bind(state.next.as_ref().map(|next| {
+                    next.iter()
+                        .map(|status| status.as_str().to_string())
+                        .collect::<Vec<_>>()
+                }))
+                .push_bind(field_names(&state.requires))
+                .push_bind(position);
+        },
+    );
+    query.push(
+        " ON CONFLICT (project_id, name) \
+         DO UPDATE SET category = EXCLUDED.category, wip_limit = EXCLUDED.wip_limit, \
+         next_states = EXCLUDED.next_states, required_fields = EXCLUDED.required_fields, \
+         position = EXCLUDED.position",
+    );
+    query.build().execute(conn).await?;
+
+    Ok(())
+}
+
+/// Checks that project `id` exists and keeps it from being deleted until the
+/// transaction ends.
+async fn lock_project(conn: &mut PgConnection, id: Uuid) -> Result<(), AppError> {
+    sqlx::query("SELECT 1 FROM projects WHERE id = $1 FOR SHARE")
+        .bind(id)
+        .fetch_optional(conn)
+        .await?
+        .map(|_| ())
+        .ok_or_else(|| unknown_project(id))
+}
+
+/// Fills in the tags of `tasks`, each ordered by name.
+async fn attach_tags<'c, E>(executor: E, tasks: &mut [Task]) -> Result<(), sqlx::Error>
+where
+    E: Executor<'c, Database = Postgres>,
+{
+    if tasks.is_empty() {
+        return Ok(());
+    }
+
+    let ids: Vec<Uuid> = tasks.iter().map(|task| task.id).collect();
+    let rows = sqlx::query(
+        r#"
+        SELECT task_tags.task_id, tags.id, tags.name, tags.color
+        FROM task_tags JOIN tags ON tags.id = task_tags.tag_id
+        WHERE task_tags.task_id = ANY($1)
+        ORDER BY tags.name COLLATE "C"
+        "#,
+    )
+    .bind(ids)
+    .fetch_all(executor)
+    .await?;
+
+    for row in &rows {
+        let task_id: Uuid = row.get("task_id");
+        if let Some(task) = tasks.iter_mut().find(|task| task.id == task_id) {
+            task.tags.push(tag_from_row(row));
+        }
+    }
+
+    Ok(())
+}
+
+/// Replaces the tags on a task with the ones named, which must all exist.
+async fn set_task_tags(
+    conn: &mut PgConnection,
+    task_id: Uuid,
+    names: &[String],
+) -> Result<(), AppError> {
+    let rows = sqlx::query(&format!(
+        "SELECT {TAG_COLUMNS} FROM tags WHERE name = ANY($1)"
+    ))
+    .bind(names)
+    .fetch_all(&mut *conn)
+    .await?;
+    let tags: Vec<Tag> = rows.iter().map(tag_from_row).collect();
+    check_tags_exist(names, &tags)?;
+
+    sqlx::query("DELETE FROM task_tags WHERE task_id = $1")
+        .bind(task_id)
+        .execute(&mut *conn)
+        .await?;
+
+    if tags.is_empty() {
+        return Ok(());
+    }
+
+    let mut query = QueryBuilder::<Postgres>::new("INSERT INTO task_tags (task_id, tag_id) ");
+    query.push_values(tags, |mut values, tag| {
+        values.push_bind(task_id).push_bind(tag.id);
+    });
+    query.build().execute(conn).await?;
+
+    Ok(())
+}
+
+async fn insert_events(
+    conn: &mut PgConnection,
+    task_id: Uuid,
+    events: Vec<NewTaskEvent>,
+    context: &WriteContext,
+    at: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    if events.is_empty() {
+        return Ok(());
+    }
+
+    let mut query = QueryBuilder::<Postgres>::new(
+        "INSERT INTO task_events (task_id, kind, field, old_value, new_value, actor, created_at) ",
+    );
+    query.push_values(events, |mut values, event| {
+        values
+            .push_bind(task_id)
+            .push_bind(event.kind)
+            .push_bind(event.field)
+            .push_bind(event.old_value)
+            .push_bind(event.new_value)
+            .push_bind(context.actor.clone())
+            .push_bind(at);
+    });
+    query.build().execute(conn).await?;
+
+    Ok(())
+}
+
+/// Appends `, column = value` for a field present in a partial update.
+fn push_assignment<'a, T>(query: &mut QueryBuilder<'a, Postgres>, column: &str, patch: Patch<T>)
+where
+    T: 'a + sqlx::Encode<'a, Postgres> + sqlx::Type<Postgres>,
+{
+    match patch {
+        Patch::Absent => {}
+        Patch::Null => {
+            query.push(format_args!(", {column} = NULL"));
+        }
+        Patch::Value(value) => {
+            query.push(format_args!(", {column} = ")).push_bind(value);
+        }
+    }
+}
+
+fn task_from_row(row: &PgRow) -> Task {
+    // Highlight columns are only selected when the filter has a search query
+    let search = row
+        .try_get::<String, _>("title_highlight")
+        .ok()
+        .map(|title| SearchMatch {
+            title: search::parse_highlight(&title),
+            snippet: search::parse_highlight(row.get("snippet")),
+        });
+
+    Task {
+        id: row.get("id"),
+        title: row.get("title"),
+        description: row.get("description"),
+        status: row.get("status"),
+        status_category: row.get("status_category"),
+        priority: row.get("priority"),
+        due_date: row.get("due_date"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+        version: row.get("version"),
+        deleted_at: row.get("deleted_at"),
+        comment_count: row.get("comment_count"),
+        tags: Vec::new(),
+        assignee_id: row.get("assignee_id"),
+        project_id: row.get("project_id"),
+        parent_id: row.get("parent_id"),
+        subtask_count: row.get("subtask_count"),
+        completed_subtask_count: row.get("completed_subtask_count"),
+        is_blocked: row.get("is_blocked"),
+        recurrence: row.get("recurrence"),
+        rank: row.get("rank"),
+        estimate_minutes: row
+            .get::<Option<i32>, _>("estimate_minutes")
+            .map(|minutes| minutes as u32),
+        logged_minutes: row.get("logged_minutes"),
+        timer_started_at: row.get("timer_started_at"),
+        custom_fields: row.get::<Json<CustomFieldValues>, _>("custom_fields").0,
+        search,
+    }
+}
+
+fn tag_from_row(row: &PgRow) -> Tag {
+    Tag {
+        id: row.get("id"),
+        name: row.get("name"),
+        color: row.get("color"),
+    }
+}
+
+fn comment_from_row(row: &PgRow) -> Comment {
+    Comment {
+        id: row.get("id"),
+        task_id: row.get("task_id"),
+        body: row.get("body"),
+        author: row.get("author"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+fn time_entry_from_row(row: &PgRow) -> TimeEntry {
+    TimeEntry {
+        id: row.get("id"),
+        task_id: row.get("task_id"),
+        member_id: row.get("member_id"),
+        started_at: row.get("started_at"),
+        ended_at: row.get("ended_at"),
+        note: row.get("note"),
+    }
+}
+
+fn attachment_from_row(row: &PgRow) -> Attachment {
+    Attachment {
+        id: row.get("id"),
+        task_id: row.get("task_id"),
+        filename: row.get("filename"),
+        content_type: row.get("content_type"),
+        size_bytes: row.get("size_bytes"),
+        sha256: row.get("sha256"),
+        created_at: row.get("created_at"),
+    }
+}
+
+fn member_from_row(row: &PgRow) -> Member {
+    Member {
+        id: row.get("id"),
+        name: row.get("name"),
+    }
+}
+
+fn project_from_row(row: &PgRow) -> Project {
+    Project {
+        id: row.get("id"),
+        name: row.get("name"),
+    }
+}
+
+fn custom_field_from_row(row: &PgRow) -> CustomField {
+    CustomField {
+        id: row.get("id"),
+        project_id: row.get("project_id"),
+        name: row.get("name"),
+        field_type: row.get("field_type"),
+        options: row.get("options"),
+    }
+}
+
+// The sort is only ever written from `TaskSort`'s `Display`
+fn saved_view_from_row(row: &PgRow) -> SavedView {
+    SavedView {
+        id: row.get("id"),
+        name: row.get("name"),
+        filter: row.get::<Json<TaskFilter>, _>("filter").0,
+        sort: row
+            .get::<Option<String>, _>("sort")
+            .and_then(|sort| sort.parse().ok()),
+        grouping: row.get("grouping"),
+        columns: row
+            .get::<Vec<String>, _>("columns")
+            .into_iter()
+            .map(TaskStatus::new)
+            .collect(),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+fn state_from_row(row: &PgRow) -> WorkflowState {
+    WorkflowState {
+        name: row.get("name"),
+        category: row.get("category"),
+        wip_limit: row
+            .get::<Option<i32>, _>("wip_limit")
+            .map(|limit| limit as u32),
+        next: row
+            .get::<Option<Vec<String>>, _>("next_states")
+            .map(|next| next.into_iter().map(TaskStatus::new).collect()),
+        requires: fields_from(row.get("required_fields")),
+    }
+}
+
+fn column_names(columns: &[TaskStatus]) -> Vec<&str> {
+    columns.iter().map(TaskStatus::as_str).collect()
+}
+
+fn field_names(fields: &[TaskField]) -> Vec<&'static str> {
+    fields.iter().map(|field| field.as_str()).collect()
+}
+
+// Only ever written from `TaskField::as_str`
+fn fields_from(names: Vec<String>) -> Vec<TaskField> {
+    names.iter().filter_map(|name| name.parse().ok()).collect()
+}
+
+fn sort_key_from_row(row: &PgRow, field: TaskSortField) -> SortKey {
+    match field {
+        TaskSortField::CreatedAt => SortKey::CreatedAt(row.get("created_at")),
+        TaskSortField::UpdatedAt => SortKey::UpdatedAt(row.get("updated_at")),
+        TaskSortField::DueDate => SortKey::DueDate(row.get("due_date")),
+        TaskSortField::Priority => SortKey::Priority(row.get("priority")),
+        TaskSortField::Title => SortKey::Title(row.get("title")),
+        TaskSortField::Relevance => SortKey::Relevance(f64::from(row.get::<f32, _>("relevance"))),
+    }
+}
+
+/// Starts a task listing query with its `SELECT`, `FROM` and filter
+/// conditions, adding relevance and highlight columns for search queries.
+fn select_tasks(filter: &TaskFilter) -> (QueryBuilder<'static, Postgres>, Conditions) {
+    let mut query = QueryBuilder::<Postgres>::new(format!("SELECT {TASK_COLUMNS}"));
+
+    if search_query(filter).is_some() {
+        let options = format!(
+            "StartSel={}, StopSel={}",
+            search::HIGHLIGHT_START,
+            search::HIGHLIGHT_STOP
+        );
+        query.push(", ts_rank(search_vector, search_query) AS relevance");
+        query.push(format_args!(
+            ", ts_headline('{}', title, search_query, ",
+            search::SEARCH_CONFIG
+        ));
+        query.push_bind(format!("{options}, HighlightAll=true"));
+        query.push(") AS title_highlight");
+        query.push(format_args!(
+            ", ts_headline('{}', COALESCE(description, ''), search_query, ",
+            search::SEARCH_CONFIG
+        ));
+        query.push_bind(format!(
+            "{options}, MaxFragments=2, MaxWords=20, MinWords=5"
+        ));
+        query.push(") AS snippet");
+    }
+
+    push_from(&mut query, filter);
+    let mut conditions = Conditions::default();
+    push_filter(&mut query, &mut conditions, filter);
+
+    (query, conditions)
+}
+
+fn search_query(filter: &TaskFilter) -> Option<String> {
+    filter.search_text().as_deref().and_then(search::to_tsquery)
+}
+
+/// Pushes the `FROM` clause, exposing the parsed search as `search_query`.
+fn push_from(query: &mut QueryBuilder<'_, Postgres>, filter: &TaskFilter) {
+    query.push(" FROM tasks");
+    if let Some(tsquery) = search_query(filter) {
+        query.push(format_args!(", to_tsquery('{}', ", search::SEARCH_CONFIG));
+        query.push_bind(tsquery).push(") AS search_query");
+    }
+}
+
+/// Joins conditions into a `WHERE` clause: `WHERE` before the first one,
+/// `AND` before the rest.
+#[derive(Default)]
+struct Conditions {
+    started: bool,
+}
+
+impl Conditions {
+    fn next(&mut self, query: &mut QueryBuilder<'_, Postgres>) {
+        query.push(if self.started { " AND " } else { " WHERE " });
+        self.started = true;
+    }
+}
+
+/// Appends parameterized conditions for `filter` to `query`, always
+/// leaving out trashed tasks.
+///
+/// Each criterion compares a bare indexed column against a bound value so the
+/// planner can use the `idx_tasks_*` indexes.
+fn push_filter(
+    query: &mut QueryBuilder<'_, Postgres>,
+    conditions: &mut Conditions,
+    filter: &TaskFilter,
+) {
+    conditions.next(query);
+    query.push("deleted_at IS NULL");
+
+    if !filter.status.is_empty() {
+        conditions.next(query);
+        query.push("status IN (");
+        let mut values = query.separated(", ");
+        for status in &filter.status {
+            values.push_bind(status.clone());
+        }
+        query.push(")");
+    }
+
+    if !filter.priority.is_empty() {
+        conditions.next(query);
+        query.push("priority IN (");
+        let mut values = query.separated(", ");
+        for priority in &filter.priority {
+            values.push_bind(*priority);
+        }
+        query.push(")");
+    }
+
+    match filter.has_due_date {
+        Some(true) => {
+            conditions.next(query);
+            query.push("due_date IS NOT NULL");
+        }
+        Some(false) => {
+            conditions.next(query);
+            query.push("due_date IS NULL");
+        }
+        None => {}
+    }
+
+    if search_query(filter).is_some() {
+        conditions.next(query);
+        query.push("search_vector @@ search_query");
+    }
+
+    if let Some(assignee) = filter.assignee {
+        conditions.next(query);
+        query.push("assignee_id = ").push_bind(assignee);
+    }
+
+    if let Some(project) = filter.project {
+        conditions.next(query);
+        query.push("project_id = ").push_bind(project);
+    }
+
+    if let Some(tag) = &filter.tag {
+        conditions.next(query);
+        query.push("EXISTS (SELECT 1");
+        push_tagged(query, slice::from_ref(tag));
+        query.push(")");
+    }
+
+    if !filter.any_tag.is_empty() {
+        conditions.next(query);
+        query.push("EXISTS (SELECT 1");
+        push_tagged(query, &filter.any_tag);
+        query.push(")");
+    }
+
+    if !filter.all_tags.is_empty() {
+        let names: BTreeSet<&String> = filter.all_tags.iter().collect();
+        conditions.next(query);
+        query.push("(SELECT COUNT(*)");
+        push_tagged(query, &filter.all_tags);
+        query.push(") = ").push_bind(names.len() as i64);
+    }
+
+    for field in &filter.custom_fields {
+        // An array contains a scalar it lists, so this also matches the
+        // options of a multi-select field
+        conditions.next(query);
+        query
+            .push("custom_fields -> ")
+            .push_bind(field.field_id.to_string())
+            .push(" @> ")
+            .push_bind(Json(field.value.clone()));
+    }
+
+    let ranges = [
+        ("due_date", "<", filter.due_before),
+        ("due_date", ">", filter.due_after),
+        ("created_at", "<", filter.created_before),
+        ("created_at", ">", filter.created_after),
+        ("updated_at", "<", filter.updated_before),
+        ("updated_at", ">", filter.updated_after),
+    ];
+    for (column, op, bound) in ranges {
+        if let Some(bound) = bound {
+            conditions.next(query);
+            query.push(format_args!("{column} {op} ")).push_bind(bound);
+        }
+    }
+
+    if let Some(task_query) = &filter.query {
+        for (negated, condition) in task_query.conditions() {
+            conditions.next(query);
+            // A negated condition also keeps the tasks it compares as NULL
+            // with, such as those without a due date
+            query.push(if negated { "NOT COALESCE((" } else { "(" });
+            push_condition(query, condition);
+            query.push(if negated { "), FALSE)" } else { ")" });
+        }
+    }
+}
+
+/// Pushes one condition of a task query.
+fn push_condition(query: &mut QueryBuilder<'_, Postgres>, condition: &Condition) {
+    match condition {
+        Condition::Status(status) => {
+            query.push("status = ").push_bind(status.clone());
+        }
+        Condition::Priority(comparison, priority) => {
+            let priorities = comparison.priorities(*priority);
+            if priorities.is_empty() {
+                query.push("FALSE");
+                return;
+            }
+            query.push("priority IN (");
+            let mut values = query.separated(", ");
+            for priority in priorities {
+                values.push_bind(priority);
+            }
+            query.push(")");
+        }
+        Condition::Date(field, comparison, date) => {
+            let column = match field {
+                DateField::Due => "due_date",
+                DateField::Created => "created_at",
+                DateField::Updated => "updated_at",
+            };
+            let (start, end) = comparison.day_range(*date);
+            let mut bounds = query.separated(" AND ");
+            if let Some(start) = start {
+                bounds
+                    .push(format_args!("{column} >= "))
+                    .push_bind_unseparated(start);
+            }
+            if let Some(end) = end {
+                bounds
+                    .push(format_args!("{column} < "))
+                    .push_bind_unseparated(end);
+            }
+        }
+        Condition::NoDueDate => {
+            query.push("due_date IS NULL");
+        }
+        Condition::Tag(name) => {
+            query.push("EXISTS (SELECT 1");
+            push_tagged(query, slice::from_ref(name));
+            query.push(")");
+        }
+        Condition::Assignee(None) => {
+            query.push("assignee_id IS NULL");
+        }
+        Condition::Assignee(Some(name)) => {
+            query
+                .push("assignee_id IN (SELECT id FROM members WHERE name = ")
+                .push_bind(name.clone())
+                .push(")");
+        }
+    }
+}
+
+/// Pushes the `FROM` and `WHERE` of a subquery over the task's tags that are
+/// among `names`.
+fn push_tagged(query: &mut QueryBuilder<'_, Postgres>, names: &[String]) {
+    query.push(
+        " FROM task_tags JOIN tags ON tags.id = task_tags.tag_id \
+         WHERE task_tags.task_id = tasks.id AND tags.name IN (",
+    );
+    let mut values = query.separated(", ");
+    for name in names {
+        values.push_bind(name.clone());
+    }
+    query.push(")");
+}
+
+/// Sort column expression. Titles compare bytewise so the order does not
+/// depend on the database collation.
+fn sort_column(field: TaskSortField) -> &'static str {
+    match field {
+        TaskSortField::CreatedAt => "created_at",
+        TaskSortField::UpdatedAt => "updated_at",
+        TaskSortField::DueDate => "due_date",
+        TaskSortField::Priority => "priority",
+        TaskSortField::Title => "title COLLATE \"C\"",
+        TaskSortField::Relevance => "ts_rank(search_vector, search_query)",
+    }
+}
+
+fn push_order_by(query: &mut QueryBuilder<'_, Postgres>, sort: TaskSort) {
+    let direction = match sort.direction {
+        SortDirection::Asc => "ASC",
+        SortDirection::Desc => "DESC",
+    };
+    let nulls = if sort.field == TaskSortField::DueDate {
+        " NULLS LAST"
+    } else {
+        ""
+    };
+    query.push(format_args!(
+        " ORDER BY {} {direction}{nulls}, id {direction}",
+        sort_column(sort.field)
+    ));
+}
+
+/// Appends the keyset condition selecting rows that sort strictly after
+/// `cursor`, consistent with [`push_order_by`].
+fn push_keyset(query: &mut QueryBuilder<'_, Postgres>, sort: TaskSort, cursor: &Cursor) {
+    let column = sort_column(sort.field);
+    let op = match sort.direction {
+        SortDirection::Asc => ">",
+        SortDirection::Desc => "<",
+    };
+
+    // Tasks without a due date come last, ordered only by id
+    if cursor.key == SortKey::DueDate(None) {
+        query.push(format_args!("({column} IS NULL AND id {op} "));
+        query.push_bind(cursor.id).push(")");
+        return;
+    }
+
+    query.push(format_args!("({column} {op} "));
+    push_key(query, &cursor.key);
+    query.push(format_args!(" OR ({column} = "));
+    push_key(query, &cursor.key);
+    query.push(format_args!(" AND id {op} "));
+    query.push_bind(cursor.id).push(")");
+    if sort.field == TaskSortField::DueDate {
+        query.push(format_args!(" OR {column} IS NULL"));
+    }
+    query.push(")");
+}
+
+fn push_key(query: &mut QueryBuilder<'_, Postgres>, key: &SortKey) {
+    match key {
+        SortKey::CreatedAt(value) | SortKey::UpdatedAt(value) => query.push_bind(*value),
+        SortKey::DueDate(value) => query.push_bind(*value),
+        SortKey::Priority(value) => query.push_bind(*value),
+        SortKey::Title(value) => query.push_bind(value.clone()),
+        SortKey::Relevance(value) => query.push_bind(*value),
+    };
 }
@@ -3,6 +3,7 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
+use common::transition::TransitionError;
 use serde_json::json;
 use thiserror::Error;
 
@@ -14,11 +15,55 @@ pub enum AppError {
     #[error("Task not found")]
     TaskNotFound,
 
+    #[error("Comment not found")]
+    CommentNotFound,
+
+    #[error("Time entry not found")]
+    TimeEntryNotFound,
+
+    #[error("Attachment not found")]
+    AttachmentNotFound,
+
+    #[error("Tag not found")]
+    TagNotFound,
+
+    #[error("Member not found")]
+    MemberNotFound,
+
+    #[error("Project not found")]
+    ProjectNotFound,
+
+    #[error("Custom field not found")]
+    CustomFieldNotFound,
+
+    #[error("Saved view not found")]
+    SavedViewNotFound,
+
+    #[error("Dependency not found")]
+    DependencyNotFound,
+
+    #[error("Dependency would create a cycle")]
+    DependencyCycle,
+
     #[error("Invalid input: {0}")]
     InvalidInput(String),
 
+    #[error("Task has been modified since it was read")]
+    PreconditionFailed,
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("{0}")]
+    PayloadTooLarge(String),
+
+    #[error("{0}")]
+    Transition(#[from] TransitionError),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] std::io::Error),
+
     #[error("Internal server error")]
-    #[allow(dead_code)]
     InternalError,
 }
 
@@ -57,8 +102,34 @@ impl IntoResponse for AppError {
 
                 (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
             }
+            AppError::Storage(err) => {
+                tracing::error!("Storage error: {}", err);
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            AppError::Transition(err) => {
+                // The reason goes alongside the message so clients can act on it
+                let mut body = json!(err);
+                body["error"] = json!(err.to_string());
+                return (StatusCode::UNPROCESSABLE_ENTITY, Json(body)).into_response();
+            }
             AppError::TaskNotFound => (StatusCode::NOT_FOUND, "Task not found"),
+            AppError::CommentNotFound => (StatusCode::NOT_FOUND, "Comment not found"),
+            AppError::TimeEntryNotFound => (StatusCode::NOT_FOUND, "Time entry not found"),
+            AppError::AttachmentNotFound => (StatusCode::NOT_FOUND, "Attachment not found"),
+            AppError::TagNotFound => (StatusCode::NOT_FOUND, "Tag not found"),
+            AppError::MemberNotFound => (StatusCode::NOT_FOUND, "Member not found"),
+            AppError::ProjectNotFound => (StatusCode::NOT_FOUND, "Project not found"),
+            AppError::CustomFieldNotFound => (StatusCode::NOT_FOUND, "Custom field not found"),
+            AppError::SavedViewNotFound => (StatusCode::NOT_FOUND, "Saved view not found"),
+            AppError::DependencyNotFound => (StatusCode::NOT_FOUND, "Dependency not found"),
+            AppError::DependencyCycle => (StatusCode::CONFLICT, "Dependency would create a cycle"),
             AppError::InvalidInput(msg) => (StatusCode::BAD_REQUEST, msg.as_str()),
+            AppError::PreconditionFailed => (
+                StatusCode::PRECONDITION_FAILED,
+                "Task has been modified since it was read",
+            ),
+            AppError::Conflict(msg) => (StatusCode::CONFLICT, msg.as_str()),
+            AppError::PayloadTooLarge(msg) => (StatusCode::PAYLOAD_TOO_LARGE, msg.as_str()),
             AppError::InternalError => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error"),
         };
 
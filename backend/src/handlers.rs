@@ -1,62 +1,1296 @@
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{multipart::MultipartError, Multipart, Path, Query, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode},
     response::Json,
 };
-use common::{CreateTaskRequest, Task, TaskFilter, UpdateTaskRequest};
-use serde::Deserialize;
+use chrono::{DateTime, Utc};
+use common::{
+    custom_fields::{CustomField, CustomFieldFilter, CustomFieldRequest},
+    query::TaskQuery,
+    recurrence::{Recurrence, DEFAULT_OCCURRENCE_PREVIEW, MAX_OCCURRENCE_PREVIEW},
+    saved_views::{SavedView, SavedViewRequest},
+    transition::TaskField,
+    Attachment, Comment, CommentRequest, CreateTaskRequest, Member, MemberRequest, MoveTaskRequest,
+    Page, Patch, Project, ProjectRequest, ReplaceTaskRequest, SortDirection, StatusCategory, Tag,
+    TagRequest, Task, TaskDependencies, TaskEvent, TaskFilter, TaskSort, TaskSortField, TimeEntry,
+    TimeEntryRequest, TimerRequest, UpdateTaskRequest, Workflow, WorkflowRequest, WorkflowState,
+    DEFAULT_PAGE_SIZE, MAX_ATTACHMENT_NAME_LENGTH, MAX_COMMENT_LENGTH, MAX_ESTIMATE_MINUTES,
+    MAX_MEMBER_NAME_LENGTH, MAX_PAGE_SIZE, MAX_PROJECT_NAME_LENGTH, MAX_STATE_NAME_LENGTH,
+    MAX_TAG_NAME_LENGTH, MAX_TIME_ENTRY_NOTE_LENGTH, MAX_WIP_LIMIT, MAX_WORKFLOW_STATES,
+};
+use serde::{
+    de::{DeserializeOwned, Error as _, IntoDeserializer},
+    Deserialize, Deserializer,
+};
 use uuid::Uuid;
 
-use crate::{error::AppError, AppState};
+use crate::{
+    blob::{self, content_key, sniff_content_type},
+    error::AppError,
+    pagination::Cursor,
+    repository::{NewAttachment, WipLimits, WriteContext},
+    search, AppState,
+};
 
 pub async fn list_tasks(
     State(app_state): State<AppState>,
     Query(filter): Query<TaskFilterQuery>,
-) -> Result<Json<Vec<Task>>, AppError> {
+    Query(params): Query<Vec<(String, String)>>,
+    Query(page): Query<PageQuery>,
+) -> Result<Json<Page<Task>>, AppError> {
+    let custom_fields = custom_field_filters(&app_state, params).await?;
+    let query = match filter.query.as_deref().map(str::parse::<TaskQuery>) {
+        Some(Ok(query)) if !query.terms.is_empty() => Some(query),
+        Some(Err(err)) => return Err(AppError::InvalidInput(format!("Invalid query: {err}"))),
+        _ => None,
+    };
     let filter = TaskFilter {
         status: filter.status,
         priority: filter.priority,
         due_before: filter.due_before,
         due_after: filter.due_after,
+        has_due_date: filter.has_due_date,
+        created_before: filter.created_before,
+        created_after: filter.created_after,
+        updated_before: filter.updated_before,
+        updated_after: filter.updated_after,
+        q: filter.q.filter(|q| !q.trim().is_empty()),
+        tag: filter.tag.filter(|tag| !tag.trim().is_empty()),
+        any_tag: filter.any_tag,
+        all_tags: filter.all_tags,
+        assignee: filter.assignee,
+        project: filter.project,
+        custom_fields,
+        query,
+    };
+
+    // Search results rank best-first unless the client picks an order
+    let is_search = filter
+        .search_text()
+        .as_deref()
+        .and_then(search::to_tsquery)
+        .is_some();
+    let sort = match page.sort {
+        Some(sort) if sort.field == TaskSortField::Relevance && !is_search => {
+            return Err(AppError::InvalidInput(
+                "Sorting by relevance requires a search query".to_string(),
+            ));
+        }
+        Some(sort) => sort,
+        None if is_search => TaskSort::new(TaskSortField::Relevance, SortDirection::Desc),
+        None => TaskSort::default(),
     };
 
-    let tasks = app_state.database.get_tasks(Some(filter)).await?;
+    let cursor = page
+        .cursor
+        .as_deref()
+        .map(|token| Cursor::decode(token, sort))
+        .transpose()?;
+    let limit = page
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+
+    let tasks = app_state
+        .repository
+        .list_tasks(&filter, sort, cursor.as_ref(), limit)
+        .await?;
     Ok(Json(tasks))
 }
 
+const ACTOR_HEADER: &str = "x-actor";
+const OVERRIDE_BLOCKERS_HEADER: &str = "x-override-blockers";
+const WIP_LIMIT_EXCEEDED_HEADER: &str = "x-wip-limit-exceeded";
+const MAX_ACTOR_LENGTH: usize = 255;
+
+/// A single task with its version as the `ETag` header.
+type TaskResponse = ([(HeaderName, String); 1], Json<Task>);
+
+pub async fn get_task(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<TaskResponse, AppError> {
+    let task = app_state.repository.get_task(id).await?;
+    Ok(task_response(task))
+}
+
 pub async fn create_task(
     State(app_state): State<AppState>,
-    Json(request): Json<CreateTaskRequest>,
-) -> Result<(StatusCode, Json<Task>), AppError> {
+    headers: HeaderMap,
+    Json(mut request): Json<CreateTaskRequest>,
+) -> Result<(StatusCode, TaskResponse), AppError> {
+    let context = WriteContext {
+        actor: actor(&headers)?,
+        ..Default::default()
+    };
     if request.title.trim().is_empty() {
         return Err(AppError::InvalidInput("Title cannot be empty".to_string()));
     }
+    if let Some(rule) = &mut request.recurrence {
+        normalize_recurrence(rule)?;
+    }
+    validate_estimate(request.estimate_minutes)?;
 
-    let task = app_state.database.create_task(request).await?;
-    Ok((StatusCode::CREATED, Json(task)))
+    let task = app_state.repository.create_task(request, &context).await?;
+    Ok((StatusCode::CREATED, task_response(task)))
+}
+
+pub async fn replace_task(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(request): Json<ReplaceTaskRequest>,
+) -> Result<(HeaderMap, TaskResponse), AppError> {
+    let context = conditional_write_context(&app_state, &headers)?;
+    let mut request = UpdateTaskRequest::from(request);
+    validate_update(&mut request)?;
+
+    let task = app_state
+        .repository
+        .update_task(id, request, &context)
+        .await?;
+    let warning = wip_warning(&app_state, &task).await?;
+    Ok((warning, task_response(task)))
 }
 
 pub async fn update_task(
     State(app_state): State<AppState>,
     Path(id): Path<Uuid>,
-    Json(request): Json<UpdateTaskRequest>,
-) -> Result<Json<Task>, AppError> {
-    let task = app_state.database.update_task(id, request).await?;
-    Ok(Json(task))
+    headers: HeaderMap,
+    Json(mut request): Json<UpdateTaskRequest>,
+) -> Result<(HeaderMap, TaskResponse), AppError> {
+    let context = conditional_write_context(&app_state, &headers)?;
+    validate_update(&mut request)?;
+    let moved = !request.status.is_absent() || !request.project_id.is_absent();
+
+    let task = app_state
+        .repository
+        .update_task(id, request, &context)
+        .await?;
+    let warning = if moved {
+        wip_warning(&app_state, &task).await?
+    } else {
+        HeaderMap::new()
+    };
+    Ok((warning, task_response(task)))
+}
+
+/// Drops a task into a board column between two of its cards.
+pub async fn move_task(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(request): Json<MoveTaskRequest>,
+) -> Result<(HeaderMap, TaskResponse), AppError> {
+    let context = conditional_write_context(&app_state, &headers)?;
+
+    let task = app_state
+        .repository
+        .move_task(id, request, &context)
+        .await?;
+    let warning = wip_warning(&app_state, &task).await?;
+    Ok((warning, task_response(task)))
+}
+
+/// Lists the states a task can move to now: those its workflow allows from
+/// its status whose guards the task meets.
+pub async fn list_transitions(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<WorkflowState>>, AppError> {
+    let task = app_state.repository.get_task(id).await?;
+    let workflow = app_state.repository.get_workflow(task.project_id).await?;
+    let states = workflow.allowed_next(&task).into_iter().cloned().collect();
+    Ok(Json(states))
 }
 
 pub async fn delete_task(
     State(app_state): State<AppState>,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<StatusCode, AppError> {
+    let context = conditional_write_context(&app_state, &headers)?;
+    app_state.repository.delete_task(id, &context).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn list_trash(State(app_state): State<AppState>) -> Result<Json<Vec<Task>>, AppError> {
+    let tasks = app_state.repository.list_trash().await?;
+    Ok(Json(tasks))
+}
+
+pub async fn restore_task(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<TaskResponse, AppError> {
+    let context = WriteContext {
+        actor: actor(&headers)?,
+        ..Default::default()
+    };
+    let task = app_state.repository.restore_task(id, &context).await?;
+    Ok(task_response(task))
+}
+
+pub async fn purge_task(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    let _removing = app_state.blob_lock.write().await;
+    let purged = app_state.repository.purge_task(id).await?;
+    blob::remove_blobs(app_state.blobs.as_ref(), &purged.blobs).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn task_history(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<TaskEvent>>, AppError> {
+    let events = app_state.repository.task_history(id).await?;
+    Ok(Json(events))
+}
+
+pub async fn list_children(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<Task>>, AppError> {
+    let children = app_state.repository.list_children(id).await?;
+    Ok(Json(children))
+}
+
+/// Lists the due dates a recurring task will repeat on, nothing for a task
+/// that does not repeat.
+pub async fn list_occurrences(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<OccurrencesQuery>,
+) -> Result<Json<Vec<DateTime<Utc>>>, AppError> {
+    let task = app_state.repository.get_task(id).await?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_OCCURRENCE_PREVIEW)
+        .clamp(1, MAX_OCCURRENCE_PREVIEW);
+
+    let occurrences = match (task.recurrence, task.due_date) {
+        (Some(rule), Some(due_date)) => rule
+            .parse::<Recurrence>()
+            .map_err(|_| AppError::InternalError)?
+            .upcoming(due_date, limit),
+        _ => Vec::new(),
+    };
+    Ok(Json(occurrences))
+}
+
+/// Stops a task repeating; the task itself is kept.
+pub async fn stop_recurrence(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<TaskResponse, AppError> {
+    let context = conditional_write_context(&app_state, &headers)?;
+    let request = UpdateTaskRequest {
+        recurrence: Patch::Null,
+        ..Default::default()
+    };
+
+    let task = app_state
+        .repository
+        .update_task(id, request, &context)
+        .await?;
+    Ok(task_response(task))
+}
+
+pub async fn list_dependencies(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<TaskDependencies>, AppError> {
+    let dependencies = app_state.repository.list_dependencies(id).await?;
+    Ok(Json(dependencies))
+}
+
+pub async fn add_dependency(
+    State(app_state): State<AppState>,
+    Path((id, blocker_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    app_state.repository.add_dependency(id, blocker_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn remove_dependency(
+    State(app_state): State<AppState>,
+    Path((id, blocker_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    app_state
+        .repository
+        .remove_dependency(id, blocker_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn list_comments(
+    State(app_state): State<AppState>,
+    Path(task_id): Path<Uuid>,
+) -> Result<Json<Vec<Comment>>, AppError> {
+    let comments = app_state.repository.list_comments(task_id).await?;
+    Ok(Json(comments))
+}
+
+pub async fn create_comment(
+    State(app_state): State<AppState>,
+    Path(task_id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(request): Json<CommentRequest>,
+) -> Result<(StatusCode, Json<Comment>), AppError> {
+    let author = actor(&headers)?;
+    validate_comment(&request)?;
+
+    let comment = app_state
+        .repository
+        .create_comment(task_id, request, author)
+        .await?;
+    Ok((StatusCode::CREATED, Json(comment)))
+}
+
+pub async fn update_comment(
+    State(app_state): State<AppState>,
+    Path((task_id, id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<CommentRequest>,
+) -> Result<Json<Comment>, AppError> {
+    validate_comment(&request)?;
+
+    let comment = app_state
+        .repository
+        .update_comment(task_id, id, request)
+        .await?;
+    Ok(Json(comment))
+}
+
+pub async fn delete_comment(
+    State(app_state): State<AppState>,
+    Path((task_id, id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    app_state.repository.delete_comment(task_id, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn list_time_entries(
+    State(app_state): State<AppState>,
+    Path(task_id): Path<Uuid>,
+) -> Result<Json<Vec<TimeEntry>>, AppError> {
+    let entries = app_state.repository.list_time_entries(task_id).await?;
+    Ok(Json(entries))
+}
+
+pub async fn start_timer(
+    State(app_state): State<AppState>,
+    Path(task_id): Path<Uuid>,
+    Json(request): Json<TimerRequest>,
+) -> Result<(StatusCode, Json<TimeEntry>), AppError> {
+    let entry = app_state
+        .repository
+        .start_timer(task_id, request.member_id)
+        .await?;
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+pub async fn stop_timer(
+    State(app_state): State<AppState>,
+    Path(task_id): Path<Uuid>,
+    Json(request): Json<TimerRequest>,
+) -> Result<Json<TimeEntry>, AppError> {
+    let entry = app_state
+        .repository
+        .stop_timer(task_id, request.member_id)
+        .await?;
+    Ok(Json(entry))
+}
+
+pub async fn create_time_entry(
+    State(app_state): State<AppState>,
+    Path(task_id): Path<Uuid>,
+    Json(mut request): Json<TimeEntryRequest>,
+) -> Result<(StatusCode, Json<TimeEntry>), AppError> {
+    validate_time_entry(&mut request)?;
+
+    let entry = app_state
+        .repository
+        .create_time_entry(task_id, request)
+        .await?;
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+pub async fn update_time_entry(
+    State(app_state): State<AppState>,
+    Path((task_id, id)): Path<(Uuid, Uuid)>,
+    Json(mut request): Json<TimeEntryRequest>,
+) -> Result<Json<TimeEntry>, AppError> {
+    validate_time_entry(&mut request)?;
+
+    let entry = app_state
+        .repository
+        .update_time_entry(task_id, id, request)
+        .await?;
+    Ok(Json(entry))
+}
+
+pub async fn delete_time_entry(
+    State(app_state): State<AppState>,
+    Path((task_id, id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    app_state.repository.delete_time_entry(task_id, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn list_attachments(
+    State(app_state): State<AppState>,
+    Path(task_id): Path<Uuid>,
+) -> Result<Json<Vec<Attachment>>, AppError> {
+    let attachments = app_state.repository.list_attachments(task_id).await?;
+    Ok(Json(attachments))
+}
+
+/// Attaches every file of a `multipart/form-data` body to the task. Parts
+/// without a file name are ignored.
+pub async fn upload_attachments(
+    State(app_state): State<AppState>,
+    Path(task_id): Path<Uuid>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<Vec<Attachment>>), AppError> {
+    // Refuse uploads to a missing task before reading them
+    app_state.repository.get_task(task_id).await?;
+
+    let mut attachments = Vec::new();
+    while let Some(mut field) = multipart.next_field().await.map_err(upload_error)? {
+        let Some(filename) = field.file_name().map(attachment_name).transpose()? else {
+            continue;
+        };
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = field.chunk().await.map_err(upload_error)? {
+            if bytes.len() + chunk.len() > app_state.max_attachment_bytes {
+                return Err(AppError::PayloadTooLarge(format!(
+                    "Attachments cannot exceed {} bytes",
+                    app_state.max_attachment_bytes
+                )));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        let sha256 = content_key(&bytes);
+        // Held until the blob is stored, so no removal finds it unused in between
+        let _uploading = app_state.blob_lock.read().await;
+        let attachment = app_state
+            .repository
+            .create_attachment(
+                task_id,
+                NewAttachment {
+                    filename,
+                    content_type: sniff_content_type(&bytes),
+                    size_bytes: bytes.len() as i64,
+                    sha256: sha256.clone(),
+                },
+            )
+            .await?;
+        if let Err(err) = app_state.blobs.put(&sha256, &bytes).await {
+            app_state
+                .repository
+                .delete_attachment(task_id, attachment.id)
+                .await?;
+            return Err(err);
+        }
+        attachments.push(attachment);
+    }
+
+    if attachments.is_empty() {
+        return Err(AppError::InvalidInput("No file was uploaded".to_string()));
+    }
+    Ok((StatusCode::CREATED, Json(attachments)))
+}
+
+/// Sends an attachment as a download under its original name.
+pub async fn download_attachment(
+    State(app_state): State<AppState>,
+    Path((task_id, id)): Path<(Uuid, Uuid)>,
+) -> Result<(HeaderMap, Vec<u8>), AppError> {
+    let attachment = app_state.repository.get_attachment(task_id, id).await?;
+    let bytes = app_state.blobs.get(&attachment.sha256).await?;
+
+    let mut headers = HeaderMap::new();
+    let content_type =
+        HeaderValue::from_str(&attachment.content_type).map_err(|_| AppError::InternalError)?;
+    headers.insert(header::CONTENT_TYPE, content_type);
+    let disposition = HeaderValue::from_str(&content_disposition(&attachment.filename))
+        .map_err(|_| AppError::InternalError)?;
+    headers.insert(header::CONTENT_DISPOSITION, disposition);
+    headers.insert(
+        header::X_CONTENT_TYPE_OPTIONS,
+        HeaderValue::from_static("nosniff"),
+    );
+    Ok((headers, bytes))
+}
+
+pub async fn delete_attachment(
+    State(app_state): State<AppState>,
+    Path((task_id, id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    let _removing = app_state.blob_lock.write().await;
+    let unused = app_state.repository.delete_attachment(task_id, id).await?;
+    blob::remove_blobs(app_state.blobs.as_ref(), unused.as_slice()).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn list_tags(State(app_state): State<AppState>) -> Result<Json<Vec<Tag>>, AppError> {
+    let tags = app_state.repository.list_tags().await?;
+    Ok(Json(tags))
+}
+
+pub async fn create_tag(
+    State(app_state): State<AppState>,
+    Json(request): Json<TagRequest>,
+) -> Result<(StatusCode, Json<Tag>), AppError> {
+    validate_tag(&request)?;
+
+    let tag = app_state.repository.create_tag(request).await?;
+    Ok((StatusCode::CREATED, Json(tag)))
+}
+
+pub async fn update_tag(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<TagRequest>,
+) -> Result<Json<Tag>, AppError> {
+    validate_tag(&request)?;
+
+    let tag = app_state.repository.update_tag(id, request).await?;
+    Ok(Json(tag))
+}
+
+pub async fn delete_tag(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
 ) -> Result<StatusCode, AppError> {
-    app_state.database.delete_task(id).await?;
+    app_state.repository.delete_tag(id).await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
+pub async fn list_members(
+    State(app_state): State<AppState>,
+) -> Result<Json<Vec<Member>>, AppError> {
+    let members = app_state.repository.list_members().await?;
+    Ok(Json(members))
+}
+
+pub async fn create_member(
+    State(app_state): State<AppState>,
+    Json(request): Json<MemberRequest>,
+) -> Result<(StatusCode, Json<Member>), AppError> {
+    validate_member(&request)?;
+
+    let member = app_state.repository.create_member(request).await?;
+    Ok((StatusCode::CREATED, Json(member)))
+}
+
+pub async fn update_member(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<MemberRequest>,
+) -> Result<Json<Member>, AppError> {
+    validate_member(&request)?;
+
+    let member = app_state.repository.update_member(id, request).await?;
+    Ok(Json(member))
+}
+
+pub async fn delete_member(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    app_state.repository.delete_member(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn list_projects(
+    State(app_state): State<AppState>,
+) -> Result<Json<Vec<Project>>, AppError> {
+    let projects = app_state.repository.list_projects().await?;
+    Ok(Json(projects))
+}
+
+pub async fn get_project(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Project>, AppError> {
+    let project = app_state.repository.get_project(id).await?;
+    Ok(Json(project))
+}
+
+pub async fn create_project(
+    State(app_state): State<AppState>,
+    Json(request): Json<ProjectRequest>,
+) -> Result<(StatusCode, Json<Project>), AppError> {
+    validate_project(&request)?;
+
+    let project = app_state.repository.create_project(request).await?;
+    Ok((StatusCode::CREATED, Json(project)))
+}
+
+pub async fn update_project(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<ProjectRequest>,
+) -> Result<Json<Project>, AppError> {
+    validate_project(&request)?;
+
+    let project = app_state.repository.update_project(id, request).await?;
+    Ok(Json(project))
+}
+
+pub async fn delete_project(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    app_state.repository.delete_project(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn get_workflow(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Workflow>, AppError> {
+    let workflow = app_state.repository.get_workflow(id).await?;
+    Ok(Json(workflow))
+}
+
+pub async fn update_workflow(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<WorkflowRequest>,
+) -> Result<Json<Workflow>, AppError> {
+    validate_workflow(&request)?;
+
+    let workflow = app_state.repository.update_workflow(id, request).await?;
+    Ok(Json(workflow))
+}
+
+/// Lists the tasks of one project, taking the same filters as `list_tasks`.
+pub async fn list_project_tasks(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(mut filter): Query<TaskFilterQuery>,
+    params: Query<Vec<(String, String)>>,
+    page: Query<PageQuery>,
+) -> Result<Json<Page<Task>>, AppError> {
+    app_state.repository.get_project(id).await?;
+
+    filter.project = Some(id);
+    list_tasks(State(app_state), Query(filter), params, page).await
+}
+
+pub async fn list_custom_fields(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<CustomField>>, AppError> {
+    let fields = app_state.repository.list_custom_fields(id).await?;
+    Ok(Json(fields))
+}
+
+pub async fn create_custom_field(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(mut request): Json<CustomFieldRequest>,
+) -> Result<(StatusCode, Json<CustomField>), AppError> {
+    request.normalize().map_err(AppError::InvalidInput)?;
+
+    let field = app_state
+        .repository
+        .create_custom_field(id, request)
+        .await?;
+    Ok((StatusCode::CREATED, Json(field)))
+}
+
+pub async fn update_custom_field(
+    State(app_state): State<AppState>,
+    Path((id, field_id)): Path<(Uuid, Uuid)>,
+    Json(mut request): Json<CustomFieldRequest>,
+) -> Result<Json<CustomField>, AppError> {
+    request.normalize().map_err(AppError::InvalidInput)?;
+
+    let field = app_state
+        .repository
+        .update_custom_field(id, field_id, request)
+        .await?;
+    Ok(Json(field))
+}
+
+pub async fn delete_custom_field(
+    State(app_state): State<AppState>,
+    Path((id, field_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    app_state
+        .repository
+        .delete_custom_field(id, field_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn list_saved_views(
+    State(app_state): State<AppState>,
+) -> Result<Json<Vec<SavedView>>, AppError> {
+    let views = app_state.repository.list_saved_views().await?;
+    Ok(Json(views))
+}
+
+pub async fn get_saved_view(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SavedView>, AppError> {
+    let view = app_state.repository.get_saved_view(id).await?;
+    Ok(Json(view))
+}
+
+pub async fn create_saved_view(
+    State(app_state): State<AppState>,
+    Json(mut request): Json<SavedViewRequest>,
+) -> Result<(StatusCode, Json<SavedView>), AppError> {
+    request.normalize().map_err(AppError::InvalidInput)?;
+
+    let view = app_state.repository.create_saved_view(request).await?;
+    Ok((StatusCode::CREATED, Json(view)))
+}
+
+pub async fn update_saved_view(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(mut request): Json<SavedViewRequest>,
+) -> Result<Json<SavedView>, AppError> {
+    request.normalize().map_err(AppError::InvalidInput)?;
+
+    let view = app_state.repository.update_saved_view(id, request).await?;
+    Ok(Json(view))
+}
+
+pub async fn delete_saved_view(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    app_state.repository.delete_saved_view(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Creates a task in the project named by the path, whatever the body says.
+pub async fn create_project_task(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(mut request): Json<CreateTaskRequest>,
+) -> Result<(StatusCode, TaskResponse), AppError> {
+    app_state.repository.get_project(id).await?;
+
+    request.project_id = Some(id);
+    create_task(State(app_state), headers, Json(request)).await
+}
+
+fn task_response(task: Task) -> TaskResponse {
+    (
+        [(header::ETAG, format!("\"{}\"", task.version))],
+        Json(task),
+    )
+}
+
+/// With [`WipLimits::Warn`], sets `X-WIP-Limit-Exceeded: <count>/<limit>`
+/// when the column the task was written into holds more live tasks than its
+/// limit. Rejected moves never get this far.
+async fn wip_warning(app_state: &AppState, task: &Task) -> Result<HeaderMap, AppError> {
+    let mut headers = HeaderMap::new();
+    if app_state.wip_limits != WipLimits::Warn {
+        return Ok(headers);
+    }
+
+    let workflow = app_state.repository.get_workflow(task.project_id).await?;
+    let Some(limit) = workflow
+        .state(&task.status)
+        .and_then(|state| state.wip_limit)
+    else {
+        return Ok(headers);
+    };
+    let count = app_state
+        .repository
+        .count_in_state(task.project_id, &task.status)
+        .await?;
+    if count > i64::from(limit) {
+        let value = HeaderValue::from_str(&format!("{count}/{limit}"))
+            .map_err(|_| AppError::InternalError)?;
+        headers.insert(WIP_LIMIT_EXCEEDED_HEADER, value);
+    }
+    Ok(headers)
+}
+
+/// Reads who is making the change from the optional `X-Actor` header, which
+/// is recorded in the task history as given.
+fn actor(headers: &HeaderMap) -> Result<Option<String>, AppError> {
+    let Some(value) = headers.get(ACTOR_HEADER) else {
+        return Ok(None);
+    };
+
+    let actor = value
+        .to_str()
+        .map_err(|_| AppError::InvalidInput("X-Actor must be visible ASCII".to_string()))?
+        .trim();
+    if actor.chars().count() > MAX_ACTOR_LENGTH {
+        return Err(AppError::InvalidInput(format!(
+            "X-Actor cannot exceed {MAX_ACTOR_LENGTH} characters"
+        )));
+    }
+
+    Ok(Some(actor.to_string()).filter(|actor| !actor.is_empty()))
+}
+
+/// The actor, `If-Match` version and blocker override of a write to an
+/// existing task, with the server's subtask completion setting.
+fn conditional_write_context(
+    app_state: &AppState,
+    headers: &HeaderMap,
+) -> Result<WriteContext, AppError> {
+    Ok(WriteContext {
+        actor: actor(headers)?,
+        expected_version: if_match_version(headers)?,
+        subtask_completion: app_state.subtask_completion,
+        wip_limits: app_state.wip_limits,
+        override_blockers: override_blockers(headers)?,
+    })
+}
+
+/// Reads `X-Override-Blockers: true`, which lets a blocked task be started or
+/// completed anyway.
+fn override_blockers(headers: &HeaderMap) -> Result<bool, AppError> {
+    let Some(value) = headers.get(OVERRIDE_BLOCKERS_HEADER) else {
+        return Ok(false);
+    };
+
+    match value.to_str().unwrap_or_default().trim() {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        _ => Err(AppError::InvalidInput(
+            "X-Override-Blockers must be true or false".to_string(),
+        )),
+    }
+}
+
+/// Reads the version a write is conditioned on from `If-Match`. Without the
+/// header, or with `*`, the write applies to whatever version is stored.
+fn if_match_version(headers: &HeaderMap) -> Result<Option<i64>, AppError> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+
+    let value = value.to_str().unwrap_or_default().trim();
+    if value == "*" {
+        return Ok(None);
+    }
+
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .and_then(|version| version.parse().ok())
+        .map(Some)
+        .ok_or_else(|| {
+            AppError::InvalidInput("If-Match must be a task ETag such as \"3\"".to_string())
+        })
+}
+
+fn validate_comment(request: &CommentRequest) -> Result<(), AppError> {
+    if request.body.trim().is_empty() {
+        return Err(AppError::InvalidInput(
+            "Comment cannot be empty".to_string(),
+        ));
+    }
+    if request.body.chars().count() > MAX_COMMENT_LENGTH {
+        return Err(AppError::InvalidInput(format!(
+            "Comment cannot exceed {MAX_COMMENT_LENGTH} characters"
+        )));
+    }
+
+    Ok(())
+}
+
+/// An entry must end after it starts; a blank note counts as none.
+fn validate_time_entry(request: &mut TimeEntryRequest) -> Result<(), AppError> {
+    if request.ended_at <= request.started_at {
+        return Err(AppError::InvalidInput(
+            "A time entry must end after it starts".to_string(),
+        ));
+    }
+    if request
+        .note
+        .as_deref()
+        .is_some_and(|note| note.trim().is_empty())
+    {
+        request.note = None;
+    }
+    if request
+        .note
+        .as_deref()
+        .is_some_and(|note| note.chars().count() > MAX_TIME_ENTRY_NOTE_LENGTH)
+    {
+        return Err(AppError::InvalidInput(format!(
+            "Note cannot exceed {MAX_TIME_ENTRY_NOTE_LENGTH} characters"
+        )));
+    }
+
+    Ok(())
+}
+
+/// The name an upload is stored under: the file name the client sent,
+/// without directories or control characters.
+fn attachment_name(raw: &str) -> Result<String, AppError> {
+    let name: String = raw
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect();
+    let name = name.trim();
+    if name.is_empty() || name == "." || name == ".." {
+        return Ok("attachment".to_string());
+    }
+    if name.chars().count() > MAX_ATTACHMENT_NAME_LENGTH {
+        return Err(AppError::InvalidInput(format!(
+            "File name cannot exceed {MAX_ATTACHMENT_NAME_LENGTH} characters"
+        )));
+    }
+    Ok(name.to_string())
+}
+
+/// `Content-Disposition` for downloading `filename`: an ASCII fallback for
+/// old clients and the exact name encoded as RFC 5987 `filename*`.
+fn content_disposition(filename: &str) -> String {
+    let fallback: String = filename
+        .chars()
+        .map(|c| {
+            if (c.is_ascii_graphic() || c == ' ') && c != '"' && c != '\\' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let encoded: String = filename
+        .bytes()
+        .map(|b| {
+            if b.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&b) {
+                char::from(b).to_string()
+            } else {
+                format!("%{b:02X}")
+            }
+        })
+        .collect();
+    format!("attachment; filename=\"{fallback}\"; filename*=UTF-8''{encoded}")
+}
+
+/// A malformed upload, or one over the body limit of the route.
+fn upload_error(err: MultipartError) -> AppError {
+    if err.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        AppError::PayloadTooLarge(err.body_text())
+    } else {
+        AppError::InvalidInput(format!("Invalid upload: {}", err.body_text()))
+    }
+}
+
+fn validate_member(request: &MemberRequest) -> Result<(), AppError> {
+    if request.name.trim().is_empty() {
+        return Err(AppError::InvalidInput(
+            "Member name cannot be empty".to_string(),
+        ));
+    }
+    if request.name.chars().count() > MAX_MEMBER_NAME_LENGTH {
+        return Err(AppError::InvalidInput(format!(
+            "Member name cannot exceed {MAX_MEMBER_NAME_LENGTH} characters"
+        )));
+    }
+
+    Ok(())
+}
+
+fn validate_project(request: &ProjectRequest) -> Result<(), AppError> {
+    if request.name.trim().is_empty() {
+        return Err(AppError::InvalidInput(
+            "Project name cannot be empty".to_string(),
+        ));
+    }
+    if request.name.chars().count() > MAX_PROJECT_NAME_LENGTH {
+        return Err(AppError::InvalidInput(format!(
+            "Project name cannot exceed {MAX_PROJECT_NAME_LENGTH} characters"
+        )));
+    }
+
+    Ok(())
+}
+
+/// New tasks start in the first state, so it must be a to-do state, and
+/// subtasks are completed into the first done state, so there must be one.
+/// State names are listed comma-separated in filters like tag names, and
+/// the moves a state allows must name states of the same workflow.
+fn validate_workflow(request: &WorkflowRequest) -> Result<(), AppError> {
+    let states = &request.states;
+    if states.is_empty() || states.len() > MAX_WORKFLOW_STATES {
+        return Err(AppError::InvalidInput(format!(
+            "A workflow needs between 1 and {MAX_WORKFLOW_STATES} states"
+        )));
+    }
+
+    for (i, state) in states.iter().enumerate() {
+        let name = state.name.as_str();
+        if name.trim().is_empty() {
+            return Err(AppError::InvalidInput(
+                "State name cannot be empty".to_string(),
+            ));
+        }
+        if name.trim() != name || name.contains(',') {
+            return Err(AppError::InvalidInput(
+                "State name cannot contain commas or surrounding spaces".to_string(),
+            ));
+        }
+        if name.chars().count() > MAX_STATE_NAME_LENGTH {
+            return Err(AppError::InvalidInput(format!(
+                "State name cannot exceed {MAX_STATE_NAME_LENGTH} characters"
+            )));
+        }
+        if state
+            .wip_limit
+            .is_some_and(|limit| limit == 0 || limit > MAX_WIP_LIMIT)
+        {
+            return Err(AppError::InvalidInput(format!(
+                "WIP limit must be between 1 and {MAX_WIP_LIMIT}"
+            )));
+        }
+        if states[..i].iter().any(|other| other.name == state.name) {
+            return Err(AppError::InvalidInput(format!(
+                "Duplicate state name: {name}"
+            )));
+        }
+        let next = state.next.as_deref().unwrap_or_default();
+        for (j, to) in next.iter().enumerate() {
+            if !states.iter().any(|other| other.name == *to) {
+                return Err(AppError::InvalidInput(format!(
+                    "{name} allows moves to an unknown state: {to}"
+                )));
+            }
+            if next[..j].contains(to) {
+                return Err(AppError::InvalidInput(format!(
+                    "{name} lists {to} more than once"
+                )));
+            }
+        }
+        check_fields_unique(&state.requires)?;
+    }
+
+    for (i, rule) in request.priority_rules.iter().enumerate() {
+        if request.priority_rules[..i]
+            .iter()
+            .any(|other| other.priority == rule.priority)
+        {
+            return Err(AppError::InvalidInput(format!(
+                "Duplicate rule for {:?} priority",
+                rule.priority
+            )));
+        }
+        check_fields_unique(&rule.requires)?;
+    }
+
+    if states[0].category != StatusCategory::Todo {
+        return Err(AppError::InvalidInput(
+            "The first state must be a Todo state".to_string(),
+        ));
+    }
+    if !states
+        .iter()
+        .any(|state| state.category == StatusCategory::Done)
+    {
+        return Err(AppError::InvalidInput(
+            "A workflow needs at least one Done state".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn check_fields_unique(fields: &[TaskField]) -> Result<(), AppError> {
+    for (i, field) in fields.iter().enumerate() {
+        if fields[..i].contains(field) {
+            return Err(AppError::InvalidInput(format!(
+                "Field required more than once: {}",
+                field.as_str()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Tag names are matched exactly and listed comma-separated in filters, so
+/// they cannot have surrounding spaces or commas.
+fn validate_tag(request: &TagRequest) -> Result<(), AppError> {
+    let name = &request.name;
+    if name.trim().is_empty() {
+        return Err(AppError::InvalidInput(
+            "Tag name cannot be empty".to_string(),
+        ));
+    }
+    if name.trim() != name || name.contains(',') {
+        return Err(AppError::InvalidInput(
+            "Tag name cannot contain commas or surrounding spaces".to_string(),
+        ));
+    }
+    if name.chars().count() > MAX_TAG_NAME_LENGTH {
+        return Err(AppError::InvalidInput(format!(
+            "Tag name cannot exceed {MAX_TAG_NAME_LENGTH} characters"
+        )));
+    }
+
+    let color = &request.color;
+    let is_hex_color = color.len() == 7
+        && color.starts_with('#')
+        && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+    if !is_hex_color {
+        return Err(AppError::InvalidInput(
+            "Tag color must be a hex colour such as #3b82f6".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Rejects updates that would clear a required field or blank the title, and
+/// rewrites a new recurrence rule in its canonical form.
+fn validate_update(request: &mut UpdateTaskRequest) -> Result<(), AppError> {
+    let cleared = [
+        ("title", matches!(request.title, Patch::Null)),
+        ("status", matches!(request.status, Patch::Null)),
+        ("priority", matches!(request.priority, Patch::Null)),
+        ("tags", matches!(request.tags, Patch::Null)),
+        ("project_id", matches!(request.project_id, Patch::Null)),
+    ];
+    if let Some((field, _)) = cleared.iter().find(|(_, is_null)| *is_null) {
+        return Err(AppError::InvalidInput(format!("{field} cannot be null")));
+    }
+
+    if let Patch::Value(title) = &request.title {
+        if title.trim().is_empty() {
+            return Err(AppError::InvalidInput("Title cannot be empty".to_string()));
+        }
+    }
+    if let Patch::Value(rule) = &mut request.recurrence {
+        normalize_recurrence(rule)?;
+    }
+    if let Patch::Value(minutes) = request.estimate_minutes {
+        validate_estimate(Some(minutes))?;
+    }
+
+    Ok(())
+}
+
+fn validate_estimate(minutes: Option<u32>) -> Result<(), AppError> {
+    if minutes.is_some_and(|minutes| minutes > MAX_ESTIMATE_MINUTES) {
+        return Err(AppError::InvalidInput(format!(
+            "Estimate cannot exceed {MAX_ESTIMATE_MINUTES} minutes"
+        )));
+    }
+    Ok(())
+}
+
+/// Checks a recurrence rule and rewrites it in its canonical form, such as
+/// `FREQ=WEEKLY;BYDAY=MO,TH` for `rrule:freq=weekly;byday=th,mo`.
+fn normalize_recurrence(rule: &mut String) -> Result<(), AppError> {
+    let recurrence: Recurrence = rule.parse().map_err(AppError::InvalidInput)?;
+    *rule = recurrence.to_string();
+    Ok(())
+}
+
+/// Reads the `field.<id>=<value>` parameters of a task listing, each value
+/// typed as its field expects. Empty values are ignored, as with `tag`.
+async fn custom_field_filters(
+    app_state: &AppState,
+    params: Vec<(String, String)>,
+) -> Result<Vec<CustomFieldFilter>, AppError> {
+    let mut filters = Vec::new();
+    for (key, raw) in params {
+        let Some(id) = key.strip_prefix("field.") else {
+            continue;
+        };
+        if raw.trim().is_empty() {
+            continue;
+        }
+        let field_id: Uuid = id
+            .parse()
+            .map_err(|_| AppError::InvalidInput(format!("Invalid custom field id: {id}")))?;
+        let field = app_state
+            .repository
+            .get_custom_field(field_id)
+            .await
+            .map_err(|err| match err {
+                AppError::CustomFieldNotFound => {
+                    AppError::InvalidInput(format!("Unknown custom field: {field_id}"))
+                }
+                err => err,
+            })?;
+        let value = field.parse_value(&raw).map_err(AppError::InvalidInput)?;
+        filters.push(CustomFieldFilter { field_id, value });
+    }
+    Ok(filters)
+}
+
+/// Task filters other than custom fields, which come as `field.<id>`
+/// parameters.
 #[derive(Deserialize)]
 pub struct TaskFilterQuery {
-    pub status: Option<common::TaskStatus>,
-    pub priority: Option<common::TaskPriority>,
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub status: Vec<common::TaskStatus>,
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub priority: Vec<common::TaskPriority>,
     pub due_before: Option<chrono::DateTime<chrono::Utc>>,
     pub due_after: Option<chrono::DateTime<chrono::Utc>>,
+    pub has_due_date: Option<bool>,
+    pub created_before: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_after: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_before: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_after: Option<chrono::DateTime<chrono::Utc>>,
+    pub q: Option<String>,
+    pub tag: Option<String>,
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub any_tag: Vec<String>,
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub all_tags: Vec<String>,
+    pub assignee: Option<Uuid>,
+    pub project: Option<Uuid>,
+    /// A query in the [`common::query`] language.
+    pub query: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct OccurrencesQuery {
+    pub limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+pub struct PageQuery {
+    pub sort: Option<TaskSort>,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+
+/// Deserializes a comma-separated query value such as `Todo,InProgress`.
+fn comma_separated<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let raw = String::deserialize(deserializer)?;
+    raw.split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|value| {
+            T::deserialize(value.into_deserializer())
+                .map_err(|err: serde::de::value::Error| D::Error::custom(err))
+        })
+        .collect()
 }
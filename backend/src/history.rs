@@ -0,0 +1,114 @@
+//! Change history recorded in the same transaction as every task write.
+
+use common::{Task, TaskEventKind};
+
+/// An event to store alongside a write. The engine assigns its id and adds
+/// the actor and time of the write.
+pub struct NewTaskEvent {
+    pub kind: TaskEventKind,
+    pub field: Option<&'static str>,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+impl NewTaskEvent {
+    /// An event about the task as a whole, such as its creation.
+    pub fn task(kind: TaskEventKind) -> Self {
+        Self {
+            kind,
+            field: None,
+            old_value: None,
+            new_value: None,
+        }
+    }
+}
+
+/// One `Updated` event for each field that differs between `before` and
+/// `after`. Enums are written by variant name, dates in RFC 3339, tags as
+/// their comma-separated names and custom field values as one JSON object.
+pub fn field_changes(before: &Task, after: &Task) -> Vec<NewTaskEvent> {
+    let fields = [
+        (
+            "title",
+            Some(before.title.clone()),
+            Some(after.title.clone()),
+        ),
+        (
+            "description",
+            before.description.clone(),
+            after.description.clone(),
+        ),
+        (
+            "status",
+            Some(before.status.to_string()),
+            Some(after.status.to_string()),
+        ),
+        (
+            "priority",
+            Some(format!("{:?}", before.priority)),
+            Some(format!("{:?}", after.priority)),
+        ),
+        (
+            "due_date",
+            before.due_date.map(|date| date.to_rfc3339()),
+            after.due_date.map(|date| date.to_rfc3339()),
+        ),
+        ("tags", tag_names(before), tag_names(after)),
+        (
+            "assignee_id",
+            before.assignee_id.map(|id| id.to_string()),
+            after.assignee_id.map(|id| id.to_string()),
+        ),
+        (
+            "project_id",
+            Some(before.project_id.to_string()),
+            Some(after.project_id.to_string()),
+        ),
+        (
+            "parent_id",
+            before.parent_id.map(|id| id.to_string()),
+            after.parent_id.map(|id| id.to_string()),
+        ),
+        (
+            "recurrence",
+            before.recurrence.clone(),
+            after.recurrence.clone(),
+        ),
+        (
+            "estimate_minutes",
+            before.estimate_minutes.map(|minutes| minutes.to_string()),
+            after.estimate_minutes.map(|minutes| minutes.to_string()),
+        ),
+        ("custom_fields", custom_values(before), custom_values(after)),
+    ];
+
+    fields
+        .into_iter()
+        .filter(|(_, old_value, new_value)| old_value != new_value)
+        .map(|(field, old_value, new_value)| NewTaskEvent {
+            kind: TaskEventKind::Updated,
+            field: Some(field),
+            old_value,
+            new_value,
+        })
+        .collect()
+}
+
+/// Comma-separated names of the task's tags, which are kept ordered by name.
+fn tag_names(task: &Task) -> Option<String> {
+    if task.tags.is_empty() {
+        return None;
+    }
+
+    let names: Vec<&str> = task.tags.iter().map(|tag| tag.name.as_str()).collect();
+    Some(names.join(", "))
+}
+
+/// The task's custom field values as a JSON object keyed by field id.
+fn custom_values(task: &Task) -> Option<String> {
+    if task.custom_fields.is_empty() {
+        return None;
+    }
+
+    serde_json::to_string(&task.custom_fields).ok()
+}
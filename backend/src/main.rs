@@ -1,24 +1,42 @@
 use axum::{
-    routing::{delete, get, post, put},
+    extract::DefaultBodyLimit,
+    routing::{delete, get, patch, post, put},
     Router,
 };
+use chrono::Utc;
 use sqlx::PgPool;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tower_http::cors::CorsLayer;
 use tracing::info;
 
+mod blob;
 mod database;
 mod error;
 mod handlers;
+mod history;
+mod memory;
+mod pagination;
+mod repository;
+mod search;
+#[cfg(feature = "sqlite")]
+mod sqlite;
 
 #[cfg(test)]
 mod tests;
 
+use blob::{BlobLock, BlobStore, LocalBlobStore, DEFAULT_MAX_ATTACHMENT_BYTES};
 use database::Database;
+use memory::InMemoryTaskRepository;
+use repository::{SubtaskCompletion, TaskRepository, WipLimits};
 
 // Application state
 pub struct AppStateData {
-    database: Database,
+    repository: Arc<dyn TaskRepository>,
+    blobs: Arc<dyn BlobStore>,
+    blob_lock: Arc<BlobLock>,
+    max_attachment_bytes: usize,
+    subtask_completion: SubtaskCompletion,
+    wip_limits: WipLimits,
 }
 
 pub type AppState = Arc<AppStateData>;
@@ -37,23 +55,179 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     info!("Connecting to database: {}", database_url);
 
-    // Create database connection pool
-    let pool = PgPool::connect(&database_url).await?;
+    // Create application state
+    let repository = connect_repository(&database_url).await?;
 
-    // Run database migrations
-    sqlx::migrate!().run(&pool).await?;
+    // Attachment content lives on local disk, apart from the task storage
+    let attachment_dir =
+        std::env::var("ATTACHMENT_DIR").unwrap_or_else(|_| "attachments".to_string());
+    let blobs: Arc<dyn BlobStore> = Arc::new(LocalBlobStore::new(attachment_dir));
+    let blob_lock = Arc::new(BlobLock::default());
+    let max_attachment_bytes = std::env::var("MAX_ATTACHMENT_BYTES")
+        .ok()
+        .and_then(|bytes| bytes.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_MAX_ATTACHMENT_BYTES);
 
-    // Create application state
-    let database = Database::new(pool);
+    // Days a task stays in the trash before it is purged; 0 keeps it forever
+    let retention_days = std::env::var("TRASH_RETENTION_DAYS")
+        .ok()
+        .and_then(|days| days.parse::<u32>().ok())
+        .unwrap_or(30);
+    if retention_days > 0 {
+        spawn_trash_purge(
+            repository.clone(),
+            blobs.clone(),
+            blob_lock.clone(),
+            retention_days,
+        );
+    }
 
-    let app_state = Arc::new(AppStateData { database });
+    // Completing a task with open subtasks is refused unless set to cascade
+    let subtask_completion = match std::env::var("SUBTASK_COMPLETION").as_deref() {
+        Ok("cascade") => SubtaskCompletion::Cascade,
+        _ => SubtaskCompletion::Block,
+    };
+
+    // Moves into a full column are refused unless set to only warn
+    let wip_limits = match std::env::var("WIP_LIMITS").as_deref() {
+        Ok("warn") => WipLimits::Warn,
+        _ => WipLimits::Reject,
+    };
+
+    let app_state = Arc::new(AppStateData {
+        repository,
+        blobs,
+        blob_lock,
+        max_attachment_bytes,
+        subtask_completion,
+        wip_limits,
+    });
 
     // Build our application with routes
     let app = Router::new()
         .route("/api/tasks", get(handlers::list_tasks))
         .route("/api/tasks", post(handlers::create_task))
-        .route("/api/tasks/:id", put(handlers::update_task))
+        .route("/api/tasks/:id", get(handlers::get_task))
+        .route("/api/tasks/:id", put(handlers::replace_task))
+        .route("/api/tasks/:id", patch(handlers::update_task))
         .route("/api/tasks/:id", delete(handlers::delete_task))
+        .route("/api/tasks/:id/move", post(handlers::move_task))
+        .route(
+            "/api/tasks/:id/transitions",
+            get(handlers::list_transitions),
+        )
+        .route("/api/tasks/:id/history", get(handlers::task_history))
+        .route("/api/tasks/:id/children", get(handlers::list_children))
+        .route(
+            "/api/tasks/:id/occurrences",
+            get(handlers::list_occurrences),
+        )
+        .route(
+            "/api/tasks/:id/recurrence",
+            delete(handlers::stop_recurrence),
+        )
+        .route(
+            "/api/tasks/:id/dependencies",
+            get(handlers::list_dependencies),
+        )
+        .route(
+            "/api/tasks/:id/dependencies/:blocker_id",
+            put(handlers::add_dependency),
+        )
+        .route(
+            "/api/tasks/:id/dependencies/:blocker_id",
+            delete(handlers::remove_dependency),
+        )
+        .route("/api/tasks/:id/comments", get(handlers::list_comments))
+        .route("/api/tasks/:id/comments", post(handlers::create_comment))
+        .route(
+            "/api/tasks/:id/comments/:comment_id",
+            put(handlers::update_comment),
+        )
+        .route(
+            "/api/tasks/:id/comments/:comment_id",
+            delete(handlers::delete_comment),
+        )
+        .route(
+            "/api/tasks/:id/attachments",
+            get(handlers::list_attachments),
+        )
+        .route(
+            "/api/tasks/:id/attachments",
+            // Room for the multipart framing around a file at the limit
+            post(handlers::upload_attachments)
+                .layer(DefaultBodyLimit::max(max_attachment_bytes + 64 * 1024)),
+        )
+        .route(
+            "/api/tasks/:id/attachments/:attachment_id",
+            get(handlers::download_attachment),
+        )
+        .route(
+            "/api/tasks/:id/attachments/:attachment_id",
+            delete(handlers::delete_attachment),
+        )
+        .route(
+            "/api/tasks/:id/time-entries",
+            get(handlers::list_time_entries),
+        )
+        .route(
+            "/api/tasks/:id/time-entries",
+            post(handlers::create_time_entry),
+        )
+        .route(
+            "/api/tasks/:id/time-entries/:entry_id",
+            put(handlers::update_time_entry),
+        )
+        .route(
+            "/api/tasks/:id/time-entries/:entry_id",
+            delete(handlers::delete_time_entry),
+        )
+        .route("/api/tasks/:id/timer/start", post(handlers::start_timer))
+        .route("/api/tasks/:id/timer/stop", post(handlers::stop_timer))
+        .route("/api/tasks/:id/restore", post(handlers::restore_task))
+        .route("/api/trash", get(handlers::list_trash))
+        .route("/api/trash/:id", delete(handlers::purge_task))
+        .route("/api/tags", get(handlers::list_tags))
+        .route("/api/tags", post(handlers::create_tag))
+        .route("/api/tags/:id", put(handlers::update_tag))
+        .route("/api/tags/:id", delete(handlers::delete_tag))
+        .route("/api/members", get(handlers::list_members))
+        .route("/api/members", post(handlers::create_member))
+        .route("/api/members/:id", put(handlers::update_member))
+        .route("/api/members/:id", delete(handlers::delete_member))
+        .route("/api/projects", get(handlers::list_projects))
+        .route("/api/projects", post(handlers::create_project))
+        .route("/api/projects/:id", get(handlers::get_project))
+        .route("/api/projects/:id", put(handlers::update_project))
+        .route("/api/projects/:id", delete(handlers::delete_project))
+        .route("/api/projects/:id/workflow", get(handlers::get_workflow))
+        .route("/api/projects/:id/workflow", put(handlers::update_workflow))
+        .route(
+            "/api/projects/:id/fields",
+            get(handlers::list_custom_fields),
+        )
+        .route(
+            "/api/projects/:id/fields",
+            post(handlers::create_custom_field),
+        )
+        .route(
+            "/api/projects/:id/fields/:field_id",
+            put(handlers::update_custom_field),
+        )
+        .route(
+            "/api/projects/:id/fields/:field_id",
+            delete(handlers::delete_custom_field),
+        )
+        .route("/api/views", get(handlers::list_saved_views))
+        .route("/api/views", post(handlers::create_saved_view))
+        .route("/api/views/:id", get(handlers::get_saved_view))
+        .route("/api/views/:id", put(handlers::update_saved_view))
+        .route("/api/views/:id", delete(handlers::delete_saved_view))
+        .route("/api/projects/:id/tasks", get(handlers::list_project_tasks))
+        .route(
+            "/api/projects/:id/tasks",
+            post(handlers::create_project_task),
+        )
         .route("/health", get(health_check))
         .layer(CorsLayer::permissive())
         .with_state(app_state);
@@ -68,6 +242,64 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Purges tasks that have been in the trash for more than `retention_days`,
+/// checking once an hour, and removes the blobs only they used.
+fn spawn_trash_purge(
+    repository: Arc<dyn TaskRepository>,
+    blobs: Arc<dyn BlobStore>,
+    blob_lock: Arc<BlobLock>,
+    retention_days: u32,
+) {
+    let retention = chrono::Duration::days(i64::from(retention_days));
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(60 * 60));
+        loop {
+            interval.tick().await;
+            let _removing = blob_lock.write().await;
+            match repository.purge_trash(Utc::now() - retention).await {
+                Ok(purged) if purged.tasks == 0 => {}
+                Ok(purged) => {
+                    blob::remove_blobs(blobs.as_ref(), &purged.blobs).await;
+                    info!("Purged {} tasks from the trash", purged.tasks);
+                }
+                Err(err) => tracing::error!("Failed to purge the trash: {err}"),
+            }
+        }
+    });
+}
+
+/// Opens task storage for `database_url`, chosen by its scheme. `memory:`
+/// keeps tasks in process memory for demos and embedding, `sqlite:` needs the
+/// `sqlite` feature and anything else is a Postgres URL.
+async fn connect_repository(
+    database_url: &str,
+) -> Result<Arc<dyn TaskRepository>, Box<dyn std::error::Error>> {
+    if database_url == "memory:" {
+        return Ok(Arc::new(InMemoryTaskRepository::new()));
+    }
+
+    if database_url.starts_with("sqlite:") {
+        #[cfg(feature = "sqlite")]
+        return Ok(Arc::new(
+            sqlite::SqliteDatabase::connect(database_url).await?,
+        ));
+
+        #[cfg(not(feature = "sqlite"))]
+        return Err(
+            "SQLite support requires building the backend with the `sqlite` feature".into(),
+        );
+    }
+
+    // Create database connection pool
+    let pool = PgPool::connect(database_url).await?;
+
+    // Run database migrations
+    sqlx::migrate!().run(&pool).await?;
+
+    Ok(Arc::new(Database::new(pool)))
+}
+
 // Health check endpoint handler
 pub async fn health_check() -> &'static str {
     "OK"
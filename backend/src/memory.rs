@@ -0,0 +1,1889 @@
+//! In-memory task storage for tests and for embedding the API without a
+//! database.
+
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::HashMap,
+    ops::Range,
+    sync::RwLock,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::{
+    custom_fields::{value_matches, CustomField, CustomFieldRequest},
+    default_workflow,
+    query::{Condition, DateField},
+    rank,
+    saved_views::{SavedView, SavedViewRequest},
+    Attachment, Comment, CommentRequest, CreateTaskRequest, HighlightSpan, Member, MemberRequest,
+    MoveTaskRequest, Page, Patch, Project, ProjectRequest, SearchMatch, SortDirection,
+    StatusCategory, Tag, TagRequest, Task, TaskDependencies, TaskEvent, TaskEventKind, TaskFilter,
+    TaskSort, TaskSortField, TaskStatus, TimeEntry, TimeEntryRequest, UpdateTaskRequest, Workflow,
+    WorkflowRequest, WorkflowState, DEFAULT_PROJECT_ID, MAX_SUBTASK_DEPTH,
+};
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    history::{self, NewTaskEvent},
+    pagination::{Cursor, SortKey},
+    repository::{
+        check_blockers, check_custom_fields, check_dependency, check_field_change, check_move,
+        check_nesting, check_new_task, check_parent_project, check_project_deletable,
+        check_project_move, check_recurrence, check_states_kept, check_tags_exist,
+        check_title_length, check_transition, check_wip_limit, done_state, is_completing,
+        move_update, neighbour_moved, next_occurrence, open_subtasks, plan_recurrence,
+        project_not_empty, settle_custom_fields, target_state, timer_already_running,
+        timer_not_running, unknown_member, unknown_parent, unknown_project, wip_limited,
+        NewAttachment, Purged, SubtaskCompletion, TaskRepository, WriteContext,
+    },
+    search::{self, SearchTerm},
+};
+
+/// `ts_rank` weights of the title (A) and description (B) in `search_vector`.
+const TITLE_WEIGHT: f64 = 1.0;
+const DESCRIPTION_WEIGHT: f64 = 0.4;
+
+/// Task storage held in a map, with the same filter, sort and cursor
+/// behaviour as [`crate::database::Database`].
+///
+/// Search words are compared case-insensitively without stemming or stop
+/// words, and the snippet is the whole description rather than a fragment.
+#[derive(Default)]
+pub struct InMemoryTaskRepository {
+    store: RwLock<Store>,
+}
+
+struct Store {
+    tasks: HashMap<Uuid, Task>,
+    events: Vec<TaskEvent>,
+    comments: Vec<Comment>,
+    /// Time entries in the order they were recorded.
+    time_entries: Vec<TimeEntry>,
+    /// Attachments in the order they were uploaded.
+    attachments: Vec<Attachment>,
+    tags: Vec<Tag>,
+    members: Vec<Member>,
+    projects: Vec<Project>,
+    /// The workflow of each project.
+    workflows: HashMap<Uuid, Workflow>,
+    /// `(blocker_id, blocked_id)` pairs.
+    dependencies: Vec<(Uuid, Uuid)>,
+    /// Custom fields in the order they were added.
+    custom_fields: Vec<CustomField>,
+    saved_views: Vec<SavedView>,
+}
+
+/// Starts with the default project, as the migrations do.
+impl Default for Store {
+    fn default() -> Self {
+        Self {
+            tasks: HashMap::new(),
+            events: Vec::new(),
+            comments: Vec::new(),
+            time_entries: Vec::new(),
+            attachments: Vec::new(),
+            tags: Vec::new(),
+            members: Vec::new(),
+            projects: vec![Project {
+                id: DEFAULT_PROJECT_ID,
+                name: "Default".to_string(),
+            }],
+            workflows: HashMap::from([(DEFAULT_PROJECT_ID, starting_workflow(DEFAULT_PROJECT_ID))]),
+            dependencies: Vec::new(),
+            custom_fields: Vec::new(),
+            saved_views: Vec::new(),
+        }
+    }
+}
+
+impl Store {
+    /// The workflow of a project that exists.
+    fn workflow(&self, project_id: Uuid) -> Workflow {
+        self.workflows[&project_id].clone()
+    }
+
+    fn live_task(&self, id: Uuid) -> Result<&Task, AppError> {
+        self.tasks
+            .get(&id)
+            .filter(|task| task.deleted_at.is_none())
+            .ok_or(AppError::TaskNotFound)
+    }
+
+    fn live_task_mut(&mut self, id: Uuid) -> Result<&mut Task, AppError> {
+        self.tasks
+            .get_mut(&id)
+            .filter(|task| task.deleted_at.is_none())
+            .ok_or(AppError::TaskNotFound)
+    }
+
+    fn record(
+        &mut self,
+        task_id: Uuid,
+        events: Vec<NewTaskEvent>,
+        context: &WriteContext,
+        at: DateTime<Utc>,
+    ) {
+        for event in events {
+            let id = self.events.len() as i64 + 1;
+            self.events.push(TaskEvent {
+                id,
+                task_id,
+                kind: event.kind,
+                field: event.field.map(str::to_string),
+                old_value: event.old_value,
+                new_value: event.new_value,
+                actor: context.actor.clone(),
+                created_at: at,
+            });
+        }
+    }
+
+    /// The custom fields of a project, in the order they were added.
+    fn fields_of(&self, project_id: Uuid) -> Vec<CustomField> {
+        self.custom_fields
+            .iter()
+            .filter(|field| field.project_id == project_id)
+            .cloned()
+            .collect()
+    }
+
+    /// Looks up the tags named, which must all exist, ordered by name.
+    fn find_tags(&self, names: &[String]) -> Result<Vec<Tag>, AppError> {
+        let mut tags: Vec<Tag> = self
+            .tags
+            .iter()
+            .filter(|tag| names.contains(&tag.name))
+            .cloned()
+            .collect();
+        check_tags_exist(names, &tags)?;
+        tags.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(tags)
+    }
+
+    /// Adds a checked task carrying `tags`, with its creation event, and
+    /// returns its id.
+    fn insert_task(
+        &mut self,
+        request: CreateTaskRequest,
+        tags: Vec<Tag>,
+        context: &WriteContext,
+        at: DateTime<Utc>,
+    ) -> Uuid {
+        let project_id = request.project_id.unwrap_or(DEFAULT_PROJECT_ID);
+        let first = self.workflows[&project_id].states[0].clone();
+        let task = Task {
+            id: Uuid::new_v4(),
+            title: request.title,
+            description: request.description,
+            rank: self.bottom_rank(project_id, &first.name, None),
+            status: first.name,
+            status_category: first.category,
+            priority: request.priority,
+            due_date: request.due_date,
+            created_at: at,
+            updated_at: at,
+            version: 1,
+            deleted_at: None,
+            comment_count: 0,
+            search: None,
+            tags,
+            assignee_id: request.assignee_id,
+            project_id,
+            parent_id: request.parent_id,
+            subtask_count: 0,
+            completed_subtask_count: 0,
+            is_blocked: false,
+            recurrence: request.recurrence,
+            estimate_minutes: request.estimate_minutes,
+            logged_minutes: 0,
+            timer_started_at: None,
+            custom_fields: request.custom_fields,
+        };
+
+        let id = task.id;
+        self.tasks.insert(id, task);
+        self.record(
+            id,
+            vec![NewTaskEvent::task(TaskEventKind::Created)],
+            context,
+            at,
+        );
+        id
+    }
+
+    /// Applies an update to a live task. With `placement`, the task goes
+    /// between the cards it names; otherwise a task that changes column goes
+    /// to the bottom of its new one.
+    fn update_task(
+        &mut self,
+        id: Uuid,
+        mut request: UpdateTaskRequest,
+        placement: Option<&MoveTaskRequest>,
+        context: &WriteContext,
+    ) -> Result<Task, AppError> {
+        if let Patch::Value(title) = &request.title {
+            check_title_length(title)?;
+        }
+
+        let tags = match request.tags.clone().into_change() {
+            Some(names) => Some(self.find_tags(&names.unwrap_or_default())?),
+            None => None,
+        };
+        if let Patch::Value(assignee_id) = request.assignee_id {
+            self.check_member(assignee_id)?;
+        }
+        if let Patch::Value(project_id) = request.project_id {
+            self.check_project(project_id)?;
+        }
+        let before = self.live_task(id)?.clone();
+        context.check_version(&before)?;
+
+        let project_id = match request.project_id {
+            Patch::Value(project_id) => project_id,
+            _ => before.project_id,
+        };
+        let moved = project_id != before.project_id;
+        if moved {
+            check_project_move(self.has_subtasks(id))?;
+        }
+        let parent_change = request.parent_id.clone().into_change();
+        if let Some(parent_id) = parent_change.unwrap_or(before.parent_id) {
+            if moved || parent_change.is_some() {
+                self.check_parent(Some(id), parent_id, project_id)?;
+            }
+        }
+        let now = Utc::now();
+        let workflow = self.workflow(project_id);
+        let target = target_state(&before, &mut request, &workflow)?;
+        settle_custom_fields(&before, &mut request, &self.fields_of(project_id))?;
+        check_transition(&before, &request, &workflow, &target)?;
+        check_blockers(&before, &target, context)?;
+        if wip_limited(&before, project_id, &target, context) {
+            check_wip_limit(&target, self.column_size(project_id, &target.name))?;
+        }
+        let completing = is_completing(&before, &target);
+        if completing {
+            self.complete_subtasks(id, done_state(&workflow), context, now)?;
+        }
+        let next = plan_recurrence(&before, &mut request, completing)?;
+        let rank = match placement {
+            Some(placement) => Some(self.place_task(id, project_id, placement)?),
+            None if moved || target.name != before.status => {
+                Some(self.bottom_rank(project_id, &target.name, None))
+            }
+            None => None,
+        };
+        let task = self.live_task_mut(id)?;
+
+        // Handlers reject nulls for required fields, so only values apply
+        if let Patch::Value(title) = request.title {
+            task.title = title;
+        }
+        if let Some(description) = request.description.into_change() {
+            task.description = description;
+        }
+        if let Patch::Value(status) = request.status {
+            task.status = status;
+            task.status_category = target.category;
+        }
+        if let Patch::Value(priority) = request.priority {
+            task.priority = priority;
+        }
+        if let Some(due_date) = request.due_date.into_change() {
+            task.due_date = due_date;
+        }
+        if let Some(tags) = tags {
+            task.tags = tags;
+        }
+        if let Some(assignee_id) = request.assignee_id.into_change() {
+            task.assignee_id = assignee_id;
+        }
+        task.project_id = project_id;
+        if let Some(parent_id) = parent_change {
+            task.parent_id = parent_id;
+        }
+        if let Some(recurrence) = request.recurrence.into_change() {
+            task.recurrence = recurrence;
+        }
+        if let Some(estimate_minutes) = request.estimate_minutes.into_change() {
+            task.estimate_minutes = estimate_minutes;
+        }
+        if let Patch::Value(custom_fields) = request.custom_fields {
+            task.custom_fields = custom_fields;
+        }
+        if let Some(rank) = rank {
+            task.rank = rank;
+        }
+        task.updated_at = now;
+        task.version += 1;
+
+        let events = history::field_changes(&before, task);
+        let task = task.clone();
+        self.record(id, events, context, now);
+        if let Some((due_date, recurrence)) = next {
+            let request = next_occurrence(&task, due_date, recurrence);
+            self.insert_task(request, task.tags.clone(), context, now);
+        }
+        self.refresh_derived_fields();
+
+        self.live_task(id).cloned()
+    }
+
+    /// Number of live tasks in a column.
+    fn column_size(&self, project_id: Uuid, status: &TaskStatus) -> i64 {
+        self.tasks
+            .values()
+            .filter(|task| {
+                task.project_id == project_id && task.status == *status && task.deleted_at.is_none()
+            })
+            .count() as i64
+    }
+
+    /// Ranks of the cards of a column, trashed ones included, leaving out
+    /// task `except`.
+    fn column_ranks<'a>(
+        &'a self,
+        project_id: Uuid,
+        status: &'a TaskStatus,
+        except: Option<Uuid>,
+    ) -> impl Iterator<Item = &'a str> {
+        self.tasks
+            .values()
+            .filter(move |task| {
+                task.project_id == project_id && task.status == *status && Some(task.id) != except
+            })
+            .map(|task| task.rank.as_str())
+    }
+
+    /// Rank below every card of a column, trashed ones included so that they
+    /// come back in place when restored, leaving out task `except`.
+    fn bottom_rank(&self, project_id: Uuid, status: &TaskStatus, except: Option<Uuid>) -> String {
+        rank::between(self.column_ranks(project_id, status, except).max(), None)
+    }
+
+    /// Rank that puts task `id` where `placement` says in its column of
+    /// `project_id`.
+    fn place_task(
+        &self,
+        id: Uuid,
+        project_id: Uuid,
+        placement: &MoveTaskRequest,
+    ) -> Result<String, AppError> {
+        let status = &placement.status;
+        // A named card must still be live in the column
+        let neighbour_rank = |neighbour_id: Uuid| {
+            self.tasks
+                .get(&neighbour_id)
+                .filter(|task| {
+                    task.project_id == project_id
+                        && task.status == *status
+                        && task.deleted_at.is_none()
+                })
+                .map(|task| task.rank.as_str())
+                .ok_or_else(neighbour_moved)
+        };
+        let ranks = || self.column_ranks(project_id, status, Some(id));
+
+        let (after, before) = match (placement.after_id, placement.before_id) {
+            (Some(after_id), _) => {
+                let after = neighbour_rank(after_id)?;
+                (Some(after), ranks().filter(|rank| *rank > after).min())
+            }
+            (None, Some(before_id)) => {
+                let before = neighbour_rank(before_id)?;
+                (ranks().filter(|rank| *rank < before).max(), Some(before))
+            }
+            (None, None) => (ranks().max(), None),
+        };
+
+        Ok(rank::between(after, before))
+    }
+
+    fn check_member(&self, id: Uuid) -> Result<(), AppError> {
+        if !self.members.iter().any(|member| member.id == id) {
+            return Err(unknown_member(id));
+        }
+        Ok(())
+    }
+
+    /// Checks that a task (`None` for a new one) can go under `parent_id` in
+    /// `project_id`.
+    fn check_parent(
+        &self,
+        task_id: Option<Uuid>,
+        parent_id: Uuid,
+        project_id: Uuid,
+    ) -> Result<(), AppError> {
+        let parent = self
+            .live_task(parent_id)
+            .map_err(|_| unknown_parent(parent_id))?;
+        check_parent_project(parent.project_id, project_id)?;
+
+        // Bounded like the recursive query, so a bad link cannot loop
+        let mut ancestry = vec![parent_id];
+        let mut next = parent.parent_id;
+        while let Some(id) = next.filter(|_| ancestry.len() <= MAX_SUBTASK_DEPTH) {
+            ancestry.push(id);
+            next = self.tasks.get(&id).and_then(|task| task.parent_id);
+        }
+
+        let height = task_id.map_or(0, |id| self.subtree_height(id));
+        check_nesting(task_id, &ancestry, height)
+    }
+
+    /// Levels of subtasks below `id`, trashed ones included, counted no
+    /// further than one past the limit.
+    fn subtree_height(&self, id: Uuid) -> usize {
+        let mut height = 0;
+        let mut level = vec![id];
+        while height <= MAX_SUBTASK_DEPTH {
+            level = self
+                .tasks
+                .values()
+                .filter(|task| task.parent_id.is_some_and(|parent| level.contains(&parent)))
+                .map(|task| task.id)
+                .collect();
+            if level.is_empty() {
+                break;
+            }
+            height += 1;
+        }
+        height
+    }
+
+    fn has_subtasks(&self, id: Uuid) -> bool {
+        self.tasks.values().any(|task| task.parent_id == Some(id))
+    }
+
+    /// Live subtasks of `id` at any depth.
+    fn live_descendants(&self, id: Uuid) -> Vec<Uuid> {
+        let mut found = Vec::new();
+        let mut pending = vec![id];
+        while let Some(parent) = pending.pop() {
+            for task in self.tasks.values() {
+                if task.parent_id == Some(parent) && task.deleted_at.is_none() {
+                    found.push(task.id);
+                    pending.push(task.id);
+                }
+            }
+        }
+        found
+    }
+
+    /// Refuses to complete task `id` with open live subtasks, or moves them
+    /// to `done` too, as `context` says.
+    fn complete_subtasks(
+        &mut self,
+        id: Uuid,
+        done: &WorkflowState,
+        context: &WriteContext,
+        at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let open: Vec<Uuid> = self
+            .live_descendants(id)
+            .into_iter()
+            .filter(|id| self.tasks[id].status_category != StatusCategory::Done)
+            .collect();
+        if open.is_empty() {
+            return Ok(());
+        }
+        if context.subtask_completion == SubtaskCompletion::Block {
+            return Err(open_subtasks());
+        }
+
+        for id in open {
+            let rank = self.bottom_rank(self.tasks[&id].project_id, &done.name, None);
+            let Some(task) = self.tasks.get_mut(&id) else {
+                continue;
+            };
+            let before = task.clone();
+            task.status = done.name.clone();
+            task.status_category = done.category;
+            task.rank = rank;
+            task.version += 1;
+            task.updated_at = at;
+            let events = history::field_changes(&before, task);
+            self.record(id, events, context, at);
+        }
+        Ok(())
+    }
+
+    /// Whether `from` blocks `to`, directly or through other tasks, trashed
+    /// ones included.
+    fn blocks_transitively(&self, from: Uuid, to: Uuid) -> bool {
+        let mut seen = vec![from];
+        let mut pending = vec![from];
+        while let Some(blocker) = pending.pop() {
+            for &(_, blocked) in self.dependencies.iter().filter(|(b, _)| *b == blocker) {
+                if blocked == to {
+                    return true;
+                }
+                if !seen.contains(&blocked) {
+                    seen.push(blocked);
+                    pending.push(blocked);
+                }
+            }
+        }
+        false
+    }
+
+    /// The live tasks `pick` returns from each dependency, oldest first.
+    fn linked_tasks(&self, pick: impl Fn(&(Uuid, Uuid)) -> Option<Uuid>) -> Vec<Task> {
+        let mut tasks: Vec<Task> = self
+            .dependencies
+            .iter()
+            .filter_map(pick)
+            .filter_map(|id| self.live_task(id).ok())
+            .cloned()
+            .collect();
+        tasks.sort_by_key(|task| (task.created_at, task.id));
+        tasks
+    }
+
+    /// Looks up the status category, recounts the live subtasks and works out
+    /// the blocked flag of every task, as the SQL engines do on each read.
+    fn refresh_derived_fields(&mut self) {
+        for task in self.tasks.values_mut() {
+            if let Some(state) = self.workflows[&task.project_id].state(&task.status) {
+                task.status_category = state.category;
+            }
+        }
+
+        let mut counts: HashMap<Uuid, (i64, i64)> = HashMap::new();
+        for task in self.tasks.values().filter(|task| task.deleted_at.is_none()) {
+            if let Some(parent_id) = task.parent_id {
+                let count = counts.entry(parent_id).or_default();
+                count.0 += 1;
+                if task.status_category == StatusCategory::Done {
+                    count.1 += 1;
+                }
+            }
+        }
+
+        for task in self.tasks.values_mut() {
+            let (total, completed) = counts.get(&task.id).copied().unwrap_or_default();
+            task.subtask_count = total;
+            task.completed_subtask_count = completed;
+        }
+
+        let open_blockers: Vec<Uuid> = self
+            .dependencies
+            .iter()
+            .filter(|(blocker, _)| {
+                self.tasks.get(blocker).is_some_and(|task| {
+                    task.deleted_at.is_none() && task.status_category != StatusCategory::Done
+                })
+            })
+            .map(|(_, blocked)| *blocked)
+            .collect();
+        for task in self.tasks.values_mut() {
+            task.is_blocked = open_blockers.contains(&task.id);
+        }
+
+        // Whole seconds are totalled before rounding down to minutes, as in SQL
+        let mut logged: HashMap<Uuid, i64> = HashMap::new();
+        let mut running: HashMap<Uuid, DateTime<Utc>> = HashMap::new();
+        for entry in &self.time_entries {
+            match entry.ended_at {
+                Some(ended_at) => {
+                    let micros = (ended_at - entry.started_at)
+                        .num_microseconds()
+                        .unwrap_or_default();
+                    *logged.entry(entry.task_id).or_default() += micros;
+                }
+                None => {
+                    let started_at = running.entry(entry.task_id).or_insert(entry.started_at);
+                    *started_at = (*started_at).min(entry.started_at);
+                }
+            }
+        }
+        for task in self.tasks.values_mut() {
+            let micros = logged.get(&task.id).copied().unwrap_or_default();
+            task.logged_minutes = (micros + 500_000) / 1_000_000 / 60;
+            task.timer_started_at = running.get(&task.id).copied();
+        }
+    }
+
+    /// Stops the running timers `pick` selects, at `at`.
+    fn stop_timers(&mut self, pick: impl Fn(&TimeEntry) -> bool, at: DateTime<Utc>) {
+        for entry in &mut self.time_entries {
+            if entry.ended_at.is_none() && pick(entry) {
+                entry.ended_at = Some(at);
+            }
+        }
+    }
+
+    fn time_entry_mut(&mut self, task_id: Uuid, id: Uuid) -> Result<&mut TimeEntry, AppError> {
+        self.time_entries
+            .iter_mut()
+            .find(|entry| entry.id == id && entry.task_id == task_id)
+            .ok_or(AppError::TimeEntryNotFound)
+    }
+
+    fn check_project(&self, id: Uuid) -> Result<(), AppError> {
+        if !self.projects.iter().any(|project| project.id == id) {
+            return Err(unknown_project(id));
+        }
+        Ok(())
+    }
+
+    fn check_field_name_free(
+        &self,
+        project_id: Uuid,
+        name: &str,
+        except: Option<Uuid>,
+    ) -> Result<(), AppError> {
+        if self.custom_fields.iter().any(|field| {
+            field.project_id == project_id && field.name == name && Some(field.id) != except
+        }) {
+            return Err(AppError::Conflict(format!(
+                "Custom field already exists: {name}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks that a view's name is free and that the project its filter
+    /// names exists.
+    fn check_view(&self, request: &SavedViewRequest, except: Option<Uuid>) -> Result<(), AppError> {
+        if self
+            .saved_views
+            .iter()
+            .any(|view| view.name == request.name && Some(view.id) != except)
+        {
+            return Err(AppError::Conflict(format!(
+                "View already exists: {}",
+                request.name
+            )));
+        }
+        match request.filter.project {
+            Some(project) => self.check_project(project),
+            None => Ok(()),
+        }
+    }
+
+    fn check_tag_name_free(&self, name: &str, except: Option<Uuid>) -> Result<(), AppError> {
+        if self
+            .tags
+            .iter()
+            .any(|tag| tag.name == name && Some(tag.id) != except)
+        {
+            return Err(AppError::Conflict(format!("Tag already exists: {name}")));
+        }
+        Ok(())
+    }
+
+    /// Drops tasks that fail `keep`, along with their history, comments and
+    /// time entries.
+    fn remove_tasks(&mut self, keep: impl Fn(&Task) -> bool) -> Purged {
+        let count = self.tasks.len();
+        self.tasks.retain(|_, task| keep(task));
+        let tasks = &self.tasks;
+        self.events
+            .retain(|event| tasks.contains_key(&event.task_id));
+        self.comments
+            .retain(|comment| tasks.contains_key(&comment.task_id));
+        self.time_entries
+            .retain(|entry| tasks.contains_key(&entry.task_id));
+        let (kept, removed): (Vec<_>, Vec<_>) = self
+            .attachments
+            .drain(..)
+            .partition(|attachment| tasks.contains_key(&attachment.task_id));
+        self.attachments = kept;
+        let mut blobs: Vec<String> = removed
+            .into_iter()
+            .map(|attachment| attachment.sha256)
+            .filter(|sha256| !self.blob_in_use(sha256))
+            .collect();
+        blobs.sort();
+        blobs.dedup();
+        self.dependencies.retain(|(blocker, blocked)| {
+            tasks.contains_key(blocker) && tasks.contains_key(blocked)
+        });
+
+        // Like `ON DELETE SET NULL`: subtasks of a removed task become
+        // top-level tasks
+        let orphans: Vec<Uuid> = tasks
+            .values()
+            .filter(|task| task.parent_id.is_some_and(|id| !tasks.contains_key(&id)))
+            .map(|task| task.id)
+            .collect();
+        for id in orphans {
+            if let Some(task) = self.tasks.get_mut(&id) {
+                task.parent_id = None;
+            }
+        }
+
+        Purged {
+            tasks: (count - self.tasks.len()) as u64,
+            blobs,
+        }
+    }
+
+    fn blob_in_use(&self, sha256: &str) -> bool {
+        self.attachments
+            .iter()
+            .any(|attachment| attachment.sha256 == sha256)
+    }
+}
+
+impl InMemoryTaskRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl TaskRepository for InMemoryTaskRepository {
+    async fn create_task(
+        &self,
+        mut request: CreateTaskRequest,
+        context: &WriteContext,
+    ) -> Result<Task, AppError> {
+        check_title_length(&request.title)?;
+
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        let tags = store.find_tags(&request.tags)?;
+        if let Some(assignee_id) = request.assignee_id {
+            store.check_member(assignee_id)?;
+        }
+        let project_id = request.project_id.unwrap_or(DEFAULT_PROJECT_ID);
+        store.check_project(project_id)?;
+        if let Some(parent_id) = request.parent_id {
+            store.check_parent(None, parent_id, project_id)?;
+        }
+        check_recurrence(request.recurrence.as_deref(), request.due_date)?;
+        check_new_task(&request, &store.workflow(project_id))?;
+        check_custom_fields(&mut request.custom_fields, &store.fields_of(project_id))?;
+
+        let id = store.insert_task(request, tags, context, Utc::now());
+        store.refresh_derived_fields();
+
+        store.live_task(id).cloned()
+    }
+
+    async fn get_task(&self, id: Uuid) -> Result<Task, AppError> {
+        let store = self.store.read().map_err(|_| AppError::InternalError)?;
+        store.live_task(id).cloned()
+    }
+
+    async fn list_tasks(
+        &self,
+        filter: &TaskFilter,
+        sort: TaskSort,
+        after: Option<&Cursor>,
+        limit: u32,
+    ) -> Result<Page<Task>, AppError> {
+        let terms = filter
+            .search_text()
+            .as_deref()
+            .map(search::parse_terms)
+            .unwrap_or_default();
+
+        let mut rows: Vec<(Task, SortKey)> = {
+            let store = self.store.read().map_err(|_| AppError::InternalError)?;
+            store
+                .tasks
+                .values()
+                .filter(|task| matches_filter(task, filter, &store.members))
+                .filter_map(|task| {
+                    let mut task = task.clone();
+                    let mut rank = 0.0;
+                    if !terms.is_empty() {
+                        let (search, search_rank) = search_task(&task, &terms)?;
+                        task.search = Some(search);
+                        rank = search_rank;
+                    }
+                    let key = sort_key(&task, sort.field, rank);
+                    Some((task, key))
+                })
+                .collect()
+        };
+        // Later pages reuse the first page's total, as in PostgreSQL
+        let total = after.map_or(rows.len() as u64, |cursor| cursor.total);
+
+        rows.sort_by(|(a, a_key), (b, b_key)| compare(sort, (a_key, a.id), (b_key, b.id)));
+        if let Some(cursor) = after {
+            rows.retain(|(task, key)| {
+                compare(sort, (key, task.id), (&cursor.key, cursor.id)) == Ordering::Greater
+            });
+        }
+
+        let next_cursor = if rows.len() > limit as usize {
+            rows.truncate(limit as usize);
+            rows.last()
+                .map(|(task, key)| Cursor::new(sort, key.clone(), task.id, total).encode())
+        } else {
+            None
+        };
+
+        Ok(Page {
+            items: rows.into_iter().map(|(task, _)| task).collect(),
+            next_cursor,
+            total_estimate: total,
+        })
+    }
+
+    async fn update_task(
+        &self,
+        id: Uuid,
+        request: UpdateTaskRequest,
+        context: &WriteContext,
+    ) -> Result<Task, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        store.update_task(id, request, None, context)
+    }
+
+    async fn move_task(
+        &self,
+        id: Uuid,
+        request: MoveTaskRequest,
+        context: &WriteContext,
+    ) -> Result<Task, AppError> {
+        check_move(id, &request)?;
+
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        store.update_task(id, move_update(&request), Some(&request), context)
+    }
+
+    async fn delete_task(&self, id: Uuid, context: &WriteContext) -> Result<(), AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        let task = store.live_task_mut(id)?;
+        context.check_version(task)?;
+
+        let now = Utc::now();
+        task.deleted_at = Some(now);
+        store.stop_timers(|entry| entry.task_id == id, now);
+        store.record(
+            id,
+            vec![NewTaskEvent::task(TaskEventKind::Deleted)],
+            context,
+            now,
+        );
+        store.refresh_derived_fields();
+
+        Ok(())
+    }
+
+    async fn list_trash(&self) -> Result<Vec<Task>, AppError> {
+        let store = self.store.read().map_err(|_| AppError::InternalError)?;
+        let mut trash: Vec<Task> = store
+            .tasks
+            .values()
+            .filter(|task| task.deleted_at.is_some())
+            .cloned()
+            .collect();
+        trash.sort_by_key(|task| Reverse((task.deleted_at, task.id)));
+
+        Ok(trash)
+    }
+
+    async fn restore_task(&self, id: Uuid, context: &WriteContext) -> Result<Task, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        let task = store
+            .tasks
+            .get_mut(&id)
+            .filter(|task| task.deleted_at.is_some())
+            .ok_or(AppError::TaskNotFound)?;
+
+        task.deleted_at = None;
+        store.record(
+            id,
+            vec![NewTaskEvent::task(TaskEventKind::Restored)],
+            context,
+            Utc::now(),
+        );
+        store.refresh_derived_fields();
+
+        store.live_task(id).cloned()
+    }
+
+    async fn purge_task(&self, id: Uuid) -> Result<Purged, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        if store
+            .tasks
+            .get(&id)
+            .is_none_or(|task| task.deleted_at.is_none())
+        {
+            return Err(AppError::TaskNotFound);
+        }
+
+        Ok(store.remove_tasks(|task| task.id != id))
+    }
+
+    async fn purge_trash(&self, before: DateTime<Utc>) -> Result<Purged, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        Ok(store.remove_tasks(|task| {
+            task.deleted_at
+                .is_none_or(|deleted_at| deleted_at >= before)
+        }))
+    }
+
+    async fn list_children(&self, id: Uuid) -> Result<Vec<Task>, AppError> {
+        let store = self.store.read().map_err(|_| AppError::InternalError)?;
+        store.live_task(id)?;
+
+        let mut children: Vec<Task> = store
+            .tasks
+            .values()
+            .filter(|task| task.parent_id == Some(id) && task.deleted_at.is_none())
+            .cloned()
+            .collect();
+        children.sort_by_key(|task| (task.created_at, task.id));
+        Ok(children)
+    }
+
+    async fn list_dependencies(&self, id: Uuid) -> Result<TaskDependencies, AppError> {
+        let store = self.store.read().map_err(|_| AppError::InternalError)?;
+        store.live_task(id)?;
+
+        Ok(TaskDependencies {
+            blocked_by: store
+                .linked_tasks(|&(blocker, blocked)| (blocked == id).then_some(blocker)),
+            blocks: store.linked_tasks(|&(blocker, blocked)| (blocker == id).then_some(blocked)),
+        })
+    }
+
+    async fn add_dependency(&self, id: Uuid, blocker_id: Uuid) -> Result<(), AppError> {
+        check_dependency(id, blocker_id)?;
+
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        store.live_task(id)?;
+        store.live_task(blocker_id)?;
+        if store.blocks_transitively(id, blocker_id) {
+            return Err(AppError::DependencyCycle);
+        }
+
+        if !store.dependencies.contains(&(blocker_id, id)) {
+            store.dependencies.push((blocker_id, id));
+            store.refresh_derived_fields();
+        }
+        Ok(())
+    }
+
+    async fn remove_dependency(&self, id: Uuid, blocker_id: Uuid) -> Result<(), AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        store.live_task(id)?;
+
+        let count = store.dependencies.len();
+        store
+            .dependencies
+            .retain(|&dependency| dependency != (blocker_id, id));
+        if store.dependencies.len() == count {
+            return Err(AppError::DependencyNotFound);
+        }
+
+        store.refresh_derived_fields();
+        Ok(())
+    }
+
+    async fn task_history(&self, id: Uuid) -> Result<Vec<TaskEvent>, AppError> {
+        let store = self.store.read().map_err(|_| AppError::InternalError)?;
+        store.live_task(id)?;
+
+        Ok(store
+            .events
+            .iter()
+            .filter(|event| event.task_id == id)
+            .cloned()
+            .collect())
+    }
+
+    async fn list_comments(&self, task_id: Uuid) -> Result<Vec<Comment>, AppError> {
+        let store = self.store.read().map_err(|_| AppError::InternalError)?;
+        store.live_task(task_id)?;
+
+        // Comments are kept in the order they were written
+        Ok(store
+            .comments
+            .iter()
+            .filter(|comment| comment.task_id == task_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn create_comment(
+        &self,
+        task_id: Uuid,
+        request: CommentRequest,
+        author: Option<String>,
+    ) -> Result<Comment, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        store.live_task_mut(task_id)?.comment_count += 1;
+
+        let now = Utc::now();
+        let comment = Comment {
+            id: Uuid::new_v4(),
+            task_id,
+            body: request.body,
+            author,
+            created_at: now,
+            updated_at: now,
+        };
+        store.comments.push(comment.clone());
+
+        Ok(comment)
+    }
+
+    async fn update_comment(
+        &self,
+        task_id: Uuid,
+        id: Uuid,
+        request: CommentRequest,
+    ) -> Result<Comment, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        store.live_task(task_id)?;
+
+        let comment = store
+            .comments
+            .iter_mut()
+            .find(|comment| comment.id == id && comment.task_id == task_id)
+            .ok_or(AppError::CommentNotFound)?;
+        comment.body = request.body;
+        comment.updated_at = Utc::now();
+
+        Ok(comment.clone())
+    }
+
+    async fn delete_comment(&self, task_id: Uuid, id: Uuid) -> Result<(), AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        store.live_task(task_id)?;
+
+        let index = store
+            .comments
+            .iter()
+            .position(|comment| comment.id == id && comment.task_id == task_id)
+            .ok_or(AppError::CommentNotFound)?;
+        store.comments.remove(index);
+        store.live_task_mut(task_id)?.comment_count -= 1;
+
+        Ok(())
+    }
+
+    async fn list_time_entries(&self, task_id: Uuid) -> Result<Vec<TimeEntry>, AppError> {
+        let store = self.store.read().map_err(|_| AppError::InternalError)?;
+        store.live_task(task_id)?;
+
+        let mut entries: Vec<TimeEntry> = store
+            .time_entries
+            .iter()
+            .filter(|entry| entry.task_id == task_id)
+            .cloned()
+            .collect();
+        entries.sort_by_key(|entry| (entry.started_at, entry.id));
+        Ok(entries)
+    }
+
+    async fn start_timer(&self, task_id: Uuid, member_id: Uuid) -> Result<TimeEntry, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        store.live_task(task_id)?;
+        store.check_member(member_id)?;
+        if store.time_entries.iter().any(|entry| {
+            entry.member_id == Some(member_id)
+                && entry.task_id == task_id
+                && entry.ended_at.is_none()
+        }) {
+            return Err(timer_already_running());
+        }
+
+        let now = Utc::now();
+        store.stop_timers(|entry| entry.member_id == Some(member_id), now);
+        let entry = TimeEntry {
+            id: Uuid::new_v4(),
+            task_id,
+            member_id: Some(member_id),
+            started_at: now,
+            ended_at: None,
+            note: None,
+        };
+        store.time_entries.push(entry.clone());
+        store.refresh_derived_fields();
+
+        Ok(entry)
+    }
+
+    async fn stop_timer(&self, task_id: Uuid, member_id: Uuid) -> Result<TimeEntry, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        store.live_task(task_id)?;
+
+        let entry = store
+            .time_entries
+            .iter_mut()
+            .find(|entry| {
+                entry.member_id == Some(member_id)
+                    && entry.task_id == task_id
+                    && entry.ended_at.is_none()
+            })
+            .ok_or_else(timer_not_running)?;
+        entry.ended_at = Some(Utc::now());
+        let entry = entry.clone();
+        store.refresh_derived_fields();
+
+        Ok(entry)
+    }
+
+    async fn create_time_entry(
+        &self,
+        task_id: Uuid,
+        request: TimeEntryRequest,
+    ) -> Result<TimeEntry, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        store.live_task(task_id)?;
+        store.check_member(request.member_id)?;
+
+        let entry = TimeEntry {
+            id: Uuid::new_v4(),
+            task_id,
+            member_id: Some(request.member_id),
+            started_at: request.started_at,
+            ended_at: Some(request.ended_at),
+            note: request.note,
+        };
+        store.time_entries.push(entry.clone());
+        store.refresh_derived_fields();
+
+        Ok(entry)
+    }
+
+    async fn update_time_entry(
+        &self,
+        task_id: Uuid,
+        id: Uuid,
+        request: TimeEntryRequest,
+    ) -> Result<TimeEntry, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        store.live_task(task_id)?;
+        store.check_member(request.member_id)?;
+
+        let entry = store.time_entry_mut(task_id, id)?;
+        entry.member_id = Some(request.member_id);
+        entry.started_at = request.started_at;
+        entry.ended_at = Some(request.ended_at);
+        entry.note = request.note;
+        let entry = entry.clone();
+        store.refresh_derived_fields();
+
+        Ok(entry)
+    }
+
+    async fn delete_time_entry(&self, task_id: Uuid, id: Uuid) -> Result<(), AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        store.live_task(task_id)?;
+
+        let index = store
+            .time_entries
+            .iter()
+            .position(|entry| entry.id == id && entry.task_id == task_id)
+            .ok_or(AppError::TimeEntryNotFound)?;
+        store.time_entries.remove(index);
+        store.refresh_derived_fields();
+
+        Ok(())
+    }
+
+    async fn list_attachments(&self, task_id: Uuid) -> Result<Vec<Attachment>, AppError> {
+        let store = self.store.read().map_err(|_| AppError::InternalError)?;
+        store.live_task(task_id)?;
+
+        Ok(store
+            .attachments
+            .iter()
+            .filter(|attachment| attachment.task_id == task_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_attachment(&self, task_id: Uuid, id: Uuid) -> Result<Attachment, AppError> {
+        let store = self.store.read().map_err(|_| AppError::InternalError)?;
+        store.live_task(task_id)?;
+
+        store
+            .attachments
+            .iter()
+            .find(|attachment| attachment.id == id && attachment.task_id == task_id)
+            .cloned()
+            .ok_or(AppError::AttachmentNotFound)
+    }
+
+    async fn create_attachment(
+        &self,
+        task_id: Uuid,
+        attachment: NewAttachment,
+    ) -> Result<Attachment, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        store.live_task(task_id)?;
+
+        let attachment = Attachment {
+            id: Uuid::new_v4(),
+            task_id,
+            filename: attachment.filename,
+            content_type: attachment.content_type,
+            size_bytes: attachment.size_bytes,
+            sha256: attachment.sha256,
+            created_at: Utc::now(),
+        };
+        store.attachments.push(attachment.clone());
+
+        Ok(attachment)
+    }
+
+    async fn delete_attachment(&self, task_id: Uuid, id: Uuid) -> Result<Option<String>, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        store.live_task(task_id)?;
+
+        let index = store
+            .attachments
+            .iter()
+            .position(|attachment| attachment.id == id && attachment.task_id == task_id)
+            .ok_or(AppError::AttachmentNotFound)?;
+        let attachment = store.attachments.remove(index);
+
+        Ok((!store.blob_in_use(&attachment.sha256)).then_some(attachment.sha256))
+    }
+
+    async fn list_tags(&self) -> Result<Vec<Tag>, AppError> {
+        let store = self.store.read().map_err(|_| AppError::InternalError)?;
+        let mut tags = store.tags.clone();
+        tags.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(tags)
+    }
+
+    async fn create_tag(&self, request: TagRequest) -> Result<Tag, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        store.check_tag_name_free(&request.name, None)?;
+
+        let tag = Tag {
+            id: Uuid::new_v4(),
+            name: request.name,
+            color: request.color,
+        };
+        store.tags.push(tag.clone());
+
+        Ok(tag)
+    }
+
+    async fn update_tag(&self, id: Uuid, request: TagRequest) -> Result<Tag, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        store.check_tag_name_free(&request.name, Some(id))?;
+
+        let tag = store
+            .tags
+            .iter_mut()
+            .find(|tag| tag.id == id)
+            .ok_or(AppError::TagNotFound)?;
+        tag.name = request.name;
+        tag.color = request.color;
+        let tag = tag.clone();
+
+        // Tasks hold copies of their tags, so renames are applied to each
+        for task in store.tasks.values_mut() {
+            if let Some(copy) = task.tags.iter_mut().find(|copy| copy.id == id) {
+                *copy = tag.clone();
+                task.tags.sort_by(|a, b| a.name.cmp(&b.name));
+            }
+        }
+
+        Ok(tag)
+    }
+
+    async fn delete_tag(&self, id: Uuid) -> Result<(), AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        let index = store
+            .tags
+            .iter()
+            .position(|tag| tag.id == id)
+            .ok_or(AppError::TagNotFound)?;
+        store.tags.remove(index);
+
+        for task in store.tasks.values_mut() {
+            task.tags.retain(|tag| tag.id != id);
+        }
+
+        Ok(())
+    }
+
+    async fn list_members(&self) -> Result<Vec<Member>, AppError> {
+        let store = self.store.read().map_err(|_| AppError::InternalError)?;
+        let mut members = store.members.clone();
+        members.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)));
+
+        Ok(members)
+    }
+
+    async fn create_member(&self, request: MemberRequest) -> Result<Member, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        let member = Member {
+            id: Uuid::new_v4(),
+            name: request.name,
+        };
+        store.members.push(member.clone());
+
+        Ok(member)
+    }
+
+    async fn update_member(&self, id: Uuid, request: MemberRequest) -> Result<Member, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        let member = store
+            .members
+            .iter_mut()
+            .find(|member| member.id == id)
+            .ok_or(AppError::MemberNotFound)?;
+        member.name = request.name;
+
+        Ok(member.clone())
+    }
+
+    async fn delete_member(&self, id: Uuid) -> Result<(), AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        let index = store
+            .members
+            .iter()
+            .position(|member| member.id == id)
+            .ok_or(AppError::MemberNotFound)?;
+        store.members.remove(index);
+        store.stop_timers(|entry| entry.member_id == Some(id), Utc::now());
+
+        // Like `ON DELETE SET NULL`: no new version or history entry
+        for task in store.tasks.values_mut() {
+            if task.assignee_id == Some(id) {
+                task.assignee_id = None;
+            }
+        }
+        for entry in &mut store.time_entries {
+            if entry.member_id == Some(id) {
+                entry.member_id = None;
+            }
+        }
+        store.refresh_derived_fields();
+
+        Ok(())
+    }
+
+    async fn list_projects(&self) -> Result<Vec<Project>, AppError> {
+        let store = self.store.read().map_err(|_| AppError::InternalError)?;
+        let mut projects = store.projects.clone();
+        projects.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)));
+
+        Ok(projects)
+    }
+
+    async fn get_project(&self, id: Uuid) -> Result<Project, AppError> {
+        let store = self.store.read().map_err(|_| AppError::InternalError)?;
+        store
+            .projects
+            .iter()
+            .find(|project| project.id == id)
+            .cloned()
+            .ok_or(AppError::ProjectNotFound)
+    }
+
+    async fn create_project(&self, request: ProjectRequest) -> Result<Project, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        let project = Project {
+            id: Uuid::new_v4(),
+            name: request.name,
+        };
+        store.projects.push(project.clone());
+        store
+            .workflows
+            .insert(project.id, starting_workflow(project.id));
+
+        Ok(project)
+    }
+
+    async fn update_project(&self, id: Uuid, request: ProjectRequest) -> Result<Project, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        let project = store
+            .projects
+            .iter_mut()
+            .find(|project| project.id == id)
+            .ok_or(AppError::ProjectNotFound)?;
+        project.name = request.name;
+
+        Ok(project.clone())
+    }
+
+    async fn delete_project(&self, id: Uuid) -> Result<(), AppError> {
+        check_project_deletable(id)?;
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        let index = store
+            .projects
+            .iter()
+            .position(|project| project.id == id)
+            .ok_or(AppError::ProjectNotFound)?;
+        if store.tasks.values().any(|task| task.project_id == id) {
+            return Err(project_not_empty());
+        }
+        store.projects.remove(index);
+        store.workflows.remove(&id);
+        store.custom_fields.retain(|field| field.project_id != id);
+        store
+            .saved_views
+            .retain(|view| view.filter.project != Some(id));
+
+        Ok(())
+    }
+
+    async fn count_in_state(&self, project_id: Uuid, status: &TaskStatus) -> Result<i64, AppError> {
+        let store = self.store.read().map_err(|_| AppError::InternalError)?;
+        Ok(store.column_size(project_id, status))
+    }
+
+    async fn get_workflow(&self, project_id: Uuid) -> Result<Workflow, AppError> {
+        let store = self.store.read().map_err(|_| AppError::InternalError)?;
+        if !store.workflows.contains_key(&project_id) {
+            return Err(AppError::ProjectNotFound);
+        }
+
+        Ok(store.workflow(project_id))
+    }
+
+    async fn update_workflow(
+        &self,
+        project_id: Uuid,
+        request: WorkflowRequest,
+    ) -> Result<Workflow, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        if !store.workflows.contains_key(&project_id) {
+            return Err(AppError::ProjectNotFound);
+        }
+        let in_use: Vec<TaskStatus> = store
+            .tasks
+            .values()
+            .filter(|task| task.project_id == project_id)
+            .map(|task| task.status.clone())
+            .collect();
+        check_states_kept(&in_use, &request)?;
+
+        let mut priority_rules = request.priority_rules;
+        priority_rules.sort_by_key(|rule| rule.priority);
+        store.workflows.insert(
+            project_id,
+            Workflow {
+                project_id,
+                states: request.states,
+                priority_rules,
+            },
+        );
+        store.refresh_derived_fields();
+
+        Ok(store.workflow(project_id))
+    }
+
+    async fn list_custom_fields(&self, project_id: Uuid) -> Result<Vec<CustomField>, AppError> {
+        let store = self.store.read().map_err(|_| AppError::InternalError)?;
+        if !store
+            .projects
+            .iter()
+            .any(|project| project.id == project_id)
+        {
+            return Err(AppError::ProjectNotFound);
+        }
+
+        Ok(store.fields_of(project_id))
+    }
+
+    async fn get_custom_field(&self, id: Uuid) -> Result<CustomField, AppError> {
+        let store = self.store.read().map_err(|_| AppError::InternalError)?;
+        store
+            .custom_fields
+            .iter()
+            .find(|field| field.id == id)
+            .cloned()
+            .ok_or(AppError::CustomFieldNotFound)
+    }
+
+    async fn create_custom_field(
+        &self,
+        project_id: Uuid,
+        request: CustomFieldRequest,
+    ) -> Result<CustomField, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        if !store
+            .projects
+            .iter()
+            .any(|project| project.id == project_id)
+        {
+            return Err(AppError::ProjectNotFound);
+        }
+        store.check_field_name_free(project_id, &request.name, None)?;
+
+        let field = CustomField {
+            id: Uuid::new_v4(),
+            project_id,
+            name: request.name,
+            field_type: request.field_type,
+            options: request.options,
+        };
+        store.custom_fields.push(field.clone());
+
+        Ok(field)
+    }
+
+    async fn update_custom_field(
+        &self,
+        project_id: Uuid,
+        id: Uuid,
+        request: CustomFieldRequest,
+    ) -> Result<CustomField, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        let field = store
+            .custom_fields
+            .iter()
+            .find(|field| field.id == id && field.project_id == project_id)
+            .ok_or(AppError::CustomFieldNotFound)?;
+        let in_use: Vec<serde_json::Value> = store
+            .tasks
+            .values()
+            .filter_map(|task| task.custom_fields.get(&id).cloned())
+            .collect();
+        check_field_change(field, &request, &in_use)?;
+        store.check_field_name_free(project_id, &request.name, Some(id))?;
+
+        let field = store
+            .custom_fields
+            .iter_mut()
+            .find(|field| field.id == id)
+            .ok_or(AppError::CustomFieldNotFound)?;
+        field.name = request.name;
+        field.options = request.options;
+
+        Ok(field.clone())
+    }
+
+    async fn delete_custom_field(&self, project_id: Uuid, id: Uuid) -> Result<(), AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        let index = store
+            .custom_fields
+            .iter()
+            .position(|field| field.id == id && field.project_id == project_id)
+            .ok_or(AppError::CustomFieldNotFound)?;
+        store.custom_fields.remove(index);
+        for task in store.tasks.values_mut() {
+            task.custom_fields.remove(&id);
+        }
+
+        Ok(())
+    }
+
+    async fn list_saved_views(&self) -> Result<Vec<SavedView>, AppError> {
+        let store = self.store.read().map_err(|_| AppError::InternalError)?;
+        let mut views = store.saved_views.clone();
+        views.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(views)
+    }
+
+    async fn get_saved_view(&self, id: Uuid) -> Result<SavedView, AppError> {
+        let store = self.store.read().map_err(|_| AppError::InternalError)?;
+        store
+            .saved_views
+            .iter()
+            .find(|view| view.id == id)
+            .cloned()
+            .ok_or(AppError::SavedViewNotFound)
+    }
+
+    async fn create_saved_view(&self, request: SavedViewRequest) -> Result<SavedView, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        store.check_view(&request, None)?;
+
+        let now = Utc::now();
+        let view = SavedView {
+            id: Uuid::new_v4(),
+            name: request.name,
+            filter: request.filter,
+            sort: request.sort,
+            grouping: request.grouping,
+            columns: request.columns,
+            created_at: now,
+            updated_at: now,
+        };
+        store.saved_views.push(view.clone());
+
+        Ok(view)
+    }
+
+    async fn update_saved_view(
+        &self,
+        id: Uuid,
+        request: SavedViewRequest,
+    ) -> Result<SavedView, AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        if !store.saved_views.iter().any(|view| view.id == id) {
+            return Err(AppError::SavedViewNotFound);
+        }
+        store.check_view(&request, Some(id))?;
+
+        let view = store
+            .saved_views
+            .iter_mut()
+            .find(|view| view.id == id)
+            .ok_or(AppError::SavedViewNotFound)?;
+        view.name = request.name;
+        view.filter = request.filter;
+        view.sort = request.sort;
+        view.grouping = request.grouping;
+        view.columns = request.columns;
+        view.updated_at = Utc::now();
+
+        Ok(view.clone())
+    }
+
+    async fn delete_saved_view(&self, id: Uuid) -> Result<(), AppError> {
+        let mut store = self.store.write().map_err(|_| AppError::InternalError)?;
+        let index = store
+            .saved_views
+            .iter()
+            .position(|view| view.id == id)
+            .ok_or(AppError::SavedViewNotFound)?;
+        store.saved_views.remove(index);
+
+        Ok(())
+    }
+}
+
+/// The workflow a new project starts with.
+fn starting_workflow(project_id: Uuid) -> Workflow {
+    Workflow {
+        project_id,
+        states: default_workflow(),
+        priority_rules: Vec::new(),
+    }
+}
+
+/// Applies every criterion of `filter` except the search query, leaving out
+/// trashed tasks. Range bounds are exclusive and never match a missing date,
+/// as in SQL.
+fn matches_filter(task: &Task, filter: &TaskFilter, members: &[Member]) -> bool {
+    task.deleted_at.is_none()
+        && (filter.status.is_empty() || filter.status.contains(&task.status))
+        && (filter.priority.is_empty() || filter.priority.contains(&task.priority))
+        && filter
+            .assignee
+            .is_none_or(|assignee| task.assignee_id == Some(assignee))
+        && filter
+            .project
+            .is_none_or(|project| task.project_id == project)
+        && filter.tag.as_ref().is_none_or(|name| has_tag(task, name))
+        && (filter.any_tag.is_empty() || filter.any_tag.iter().any(|name| has_tag(task, name)))
+        && filter.all_tags.iter().all(|name| has_tag(task, name))
+        && filter
+            .has_due_date
+            .is_none_or(|has_due_date| task.due_date.is_some() == has_due_date)
+        && in_range(task.due_date, filter.due_after, filter.due_before)
+        && in_range(
+            Some(task.created_at),
+            filter.created_after,
+            filter.created_before,
+        )
+        && in_range(
+            Some(task.updated_at),
+            filter.updated_after,
+            filter.updated_before,
+        )
+        && filter.custom_fields.iter().all(|wanted| {
+            task.custom_fields
+                .get(&wanted.field_id)
+                .is_some_and(|value| value_matches(value, &wanted.value))
+        })
+        && filter.query.as_ref().is_none_or(|query| {
+            query
+                .conditions()
+                .all(|(negated, condition)| matches_condition(task, condition, members) != negated)
+        })
+}
+
+/// Whether a task meets one condition of a task query. Like a SQL
+/// comparison with NULL, a date comparison fails for a missing due date.
+fn matches_condition(task: &Task, condition: &Condition, members: &[Member]) -> bool {
+    match condition {
+        Condition::Status(status) => task.status == *status,
+        Condition::Priority(comparison, priority) => {
+            comparison.priorities(*priority).contains(&task.priority)
+        }
+        Condition::Date(field, comparison, date) => {
+            let value = match field {
+                DateField::Due => task.due_date,
+                DateField::Created => Some(task.created_at),
+                DateField::Updated => Some(task.updated_at),
+            };
+            let (start, end) = comparison.day_range(*date);
+            value.is_some_and(|value| {
+                start.is_none_or(|start| value >= start) && end.is_none_or(|end| value < end)
+            })
+        }
+        Condition::NoDueDate => task.due_date.is_none(),
+        Condition::Tag(name) => has_tag(task, name),
+        Condition::Assignee(None) => task.assignee_id.is_none(),
+        Condition::Assignee(Some(name)) => task.assignee_id.is_some_and(|id| {
+            members
+                .iter()
+                .any(|member| member.id == id && member.name == *name)
+        }),
+    }
+}
+
+fn has_tag(task: &Task, name: &str) -> bool {
+    task.tags.iter().any(|tag| tag.name == name)
+}
+
+fn in_range(
+    value: Option<DateTime<Utc>>,
+    after: Option<DateTime<Utc>>,
+    before: Option<DateTime<Utc>>,
+) -> bool {
+    match value {
+        Some(value) => {
+            after.is_none_or(|after| value > after) && before.is_none_or(|before| value < before)
+        }
+        None => after.is_none() && before.is_none(),
+    }
+}
+
+/// Matches `terms` against a task, returning its highlights and rank, or
+/// `None` when the task does not match.
+fn search_task(task: &Task, terms: &[SearchTerm]) -> Option<(SearchMatch, f64)> {
+    let description = task.description.as_deref().unwrap_or_default();
+    let title_words = words(&task.title);
+    let description_words = words(description);
+    let mut title_matched = vec![false; title_words.len()];
+    let mut description_matched = vec![false; description_words.len()];
+    let mut rank = 0.0;
+
+    for term in terms {
+        let title_hits = occurrences(term, &title_words);
+        let description_hits = occurrences(term, &description_words);
+
+        if term.negated {
+            if !title_hits.is_empty() || !description_hits.is_empty() {
+                return None;
+            }
+            continue;
+        }
+        if title_hits.is_empty() && description_hits.is_empty() {
+            return None;
+        }
+
+        rank += title_hits.len() as f64 * TITLE_WEIGHT
+            + description_hits.len() as f64 * DESCRIPTION_WEIGHT;
+        for start in title_hits {
+            title_matched[start..start + term.words.len()].fill(true);
+        }
+        for start in description_hits {
+            description_matched[start..start + term.words.len()].fill(true);
+        }
+    }
+
+    let search = SearchMatch {
+        title: highlight(&task.title, &title_words, &title_matched),
+        snippet: highlight(description, &description_words, &description_matched),
+    };
+    Some((search, rank))
+}
+
+/// Byte ranges and lowercased text of the words in `text`, split the same
+/// way as search input.
+fn words(text: &str) -> Vec<(Range<usize>, String)> {
+    let mut words = Vec::new();
+    let mut start = None;
+
+    for (index, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(index),
+            (false, Some(begin)) => {
+                words.push((begin..index, text[begin..index].to_lowercase()));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    words
+}
+
+/// Start indexes of every occurrence of `term` in `words`.
+fn occurrences(term: &SearchTerm, words: &[(Range<usize>, String)]) -> Vec<usize> {
+    let needle: Vec<String> = term.words.iter().map(|word| word.to_lowercase()).collect();
+    if words.len() < needle.len() {
+        return Vec::new();
+    }
+
+    (0..=words.len() - needle.len())
+        .filter(|&start| {
+            needle.iter().enumerate().all(|(offset, word)| {
+                let candidate = &words[start + offset].1;
+                if term.prefix && offset == needle.len() - 1 {
+                    candidate.starts_with(word.as_str())
+                } else {
+                    candidate == word
+                }
+            })
+        })
+        .collect()
+}
+
+/// Splits `text` into spans, marking the matched words.
+fn highlight(text: &str, words: &[(Range<usize>, String)], matched: &[bool]) -> Vec<HighlightSpan> {
+    let mut spans: Vec<HighlightSpan> = Vec::new();
+    let mut push = |text: &str, matched: bool| {
+        if text.is_empty() {
+            return;
+        }
+        match spans.last_mut() {
+            Some(last) if last.matched == matched => last.text.push_str(text),
+            _ => spans.push(HighlightSpan {
+                text: text.to_string(),
+                matched,
+            }),
+        }
+    };
+
+    let mut position = 0;
+    for ((range, _), &is_match) in words.iter().zip(matched) {
+        push(&text[position..range.start], false);
+        push(&text[range.clone()], is_match);
+        position = range.end;
+    }
+    push(&text[position..], false);
+
+    spans
+}
+
+fn sort_key(task: &Task, field: TaskSortField, rank: f64) -> SortKey {
+    match field {
+        TaskSortField::CreatedAt => SortKey::CreatedAt(task.created_at),
+        TaskSortField::UpdatedAt => SortKey::UpdatedAt(task.updated_at),
+        TaskSortField::DueDate => SortKey::DueDate(task.due_date),
+        TaskSortField::Priority => SortKey::Priority(task.priority),
+        TaskSortField::Title => SortKey::Title(task.title.clone()),
+        TaskSortField::Relevance => SortKey::Relevance(rank),
+    }
+}
+
+/// Orders two `(sort key, id)` positions like the Postgres `ORDER BY`:
+/// the sort key then the id in the requested direction, with tasks without
+/// a due date last and titles compared bytewise.
+fn compare(sort: TaskSort, (a, a_id): (&SortKey, Uuid), (b, b_id): (&SortKey, Uuid)) -> Ordering {
+    let ordering = match (a, b) {
+        (SortKey::DueDate(None), SortKey::DueDate(Some(_))) => return Ordering::Greater,
+        (SortKey::DueDate(Some(_)), SortKey::DueDate(None)) => return Ordering::Less,
+        (SortKey::CreatedAt(a), SortKey::CreatedAt(b))
+        | (SortKey::UpdatedAt(a), SortKey::UpdatedAt(b)) => a.cmp(b),
+        (SortKey::DueDate(a), SortKey::DueDate(b)) => a.cmp(b),
+        (SortKey::Priority(a), SortKey::Priority(b)) => a.cmp(b),
+        (SortKey::Title(a), SortKey::Title(b)) => a.cmp(b),
+        (SortKey::Relevance(a), SortKey::Relevance(b)) => a.total_cmp(b),
+        _ => Ordering::Equal,
+    };
+    let ordering = ordering.then(a_id.cmp(&b_id));
+
+    match sort.direction {
+        SortDirection::Asc => ordering,
+        SortDirection::Desc => ordering.reverse(),
+    }
+}
@@ -0,0 +1,77 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use common::{TaskPriority, TaskSort, TaskSortField};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::AppError;
+
+/// Value of the sort column for the last task on a page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SortKey {
+    CreatedAt(DateTime<Utc>),
+    UpdatedAt(DateTime<Utc>),
+    DueDate(Option<DateTime<Utc>>),
+    Priority(TaskPriority),
+    Title(String),
+    Relevance(f64),
+}
+
+impl SortKey {
+    fn field(&self) -> TaskSortField {
+        match self {
+            SortKey::CreatedAt(_) => TaskSortField::CreatedAt,
+            SortKey::UpdatedAt(_) => TaskSortField::UpdatedAt,
+            SortKey::DueDate(_) => TaskSortField::DueDate,
+            SortKey::Priority(_) => TaskSortField::Priority,
+            SortKey::Title(_) => TaskSortField::Title,
+            SortKey::Relevance(_) => TaskSortField::Relevance,
+        }
+    }
+}
+
+/// Keyset position after which the next page starts.
+///
+/// Encoded as URL-safe base64 JSON so clients can treat it as an opaque token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cursor {
+    pub sort: TaskSort,
+    pub key: SortKey,
+    pub id: Uuid,
+    /// Total counted for the first page, served again on later pages so
+    /// that only the first one pays for counting.
+    #[serde(default)]
+    pub total: u64,
+}
+
+impl Cursor {
+    pub fn new(sort: TaskSort, key: SortKey, id: Uuid, total: u64) -> Self {
+        Self {
+            sort,
+            key,
+            id,
+            total,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).expect("cursor serialization cannot fail");
+        URL_SAFE_NO_PAD.encode(json)
+    }
+
+    /// Decodes a cursor and checks it was issued for the requested ordering.
+    pub fn decode(token: &str, sort: TaskSort) -> Result<Self, AppError> {
+        let invalid = || AppError::InvalidInput("Invalid cursor".to_string());
+
+        let bytes = URL_SAFE_NO_PAD.decode(token).map_err(|_| invalid())?;
+        let cursor: Cursor = serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+
+        if cursor.sort != sort || cursor.key.field() != sort.field {
+            return Err(AppError::InvalidInput(
+                "Cursor does not match the requested sort".to_string(),
+            ));
+        }
+
+        Ok(cursor)
+    }
+}
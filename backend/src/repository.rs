@@ -0,0 +1,824 @@
+//! Storage abstraction for tasks.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::{
+    custom_fields::{
+        check_values, value_matches, CustomField, CustomFieldRequest, CustomFieldValues,
+    },
+    saved_views::{SavedView, SavedViewRequest},
+    transition::{has_text, FilledFields},
+    DEFAULT_PROJECT_ID, MAX_SUBTASK_DEPTH,
+};
+use common::{
+    recurrence::Recurrence, Attachment, Comment, CommentRequest, CreateTaskRequest, Member,
+    MemberRequest, MoveTaskRequest, Page, Patch, Project, ProjectRequest, StatusCategory, Tag,
+    TagRequest, Task, TaskDependencies, TaskEvent, TaskFilter, TaskSort, TaskStatus, TimeEntry,
+    TimeEntryRequest, UpdateTaskRequest, Workflow, WorkflowRequest, WorkflowState,
+};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{error::AppError, pagination::Cursor};
+
+/// Task storage used by the HTTP handlers.
+///
+/// Implementations must agree on filter, search and sort semantics so that
+/// clients see the same results and cursors whichever engine is configured.
+/// Deleting moves a task to the trash; trashed tasks are left out of every
+/// read and write except the trash methods.
+#[async_trait]
+pub trait TaskRepository: Send + Sync {
+    /// Creates a task in the first state of its project's workflow.
+    async fn create_task(
+        &self,
+        request: CreateTaskRequest,
+        context: &WriteContext,
+    ) -> Result<Task, AppError>;
+
+    async fn get_task(&self, id: Uuid) -> Result<Task, AppError>;
+
+    /// Returns one page of tasks matching `filter`, ordered by `sort` and
+    /// starting after `after` when given.
+    async fn list_tasks(
+        &self,
+        filter: &TaskFilter,
+        sort: TaskSort,
+        after: Option<&Cursor>,
+        limit: u32,
+    ) -> Result<Page<Task>, AppError>;
+
+    /// Applies `request` and increments the task's version. The status must
+    /// be a state of the project's workflow; a task moved to another project
+    /// keeps its status when that workflow has it and otherwise takes the
+    /// first state of the same category. Completing a task, that is moving it
+    /// into a [`StatusCategory::Done`] state, with open subtasks is refused or
+    /// cascades to them, as set by [`WriteContext::subtask_completion`]. A
+    /// blocked task cannot be started or completed unless
+    /// [`WriteContext::override_blockers`] is set, and a task cannot enter
+    /// a column at its WIP limit under [`WipLimits::Reject`]. Completing a
+    /// recurring task creates its next occurrence. A task that changes status
+    /// or project goes to the bottom of its new column.
+    async fn update_task(
+        &self,
+        id: Uuid,
+        request: UpdateTaskRequest,
+        context: &WriteContext,
+    ) -> Result<Task, AppError>;
+
+    /// Puts the task in the column of `request.status` between the named
+    /// cards, which must be live tasks already in that column of the task's
+    /// project. The status change goes through the same checks as
+    /// [`TaskRepository::update_task`].
+    async fn move_task(
+        &self,
+        id: Uuid,
+        request: MoveTaskRequest,
+        context: &WriteContext,
+    ) -> Result<Task, AppError>;
+
+    /// Moves the task to the trash and stops the timers running on it.
+    async fn delete_task(&self, id: Uuid, context: &WriteContext) -> Result<(), AppError>;
+
+    /// Returns trashed tasks, most recently deleted first.
+    async fn list_trash(&self) -> Result<Vec<Task>, AppError>;
+
+    /// Takes a task out of the trash.
+    async fn restore_task(&self, id: Uuid, context: &WriteContext) -> Result<Task, AppError>;
+
+    /// Permanently deletes a trashed task with its attachments.
+    async fn purge_task(&self, id: Uuid) -> Result<Purged, AppError>;
+
+    /// Permanently deletes tasks trashed before `before`, with their
+    /// attachments.
+    async fn purge_trash(&self, before: DateTime<Utc>) -> Result<Purged, AppError>;
+
+    /// Returns the change history of a live task, oldest first.
+    async fn task_history(&self, id: Uuid) -> Result<Vec<TaskEvent>, AppError>;
+
+    /// Returns the live direct subtasks of a live task, oldest first.
+    async fn list_children(&self, id: Uuid) -> Result<Vec<Task>, AppError>;
+
+    /// Returns the live tasks blocking and blocked by a live task.
+    async fn list_dependencies(&self, id: Uuid) -> Result<TaskDependencies, AppError>;
+
+    /// Makes live task `blocker_id` block live task `id`; adding a relation
+    /// that exists does nothing. Fails with [`AppError::DependencyCycle`]
+    /// when `id` already blocks `blocker_id`, directly or through others.
+    async fn add_dependency(&self, id: Uuid, blocker_id: Uuid) -> Result<(), AppError>;
+
+    /// Fails with [`AppError::DependencyNotFound`] unless `blocker_id`
+    /// blocks live task `id`.
+    async fn remove_dependency(&self, id: Uuid, blocker_id: Uuid) -> Result<(), AppError>;
+
+    /// Returns the comments on a live task, oldest first.
+    async fn list_comments(&self, task_id: Uuid) -> Result<Vec<Comment>, AppError>;
+
+    async fn create_comment(
+        &self,
+        task_id: Uuid,
+        request: CommentRequest,
+        author: Option<String>,
+    ) -> Result<Comment, AppError>;
+
+    /// Replaces the body of a comment on a live task.
+    async fn update_comment(
+        &self,
+        task_id: Uuid,
+        id: Uuid,
+        request: CommentRequest,
+    ) -> Result<Comment, AppError>;
+
+    async fn delete_comment(&self, task_id: Uuid, id: Uuid) -> Result<(), AppError>;
+
+    /// Returns the time entries of a live task, earliest start first.
+    async fn list_time_entries(&self, task_id: Uuid) -> Result<Vec<TimeEntry>, AppError>;
+
+    /// Starts a member's timer on a live task, stopping the one they have
+    /// running on any other task. Fails with [`AppError::Conflict`] when it
+    /// is already running on this one.
+    async fn start_timer(&self, task_id: Uuid, member_id: Uuid) -> Result<TimeEntry, AppError>;
+
+    /// Stops a member's timer on a live task. Fails with
+    /// [`AppError::Conflict`] when it is not running on this one.
+    async fn stop_timer(&self, task_id: Uuid, member_id: Uuid) -> Result<TimeEntry, AppError>;
+
+    /// Records time spent on a live task by hand.
+    async fn create_time_entry(
+        &self,
+        task_id: Uuid,
+        request: TimeEntryRequest,
+    ) -> Result<TimeEntry, AppError>;
+
+    /// Replaces the member, times and note of an entry on a live task.
+    async fn update_time_entry(
+        &self,
+        task_id: Uuid,
+        id: Uuid,
+        request: TimeEntryRequest,
+    ) -> Result<TimeEntry, AppError>;
+
+    async fn delete_time_entry(&self, task_id: Uuid, id: Uuid) -> Result<(), AppError>;
+
+    /// Returns the attachments of a live task, oldest first.
+    async fn list_attachments(&self, task_id: Uuid) -> Result<Vec<Attachment>, AppError>;
+
+    /// Returns one attachment of a live task.
+    async fn get_attachment(&self, task_id: Uuid, id: Uuid) -> Result<Attachment, AppError>;
+
+    /// Records a file attached to a live task. The blob itself is stored by
+    /// the caller.
+    async fn create_attachment(
+        &self,
+        task_id: Uuid,
+        attachment: NewAttachment,
+    ) -> Result<Attachment, AppError>;
+
+    /// Deletes an attachment of a live task, returning its blob if no
+    /// attachment uses it any more.
+    async fn delete_attachment(&self, task_id: Uuid, id: Uuid) -> Result<Option<String>, AppError>;
+
+    /// Returns every tag, ordered by name.
+    async fn list_tags(&self) -> Result<Vec<Tag>, AppError>;
+
+    /// Fails with [`AppError::Conflict`] when the name is taken.
+    async fn create_tag(&self, request: TagRequest) -> Result<Tag, AppError>;
+
+    /// Renames or recolours a tag; tasks carrying it follow.
+    async fn update_tag(&self, id: Uuid, request: TagRequest) -> Result<Tag, AppError>;
+
+    /// Deletes a tag and takes it off every task.
+    async fn delete_tag(&self, id: Uuid) -> Result<(), AppError>;
+
+    /// Returns every member, ordered by name.
+    async fn list_members(&self) -> Result<Vec<Member>, AppError>;
+
+    async fn create_member(&self, request: MemberRequest) -> Result<Member, AppError>;
+
+    async fn update_member(&self, id: Uuid, request: MemberRequest) -> Result<Member, AppError>;
+
+    /// Deletes a member and unassigns their tasks. Their time entries are
+    /// kept without a member and a timer they had running is stopped.
+    async fn delete_member(&self, id: Uuid) -> Result<(), AppError>;
+
+    /// Returns every project, ordered by name.
+    async fn list_projects(&self) -> Result<Vec<Project>, AppError>;
+
+    async fn get_project(&self, id: Uuid) -> Result<Project, AppError>;
+
+    /// Creates a project with the [default workflow](common::default_workflow).
+    async fn create_project(&self, request: ProjectRequest) -> Result<Project, AppError>;
+
+    async fn update_project(&self, id: Uuid, request: ProjectRequest) -> Result<Project, AppError>;
+
+    /// Deletes an empty project. Fails with [`AppError::Conflict`] for the
+    /// default project or one that still holds tasks, trashed ones included.
+    async fn delete_project(&self, id: Uuid) -> Result<(), AppError>;
+
+    /// Counts the live tasks of a project in `status`.
+    async fn count_in_state(&self, project_id: Uuid, status: &TaskStatus) -> Result<i64, AppError>;
+
+    /// Returns the states of a project's workflow in board order.
+    async fn get_workflow(&self, project_id: Uuid) -> Result<Workflow, AppError>;
+
+    /// Replaces the states of a project's workflow. States are matched by
+    /// name, so a state can change category or place; one left out must not
+    /// hold any tasks, trashed ones included.
+    async fn update_workflow(
+        &self,
+        project_id: Uuid,
+        request: WorkflowRequest,
+    ) -> Result<Workflow, AppError>;
+
+    /// Returns a project's custom fields in the order they were added.
+    async fn list_custom_fields(&self, project_id: Uuid) -> Result<Vec<CustomField>, AppError>;
+
+    async fn get_custom_field(&self, id: Uuid) -> Result<CustomField, AppError>;
+
+    /// Adds a custom field to a project. Names are unique within a project.
+    async fn create_custom_field(
+        &self,
+        project_id: Uuid,
+        request: CustomFieldRequest,
+    ) -> Result<CustomField, AppError>;
+
+    /// Renames a custom field or changes its options. Its type cannot change,
+    /// and an option tasks still use, trashed ones included, cannot be
+    /// removed.
+    async fn update_custom_field(
+        &self,
+        project_id: Uuid,
+        id: Uuid,
+        request: CustomFieldRequest,
+    ) -> Result<CustomField, AppError>;
+
+    /// Deletes a custom field and its value on every task.
+    async fn delete_custom_field(&self, project_id: Uuid, id: Uuid) -> Result<(), AppError>;
+
+    /// Returns every saved view, ordered by name.
+    async fn list_saved_views(&self) -> Result<Vec<SavedView>, AppError>;
+
+    async fn get_saved_view(&self, id: Uuid) -> Result<SavedView, AppError>;
+
+    /// Saves a board setup. Names are unique, and a view whose filter names a
+    /// project is deleted with it.
+    async fn create_saved_view(&self, request: SavedViewRequest) -> Result<SavedView, AppError>;
+
+    async fn update_saved_view(
+        &self,
+        id: Uuid,
+        request: SavedViewRequest,
+    ) -> Result<SavedView, AppError>;
+
+    async fn delete_saved_view(&self, id: Uuid) -> Result<(), AppError>;
+}
+
+/// An uploaded file as the handler describes it to the repository.
+#[derive(Debug, Clone)]
+pub struct NewAttachment {
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// The blob's key, from [`crate::blob::content_key`].
+    pub sha256: String,
+}
+
+/// What a purge removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Purged {
+    /// How many tasks were deleted.
+    pub tasks: u64,
+    /// Blobs the deleted attachments used that no attachment uses any more,
+    /// for the caller to remove from the blob store.
+    pub blobs: Vec<String>,
+}
+
+/// Who is making a write and what it is conditioned on. Every write records
+/// its changes in the task's history in the same transaction.
+#[derive(Debug, Clone, Default)]
+pub struct WriteContext {
+    /// Recorded as the actor of the history events; `None` when unknown.
+    pub actor: Option<String>,
+    /// With `Some`, the write fails with [`AppError::PreconditionFailed`]
+    /// unless the task is still at that version.
+    pub expected_version: Option<i64>,
+    /// What completing a task does to its open subtasks.
+    pub subtask_completion: SubtaskCompletion,
+    /// Lets a blocked task be started or completed anyway.
+    pub override_blockers: bool,
+    /// What a move into a column at its WIP limit does.
+    pub wip_limits: WipLimits,
+}
+
+/// What completing a task does to its open subtasks, live ones at any depth.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SubtaskCompletion {
+    /// The update fails with [`AppError::Conflict`].
+    #[default]
+    Block,
+    /// The subtasks are completed too, each with its own history entry.
+    Cascade,
+}
+
+/// What moving a task into a column that is already at its WIP limit does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WipLimits {
+    /// The update fails with [`AppError::Conflict`].
+    #[default]
+    Reject,
+    /// The update goes ahead and the handler warns about the full column.
+    Warn,
+}
+
+impl WriteContext {
+    pub fn check_version(&self, task: &Task) -> Result<(), AppError> {
+        match self.expected_version {
+            Some(version) if version != task.version => Err(AppError::PreconditionFailed),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Longest task title, matching `tasks.title VARCHAR(255)` in PostgreSQL.
+const MAX_TITLE_LENGTH: usize = 255;
+
+/// Rejects titles the PostgreSQL schema would refuse, for engines that do not
+/// enforce the column length themselves.
+pub fn check_title_length(title: &str) -> Result<(), AppError> {
+    if title.chars().count() > MAX_TITLE_LENGTH {
+        return Err(AppError::InvalidInput(
+            "Input value exceeds maximum length".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Error for a task write naming an assignee who is not a member.
+pub fn unknown_member(id: Uuid) -> AppError {
+    AppError::InvalidInput(format!("Unknown member: {id}"))
+}
+
+/// Error for a task write naming a project that does not exist.
+pub fn unknown_project(id: Uuid) -> AppError {
+    AppError::InvalidInput(format!("Unknown project: {id}"))
+}
+
+/// Refuses to delete the default project, which tasks fall back to.
+pub fn check_project_deletable(id: Uuid) -> Result<(), AppError> {
+    if id == DEFAULT_PROJECT_ID {
+        return Err(AppError::Conflict(
+            "The default project cannot be deleted".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Turns a foreign key violation from `tasks.project_id` into
+/// [`AppError::Conflict`].
+pub fn project_in_use(err: sqlx::Error) -> AppError {
+    match &err {
+        sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => project_not_empty(),
+        _ => AppError::Database(err),
+    }
+}
+
+/// Error for deleting a project that still holds tasks.
+pub fn project_not_empty() -> AppError {
+    AppError::Conflict("Project still has tasks".to_string())
+}
+
+/// Error for a task write naming a parent that is not a live task.
+pub fn unknown_parent(id: Uuid) -> AppError {
+    AppError::InvalidInput(format!("Unknown parent task: {id}"))
+}
+
+/// Checks that a subtask stays in the project of its parent.
+pub fn check_parent_project(parent_project_id: Uuid, project_id: Uuid) -> Result<(), AppError> {
+    if parent_project_id != project_id {
+        return Err(AppError::InvalidInput(
+            "A subtask must be in the same project as its parent".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that putting a task under a parent keeps the tree free of cycles
+/// and within [`MAX_SUBTASK_DEPTH`]. `ancestry` lists the parent and its own
+/// ancestors, nearest first; `task_id` is `None` for a new task, and `height`
+/// is how many levels of subtasks the task already has below it.
+pub fn check_nesting(
+    task_id: Option<Uuid>,
+    ancestry: &[Uuid],
+    height: usize,
+) -> Result<(), AppError> {
+    if task_id.is_some_and(|id| ancestry.contains(&id)) {
+        return Err(AppError::InvalidInput(
+            "A task cannot be a subtask of itself or of its own subtasks".to_string(),
+        ));
+    }
+    if ancestry.len() + height > MAX_SUBTASK_DEPTH {
+        return Err(AppError::InvalidInput(format!(
+            "Subtasks cannot be nested more than {MAX_SUBTASK_DEPTH} levels deep"
+        )));
+    }
+    Ok(())
+}
+
+/// Subtasks follow their parent's project, so a task that has any cannot
+/// move on its own.
+pub fn check_project_move(has_subtasks: bool) -> Result<(), AppError> {
+    if has_subtasks {
+        return Err(AppError::InvalidInput(
+            "A task with subtasks cannot move to another project".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Error for completing a task with open subtasks under
+/// [`SubtaskCompletion::Block`].
+pub fn open_subtasks() -> AppError {
+    AppError::Conflict("Task has open subtasks".to_string())
+}
+
+/// Refuses to start or complete a blocked task, that is move it to a state
+/// outside [`StatusCategory::Todo`], unless the write overrides its blockers.
+pub fn check_blockers(
+    before: &Task,
+    target: &WorkflowState,
+    context: &WriteContext,
+) -> Result<(), AppError> {
+    let starting = target.category != StatusCategory::Todo && target.name != before.status;
+    if starting && before.is_blocked && !context.override_blockers {
+        return Err(AppError::Conflict(
+            "Task is blocked by unfinished tasks".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Whether a write moving `before` into `target` of `project_id` must check
+/// the state's WIP limit: the task enters a column with a limit, and limits
+/// reject rather than warn. Reordering within a column never counts.
+pub fn wip_limited(
+    before: &Task,
+    project_id: Uuid,
+    target: &WorkflowState,
+    context: &WriteContext,
+) -> bool {
+    let entering = project_id != before.project_id || target.name != before.status;
+    entering && target.wip_limit.is_some() && context.wip_limits == WipLimits::Reject
+}
+
+/// Refuses a move into a column already holding `count` live tasks, when
+/// that fills the state's WIP limit.
+pub fn check_wip_limit(target: &WorkflowState, count: i64) -> Result<(), AppError> {
+    match target.wip_limit {
+        Some(limit) if count >= i64::from(limit) => Err(AppError::Conflict(format!(
+            "{} is at its WIP limit of {limit}",
+            target.name
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Refuses an update the workflow of the project the task ends up in does
+/// not allow: a move its transitions leave out, or into a state or to a
+/// priority whose guard the task, as the update leaves it, does not meet. A
+/// task coming from another project only has to meet the guard of the state
+/// it lands in.
+pub fn check_transition(
+    before: &Task,
+    request: &UpdateTaskRequest,
+    workflow: &Workflow,
+    target: &WorkflowState,
+) -> Result<(), AppError> {
+    let fields = filled_after(before, request);
+    let moved = workflow.project_id != before.project_id;
+    if moved || target.name != before.status {
+        let from = (!moved).then_some(&before.status);
+        workflow.check_transition(from, target, fields)?;
+    }
+    if let Patch::Value(priority) = request.priority {
+        if priority != before.priority {
+            workflow.check_priority(priority, fields)?;
+        }
+    }
+    Ok(())
+}
+
+/// Refuses a new task that does not meet the guard of the first state of
+/// its project's workflow, where it starts, or of its priority.
+pub fn check_new_task(request: &CreateTaskRequest, workflow: &Workflow) -> Result<(), AppError> {
+    let fields = FilledFields {
+        description: has_text(request.description.as_deref()),
+        due_date: request.due_date.is_some(),
+        assignee: request.assignee_id.is_some(),
+    };
+    workflow.check_transition(None, &workflow.states[0], fields)?;
+    workflow.check_priority(request.priority, fields)?;
+    Ok(())
+}
+
+/// The guarded fields `before` has filled in once `request` is applied.
+fn filled_after(before: &Task, request: &UpdateTaskRequest) -> FilledFields {
+    let mut fields = FilledFields::of(before);
+    if let Some(description) = request.description.clone().into_change() {
+        fields.description = has_text(description.as_deref());
+    }
+    if let Some(due_date) = request.due_date.clone().into_change() {
+        fields.due_date = due_date.is_some();
+    }
+    if let Some(assignee_id) = request.assignee_id.clone().into_change() {
+        fields.assignee = assignee_id.is_some();
+    }
+    fields
+}
+
+/// A task cannot be placed next to itself.
+pub fn check_move(id: Uuid, request: &MoveTaskRequest) -> Result<(), AppError> {
+    if request.after_id == Some(id) || request.before_id == Some(id) {
+        return Err(AppError::InvalidInput(
+            "A task cannot be placed next to itself".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Error for a move naming a card that is no longer in the target column,
+/// most likely because another client moved it first.
+pub fn neighbour_moved() -> AppError {
+    AppError::Conflict("The column changed; reload and try again".to_string())
+}
+
+/// The status half of a move, applied as an update.
+pub fn move_update(request: &MoveTaskRequest) -> UpdateTaskRequest {
+    UpdateTaskRequest {
+        status: Patch::Value(request.status.clone()),
+        ..Default::default()
+    }
+}
+
+/// The state an update leaves a task in: the one it asks for, or else the
+/// task's own status carried over to `workflow`, which is the workflow of the
+/// project the task ends up in. Also makes the request set that state, so a
+/// project move records the status it changes to.
+pub fn target_state(
+    before: &Task,
+    request: &mut UpdateTaskRequest,
+    workflow: &Workflow,
+) -> Result<WorkflowState, AppError> {
+    let target = match &request.status {
+        Patch::Value(status) => workflow
+            .state(status)
+            .ok_or_else(|| unknown_status(status))?,
+        _ => carried_state(before, workflow),
+    }
+    .clone();
+
+    if target.name != before.status {
+        request.status = Patch::Value(target.name.clone());
+    }
+    Ok(target)
+}
+
+/// The state a task keeps in `workflow`: its own status when the workflow has
+/// it, otherwise the first state of the same category, otherwise the first.
+fn carried_state<'a>(task: &Task, workflow: &'a Workflow) -> &'a WorkflowState {
+    workflow
+        .state(&task.status)
+        .or_else(|| workflow.first_of(task.status_category))
+        .unwrap_or(&workflow.states[0])
+}
+
+/// Whether moving from `before` to `target` completes the task.
+pub fn is_completing(before: &Task, target: &WorkflowState) -> bool {
+    target.category == StatusCategory::Done && before.status_category != StatusCategory::Done
+}
+
+/// The state open subtasks are completed into when their parent is; every
+/// workflow has one.
+pub fn done_state(workflow: &Workflow) -> &WorkflowState {
+    workflow
+        .first_of(StatusCategory::Done)
+        .unwrap_or(&workflow.states[0])
+}
+
+/// Error for a task write naming a status that is not in the workflow of the
+/// task's project.
+pub fn unknown_status(status: &TaskStatus) -> AppError {
+    AppError::InvalidInput(format!("Unknown status: {status}"))
+}
+
+/// Refuses a workflow that leaves out a state tasks are still in. `in_use`
+/// lists the statuses of the project's tasks, trashed ones included.
+pub fn check_states_kept(in_use: &[TaskStatus], request: &WorkflowRequest) -> Result<(), AppError> {
+    match in_use
+        .iter()
+        .find(|status| !request.states.iter().any(|state| state.name == **status))
+    {
+        Some(status) => Err(AppError::Conflict(format!(
+            "Workflow state still has tasks: {status}"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// A task cannot block itself; longer cycles are found by each engine.
+pub fn check_dependency(id: Uuid, blocker_id: Uuid) -> Result<(), AppError> {
+    if id == blocker_id {
+        return Err(AppError::DependencyCycle);
+    }
+    Ok(())
+}
+
+/// A recurring task needs a due date to count its occurrences from.
+pub fn check_recurrence(
+    recurrence: Option<&str>,
+    due_date: Option<DateTime<Utc>>,
+) -> Result<(), AppError> {
+    if recurrence.is_some() && due_date.is_none() {
+        return Err(AppError::InvalidInput(
+            "A recurring task needs a due date".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks the recurrence an update leaves the task with. When the update
+/// completes a recurring task whose series goes on, returns the due date and
+/// rule of the next occurrence and takes the rule off this one, so that only
+/// the newest occurrence carries it.
+pub fn plan_recurrence(
+    before: &Task,
+    request: &mut UpdateTaskRequest,
+    completing: bool,
+) -> Result<Option<(DateTime<Utc>, String)>, AppError> {
+    let recurrence = request
+        .recurrence
+        .clone()
+        .into_change()
+        .unwrap_or_else(|| before.recurrence.clone());
+    let due_date = request
+        .due_date
+        .clone()
+        .into_change()
+        .unwrap_or(before.due_date);
+    check_recurrence(recurrence.as_deref(), due_date)?;
+
+    let next = recurrence
+        .filter(|_| completing)
+        .and_then(|rule| rule.parse::<Recurrence>().ok())
+        .zip(due_date)
+        .and_then(|(rule, due_date)| rule.next(due_date));
+
+    Ok(next.map(|(due_date, rule)| {
+        request.recurrence = Patch::Null;
+        (due_date, rule.to_string())
+    }))
+}
+
+/// The next task of a series: a copy of `task` due at `due_date`, carrying
+/// `recurrence` on.
+pub fn next_occurrence(
+    task: &Task,
+    due_date: DateTime<Utc>,
+    recurrence: String,
+) -> CreateTaskRequest {
+    CreateTaskRequest {
+        title: task.title.clone(),
+        description: task.description.clone(),
+        priority: task.priority,
+        due_date: Some(due_date),
+        tags: task.tags.iter().map(|tag| tag.name.clone()).collect(),
+        assignee_id: task.assignee_id,
+        project_id: Some(task.project_id),
+        parent_id: task.parent_id,
+        recurrence: Some(recurrence),
+        estimate_minutes: task.estimate_minutes,
+        custom_fields: task.custom_fields.clone(),
+    }
+}
+
+/// Error for starting a member's timer on the task it is already running on.
+pub fn timer_already_running() -> AppError {
+    AppError::Conflict("The member's timer is already running on this task".to_string())
+}
+
+/// Error for stopping a member's timer on a task it is not running on.
+pub fn timer_not_running() -> AppError {
+    AppError::Conflict("The member has no timer running on this task".to_string())
+}
+
+/// Turns a unique violation on `tags.name` into [`AppError::Conflict`].
+pub fn tag_name_conflict(err: sqlx::Error, name: &str) -> AppError {
+    match &err {
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+            AppError::Conflict(format!("Tag already exists: {name}"))
+        }
+        _ => AppError::Database(err),
+    }
+}
+
+/// Fails with [`AppError::InvalidInput`] naming the first of `names` that is
+/// not among `found`.
+pub fn check_tags_exist(names: &[String], found: &[Tag]) -> Result<(), AppError> {
+    match names
+        .iter()
+        .find(|name| !found.iter().any(|tag| &tag.name == *name))
+    {
+        Some(name) => Err(AppError::InvalidInput(format!("Unknown tag: {name}"))),
+        None => Ok(()),
+    }
+}
+
+/// Checks a new task's custom field values against `fields`, those of its
+/// project, and puts them in their stored form.
+pub fn check_custom_fields(
+    values: &mut CustomFieldValues,
+    fields: &[CustomField],
+) -> Result<(), AppError> {
+    check_values(values, fields).map_err(AppError::InvalidInput)
+}
+
+/// Settles the custom field values an update leaves a task with, checked
+/// against `fields`, those of the project the task ends up in. A task moved
+/// to another project without new values keeps only those the project has
+/// fields for.
+pub fn settle_custom_fields(
+    before: &Task,
+    request: &mut UpdateTaskRequest,
+    fields: &[CustomField],
+) -> Result<(), AppError> {
+    match &mut request.custom_fields {
+        Patch::Value(values) => check_custom_fields(values, fields),
+        Patch::Null => {
+            request.custom_fields = Patch::Value(CustomFieldValues::new());
+            Ok(())
+        }
+        Patch::Absent => {
+            let kept: CustomFieldValues = before
+                .custom_fields
+                .iter()
+                .filter(|(id, _)| fields.iter().any(|field| field.id == **id))
+                .map(|(id, value)| (*id, value.clone()))
+                .collect();
+            if kept.len() != before.custom_fields.len() {
+                request.custom_fields = Patch::Value(kept);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Refuses a change to `field` that its type or stored values do not allow.
+/// `in_use` holds the field's value on each task that has one, trashed ones
+/// included.
+pub fn check_field_change(
+    field: &CustomField,
+    request: &CustomFieldRequest,
+    in_use: &[Value],
+) -> Result<(), AppError> {
+    if request.field_type != field.field_type {
+        return Err(AppError::InvalidInput(
+            "A custom field's type cannot be changed".to_string(),
+        ));
+    }
+    let removed = field
+        .options
+        .iter()
+        .filter(|option| !request.options.contains(option));
+    for option in removed {
+        let wanted = Value::String(option.clone());
+        if in_use.iter().any(|value| value_matches(value, &wanted)) {
+            return Err(AppError::Conflict(format!("Option still in use: {option}")));
+        }
+    }
+    Ok(())
+}
+
+/// Turns a unique violation on a custom field's name into
+/// [`AppError::Conflict`].
+pub fn field_name_conflict(err: sqlx::Error, name: &str) -> AppError {
+    match &err {
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+            AppError::Conflict(format!("Custom field already exists: {name}"))
+        }
+        _ => AppError::Database(err),
+    }
+}
+
+/// Turns a unique violation on a saved view's name into
+/// [`AppError::Conflict`], and a view naming a missing project into
+/// [`AppError::InvalidInput`].
+pub fn view_write_failed(err: sqlx::Error, request: &SavedViewRequest) -> AppError {
+    match (&err, request.filter.project) {
+        (sqlx::Error::Database(db_err), _) if db_err.is_unique_violation() => {
+            AppError::Conflict(format!("View already exists: {}", request.name))
+        }
+        (sqlx::Error::Database(db_err), Some(project)) if db_err.is_foreign_key_violation() => {
+            unknown_project(project)
+        }
+        _ => AppError::Database(err),
+    }
+}
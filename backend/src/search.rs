@@ -0,0 +1,137 @@
+//! Full-text search helpers for the `q` task filter.
+
+use common::HighlightSpan;
+
+/// Text search configuration used by the `tasks.search_vector` trigger.
+pub const SEARCH_CONFIG: &str = "english";
+
+/// Delimiters passed to `ts_headline`. Control characters cannot be typed
+/// into a task, so they never collide with user text.
+pub const HIGHLIGHT_START: char = '\u{2}';
+pub const HIGHLIGHT_STOP: char = '\u{3}';
+
+/// One term of a search query: a word or a quoted phrase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchTerm {
+    /// Consecutive words to match, at least one.
+    pub words: Vec<String>,
+    /// Whether the last word matches as a prefix.
+    pub prefix: bool,
+    /// Whether tasks matching the term are excluded.
+    pub negated: bool,
+}
+
+/// Parses user search syntax into terms.
+///
+/// Bare words are separate terms, `"quoted words"` become a phrase, a
+/// trailing `*` turns the last word into a prefix match and a leading `-`
+/// negates a term. Everything but letters and digits is treated as a word
+/// separator, so terms never contain user-supplied operators.
+pub fn parse_terms(input: &str) -> Vec<SearchTerm> {
+    let mut terms = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let negated = c == '-';
+        if negated {
+            chars.next();
+        }
+
+        let (text, quoted) = if chars.peek() == Some(&'"') {
+            chars.next();
+            let text: String = chars.by_ref().take_while(|&c| c != '"').collect();
+            (text, true)
+        } else {
+            let mut text = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                text.push(c);
+                chars.next();
+            }
+            (text, false)
+        };
+
+        let words: Vec<String> = text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_string)
+            .collect();
+        if words.is_empty() {
+            continue;
+        }
+
+        terms.push(SearchTerm {
+            words,
+            prefix: !quoted && text.ends_with('*'),
+            negated,
+        });
+    }
+
+    terms
+}
+
+/// Translates user search syntax into a `to_tsquery` expression.
+///
+/// Terms from [`parse_terms`] are ANDed together. Returns `None` when the
+/// input has no searchable words.
+pub fn to_tsquery(input: &str) -> Option<String> {
+    let terms: Vec<String> = parse_terms(input)
+        .into_iter()
+        .map(|term| {
+            let mut text = term.words.join(" <-> ");
+            if term.prefix {
+                text.push_str(":*");
+            }
+            if term.words.len() > 1 {
+                text = format!("({text})");
+            }
+            if term.negated {
+                text = format!("!{text}");
+            }
+            text
+        })
+        .collect();
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" & "))
+    }
+}
+
+/// Splits `ts_headline` output into plain and matched spans.
+pub fn parse_highlight(text: &str) -> Vec<HighlightSpan> {
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut matched = false;
+
+    for c in text.chars() {
+        if c == HIGHLIGHT_START || c == HIGHLIGHT_STOP {
+            if !current.is_empty() {
+                spans.push(HighlightSpan {
+                    text: std::mem::take(&mut current),
+                    matched,
+                });
+            }
+            matched = c == HIGHLIGHT_START;
+        } else {
+            current.push(c);
+        }
+    }
+
+    if !current.is_empty() {
+        spans.push(HighlightSpan {
+            text: current,
+            matched,
+        });
+    }
+
+    spans
+}
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod blob_test_suite {
+    use crate::{
+        blob::{content_key, sniff_content_type, BlobStore, LocalBlobStore},
+        error::AppError,
+    };
+
+    #[test]
+    fn test_content_key_is_hex_sha256() {
+        assert_eq!(
+            content_key(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_content_type_is_sniffed() {
+        assert_eq!(sniff_content_type(b"%PDF-1.7\n"), "application/pdf");
+        assert_eq!(
+            sniff_content_type(b"<html><script>alert(1)</script></html>"),
+            "text/plain; charset=utf-8"
+        );
+        assert_eq!(
+            sniff_content_type(&[0xff, 0xfe, 0x00, 0x81]),
+            "application/octet-stream"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_local_store_keeps_one_file_per_content() {
+        let root = std::env::temp_dir().join(format!("blobs-{}", uuid::Uuid::new_v4()));
+        let store = LocalBlobStore::new(&root);
+        let key = content_key(b"stack trace");
+
+        store.put(&key, b"stack trace").await.unwrap();
+        store.put(&key, b"stack trace").await.unwrap();
+        assert_eq!(store.get(&key).await.unwrap(), b"stack trace");
+        let shard = std::fs::read_dir(root.join(&key[..2])).unwrap().count();
+        assert_eq!(shard, 1);
+
+        store.delete(&key).await.unwrap();
+        store.delete(&key).await.unwrap();
+        assert!(matches!(
+            store.get(&key).await,
+            Err(AppError::AttachmentNotFound)
+        ));
+        assert!(matches!(
+            store.get("../../etc/passwd").await,
+            Err(AppError::AttachmentNotFound)
+        ));
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -8,6 +8,7 @@ use std::env;
 // Import the main application components
 use crate::database::Database;
 use crate::{AppState, AppStateData};
+use common::{TaskFilter, TaskSort};
 
 #[tokio::test]
 async fn test_health_check_endpoint() {
@@ -27,8 +28,15 @@ async fn test_application_state_creation() {
         .await
         .expect("Failed to connect to test database");
 
-    let database = Database::new(pool);
-    let app_state = std::sync::Arc::new(AppStateData { database });
+    let repository = std::sync::Arc::new(Database::new(pool));
+    let app_state = std::sync::Arc::new(AppStateData {
+        repository,
+        blobs: std::sync::Arc::new(crate::tests::MemoryBlobStore::new()),
+        blob_lock: Default::default(),
+        max_attachment_bytes: crate::blob::DEFAULT_MAX_ATTACHMENT_BYTES,
+        subtask_completion: Default::default(),
+        wip_limits: Default::default(),
+    });
 
     // Verify state structure
     assert!(std::sync::Arc::strong_count(&app_state) == 1);
@@ -112,7 +120,7 @@ async fn test_cors_configuration() {
 async fn test_application_router_structure() {
     // Test that we can construct the application router structure
     use axum::{
-        routing::{delete, get, post, put},
+        routing::{delete, get, patch, post, put},
         Router,
     };
 
@@ -126,14 +134,22 @@ async fn test_application_router_structure() {
         .await
         .expect("Failed to connect to test database");
 
-    let database = Database::new(pool);
-    let app_state = std::sync::Arc::new(AppStateData { database });
+    let repository = std::sync::Arc::new(Database::new(pool));
+    let app_state = std::sync::Arc::new(AppStateData {
+        repository,
+        blobs: std::sync::Arc::new(crate::tests::MemoryBlobStore::new()),
+        blob_lock: Default::default(),
+        max_attachment_bytes: crate::blob::DEFAULT_MAX_ATTACHMENT_BYTES,
+        subtask_completion: Default::default(),
+        wip_limits: Default::default(),
+    });
 
     // Build router with our routes (testing route structure)
     let router: Router<AppState> = Router::new()
         .route("/api/tasks", get(crate::handlers::list_tasks))
         .route("/api/tasks", post(crate::handlers::create_task))
-        .route("/api/tasks/:id", put(crate::handlers::update_task))
+        .route("/api/tasks/:id", put(crate::handlers::replace_task))
+        .route("/api/tasks/:id", patch(crate::handlers::update_task))
         .route("/api/tasks/:id", delete(crate::handlers::delete_task))
         .route("/health", get(crate::health_check))
         .with_state(app_state);
@@ -174,14 +190,24 @@ async fn test_application_state_thread_safety() {
         .await
         .expect("Failed to connect to test database");
 
-    let database = Database::new(pool);
-    let app_state: AppState = std::sync::Arc::new(AppStateData { database });
+    let repository = std::sync::Arc::new(Database::new(pool));
+    let app_state: AppState = std::sync::Arc::new(AppStateData {
+        repository,
+        blobs: std::sync::Arc::new(crate::tests::MemoryBlobStore::new()),
+        blob_lock: Default::default(),
+        max_attachment_bytes: crate::blob::DEFAULT_MAX_ATTACHMENT_BYTES,
+        subtask_completion: Default::default(),
+        wip_limits: Default::default(),
+    });
 
     // Test that state can be moved to different threads
     let state_clone = app_state.clone();
     let handle = tokio::spawn(async move {
         // Perform a database operation to verify the state works across threads
-        let result = state_clone.database.get_tasks(None).await;
+        let result = state_clone
+            .repository
+            .list_tasks(&TaskFilter::default(), TaskSort::default(), None, 10)
+            .await;
         assert!(
             result.is_ok(),
             "Database operations should work across threads"
@@ -240,13 +266,14 @@ async fn test_handler_module_structure() {
     // Test that all required handlers are available
     // This ensures our handler module is properly structured
 
-    use crate::handlers::{create_task, delete_task, list_tasks, update_task};
+    use crate::handlers::{create_task, delete_task, list_tasks, replace_task, update_task};
 
     // We can't easily test the handlers without full integration,
     // but we can test that they exist and are properly imported
     assert!(format!("{:?}", create_task as *const ()).contains("0x"));
     assert!(format!("{:?}", delete_task as *const ()).contains("0x"));
     assert!(format!("{:?}", list_tasks as *const ()).contains("0x"));
+    assert!(format!("{:?}", replace_task as *const ()).contains("0x"));
     assert!(format!("{:?}", update_task as *const ()).contains("0x"));
 }
 
@@ -254,7 +281,10 @@ async fn test_handler_module_structure() {
 async fn test_common_types_integration() {
     // Test that common types are properly integrated
     use chrono::Utc;
-    use common::{CreateTaskRequest, Task, TaskPriority, TaskStatus, UpdateTaskRequest};
+    use common::{
+        CreateTaskRequest, Patch, StatusCategory, Task, TaskPriority, TaskStatus,
+        UpdateTaskRequest, DEFAULT_PROJECT_ID,
+    };
     use uuid::Uuid;
 
     // Test CreateTaskRequest
@@ -263,6 +293,7 @@ async fn test_common_types_integration() {
         description: Some("Test Description".to_string()),
         priority: TaskPriority::High,
         due_date: None,
+        ..Default::default()
     };
 
     assert_eq!(create_request.title, "Test Task");
@@ -270,29 +301,53 @@ async fn test_common_types_integration() {
 
     // Test UpdateTaskRequest
     let update_request = UpdateTaskRequest {
-        title: Some("Updated Title".to_string()),
-        description: None,
-        status: Some(TaskStatus::Completed),
-        priority: Some(TaskPriority::Low),
-        due_date: None,
+        title: Patch::Value("Updated Title".to_string()),
+        status: Patch::Value(TaskStatus::new("Completed")),
+        priority: Patch::Value(TaskPriority::Low),
+        ..Default::default()
     };
 
-    assert_eq!(update_request.title, Some("Updated Title".to_string()));
-    assert_eq!(update_request.status, Some(TaskStatus::Completed));
+    assert_eq!(
+        update_request.title,
+        Patch::Value("Updated Title".to_string())
+    );
+    assert_eq!(
+        update_request.status,
+        Patch::Value(TaskStatus::new("Completed"))
+    );
+    assert!(update_request.description.is_absent());
 
     // Test Task structure
     let task = Task {
         id: Uuid::new_v4(),
         title: "Sample Task".to_string(),
         description: Some("Sample Description".to_string()),
-        status: TaskStatus::InProgress,
+        status: TaskStatus::new("InProgress"),
+        status_category: StatusCategory::Doing,
         priority: TaskPriority::Medium,
         due_date: None,
         created_at: Utc::now(),
         updated_at: Utc::now(),
+        version: 1,
+        deleted_at: None,
+        comment_count: 0,
+        search: None,
+        tags: Vec::new(),
+        assignee_id: None,
+        project_id: DEFAULT_PROJECT_ID,
+        parent_id: None,
+        subtask_count: 0,
+        completed_subtask_count: 0,
+        is_blocked: false,
+        recurrence: None,
+        rank: String::new(),
+        estimate_minutes: None,
+        logged_minutes: 0,
+        timer_started_at: None,
+        custom_fields: Default::default(),
     };
 
     assert_eq!(task.title, "Sample Task");
-    assert_eq!(task.status, TaskStatus::InProgress);
+    assert_eq!(task.status, TaskStatus::new("InProgress"));
     assert_eq!(task.priority, TaskPriority::Medium);
 }
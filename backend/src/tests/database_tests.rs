@@ -1,8 +1,23 @@
 #[cfg(test)]
 mod database_test_suite {
-    use crate::{database::Database, error::AppError};
-    use chrono::Utc;
-    use common::{CreateTaskRequest, TaskFilter, TaskPriority, TaskStatus, UpdateTaskRequest};
+    use crate::{
+        database::Database,
+        error::AppError,
+        memory::InMemoryTaskRepository,
+        pagination::Cursor,
+        repository::{NewAttachment, SubtaskCompletion, TaskRepository, WipLimits, WriteContext},
+        tests::list_all,
+    };
+    use chrono::{Duration, TimeZone, Utc};
+    use common::{
+        custom_fields::{CustomFieldFilter, CustomFieldRequest, CustomFieldType},
+        default_workflow,
+        saved_views::{SavedViewRequest, ViewGrouping},
+        transition::{PriorityRule, TaskField, TransitionError},
+        CommentRequest, CreateTaskRequest, MemberRequest, MoveTaskRequest, Patch, ProjectRequest,
+        StatusCategory, TagRequest, TaskEventKind, TaskFilter, TaskPriority, TaskSort, TaskStatus,
+        TimeEntryRequest, UpdateTaskRequest, WorkflowRequest, WorkflowState, DEFAULT_PROJECT_ID,
+    };
     use serial_test::serial;
     use sqlx::PgPool;
     use std::env;
@@ -24,7 +39,14 @@ mod database_test_suite {
             .expect("Failed to run migrations");
 
         // Clean all existing data for fresh tests
-        sqlx::query("DELETE FROM tasks")
+        for table in ["tasks", "tags", "members", "custom_fields", "saved_views"] {
+            sqlx::query(&format!("DELETE FROM {table}"))
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        sqlx::query("DELETE FROM projects WHERE id <> $1")
+            .bind(common::DEFAULT_PROJECT_ID)
             .execute(&pool)
             .await
             .unwrap();
@@ -32,6 +54,21 @@ mod database_test_suite {
         pool
     }
 
+    /// A fresh repository of every engine under test, PostgreSQL first.
+    async fn repositories() -> Vec<Box<dyn TaskRepository>> {
+        let postgres = Database::new(setup_test_db().await);
+        #[cfg(feature = "sqlite")]
+        let sqlite = crate::sqlite::SqliteDatabase::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        vec![
+            Box::new(postgres),
+            Box::new(InMemoryTaskRepository::new()),
+            #[cfg(feature = "sqlite")]
+            Box::new(sqlite),
+        ]
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_create_task_success() {
@@ -43,16 +80,19 @@ mod database_test_suite {
             description: Some("Test Description".to_string()),
             priority: common::TaskPriority::Low,
             due_date: None,
+            ..Default::default()
         };
 
-        let result = database.create_task(request.clone()).await;
+        let result = database
+            .create_task(request.clone(), &WriteContext::default())
+            .await;
         assert!(result.is_ok());
 
         let task = result.unwrap();
         assert_eq!(task.title, request.title);
         assert_eq!(task.description, request.description);
         assert_eq!(task.priority, request.priority);
-        assert_eq!(task.status, TaskStatus::Todo);
+        assert_eq!(task.status, TaskStatus::new("Todo"));
         assert!(task.id != Uuid::nil());
         assert!(task.created_at <= Utc::now());
         assert!(task.updated_at <= Utc::now());
@@ -70,9 +110,12 @@ mod database_test_suite {
             description: None,
             priority: TaskPriority::Low,
             due_date: Some(due_date),
+            ..Default::default()
         };
 
-        let result = database.create_task(request.clone()).await;
+        let result = database
+            .create_task(request.clone(), &WriteContext::default())
+            .await;
         assert!(result.is_ok());
 
         let task = result.unwrap();
@@ -89,7 +132,7 @@ mod database_test_suite {
         let pool = setup_test_db().await;
         let database = Database::new(pool);
 
-        let result = database.get_tasks(None).await;
+        let result = list_all(&database, TaskFilter::default()).await;
         assert!(result.is_ok());
         // Don't assert empty - other concurrent tests might add data
         // Just ensure we get a valid response
@@ -107,6 +150,7 @@ mod database_test_suite {
             description: Some("Description 1".to_string()),
             priority: TaskPriority::High,
             due_date: None,
+            ..Default::default()
         };
 
         let task2 = CreateTaskRequest {
@@ -114,12 +158,19 @@ mod database_test_suite {
             description: None,
             priority: TaskPriority::Low,
             due_date: Some(Utc::now() + chrono::Duration::days(3)),
+            ..Default::default()
         };
 
-        database.create_task(task1).await.unwrap();
-        database.create_task(task2).await.unwrap();
+        database
+            .create_task(task1, &WriteContext::default())
+            .await
+            .unwrap();
+        database
+            .create_task(task2, &WriteContext::default())
+            .await
+            .unwrap();
 
-        let result = database.get_tasks(None).await;
+        let result = list_all(&database, TaskFilter::default()).await;
         assert!(result.is_ok());
 
         let tasks = result.unwrap();
@@ -134,12 +185,16 @@ mod database_test_suite {
 
         // Create tasks with different statuses
         let task = database
-            .create_task(CreateTaskRequest {
-                title: "Todo Task".to_string(),
-                description: None,
-                priority: TaskPriority::High,
-                due_date: None,
-            })
+            .create_task(
+                CreateTaskRequest {
+                    title: "Todo Task".to_string(),
+                    description: None,
+                    priority: TaskPriority::High,
+                    due_date: None,
+                    ..Default::default()
+                },
+                &WriteContext::default(),
+            )
             .await
             .unwrap();
 
@@ -148,41 +203,44 @@ mod database_test_suite {
             .update_task(
                 task.id,
                 UpdateTaskRequest {
-                    title: None,
-                    description: None,
-                    status: Some(TaskStatus::InProgress),
-                    priority: None,
-                    due_date: None,
+                    status: Patch::Value(TaskStatus::new("InProgress")),
+                    ..Default::default()
                 },
+                &WriteContext::default(),
             )
             .await
             .unwrap();
 
         // Create another todo task
         database
-            .create_task(CreateTaskRequest {
-                title: "Another Todo".to_string(),
-                description: None,
-                priority: TaskPriority::Low,
-                due_date: None,
-            })
+            .create_task(
+                CreateTaskRequest {
+                    title: "Another Todo".to_string(),
+                    description: None,
+                    priority: TaskPriority::Low,
+                    due_date: None,
+                    ..Default::default()
+                },
+                &WriteContext::default(),
+            )
             .await
             .unwrap();
 
         // Test filter for Todo status
         let filter = TaskFilter {
-            status: Some(TaskStatus::Todo),
-            priority: None,
+            status: vec![TaskStatus::new("Todo")],
+            priority: Vec::new(),
             due_before: None,
             due_after: None,
+            ..Default::default()
         };
 
-        let result = database.get_tasks(Some(filter)).await;
+        let result = list_all(&database, filter).await;
         assert!(result.is_ok());
 
         let tasks = result.unwrap();
         assert_eq!(tasks.len(), 1);
-        assert_eq!(tasks[0].status, TaskStatus::Todo);
+        assert_eq!(tasks[0].status, TaskStatus::new("Todo"));
     }
 
     #[tokio::test]
@@ -192,33 +250,42 @@ mod database_test_suite {
         let database = Database::new(pool);
 
         database
-            .create_task(CreateTaskRequest {
-                title: "Work Task".to_string(),
-                description: None,
-                priority: TaskPriority::High,
-                due_date: None,
-            })
+            .create_task(
+                CreateTaskRequest {
+                    title: "Work Task".to_string(),
+                    description: None,
+                    priority: TaskPriority::High,
+                    due_date: None,
+                    ..Default::default()
+                },
+                &WriteContext::default(),
+            )
             .await
             .unwrap();
 
         database
-            .create_task(CreateTaskRequest {
-                title: "Personal Task".to_string(),
-                description: None,
-                priority: TaskPriority::Low,
-                due_date: None,
-            })
+            .create_task(
+                CreateTaskRequest {
+                    title: "Personal Task".to_string(),
+                    description: None,
+                    priority: TaskPriority::Low,
+                    due_date: None,
+                    ..Default::default()
+                },
+                &WriteContext::default(),
+            )
             .await
             .unwrap();
 
         let filter = TaskFilter {
-            status: None,
-            priority: Some(TaskPriority::High),
+            status: Vec::new(),
+            priority: vec![TaskPriority::High],
             due_before: None,
             due_after: None,
+            ..Default::default()
         };
 
-        let result = database.get_tasks(Some(filter)).await;
+        let result = list_all(&database, filter).await;
         assert!(result.is_ok());
 
         let tasks = result.unwrap();
@@ -233,12 +300,16 @@ mod database_test_suite {
         let database = Database::new(pool);
 
         let created_task = database
-            .create_task(CreateTaskRequest {
-                title: "Find Me".to_string(),
-                description: Some("Found!".to_string()),
-                priority: TaskPriority::Medium,
-                due_date: None,
-            })
+            .create_task(
+                CreateTaskRequest {
+                    title: "Find Me".to_string(),
+                    description: Some("Found!".to_string()),
+                    priority: TaskPriority::Medium,
+                    due_date: None,
+                    ..Default::default()
+                },
+                &WriteContext::default(),
+            )
             .await
             .unwrap();
 
@@ -270,25 +341,40 @@ mod database_test_suite {
         let database = Database::new(pool);
 
         let created_task = database
-            .create_task(CreateTaskRequest {
-                title: "Original Title".to_string(),
-                description: Some("Original Description".to_string()),
-                priority: TaskPriority::High,
-                due_date: None,
-            })
+            .create_task(
+                CreateTaskRequest {
+                    title: "Original Title".to_string(),
+                    description: Some("Original Description".to_string()),
+                    priority: TaskPriority::High,
+                    due_date: None,
+                    ..Default::default()
+                },
+                &WriteContext::default(),
+            )
             .await
             .unwrap();
 
         let update_request = UpdateTaskRequest {
-            title: Some("Updated Title".to_string()),
-            description: Some("Updated Description".to_string()),
-            status: Some(TaskStatus::InProgress),
-            priority: Some(TaskPriority::Low),
-            due_date: Some(Utc::now() + chrono::Duration::days(5)),
+            title: Patch::Value("Updated Title".to_string()),
+            description: Patch::Value("Updated Description".to_string()),
+            status: Patch::Value(TaskStatus::new("InProgress")),
+            priority: Patch::Value(TaskPriority::Low),
+            due_date: Patch::Value(Utc::now() + chrono::Duration::days(5)),
+            tags: Patch::Absent,
+            assignee_id: Patch::Absent,
+            project_id: Patch::Absent,
+            parent_id: Patch::Absent,
+            recurrence: Patch::Absent,
+            estimate_minutes: Patch::Absent,
+            custom_fields: Patch::Absent,
         };
 
         let result = database
-            .update_task(created_task.id, update_request.clone())
+            .update_task(
+                created_task.id,
+                update_request.clone(),
+                &WriteContext::default(),
+            )
             .await;
         assert!(result.is_ok());
 
@@ -298,7 +384,7 @@ mod database_test_suite {
             updated_task.description,
             Some("Updated Description".to_string())
         );
-        assert_eq!(updated_task.status, TaskStatus::InProgress);
+        assert_eq!(updated_task.status, TaskStatus::new("InProgress"));
         assert_eq!(updated_task.priority, TaskPriority::Low);
         assert!(updated_task.due_date.is_some());
         assert!(updated_task.updated_at > created_task.updated_at);
@@ -311,25 +397,28 @@ mod database_test_suite {
         let database = Database::new(pool);
 
         let created_task = database
-            .create_task(CreateTaskRequest {
-                title: "Original Title".to_string(),
-                description: Some("Original Description".to_string()),
-                priority: TaskPriority::High,
-                due_date: None,
-            })
+            .create_task(
+                CreateTaskRequest {
+                    title: "Original Title".to_string(),
+                    description: Some("Original Description".to_string()),
+                    priority: TaskPriority::High,
+                    due_date: None,
+                    ..Default::default()
+                },
+                &WriteContext::default(),
+            )
             .await
             .unwrap();
 
         // Only update title
         let update_request = UpdateTaskRequest {
-            title: Some("Only Title Updated".to_string()),
-            description: None,
-            status: None,
-            priority: None,
-            due_date: None,
+            title: Patch::Value("Only Title Updated".to_string()),
+            ..Default::default()
         };
 
-        let result = database.update_task(created_task.id, update_request).await;
+        let result = database
+            .update_task(created_task.id, update_request, &WriteContext::default())
+            .await;
         assert!(result.is_ok());
 
         let updated_task = result.unwrap();
@@ -340,6 +429,114 @@ mod database_test_suite {
         assert_eq!(updated_task.due_date, created_task.due_date);
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_update_task_clears_nullable_fields() {
+        let pool = setup_test_db().await;
+        let database = Database::new(pool);
+
+        let created_task = database
+            .create_task(
+                CreateTaskRequest {
+                    title: "Has Everything".to_string(),
+                    description: Some("To be cleared".to_string()),
+                    priority: TaskPriority::Medium,
+                    due_date: Some(Utc::now()),
+                    ..Default::default()
+                },
+                &WriteContext::default(),
+            )
+            .await
+            .unwrap();
+
+        let update_request = UpdateTaskRequest {
+            description: Patch::Null,
+            due_date: Patch::Null,
+            ..Default::default()
+        };
+
+        let updated_task = database
+            .update_task(created_task.id, update_request, &WriteContext::default())
+            .await
+            .unwrap();
+        assert_eq!(updated_task.title, "Has Everything");
+        assert_eq!(updated_task.description, None);
+        assert_eq!(updated_task.due_date, None);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_update_task_checks_expected_version() {
+        let pool = setup_test_db().await;
+        let database = Database::new(pool);
+
+        let created_task = database
+            .create_task(
+                CreateTaskRequest {
+                    title: "Versioned".to_string(),
+                    description: None,
+                    priority: TaskPriority::Low,
+                    due_date: None,
+                    ..Default::default()
+                },
+                &WriteContext::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(created_task.version, 1);
+
+        let update_request = UpdateTaskRequest {
+            status: Patch::Value(TaskStatus::new("InProgress")),
+            ..Default::default()
+        };
+        let updated_task = database
+            .update_task(
+                created_task.id,
+                update_request.clone(),
+                &WriteContext {
+                    expected_version: Some(1),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated_task.version, 2);
+
+        let result = database
+            .update_task(
+                created_task.id,
+                update_request,
+                &WriteContext {
+                    expected_version: Some(1),
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(AppError::PreconditionFailed)));
+
+        let result = database
+            .delete_task(
+                created_task.id,
+                &WriteContext {
+                    expected_version: Some(1),
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(AppError::PreconditionFailed)));
+
+        let result = database
+            .delete_task(
+                created_task.id,
+                &WriteContext {
+                    expected_version: Some(2),
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(result.is_ok());
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_update_task_not_found() {
@@ -348,14 +545,13 @@ mod database_test_suite {
 
         let non_existent_id = Uuid::new_v4();
         let update_request = UpdateTaskRequest {
-            title: Some("This won't work".to_string()),
-            description: None,
-            status: None,
-            priority: None,
-            due_date: None,
+            title: Patch::Value("This won't work".to_string()),
+            ..Default::default()
         };
 
-        let result = database.update_task(non_existent_id, update_request).await;
+        let result = database
+            .update_task(non_existent_id, update_request, &WriteContext::default())
+            .await;
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), AppError::TaskNotFound));
     }
@@ -367,16 +563,22 @@ mod database_test_suite {
         let database = Database::new(pool);
 
         let created_task = database
-            .create_task(CreateTaskRequest {
-                title: "Delete Me".to_string(),
-                description: None,
-                priority: TaskPriority::Medium,
-                due_date: None,
-            })
+            .create_task(
+                CreateTaskRequest {
+                    title: "Delete Me".to_string(),
+                    description: None,
+                    priority: TaskPriority::Medium,
+                    due_date: None,
+                    ..Default::default()
+                },
+                &WriteContext::default(),
+            )
             .await
             .unwrap();
 
-        let result = database.delete_task(created_task.id).await;
+        let result = database
+            .delete_task(created_task.id, &WriteContext::default())
+            .await;
         assert!(result.is_ok());
 
         // Verify task is deleted
@@ -392,7 +594,9 @@ mod database_test_suite {
         let database = Database::new(pool);
 
         let non_existent_id = Uuid::new_v4();
-        let result = database.delete_task(non_existent_id).await;
+        let result = database
+            .delete_task(non_existent_id, &WriteContext::default())
+            .await;
 
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), AppError::TaskNotFound));
@@ -411,54 +615,71 @@ mod database_test_suite {
 
         // Create tasks with different due dates
         database
-            .create_task(CreateTaskRequest {
-                title: "Past Task".to_string(),
-                description: None,
-                priority: TaskPriority::High,
-                due_date: Some(yesterday),
-            })
+            .create_task(
+                CreateTaskRequest {
+                    title: "Past Task".to_string(),
+                    description: None,
+                    priority: TaskPriority::High,
+                    due_date: Some(yesterday),
+                    ..Default::default()
+                },
+                &WriteContext::default(),
+            )
             .await
             .unwrap();
 
         database
-            .create_task(CreateTaskRequest {
-                title: "Future Task".to_string(),
-                description: None,
-                priority: TaskPriority::High,
-                due_date: Some(tomorrow),
-            })
+            .create_task(
+                CreateTaskRequest {
+                    title: "Future Task".to_string(),
+                    description: None,
+                    priority: TaskPriority::High,
+                    due_date: Some(tomorrow),
+                    ..Default::default()
+                },
+                &WriteContext::default(),
+            )
             .await
             .unwrap();
 
         database
-            .create_task(CreateTaskRequest {
-                title: "Far Future Task".to_string(),
-                description: None,
-                priority: TaskPriority::High,
-                due_date: Some(next_week),
-            })
+            .create_task(
+                CreateTaskRequest {
+                    title: "Far Future Task".to_string(),
+                    description: None,
+                    priority: TaskPriority::High,
+                    due_date: Some(next_week),
+                    ..Default::default()
+                },
+                &WriteContext::default(),
+            )
             .await
             .unwrap();
 
         database
-            .create_task(CreateTaskRequest {
-                title: "No Due Date".to_string(),
-                description: None,
-                priority: TaskPriority::High,
-                due_date: None,
-            })
+            .create_task(
+                CreateTaskRequest {
+                    title: "No Due Date".to_string(),
+                    description: None,
+                    priority: TaskPriority::High,
+                    due_date: None,
+                    ..Default::default()
+                },
+                &WriteContext::default(),
+            )
             .await
             .unwrap();
 
         // Test due_before filter
         let filter = TaskFilter {
-            status: None,
-            priority: None,
+            status: Vec::new(),
+            priority: Vec::new(),
             due_before: Some(now + chrono::Duration::days(2)),
             due_after: None,
+            ..Default::default()
         };
 
-        let result = database.get_tasks(Some(filter)).await;
+        let result = list_all(&database, filter).await;
         assert!(result.is_ok());
 
         let tasks = result.unwrap();
@@ -466,16 +687,2069 @@ mod database_test_suite {
 
         // Test due_after filter
         let filter = TaskFilter {
-            status: None,
-            priority: None,
+            status: Vec::new(),
+            priority: Vec::new(),
             due_before: None,
             due_after: Some(now),
+            ..Default::default()
         };
 
-        let result = database.get_tasks(Some(filter)).await;
+        let result = list_all(&database, filter).await;
         assert!(result.is_ok());
 
         let tasks = result.unwrap();
         assert_eq!(tasks.len(), 2); // tomorrow and next_week tasks
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_tasks_with_multi_value_and_range_filters() {
+        let pool = setup_test_db().await;
+        let database = Database::new(pool);
+
+        let before_all = Utc::now() - chrono::Duration::seconds(1);
+
+        let first = database
+            .create_task(
+                CreateTaskRequest {
+                    title: "First".to_string(),
+                    description: None,
+                    priority: TaskPriority::Low,
+                    due_date: Some(Utc::now() + chrono::Duration::days(1)),
+                    ..Default::default()
+                },
+                &WriteContext::default(),
+            )
+            .await
+            .unwrap();
+
+        let second = database
+            .create_task(
+                CreateTaskRequest {
+                    title: "Second".to_string(),
+                    description: None,
+                    priority: TaskPriority::Medium,
+                    due_date: None,
+                    ..Default::default()
+                },
+                &WriteContext::default(),
+            )
+            .await
+            .unwrap();
+
+        database
+            .update_task(
+                second.id,
+                UpdateTaskRequest {
+                    status: Patch::Value(TaskStatus::new("InProgress")),
+                    ..Default::default()
+                },
+                &WriteContext::default(),
+            )
+            .await
+            .unwrap();
+
+        database
+            .create_task(
+                CreateTaskRequest {
+                    title: "Third".to_string(),
+                    description: None,
+                    priority: TaskPriority::Urgent,
+                    due_date: None,
+                    ..Default::default()
+                },
+                &WriteContext::default(),
+            )
+            .await
+            .unwrap();
+
+        // Any of several statuses
+        let filter = TaskFilter {
+            status: vec![TaskStatus::new("InProgress"), TaskStatus::new("Completed")],
+            ..Default::default()
+        };
+        let tasks = list_all(&database, filter).await.unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].id, second.id);
+
+        // Any of several priorities combined with a due date presence check
+        let filter = TaskFilter {
+            priority: vec![TaskPriority::Low, TaskPriority::Urgent],
+            has_due_date: Some(false),
+            ..Default::default()
+        };
+        let tasks = list_all(&database, filter).await.unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "Third");
+
+        let filter = TaskFilter {
+            has_due_date: Some(true),
+            ..Default::default()
+        };
+        let tasks = list_all(&database, filter).await.unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].id, first.id);
+
+        // Created and updated ranges
+        let filter = TaskFilter {
+            created_after: Some(before_all),
+            created_before: Some(Utc::now() + chrono::Duration::seconds(1)),
+            ..Default::default()
+        };
+        assert_eq!(list_all(&database, filter).await.unwrap().len(), 3);
+
+        let filter = TaskFilter {
+            created_before: Some(before_all),
+            ..Default::default()
+        };
+        assert!(list_all(&database, filter).await.unwrap().is_empty());
+
+        let filter = TaskFilter {
+            updated_after: Some(first.updated_at),
+            ..Default::default()
+        };
+        let tasks = list_all(&database, filter).await.unwrap();
+        assert!(tasks.iter().all(|t| t.id != first.id));
+        assert!(tasks.iter().any(|t| t.id == second.id));
+    }
+
+    /// Walks every page one task at a time and returns the titles in order.
+    async fn listed_titles(
+        repository: &dyn TaskRepository,
+        filter: &TaskFilter,
+        sort: TaskSort,
+    ) -> Vec<String> {
+        let mut titles = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = repository
+                .list_tasks(filter, sort, cursor.as_ref(), 1)
+                .await
+                .unwrap();
+            titles.extend(page.items.into_iter().map(|task| task.title));
+            match page.next_cursor {
+                Some(token) => cursor = Some(Cursor::decode(&token, sort).unwrap()),
+                None => break,
+            }
+        }
+        titles
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_repositories_agree_on_filter_and_sort() {
+        let repositories = repositories().await;
+
+        let tasks = [
+            (
+                "Fix login page",
+                Some("Users cannot sign in"),
+                TaskPriority::High,
+                Some(1),
+            ),
+            (
+                "Write release notes",
+                Some("Mention the new login flow"),
+                TaskPriority::Low,
+                Some(2),
+            ),
+            ("Deploy staging", None, TaskPriority::Urgent, Some(3)),
+            (
+                "Deployment checklist",
+                Some("Page the on-call engineer"),
+                TaskPriority::Medium,
+                None,
+            ),
+        ];
+        for repository in &repositories {
+            for (title, description, priority, due_in_days) in tasks {
+                repository
+                    .create_task(
+                        CreateTaskRequest {
+                            title: title.to_string(),
+                            description: description.map(str::to_string),
+                            priority,
+                            due_date: due_in_days
+                                .map(|days| Utc::now() + chrono::Duration::days(days)),
+                            ..Default::default()
+                        },
+                        &WriteContext::default(),
+                    )
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let search = |q: &str| TaskFilter {
+            q: Some(q.to_string()),
+            ..Default::default()
+        };
+        let cases = [
+            (TaskFilter::default(), "title"),
+            (TaskFilter::default(), "due_date"),
+            (TaskFilter::default(), "-due_date"),
+            (TaskFilter::default(), "-priority"),
+            (TaskFilter::default(), "-created_at"),
+            (
+                TaskFilter {
+                    priority: vec![TaskPriority::Low, TaskPriority::Urgent],
+                    ..Default::default()
+                },
+                "title",
+            ),
+            (search("login"), "-relevance"),
+            (search("deploy*"), "title"),
+            (search("\"login page\""), "title"),
+            (search("page -login"), "title"),
+        ];
+        for (filter, sort) in cases {
+            let sort: TaskSort = sort.parse().unwrap();
+            let expected = listed_titles(repositories[0].as_ref(), &filter, sort).await;
+            assert!(!expected.is_empty(), "no results for {filter:?} {sort}");
+            for repository in &repositories[1..] {
+                assert_eq!(
+                    listed_titles(repository.as_ref(), &filter, sort).await,
+                    expected,
+                    "engines disagree on {filter:?} {sort}"
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_later_pages_reuse_first_total() {
+        let repositories = repositories().await;
+
+        let create = |title: &str| CreateTaskRequest {
+            title: title.to_string(),
+            description: None,
+            priority: TaskPriority::Medium,
+            due_date: None,
+            ..Default::default()
+        };
+        let sort = TaskSort::default();
+        for repository in repositories {
+            for title in ["A", "B", "C"] {
+                repository
+                    .create_task(create(title), &WriteContext::default())
+                    .await
+                    .unwrap();
+            }
+
+            let first = repository
+                .list_tasks(&TaskFilter::default(), sort, None, 1)
+                .await
+                .unwrap();
+            assert_eq!(first.total_estimate, 3);
+
+            // The total is not counted again for the pages after the first
+            repository
+                .create_task(create("D"), &WriteContext::default())
+                .await
+                .unwrap();
+            let cursor = Cursor::decode(&first.next_cursor.unwrap(), sort).unwrap();
+            let second = repository
+                .list_tasks(&TaskFilter::default(), sort, Some(&cursor), 1)
+                .await
+                .unwrap();
+            assert_eq!(second.total_estimate, 3);
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_purge_trash_keeps_recent_and_live_tasks() {
+        let repositories = repositories().await;
+
+        for repository in repositories {
+            let mut ids = Vec::new();
+            for title in ["Trashed", "Kept"] {
+                let task = repository
+                    .create_task(
+                        CreateTaskRequest {
+                            title: title.to_string(),
+                            description: None,
+                            priority: TaskPriority::Medium,
+                            due_date: None,
+                            ..Default::default()
+                        },
+                        &WriteContext::default(),
+                    )
+                    .await
+                    .unwrap();
+                ids.push(task.id);
+            }
+            repository
+                .delete_task(ids[0], &WriteContext::default())
+                .await
+                .unwrap();
+
+            // Nothing was trashed a day ago
+            let purged = repository
+                .purge_trash(Utc::now() - chrono::Duration::days(1))
+                .await
+                .unwrap();
+            assert_eq!(purged.tasks, 0);
+            assert_eq!(repository.list_trash().await.unwrap().len(), 1);
+
+            let purged = repository
+                .purge_trash(Utc::now() + chrono::Duration::seconds(1))
+                .await
+                .unwrap();
+            assert_eq!(purged.tasks, 1);
+            assert!(repository.list_trash().await.unwrap().is_empty());
+            assert!(matches!(
+                repository
+                    .restore_task(ids[0], &WriteContext::default())
+                    .await,
+                Err(AppError::TaskNotFound)
+            ));
+            assert!(repository.get_task(ids[1]).await.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_history_records_only_changed_fields() {
+        let repositories = repositories().await;
+
+        let due_date = "2030-01-02T03:04:05Z".parse().unwrap();
+        let context = WriteContext {
+            actor: Some("carol".to_string()),
+            ..Default::default()
+        };
+
+        for repository in repositories {
+            let task = repository
+                .create_task(
+                    CreateTaskRequest {
+                        title: "Tracked".to_string(),
+                        description: None,
+                        priority: TaskPriority::Medium,
+                        due_date: None,
+                        ..Default::default()
+                    },
+                    &context,
+                )
+                .await
+                .unwrap();
+
+            // Rewriting a field with its current value is not a change
+            let update = UpdateTaskRequest {
+                title: Patch::Value("Tracked".to_string()),
+                due_date: Patch::Value(due_date),
+                ..Default::default()
+            };
+            let updated = repository
+                .update_task(task.id, update.clone(), &context)
+                .await
+                .unwrap();
+            assert_eq!(updated.version, 2);
+            repository
+                .update_task(task.id, update, &context)
+                .await
+                .unwrap();
+
+            let events = repository.task_history(task.id).await.unwrap();
+            assert_eq!(events.len(), 2);
+            assert_eq!(events[0].kind, TaskEventKind::Created);
+            assert_eq!(events[1].kind, TaskEventKind::Updated);
+            assert_eq!(events[1].field.as_deref(), Some("due_date"));
+            assert_eq!(events[1].old_value, None);
+            assert_eq!(
+                events[1].new_value.as_deref(),
+                Some("2030-01-02T03:04:05+00:00")
+            );
+            assert!(events
+                .iter()
+                .all(|event| event.actor.as_deref() == Some("carol")));
+            assert!(events[0].id < events[1].id);
+
+            assert!(matches!(
+                repository.task_history(Uuid::new_v4()).await,
+                Err(AppError::TaskNotFound)
+            ));
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_comment_count_follows_thread() {
+        let repositories = repositories().await;
+
+        for repository in repositories {
+            let task = repository
+                .create_task(
+                    CreateTaskRequest {
+                        title: "Talked about".to_string(),
+                        description: None,
+                        priority: TaskPriority::Medium,
+                        due_date: None,
+                        ..Default::default()
+                    },
+                    &WriteContext::default(),
+                )
+                .await
+                .unwrap();
+
+            let mut ids = Vec::new();
+            for body in ["First", "Second"] {
+                let comment = repository
+                    .create_comment(
+                        task.id,
+                        CommentRequest {
+                            body: body.to_string(),
+                        },
+                        None,
+                    )
+                    .await
+                    .unwrap();
+                ids.push(comment.id);
+            }
+            repository.delete_comment(task.id, ids[0]).await.unwrap();
+
+            let task = repository.get_task(task.id).await.unwrap();
+            assert_eq!(task.comment_count, 1);
+            let update = UpdateTaskRequest {
+                status: Patch::Value(TaskStatus::new("InProgress")),
+                ..Default::default()
+            };
+            let updated = repository
+                .update_task(task.id, update, &WriteContext::default())
+                .await
+                .unwrap();
+            assert_eq!(updated.comment_count, 1);
+
+            let comments = repository.list_comments(task.id).await.unwrap();
+            assert_eq!(comments.len(), 1);
+            assert_eq!(comments[0].id, ids[1]);
+            assert!(matches!(
+                repository.delete_comment(task.id, ids[0]).await,
+                Err(AppError::CommentNotFound)
+            ));
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_tag_filters_agree_across_engines() {
+        let repositories = repositories().await;
+
+        for repository in repositories {
+            for name in ["a", "b", "c"] {
+                repository
+                    .create_tag(TagRequest {
+                        name: name.to_string(),
+                        color: "#888888".to_string(),
+                    })
+                    .await
+                    .unwrap();
+            }
+            for (title, tags) in [
+                ("ab", vec!["b", "a"]),
+                ("bc", vec!["b", "c"]),
+                ("none", vec![]),
+            ] {
+                let task = repository
+                    .create_task(
+                        CreateTaskRequest {
+                            title: title.to_string(),
+                            description: None,
+                            priority: TaskPriority::Medium,
+                            due_date: None,
+                            tags: tags.into_iter().map(String::from).collect(),
+                            ..Default::default()
+                        },
+                        &WriteContext::default(),
+                    )
+                    .await
+                    .unwrap();
+                let names: Vec<&str> = task.tags.iter().map(|tag| tag.name.as_str()).collect();
+                assert!(names.is_sorted(), "{names:?}");
+            }
+
+            let cases = [
+                (
+                    TaskFilter {
+                        tag: Some("b".to_string()),
+                        ..Default::default()
+                    },
+                    vec!["ab", "bc"],
+                ),
+                (
+                    TaskFilter {
+                        any_tag: vec!["a".to_string(), "c".to_string()],
+                        ..Default::default()
+                    },
+                    vec!["ab", "bc"],
+                ),
+                (
+                    TaskFilter {
+                        all_tags: vec!["a".to_string(), "b".to_string()],
+                        ..Default::default()
+                    },
+                    vec!["ab"],
+                ),
+                (
+                    TaskFilter {
+                        tag: Some("c".to_string()),
+                        all_tags: vec!["a".to_string()],
+                        ..Default::default()
+                    },
+                    vec![],
+                ),
+            ];
+            for (filter, expected) in cases {
+                let page = repository
+                    .list_tasks(&filter, TaskSort::default(), None, 10)
+                    .await
+                    .unwrap();
+                let mut titles: Vec<&str> =
+                    page.items.iter().map(|task| task.title.as_str()).collect();
+                titles.sort();
+                assert_eq!(titles, expected, "{filter:?}");
+                assert_eq!(page.total_estimate, expected.len() as u64);
+            }
+
+            let unknown = CreateTaskRequest {
+                title: "Unknown".to_string(),
+                description: None,
+                priority: TaskPriority::Medium,
+                due_date: None,
+                tags: vec!["z".to_string()],
+                ..Default::default()
+            };
+            assert!(matches!(
+                repository
+                    .create_task(unknown, &WriteContext::default())
+                    .await,
+                Err(AppError::InvalidInput(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_task_queries_agree_across_engines() {
+        let repositories = repositories().await;
+        let at = |day, hour| Utc.with_ymd_and_hms(2026, 10, day, hour, 0, 0).unwrap();
+
+        for repository in repositories {
+            repository
+                .create_tag(TagRequest {
+                    name: "bug".to_string(),
+                    color: "#d73a4a".to_string(),
+                })
+                .await
+                .unwrap();
+            let ada = repository
+                .create_member(MemberRequest {
+                    name: "Ada Lovelace".to_string(),
+                })
+                .await
+                .unwrap();
+            for (title, description, priority, due_date, tags, assignee_id) in [
+                (
+                    "Login",
+                    "The login page times out",
+                    TaskPriority::Urgent,
+                    Some(at(30, 23)),
+                    vec!["bug".to_string()],
+                    Some(ada.id),
+                ),
+                (
+                    "Crash",
+                    "Crashes on start",
+                    TaskPriority::High,
+                    Some(at(31, 8)),
+                    vec!["bug".to_string()],
+                    None,
+                ),
+                (
+                    "Docs",
+                    "Write the guide",
+                    TaskPriority::Low,
+                    None,
+                    vec![],
+                    None,
+                ),
+            ] {
+                repository
+                    .create_task(
+                        CreateTaskRequest {
+                            title: title.to_string(),
+                            description: Some(description.to_string()),
+                            priority,
+                            due_date,
+                            tags,
+                            assignee_id,
+                            ..Default::default()
+                        },
+                        &WriteContext::default(),
+                    )
+                    .await
+                    .unwrap();
+            }
+
+            let cases = [
+                ("priority>=High", vec!["Crash", "Login"]),
+                ("priority<Medium", vec!["Docs"]),
+                ("priority>Urgent", vec![]),
+                ("due<2026-10-31", vec!["Login"]),
+                ("due:2026-10-31", vec!["Crash"]),
+                ("due>=2026-10-30 due<=2026-10-31", vec!["Crash", "Login"]),
+                ("-due<2026-10-31", vec!["Crash", "Docs"]),
+                ("due:none", vec!["Docs"]),
+                ("tag:bug -assignee:none", vec!["Login"]),
+                ("assignee:\"Ada Lovelace\"", vec!["Login"]),
+                ("-assignee:\"Ada Lovelace\"", vec!["Crash", "Docs"]),
+                ("assignee:Nobody", vec![]),
+                ("status:Todo -tag:bug", vec!["Docs"]),
+                ("created<2000-01-01", vec![]),
+                ("tag:bug \"login page\"", vec!["Login"]),
+                ("-priority:Urgent start", vec!["Crash"]),
+            ];
+            for (text, expected) in cases {
+                let filter = TaskFilter {
+                    query: Some(text.parse().unwrap()),
+                    ..Default::default()
+                };
+                let page = repository
+                    .list_tasks(&filter, TaskSort::default(), None, 10)
+                    .await
+                    .unwrap();
+                let mut titles: Vec<&str> =
+                    page.items.iter().map(|task| task.title.as_str()).collect();
+                titles.sort();
+                assert_eq!(titles, expected, "{text}");
+            }
+
+            // Free text of the query narrows a `q` search
+            let filter = TaskFilter {
+                q: Some("page".to_string()),
+                query: Some("-guide".parse().unwrap()),
+                ..Default::default()
+            };
+            let tasks = list_all(repository.as_ref(), filter).await.unwrap();
+            assert_eq!(tasks.len(), 1);
+            assert_eq!(tasks[0].title, "Login");
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_deleting_member_unassigns_tasks() {
+        let repositories = repositories().await;
+
+        for repository in repositories {
+            let member = repository
+                .create_member(MemberRequest {
+                    name: "Sarah".to_string(),
+                })
+                .await
+                .unwrap();
+            let task = repository
+                .create_task(
+                    CreateTaskRequest {
+                        title: "Assigned".to_string(),
+                        description: None,
+                        priority: TaskPriority::Medium,
+                        due_date: None,
+                        assignee_id: Some(member.id),
+                        ..Default::default()
+                    },
+                    &WriteContext::default(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(task.assignee_id, Some(member.id));
+
+            repository.delete_member(member.id).await.unwrap();
+            let task = repository.get_task(task.id).await.unwrap();
+            assert_eq!(task.assignee_id, None);
+            assert_eq!(task.version, 1);
+
+            let update = UpdateTaskRequest {
+                assignee_id: Patch::Value(member.id),
+                ..Default::default()
+            };
+            assert!(matches!(
+                repository
+                    .update_task(task.id, update, &WriteContext::default())
+                    .await,
+                Err(AppError::InvalidInput(_))
+            ));
+            assert!(matches!(
+                repository.delete_member(member.id).await,
+                Err(AppError::MemberNotFound)
+            ));
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_deleting_project_requires_it_to_be_empty() {
+        let repositories = repositories().await;
+
+        for repository in repositories {
+            let project = repository
+                .create_project(ProjectRequest {
+                    name: "Website".to_string(),
+                })
+                .await
+                .unwrap();
+            let task = repository
+                .create_task(
+                    CreateTaskRequest {
+                        title: "Landing page".to_string(),
+                        description: None,
+                        priority: TaskPriority::Medium,
+                        due_date: None,
+                        project_id: Some(project.id),
+                        ..Default::default()
+                    },
+                    &WriteContext::default(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(task.project_id, project.id);
+
+            // Trashed tasks still belong to the project until purged
+            repository
+                .delete_task(task.id, &WriteContext::default())
+                .await
+                .unwrap();
+            assert!(matches!(
+                repository.delete_project(project.id).await,
+                Err(AppError::Conflict(_))
+            ));
+            repository.purge_task(task.id).await.unwrap();
+            repository.delete_project(project.id).await.unwrap();
+
+            assert!(matches!(
+                repository.get_project(project.id).await,
+                Err(AppError::ProjectNotFound)
+            ));
+            assert!(matches!(
+                repository.delete_project(DEFAULT_PROJECT_ID).await,
+                Err(AppError::Conflict(_))
+            ));
+            let projects = repository.list_projects().await.unwrap();
+            assert_eq!(projects.len(), 1);
+            assert_eq!(projects[0].id, DEFAULT_PROJECT_ID);
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_completing_parent_cascades_to_subtasks() {
+        let repositories = repositories().await;
+
+        let cascade = WriteContext {
+            subtask_completion: SubtaskCompletion::Cascade,
+            ..Default::default()
+        };
+        let complete = UpdateTaskRequest {
+            status: Patch::Value(TaskStatus::new("Completed")),
+            ..Default::default()
+        };
+        for repository in repositories {
+            let mut parent_id = None;
+            let mut tasks = Vec::new();
+            for title in ["Launch", "Design", "Mockups"] {
+                let task = repository
+                    .create_task(
+                        CreateTaskRequest {
+                            title: title.to_string(),
+                            description: None,
+                            priority: TaskPriority::Medium,
+                            due_date: None,
+                            parent_id,
+                            ..Default::default()
+                        },
+                        &WriteContext::default(),
+                    )
+                    .await
+                    .unwrap();
+                parent_id = Some(task.id);
+                tasks.push(task);
+            }
+
+            assert!(matches!(
+                repository
+                    .update_task(tasks[0].id, complete.clone(), &WriteContext::default())
+                    .await,
+                Err(AppError::Conflict(_))
+            ));
+            let parent = repository
+                .update_task(tasks[0].id, complete.clone(), &cascade)
+                .await
+                .unwrap();
+            assert_eq!(parent.status, TaskStatus::new("Completed"));
+            assert_eq!(
+                (parent.completed_subtask_count, parent.subtask_count),
+                (1, 1)
+            );
+
+            // Grandchildren are completed too, each with its own history
+            for task in &tasks[1..] {
+                let task = repository.get_task(task.id).await.unwrap();
+                assert_eq!(task.status, TaskStatus::new("Completed"));
+                assert_eq!(task.version, 2);
+                let events = repository.task_history(task.id).await.unwrap();
+                let last = events.last().unwrap();
+                assert_eq!(last.field.as_deref(), Some("status"));
+                assert_eq!(last.new_value.as_deref(), Some("Completed"));
+            }
+
+            // Purging a parent leaves its subtasks at the top level
+            repository
+                .delete_task(tasks[0].id, &WriteContext::default())
+                .await
+                .unwrap();
+            repository.purge_task(tasks[0].id).await.unwrap();
+            let orphan = repository.get_task(tasks[1].id).await.unwrap();
+            assert_eq!(orphan.parent_id, None);
+            assert_eq!(orphan.subtask_count, 1);
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_dependency_cycles_are_refused() {
+        let repositories = repositories().await;
+
+        for repository in repositories {
+            let mut ids = Vec::new();
+            for title in ["First", "Second", "Third"] {
+                let task = repository
+                    .create_task(
+                        CreateTaskRequest {
+                            title: title.to_string(),
+                            description: None,
+                            priority: TaskPriority::Medium,
+                            due_date: None,
+                            ..Default::default()
+                        },
+                        &WriteContext::default(),
+                    )
+                    .await
+                    .unwrap();
+                ids.push(task.id);
+            }
+            repository.add_dependency(ids[1], ids[0]).await.unwrap();
+            repository.add_dependency(ids[2], ids[1]).await.unwrap();
+
+            // A trashed task in the middle still links the chain
+            repository
+                .delete_task(ids[1], &WriteContext::default())
+                .await
+                .unwrap();
+            assert!(matches!(
+                repository.add_dependency(ids[0], ids[2]).await,
+                Err(AppError::DependencyCycle)
+            ));
+            assert!(matches!(
+                repository.add_dependency(ids[0], ids[0]).await,
+                Err(AppError::DependencyCycle)
+            ));
+            assert!(!repository.get_task(ids[2]).await.unwrap().is_blocked);
+            repository
+                .restore_task(ids[1], &WriteContext::default())
+                .await
+                .unwrap();
+
+            let start = UpdateTaskRequest {
+                status: Patch::Value(TaskStatus::new("InProgress")),
+                ..Default::default()
+            };
+            assert!(matches!(
+                repository
+                    .update_task(ids[1], start.clone(), &WriteContext::default())
+                    .await,
+                Err(AppError::Conflict(_))
+            ));
+            let overriding = WriteContext {
+                override_blockers: true,
+                ..Default::default()
+            };
+            let task = repository
+                .update_task(ids[1], start, &overriding)
+                .await
+                .unwrap();
+            assert!(task.is_blocked);
+            assert_eq!(task.status, TaskStatus::new("InProgress"));
+
+            // Purging a task takes its dependencies with it
+            repository
+                .delete_task(ids[0], &WriteContext::default())
+                .await
+                .unwrap();
+            repository.purge_task(ids[0]).await.unwrap();
+            let dependencies = repository.list_dependencies(ids[1]).await.unwrap();
+            assert!(dependencies.blocked_by.is_empty());
+            assert_eq!(dependencies.blocks.len(), 1);
+            assert!(matches!(
+                repository.remove_dependency(ids[1], ids[0]).await,
+                Err(AppError::DependencyNotFound)
+            ));
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_completing_recurring_task_creates_next_occurrence() {
+        let repositories = repositories().await;
+
+        let due_date = Utc.with_ymd_and_hms(2025, 1, 31, 9, 0, 0).unwrap();
+        for repository in repositories {
+            repository
+                .create_tag(TagRequest {
+                    name: "rent".to_string(),
+                    color: "#888888".to_string(),
+                })
+                .await
+                .unwrap();
+            let request = CreateTaskRequest {
+                title: "Pay rent".to_string(),
+                description: None,
+                priority: TaskPriority::Urgent,
+                due_date: None,
+                tags: vec!["rent".to_string()],
+                recurrence: Some("FREQ=MONTHLY;COUNT=2".to_string()),
+                ..Default::default()
+            };
+            assert!(matches!(
+                repository
+                    .create_task(request.clone(), &WriteContext::default())
+                    .await,
+                Err(AppError::InvalidInput(_))
+            ));
+            let task = repository
+                .create_task(
+                    CreateTaskRequest {
+                        due_date: Some(due_date),
+                        ..request
+                    },
+                    &WriteContext::default(),
+                )
+                .await
+                .unwrap();
+
+            let complete = UpdateTaskRequest {
+                status: Patch::Value(TaskStatus::new("Completed")),
+                ..Default::default()
+            };
+            let completed = repository
+                .update_task(task.id, complete.clone(), &WriteContext::default())
+                .await
+                .unwrap();
+            assert_eq!(completed.recurrence, None);
+
+            // February has no 31st, so the rent is next due in March
+            let filter = TaskFilter {
+                tag: Some("rent".to_string()),
+                status: vec![TaskStatus::new("Todo")],
+                ..Default::default()
+            };
+            let page = repository
+                .list_tasks(&filter, TaskSort::default(), None, 10)
+                .await
+                .unwrap();
+            assert_eq!(page.items.len(), 1);
+            let next = &page.items[0];
+            assert_eq!(next.title, "Pay rent");
+            assert_eq!(next.priority, TaskPriority::Urgent);
+            assert_eq!(next.tags[0].name, "rent");
+            assert_eq!(
+                next.due_date,
+                Some(Utc.with_ymd_and_hms(2025, 3, 31, 9, 0, 0).unwrap())
+            );
+            assert_eq!(next.recurrence.as_deref(), Some("FREQ=MONTHLY;COUNT=1"));
+            let history = repository.task_history(next.id).await.unwrap();
+            assert_eq!(history[0].kind, TaskEventKind::Created);
+
+            // The last occurrence ends the series and keeps its rule
+            let last = repository
+                .update_task(next.id, complete, &WriteContext::default())
+                .await
+                .unwrap();
+            assert_eq!(last.recurrence.as_deref(), Some("FREQ=MONTHLY;COUNT=1"));
+            let page = repository
+                .list_tasks(&filter, TaskSort::default(), None, 10)
+                .await
+                .unwrap();
+            assert!(page.items.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_custom_workflow_drives_status() {
+        let repositories = repositories().await;
+
+        let state = |name: &str, category| WorkflowState {
+            name: TaskStatus::new(name),
+            category,
+            wip_limit: None,
+            next: None,
+            requires: Vec::new(),
+        };
+        let support = WorkflowRequest {
+            states: vec![
+                state("Triage", StatusCategory::Todo),
+                state("Fixing", StatusCategory::Doing),
+                state("Shipped", StatusCategory::Done),
+            ],
+            ..Default::default()
+        };
+        let move_to = |status: &str| UpdateTaskRequest {
+            status: Patch::Value(TaskStatus::new(status)),
+            ..Default::default()
+        };
+        for repository in repositories {
+            let project = repository
+                .create_project(ProjectRequest {
+                    name: "Support".to_string(),
+                })
+                .await
+                .unwrap();
+            let workflow = repository.get_workflow(project.id).await.unwrap();
+            assert_eq!(workflow.states, default_workflow());
+
+            let workflow = repository
+                .update_workflow(project.id, support.clone())
+                .await
+                .unwrap();
+            assert_eq!(workflow.states, support.states);
+            assert!(matches!(
+                repository.get_workflow(Uuid::new_v4()).await,
+                Err(AppError::ProjectNotFound)
+            ));
+
+            // New tasks start in the first state
+            let mut tasks = Vec::new();
+            for title in ["Crash on login", "Stack trace"] {
+                let task = repository
+                    .create_task(
+                        CreateTaskRequest {
+                            title: title.to_string(),
+                            project_id: Some(project.id),
+                            parent_id: tasks.first().map(|task: &common::Task| task.id),
+                            ..Default::default()
+                        },
+                        &WriteContext::default(),
+                    )
+                    .await
+                    .unwrap();
+                tasks.push(task);
+            }
+            assert_eq!(tasks[0].status, TaskStatus::new("Triage"));
+            assert_eq!(tasks[0].status_category, StatusCategory::Todo);
+            let dependent = repository
+                .create_task(
+                    CreateTaskRequest {
+                        title: "Release notes".to_string(),
+                        project_id: Some(project.id),
+                        ..Default::default()
+                    },
+                    &WriteContext::default(),
+                )
+                .await
+                .unwrap();
+            repository
+                .add_dependency(dependent.id, tasks[1].id)
+                .await
+                .unwrap();
+
+            // Statuses outside the workflow are refused
+            assert!(matches!(
+                repository
+                    .update_task(tasks[0].id, move_to("Completed"), &WriteContext::default())
+                    .await,
+                Err(AppError::InvalidInput(_))
+            ));
+
+            // Any state in the Done category completes a task
+            let subtask = repository
+                .update_task(tasks[1].id, move_to("Shipped"), &WriteContext::default())
+                .await
+                .unwrap();
+            assert_eq!(subtask.status_category, StatusCategory::Done);
+            let parent = repository.get_task(tasks[0].id).await.unwrap();
+            assert_eq!(parent.completed_subtask_count, 1);
+            let dependent = repository.get_task(dependent.id).await.unwrap();
+            assert!(!dependent.is_blocked);
+
+            // A state that still holds tasks cannot be removed
+            let mut without_triage = support.clone();
+            without_triage.states.remove(0);
+            assert!(matches!(
+                repository.update_workflow(project.id, without_triage).await,
+                Err(AppError::Conflict(_))
+            ));
+
+            // Moving projects keeps the state's category
+            let dependent = repository
+                .update_task(dependent.id, move_to("Fixing"), &WriteContext::default())
+                .await
+                .unwrap();
+            assert_eq!(dependent.status_category, StatusCategory::Doing);
+            let dependent = repository
+                .update_task(
+                    dependent.id,
+                    UpdateTaskRequest {
+                        project_id: Patch::Value(DEFAULT_PROJECT_ID),
+                        ..Default::default()
+                    },
+                    &WriteContext::default(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(dependent.status, TaskStatus::new("InProgress"));
+            assert_eq!(dependent.status_category, StatusCategory::Doing);
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_wip_limits_refuse_moves_into_full_columns() {
+        let repositories = repositories().await;
+
+        let mut states = default_workflow();
+        states[1].wip_limit = Some(1);
+        let kanban = WorkflowRequest {
+            states,
+            ..Default::default()
+        };
+        let move_to = |status: &str| MoveTaskRequest {
+            status: TaskStatus::new(status),
+            after_id: None,
+            before_id: None,
+        };
+        for repository in repositories {
+            let project = repository
+                .create_project(ProjectRequest {
+                    name: "Kanban".to_string(),
+                })
+                .await
+                .unwrap();
+            repository
+                .update_workflow(project.id, kanban.clone())
+                .await
+                .unwrap();
+            let mut ids = Vec::new();
+            for title in ["Doing", "Waiting", "Later"] {
+                let task = repository
+                    .create_task(
+                        CreateTaskRequest {
+                            title: title.to_string(),
+                            project_id: Some(project.id),
+                            ..Default::default()
+                        },
+                        &WriteContext::default(),
+                    )
+                    .await
+                    .unwrap();
+                ids.push(task.id);
+            }
+            let in_progress = TaskStatus::new("InProgress");
+
+            // The first move takes the only place, and the column is full
+            repository
+                .move_task(ids[0], move_to("InProgress"), &WriteContext::default())
+                .await
+                .unwrap();
+            assert!(matches!(
+                repository
+                    .move_task(ids[1], move_to("InProgress"), &WriteContext::default())
+                    .await,
+                Err(AppError::Conflict(_))
+            ));
+            assert!(matches!(
+                repository
+                    .update_task(
+                        ids[1],
+                        UpdateTaskRequest {
+                            status: Patch::Value(in_progress.clone()),
+                            ..Default::default()
+                        },
+                        &WriteContext::default(),
+                    )
+                    .await,
+                Err(AppError::Conflict(_))
+            ));
+
+            // Reordering within the full column is not a move into it
+            repository
+                .move_task(ids[0], move_to("InProgress"), &WriteContext::default())
+                .await
+                .unwrap();
+
+            // In warn mode the move goes ahead over the limit
+            let warn = WriteContext {
+                wip_limits: WipLimits::Warn,
+                ..Default::default()
+            };
+            repository
+                .move_task(ids[1], move_to("InProgress"), &warn)
+                .await
+                .unwrap();
+            assert_eq!(
+                repository
+                    .count_in_state(project.id, &in_progress)
+                    .await
+                    .unwrap(),
+                2
+            );
+
+            // Trashed tasks do not take a place
+            for id in &ids[..2] {
+                repository
+                    .delete_task(*id, &WriteContext::default())
+                    .await
+                    .unwrap();
+            }
+            assert_eq!(
+                repository
+                    .count_in_state(project.id, &in_progress)
+                    .await
+                    .unwrap(),
+                0
+            );
+            repository
+                .move_task(ids[2], move_to("InProgress"), &WriteContext::default())
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_workflow_rules_refuse_moves() {
+        let repositories = repositories().await;
+
+        // Todo only leads to InProgress, which needs a description, and
+        // urgent tasks need a due date
+        let mut states = default_workflow();
+        states[0].next = Some(vec![TaskStatus::new("InProgress")]);
+        states[1].requires = vec![TaskField::Description];
+        let guarded = WorkflowRequest {
+            states,
+            priority_rules: vec![PriorityRule {
+                priority: TaskPriority::Urgent,
+                requires: vec![TaskField::DueDate],
+            }],
+        };
+        let update = |status: Option<&str>, description: Option<&str>| UpdateTaskRequest {
+            status: status.map_or(Patch::Absent, |status| {
+                Patch::Value(TaskStatus::new(status))
+            }),
+            description: description.map_or(Patch::Absent, |text| Patch::Value(text.to_string())),
+            ..Default::default()
+        };
+        for repository in repositories {
+            let project = repository
+                .create_project(ProjectRequest {
+                    name: "Guarded".to_string(),
+                })
+                .await
+                .unwrap();
+            let workflow = repository
+                .update_workflow(project.id, guarded.clone())
+                .await
+                .unwrap();
+            assert_eq!(workflow.priority_rules, guarded.priority_rules);
+            assert_eq!(workflow.states[0].next, guarded.states[0].next);
+            assert_eq!(workflow.states[1].requires, vec![TaskField::Description]);
+
+            let new_task = |priority| CreateTaskRequest {
+                title: "Outage".to_string(),
+                priority,
+                project_id: Some(project.id),
+                ..Default::default()
+            };
+            assert!(matches!(
+                repository
+                    .create_task(new_task(TaskPriority::Urgent), &WriteContext::default())
+                    .await,
+                Err(AppError::Transition(
+                    TransitionError::PriorityRequiresFields { .. }
+                ))
+            ));
+            let task = repository
+                .create_task(new_task(TaskPriority::High), &WriteContext::default())
+                .await
+                .unwrap();
+
+            // Skipping a state is refused, naming the moves there are
+            match repository
+                .update_task(
+                    task.id,
+                    update(Some("Completed"), None),
+                    &WriteContext::default(),
+                )
+                .await
+            {
+                Err(AppError::Transition(TransitionError::TransitionNotAllowed {
+                    allowed,
+                    ..
+                })) => assert_eq!(allowed, vec![TaskStatus::new("InProgress")]),
+                other => panic!("expected a refused transition, got {other:?}"),
+            }
+
+            // The guard looks at the task as the update leaves it
+            match repository
+                .update_task(
+                    task.id,
+                    update(Some("InProgress"), Some("  ")),
+                    &WriteContext::default(),
+                )
+                .await
+            {
+                Err(AppError::Transition(TransitionError::StateRequiresFields {
+                    missing, ..
+                })) => assert_eq!(missing, vec![TaskField::Description]),
+                other => panic!("expected a guarded state, got {other:?}"),
+            }
+            let task = repository
+                .update_task(
+                    task.id,
+                    update(Some("InProgress"), Some("Users cannot log in")),
+                    &WriteContext::default(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(task.status, TaskStatus::new("InProgress"));
+
+            // States without a next list lead anywhere, by move or update
+            for status in ["Backlog", "InProgress"] {
+                repository
+                    .move_task(
+                        task.id,
+                        MoveTaskRequest {
+                            status: TaskStatus::new(status),
+                            after_id: None,
+                            before_id: None,
+                        },
+                        &WriteContext::default(),
+                    )
+                    .await
+                    .unwrap();
+            }
+
+            let urgent = |due_date| UpdateTaskRequest {
+                priority: Patch::Value(TaskPriority::Urgent),
+                due_date,
+                ..Default::default()
+            };
+            assert!(matches!(
+                repository
+                    .update_task(task.id, urgent(Patch::Absent), &WriteContext::default())
+                    .await,
+                Err(AppError::Transition(
+                    TransitionError::PriorityRequiresFields { .. }
+                ))
+            ));
+            let task = repository
+                .update_task(
+                    task.id,
+                    urgent(Patch::Value(Utc::now())),
+                    &WriteContext::default(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(task.priority, TaskPriority::Urgent);
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_moves_keep_column_order() {
+        let repositories = repositories().await;
+
+        for repository in repositories {
+            let mut ids = Vec::new();
+            for title in ["First", "Last", "A", "B", "C", "D"] {
+                let task = repository
+                    .create_task(
+                        CreateTaskRequest {
+                            title: title.to_string(),
+                            description: None,
+                            priority: TaskPriority::Medium,
+                            due_date: None,
+                            ..Default::default()
+                        },
+                        &WriteContext::default(),
+                    )
+                    .await
+                    .unwrap();
+                ids.push(task.id);
+            }
+            let (first, last) = (ids[0], ids[1]);
+
+            // Moves and status changes without a position go to the bottom
+            for id in &ids[2..] {
+                repository
+                    .move_task(
+                        *id,
+                        MoveTaskRequest {
+                            status: TaskStatus::new("InProgress"),
+                            after_id: None,
+                            before_id: None,
+                        },
+                        &WriteContext::default(),
+                    )
+                    .await
+                    .unwrap();
+            }
+            for id in [first, last] {
+                repository
+                    .update_task(
+                        id,
+                        UpdateTaskRequest {
+                            status: Patch::Value(TaskStatus::new("InProgress")),
+                            ..Default::default()
+                        },
+                        &WriteContext::default(),
+                    )
+                    .await
+                    .unwrap();
+            }
+            repository
+                .move_task(
+                    first,
+                    MoveTaskRequest {
+                        status: TaskStatus::new("InProgress"),
+                        after_id: None,
+                        before_id: Some(ids[2]),
+                    },
+                    &WriteContext::default(),
+                )
+                .await
+                .unwrap();
+
+            // Each card dropped just below "First" pushes the others down
+            for id in &ids[2..] {
+                repository
+                    .move_task(
+                        *id,
+                        MoveTaskRequest {
+                            status: TaskStatus::new("InProgress"),
+                            after_id: Some(first),
+                            before_id: None,
+                        },
+                        &WriteContext::default(),
+                    )
+                    .await
+                    .unwrap();
+            }
+
+            let filter = TaskFilter {
+                status: vec![TaskStatus::new("InProgress")],
+                ..Default::default()
+            };
+            let mut column = repository
+                .list_tasks(&filter, TaskSort::default(), None, 10)
+                .await
+                .unwrap()
+                .items;
+            column.sort_by(|a, b| a.rank.cmp(&b.rank).then(a.id.cmp(&b.id)));
+            let titles: Vec<&str> = column.iter().map(|task| task.title.as_str()).collect();
+            assert_eq!(titles, ["First", "D", "C", "B", "A", "Last"]);
+            assert!(column.windows(2).all(|pair| pair[0].rank < pair[1].rank));
+
+            // A trashed neighbour no longer holds a place
+            repository
+                .delete_task(last, &WriteContext::default())
+                .await
+                .unwrap();
+            assert!(matches!(
+                repository
+                    .move_task(
+                        first,
+                        MoveTaskRequest {
+                            status: TaskStatus::new("InProgress"),
+                            after_id: Some(last),
+                            before_id: None,
+                        },
+                        &WriteContext::default(),
+                    )
+                    .await,
+                Err(AppError::Conflict(_))
+            ));
+            assert!(matches!(
+                repository
+                    .move_task(
+                        first,
+                        MoveTaskRequest {
+                            status: TaskStatus::new("Todo"),
+                            after_id: None,
+                            before_id: Some(first),
+                        },
+                        &WriteContext::default(),
+                    )
+                    .await,
+                Err(AppError::InvalidInput(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_time_tracking_totals_and_timers() {
+        let repositories = repositories().await;
+
+        for repository in repositories {
+            let member = repository
+                .create_member(MemberRequest {
+                    name: "Sarah".to_string(),
+                })
+                .await
+                .unwrap();
+            let mut tasks = Vec::new();
+            for title in ["Invoice export", "Client call"] {
+                let task = repository
+                    .create_task(
+                        CreateTaskRequest {
+                            title: title.to_string(),
+                            estimate_minutes: Some(120),
+                            ..Default::default()
+                        },
+                        &WriteContext::default(),
+                    )
+                    .await
+                    .unwrap();
+                tasks.push(task);
+            }
+            let (first, second) = (&tasks[0], &tasks[1]);
+            assert_eq!(first.estimate_minutes, Some(120));
+            assert_eq!(first.logged_minutes, 0);
+
+            // Manual entries add up, and editing one changes the total
+            let started_at = Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+            let entry = repository
+                .create_time_entry(
+                    first.id,
+                    TimeEntryRequest {
+                        member_id: member.id,
+                        started_at,
+                        ended_at: started_at + Duration::minutes(45),
+                        note: Some("Draft".to_string()),
+                    },
+                )
+                .await
+                .unwrap();
+            repository
+                .create_time_entry(
+                    first.id,
+                    TimeEntryRequest {
+                        member_id: member.id,
+                        started_at: started_at + Duration::hours(2),
+                        ended_at: started_at + Duration::hours(3),
+                        note: None,
+                    },
+                )
+                .await
+                .unwrap();
+            assert_eq!(
+                repository.get_task(first.id).await.unwrap().logged_minutes,
+                105
+            );
+            repository
+                .update_time_entry(
+                    first.id,
+                    entry.id,
+                    TimeEntryRequest {
+                        member_id: member.id,
+                        started_at,
+                        ended_at: started_at + Duration::minutes(30),
+                        note: None,
+                    },
+                )
+                .await
+                .unwrap();
+            let task = repository.get_task(first.id).await.unwrap();
+            assert_eq!(task.logged_minutes, 90);
+            assert_eq!(task.version, first.version);
+            assert!(matches!(
+                repository.delete_time_entry(second.id, entry.id).await,
+                Err(AppError::TimeEntryNotFound)
+            ));
+
+            // Starting a timer on another task stops the one running
+            repository.start_timer(first.id, member.id).await.unwrap();
+            assert!(matches!(
+                repository.start_timer(first.id, member.id).await,
+                Err(AppError::Conflict(_))
+            ));
+            assert!(repository
+                .get_task(first.id)
+                .await
+                .unwrap()
+                .timer_started_at
+                .is_some());
+            repository.start_timer(second.id, member.id).await.unwrap();
+            assert_eq!(
+                repository
+                    .get_task(first.id)
+                    .await
+                    .unwrap()
+                    .timer_started_at,
+                None
+            );
+            assert!(matches!(
+                repository.stop_timer(first.id, member.id).await,
+                Err(AppError::Conflict(_))
+            ));
+            let stopped = repository.stop_timer(second.id, member.id).await.unwrap();
+            assert!(stopped.ended_at.is_some());
+            assert_eq!(
+                repository
+                    .get_task(second.id)
+                    .await
+                    .unwrap()
+                    .timer_started_at,
+                None
+            );
+
+            // Deleting the task stops its timer; deleting the member keeps
+            // the entries without them
+            repository.start_timer(second.id, member.id).await.unwrap();
+            repository
+                .delete_task(second.id, &WriteContext::default())
+                .await
+                .unwrap();
+            repository.start_timer(first.id, member.id).await.unwrap();
+            repository.delete_member(member.id).await.unwrap();
+            let entries = repository.list_time_entries(first.id).await.unwrap();
+            assert_eq!(entries.len(), 4);
+            assert!(entries
+                .iter()
+                .all(|entry| entry.member_id.is_none() && entry.ended_at.is_some()));
+            assert_eq!(entries[0].started_at, started_at);
+
+            // Clearing the estimate
+            let update = UpdateTaskRequest {
+                estimate_minutes: Patch::Null,
+                ..Default::default()
+            };
+            let task = repository
+                .update_task(first.id, update, &WriteContext::default())
+                .await
+                .unwrap();
+            assert_eq!(task.estimate_minutes, None);
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_purges_report_blobs_left_unused() {
+        let repositories = repositories().await;
+
+        for repository in repositories {
+            let mut ids = Vec::new();
+            for title in ["Crash report", "Duplicate report"] {
+                let task = repository
+                    .create_task(
+                        CreateTaskRequest {
+                            title: title.to_string(),
+                            ..Default::default()
+                        },
+                        &WriteContext::default(),
+                    )
+                    .await
+                    .unwrap();
+                ids.push(task.id);
+            }
+            let attach = |task_id: Uuid, filename: &str, sha256: &str| {
+                repository.create_attachment(
+                    task_id,
+                    NewAttachment {
+                        filename: filename.to_string(),
+                        content_type: "text/plain; charset=utf-8".to_string(),
+                        size_bytes: 12,
+                        sha256: sha256.repeat(64),
+                    },
+                )
+            };
+            let log = attach(ids[0], "crash.log", "a").await.unwrap();
+            attach(ids[0], "trace.txt", "b").await.unwrap();
+            attach(ids[1], "crash.log", "a").await.unwrap();
+            let shot = attach(ids[1], "screen.png", "c").await.unwrap();
+
+            let attachments = repository.list_attachments(ids[0]).await.unwrap();
+            assert_eq!(attachments.len(), 2);
+            assert_eq!(attachments[0], log);
+            assert_eq!(
+                repository.get_attachment(ids[0], log.id).await.unwrap(),
+                log
+            );
+            assert!(matches!(
+                repository.get_attachment(ids[1], log.id).await,
+                Err(AppError::AttachmentNotFound)
+            ));
+
+            // Deleting hands back the blob only once nothing else uses it
+            assert_eq!(
+                repository.delete_attachment(ids[1], shot.id).await.unwrap(),
+                Some("c".repeat(64))
+            );
+
+            // Trashed tasks keep their attachments until purged
+            for id in &ids {
+                repository
+                    .delete_task(*id, &WriteContext::default())
+                    .await
+                    .unwrap();
+            }
+            assert!(matches!(
+                repository.list_attachments(ids[0]).await,
+                Err(AppError::TaskNotFound)
+            ));
+            let purged = repository.purge_task(ids[0]).await.unwrap();
+            assert_eq!(purged.tasks, 1);
+            assert_eq!(purged.blobs, ["b".repeat(64)]);
+
+            let purged = repository
+                .purge_trash(Utc::now() + chrono::Duration::seconds(1))
+                .await
+                .unwrap();
+            assert_eq!(purged.tasks, 1);
+            assert_eq!(purged.blobs, ["a".repeat(64)]);
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_custom_fields_are_checked_and_filterable() {
+        let repositories = repositories().await;
+
+        for repository in repositories {
+            let project = repository
+                .create_project(ProjectRequest {
+                    name: "Support".to_string(),
+                })
+                .await
+                .unwrap();
+            let field = |name: &str, field_type, options: &[&str]| CustomFieldRequest {
+                name: name.to_string(),
+                field_type,
+                options: options.iter().map(|option| option.to_string()).collect(),
+            };
+            let severity = repository
+                .create_custom_field(
+                    DEFAULT_PROJECT_ID,
+                    field("Severity", CustomFieldType::SingleSelect, &["S1", "S2"]),
+                )
+                .await
+                .unwrap();
+            let points = repository
+                .create_custom_field(
+                    DEFAULT_PROJECT_ID,
+                    field("Story points", CustomFieldType::Number, &[]),
+                )
+                .await
+                .unwrap();
+            let components = repository
+                .create_custom_field(
+                    DEFAULT_PROJECT_ID,
+                    field("Components", CustomFieldType::MultiSelect, &["API", "UI"]),
+                )
+                .await
+                .unwrap();
+            assert!(matches!(
+                repository
+                    .create_custom_field(
+                        DEFAULT_PROJECT_ID,
+                        field("Severity", CustomFieldType::Text, &[]),
+                    )
+                    .await,
+                Err(AppError::Conflict(_))
+            ));
+            // Names are unique per project only
+            repository
+                .create_custom_field(project.id, field("Severity", CustomFieldType::Text, &[]))
+                .await
+                .unwrap();
+            let fields = repository
+                .list_custom_fields(DEFAULT_PROJECT_ID)
+                .await
+                .unwrap();
+            assert_eq!(
+                fields,
+                [severity.clone(), points.clone(), components.clone()]
+            );
+
+            // Values are stored in their canonical form, unset ones dropped
+            let task = repository
+                .create_task(
+                    CreateTaskRequest {
+                        title: "Login fails".to_string(),
+                        custom_fields: [
+                            (severity.id, serde_json::json!("S1")),
+                            (points.id, serde_json::json!(3.0)),
+                            (components.id, serde_json::json!(["UI", "API"])),
+                        ]
+                        .into(),
+                        ..Default::default()
+                    },
+                    &WriteContext::default(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(task.custom_fields[&points.id], serde_json::json!(3));
+            assert_eq!(
+                task.custom_fields[&components.id],
+                serde_json::json!(["API", "UI"])
+            );
+            let other = repository
+                .create_task(
+                    CreateTaskRequest {
+                        title: "Typo on pricing page".to_string(),
+                        custom_fields: [
+                            (severity.id, serde_json::json!("S2")),
+                            (points.id, serde_json::json!("")),
+                        ]
+                        .into(),
+                        ..Default::default()
+                    },
+                    &WriteContext::default(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(other.custom_fields.len(), 1);
+            let bad_values = [
+                (severity.id, serde_json::json!("S9")),
+                (points.id, serde_json::json!("three")),
+                (Uuid::new_v4(), serde_json::json!("x")),
+            ];
+            for (id, value) in bad_values {
+                let request = CreateTaskRequest {
+                    title: "Bad".to_string(),
+                    custom_fields: [(id, value)].into(),
+                    ..Default::default()
+                };
+                assert!(matches!(
+                    repository
+                        .create_task(request, &WriteContext::default())
+                        .await,
+                    Err(AppError::InvalidInput(_))
+                ));
+            }
+
+            // Filters match a value, or one of a multi-select's options
+            let matching = |field_id, value: serde_json::Value| TaskFilter {
+                custom_fields: vec![CustomFieldFilter { field_id, value }],
+                ..Default::default()
+            };
+            let found = list_all(&*repository, matching(severity.id, "S2".into()))
+                .await
+                .unwrap();
+            assert_eq!(found.len(), 1);
+            assert_eq!(found[0].id, other.id);
+            for (field_id, value) in [
+                (components.id, serde_json::json!("API")),
+                (points.id, serde_json::json!(3)),
+            ] {
+                let found = list_all(&*repository, matching(field_id, value))
+                    .await
+                    .unwrap();
+                assert_eq!(found.len(), 1);
+                assert_eq!(found[0].id, task.id);
+            }
+            assert!(list_all(&*repository, matching(points.id, 4.into()))
+                .await
+                .unwrap()
+                .is_empty());
+
+            // A field's type is fixed, and an option in use stays
+            assert!(matches!(
+                repository
+                    .update_custom_field(
+                        DEFAULT_PROJECT_ID,
+                        severity.id,
+                        field("Severity", CustomFieldType::Text, &[]),
+                    )
+                    .await,
+                Err(AppError::InvalidInput(_))
+            ));
+            assert!(matches!(
+                repository
+                    .update_custom_field(
+                        DEFAULT_PROJECT_ID,
+                        components.id,
+                        field("Components", CustomFieldType::MultiSelect, &["UI"]),
+                    )
+                    .await,
+                Err(AppError::Conflict(_))
+            ));
+            let renamed = repository
+                .update_custom_field(
+                    DEFAULT_PROJECT_ID,
+                    severity.id,
+                    field("Impact", CustomFieldType::SingleSelect, &["S1", "S2", "S3"]),
+                )
+                .await
+                .unwrap();
+            assert_eq!(renamed.name, "Impact");
+            assert!(matches!(
+                repository
+                    .update_custom_field(
+                        project.id,
+                        severity.id,
+                        field("Impact", CustomFieldType::SingleSelect, &["S1"]),
+                    )
+                    .await,
+                Err(AppError::CustomFieldNotFound)
+            ));
+
+            // An update replaces every value, recording the change
+            let update = UpdateTaskRequest {
+                custom_fields: Patch::Value([(points.id, serde_json::json!(5))].into()),
+                ..Default::default()
+            };
+            let updated = repository
+                .update_task(task.id, update, &WriteContext::default())
+                .await
+                .unwrap();
+            assert_eq!(
+                updated.custom_fields,
+                [(points.id, serde_json::json!(5))].into()
+            );
+            let history = repository.task_history(task.id).await.unwrap();
+            assert!(history
+                .iter()
+                .any(|event| event.field.as_deref() == Some("custom_fields")));
+
+            // Deleting a field removes its values
+            repository
+                .delete_custom_field(DEFAULT_PROJECT_ID, points.id)
+                .await
+                .unwrap();
+            assert!(repository
+                .get_task(task.id)
+                .await
+                .unwrap()
+                .custom_fields
+                .is_empty());
+            assert!(matches!(
+                repository.get_custom_field(points.id).await,
+                Err(AppError::CustomFieldNotFound)
+            ));
+
+            // Moving to another project keeps only the values it has fields for
+            let update = UpdateTaskRequest {
+                project_id: Patch::Value(project.id),
+                ..Default::default()
+            };
+            let moved = repository
+                .update_task(other.id, update, &WriteContext::default())
+                .await
+                .unwrap();
+            assert!(moved.custom_fields.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_saved_views_round_trip_and_follow_their_project() {
+        let repositories = repositories().await;
+
+        for repository in repositories {
+            let project = repository
+                .create_project(ProjectRequest {
+                    name: "Support".to_string(),
+                })
+                .await
+                .unwrap();
+            let request = SavedViewRequest {
+                name: "Urgent bugs".to_string(),
+                filter: TaskFilter {
+                    priority: vec![TaskPriority::Urgent],
+                    any_tag: vec!["bug".to_string()],
+                    project: Some(project.id),
+                    ..Default::default()
+                },
+                sort: Some("-due_date".parse().unwrap()),
+                grouping: ViewGrouping::Assignee,
+                columns: vec![TaskStatus::new("Todo"), TaskStatus::new("InProgress")],
+            };
+            let urgent = repository.create_saved_view(request.clone()).await.unwrap();
+            assert_eq!(urgent.filter, request.filter);
+            assert_eq!(urgent.sort, request.sort);
+            assert_eq!(urgent.grouping, ViewGrouping::Assignee);
+            assert_eq!(urgent.columns, request.columns);
+            assert_eq!(repository.get_saved_view(urgent.id).await.unwrap(), urgent);
+
+            let everything = repository
+                .create_saved_view(SavedViewRequest {
+                    name: "Everything".to_string(),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            assert!(matches!(
+                repository.create_saved_view(request.clone()).await,
+                Err(AppError::Conflict(_))
+            ));
+            let elsewhere = SavedViewRequest {
+                name: "Elsewhere".to_string(),
+                filter: TaskFilter {
+                    project: Some(Uuid::new_v4()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            assert!(matches!(
+                repository.create_saved_view(elsewhere.clone()).await,
+                Err(AppError::InvalidInput(_))
+            ));
+            assert!(matches!(
+                repository.update_saved_view(everything.id, elsewhere).await,
+                Err(AppError::InvalidInput(_))
+            ));
+
+            let renamed = repository
+                .update_saved_view(
+                    everything.id,
+                    SavedViewRequest {
+                        name: "All work".to_string(),
+                        ..Default::default()
+                    },
+                )
+                .await
+                .unwrap();
+            assert_eq!(renamed.created_at, everything.created_at);
+            assert!(renamed.updated_at >= everything.updated_at);
+            assert!(matches!(
+                repository
+                    .update_saved_view(everything.id, request.clone())
+                    .await,
+                Err(AppError::Conflict(_))
+            ));
+            let names: Vec<String> = repository
+                .list_saved_views()
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|view| view.name)
+                .collect();
+            assert_eq!(names, ["All work", "Urgent bugs"]);
+
+            // A view of a project's board goes with the project
+            repository.delete_project(project.id).await.unwrap();
+            assert!(matches!(
+                repository.get_saved_view(urgent.id).await,
+                Err(AppError::SavedViewNotFound)
+            ));
+            repository.delete_saved_view(everything.id).await.unwrap();
+            assert!(matches!(
+                repository.delete_saved_view(everything.id).await,
+                Err(AppError::SavedViewNotFound)
+            ));
+            assert!(repository.list_saved_views().await.unwrap().is_empty());
+        }
+    }
 }
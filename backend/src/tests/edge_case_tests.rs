@@ -5,8 +5,10 @@
 
 use crate::database::Database;
 use crate::error::AppError;
+use crate::repository::WriteContext;
+use crate::tests::list_all;
 use chrono::Utc;
-use common::{CreateTaskRequest, TaskFilter, TaskPriority, TaskStatus, UpdateTaskRequest};
+use common::{CreateTaskRequest, Patch, TaskFilter, TaskPriority, TaskStatus, UpdateTaskRequest};
 use serial_test::serial;
 use sqlx::PgPool;
 use uuid::Uuid;
@@ -30,8 +32,7 @@ async fn test_edge_case_empty_task_operations() {
     let database = create_test_database().await;
 
     // Test retrieving tasks when database is empty (or nearly empty)
-    let tasks = database
-        .get_tasks(None)
+    let tasks = list_all(&database, TaskFilter::default())
         .await
         .expect("Getting tasks from empty database should succeed");
 
@@ -60,18 +61,20 @@ async fn test_edge_case_invalid_uuid_operations() {
     // Test updating a non-existent task
     let non_existent_id = Uuid::new_v4();
     let update_request = UpdateTaskRequest {
-        title: Some("Updated Title".to_string()),
-        description: None,
-        status: Some(TaskStatus::Completed),
-        priority: None,
-        due_date: None,
+        title: Patch::Value("Updated Title".to_string()),
+        status: Patch::Value(TaskStatus::new("Completed")),
+        ..Default::default()
     };
 
-    let result = database.update_task(non_existent_id, update_request).await;
+    let result = database
+        .update_task(non_existent_id, update_request, &WriteContext::default())
+        .await;
     assert!(result.is_err(), "Updating non-existent task should fail");
 
     // Test deleting a non-existent task
-    let delete_result = database.delete_task(non_existent_id).await;
+    let delete_result = database
+        .delete_task(non_existent_id, &WriteContext::default())
+        .await;
     assert!(
         delete_result.is_err(),
         "Delete should fail for non-existent task"
@@ -88,10 +91,11 @@ async fn test_edge_case_task_title_boundaries() {
         description: Some("Test description".to_string()),
         priority: TaskPriority::Low,
         due_date: None,
+        ..Default::default()
     };
 
     let min_title_task = database
-        .create_task(min_title_request)
+        .create_task(min_title_request, &WriteContext::default())
         .await
         .expect("Creating task with minimum title should succeed");
     assert_eq!(min_title_task.title, "A");
@@ -103,10 +107,11 @@ async fn test_edge_case_task_title_boundaries() {
         description: Some("Test description".to_string()),
         priority: TaskPriority::High,
         due_date: None,
+        ..Default::default()
     };
 
     let long_title_task = database
-        .create_task(long_title_request)
+        .create_task(long_title_request, &WriteContext::default())
         .await
         .expect("Creating task with long title should succeed");
     assert_eq!(long_title_task.title, long_title);
@@ -118,18 +123,25 @@ async fn test_edge_case_task_title_boundaries() {
         description: Some("Test description".to_string()),
         priority: TaskPriority::Medium,
         due_date: None,
+        ..Default::default()
     };
 
     let special_task = database
-        .create_task(special_request)
+        .create_task(special_request, &WriteContext::default())
         .await
         .expect("Creating task with special characters should succeed");
     assert_eq!(special_task.title, special_title);
 
     // Cleanup
-    let _ = database.delete_task(min_title_task.id).await;
-    let _ = database.delete_task(long_title_task.id).await;
-    let _ = database.delete_task(special_task.id).await;
+    let _ = database
+        .delete_task(min_title_task.id, &WriteContext::default())
+        .await;
+    let _ = database
+        .delete_task(long_title_task.id, &WriteContext::default())
+        .await;
+    let _ = database
+        .delete_task(special_task.id, &WriteContext::default())
+        .await;
 }
 
 #[tokio::test]
@@ -142,10 +154,11 @@ async fn test_edge_case_task_description_boundaries() {
         description: None,
         priority: TaskPriority::Low,
         due_date: None,
+        ..Default::default()
     };
 
     let no_desc_task = database
-        .create_task(no_desc_request)
+        .create_task(no_desc_request, &WriteContext::default())
         .await
         .expect("Creating task without description should succeed");
     assert!(no_desc_task.description.is_none());
@@ -156,10 +169,11 @@ async fn test_edge_case_task_description_boundaries() {
         description: Some("".to_string()),
         priority: TaskPriority::Medium,
         due_date: None,
+        ..Default::default()
     };
 
     let empty_desc_task = database
-        .create_task(empty_desc_request)
+        .create_task(empty_desc_request, &WriteContext::default())
         .await
         .expect("Creating task with empty description should succeed");
     assert_eq!(empty_desc_task.description, Some("".to_string()));
@@ -171,18 +185,25 @@ async fn test_edge_case_task_description_boundaries() {
         description: Some(long_description.clone()),
         priority: TaskPriority::High,
         due_date: None,
+        ..Default::default()
     };
 
     let long_desc_task = database
-        .create_task(long_desc_request)
+        .create_task(long_desc_request, &WriteContext::default())
         .await
         .expect("Creating task with long description should succeed");
     assert_eq!(long_desc_task.description, Some(long_description));
 
     // Cleanup
-    let _ = database.delete_task(no_desc_task.id).await;
-    let _ = database.delete_task(empty_desc_task.id).await;
-    let _ = database.delete_task(long_desc_task.id).await;
+    let _ = database
+        .delete_task(no_desc_task.id, &WriteContext::default())
+        .await;
+    let _ = database
+        .delete_task(empty_desc_task.id, &WriteContext::default())
+        .await;
+    let _ = database
+        .delete_task(long_desc_task.id, &WriteContext::default())
+        .await;
 }
 
 #[tokio::test]
@@ -204,10 +225,11 @@ async fn test_edge_case_priority_enum_coverage() {
             description: Some(format!("Testing priority: {priority:?}")),
             priority: *priority,
             due_date: None,
+            ..Default::default()
         };
 
         let task = database
-            .create_task(request)
+            .create_task(request, &WriteContext::default())
             .await
             .expect("Creating task with all priorities should succeed");
         assert_eq!(task.priority, *priority);
@@ -217,13 +239,13 @@ async fn test_edge_case_priority_enum_coverage() {
     // Test filtering by each priority
     for priority in &priorities {
         let filter = TaskFilter {
-            status: None,
-            priority: Some(*priority),
+            status: Vec::new(),
+            priority: vec![*priority],
             due_before: None,
             due_after: None,
+            ..Default::default()
         };
-        let filtered_tasks = database
-            .get_tasks(Some(filter))
+        let filtered_tasks = list_all(&database, filter)
             .await
             .expect("Filtering by priority should succeed");
 
@@ -244,7 +266,9 @@ async fn test_edge_case_priority_enum_coverage() {
 
     // Cleanup
     for task_id in task_ids {
-        let _ = database.delete_task(task_id).await;
+        let _ = database
+            .delete_task(task_id, &WriteContext::default())
+            .await;
     }
 }
 
@@ -258,29 +282,29 @@ async fn test_edge_case_status_enum_coverage() {
         description: Some("Testing status transitions".to_string()),
         priority: TaskPriority::Medium,
         due_date: None,
+        ..Default::default()
     };
 
     let mut task = database
-        .create_task(request)
+        .create_task(request, &WriteContext::default())
         .await
         .expect("Creating task should succeed");
-    assert_eq!(task.status, TaskStatus::Todo);
+    assert_eq!(task.status, TaskStatus::new("Todo"));
 
     // Test transition to InProgress
     let in_progress_update = UpdateTaskRequest {
-        title: None,
-        description: None,
-        status: Some(TaskStatus::InProgress),
-        priority: None,
-        due_date: None,
+        status: Patch::Value(TaskStatus::new("InProgress")),
+        ..Default::default()
     };
 
-    let update_result = database.update_task(task.id, in_progress_update).await;
+    let update_result = database
+        .update_task(task.id, in_progress_update, &WriteContext::default())
+        .await;
 
     // Handle concurrent test interference gracefully
     task = match update_result {
         Ok(updated_task) => {
-            assert_eq!(updated_task.status, TaskStatus::InProgress);
+            assert_eq!(updated_task.status, TaskStatus::new("InProgress"));
             updated_task
         }
         Err(_) => {
@@ -291,9 +315,10 @@ async fn test_edge_case_status_enum_coverage() {
                 description: Some("Testing status transitions".to_string()),
                 priority: TaskPriority::Medium,
                 due_date: None,
+                ..Default::default()
             };
             database
-                .create_task(new_request)
+                .create_task(new_request, &WriteContext::default())
                 .await
                 .expect("Creating replacement task should succeed")
         }
@@ -301,49 +326,43 @@ async fn test_edge_case_status_enum_coverage() {
 
     // Test transition to Completed
     let completed_update = UpdateTaskRequest {
-        title: None,
-        description: None,
-        status: Some(TaskStatus::Completed),
-        priority: None,
-        due_date: None,
+        status: Patch::Value(TaskStatus::new("Completed")),
+        ..Default::default()
     };
 
     task = database
-        .update_task(task.id, completed_update)
+        .update_task(task.id, completed_update, &WriteContext::default())
         .await
         .expect("Updating to Completed should succeed");
-    assert_eq!(task.status, TaskStatus::Completed);
+    assert_eq!(task.status, TaskStatus::new("Completed"));
 
     // Test transition back to Todo
     let todo_update = UpdateTaskRequest {
-        title: None,
-        description: None,
-        status: Some(TaskStatus::Todo),
-        priority: None,
-        due_date: None,
+        status: Patch::Value(TaskStatus::new("Todo")),
+        ..Default::default()
     };
 
     task = database
-        .update_task(task.id, todo_update)
+        .update_task(task.id, todo_update, &WriteContext::default())
         .await
         .expect("Updating back to Todo should succeed");
-    assert_eq!(task.status, TaskStatus::Todo);
+    assert_eq!(task.status, TaskStatus::new("Todo"));
 
     // Test filtering by each status
     let statuses = [
-        TaskStatus::Todo,
-        TaskStatus::InProgress,
-        TaskStatus::Completed,
+        TaskStatus::new("Todo"),
+        TaskStatus::new("InProgress"),
+        TaskStatus::new("Completed"),
     ];
     for status in &statuses {
         let filter = TaskFilter {
-            status: Some(*status),
-            priority: None,
+            status: vec![status.clone()],
+            priority: Vec::new(),
             due_before: None,
             due_after: None,
+            ..Default::default()
         };
-        let filtered_tasks = database
-            .get_tasks(Some(filter))
+        let filtered_tasks = list_all(&database, filter)
             .await
             .expect("Filtering by status should succeed");
 
@@ -357,7 +376,9 @@ async fn test_edge_case_status_enum_coverage() {
     }
 
     // Cleanup
-    let _ = database.delete_task(task.id).await;
+    let _ = database
+        .delete_task(task.id, &WriteContext::default())
+        .await;
 }
 
 #[tokio::test]
@@ -372,10 +393,11 @@ async fn test_edge_case_date_handling() {
         description: Some("Task with future due date".to_string()),
         priority: TaskPriority::High,
         due_date: Some(future_date),
+        ..Default::default()
     };
 
     let future_task = database
-        .create_task(future_request)
+        .create_task(future_request, &WriteContext::default())
         .await
         .expect("Creating task with future due date should succeed");
 
@@ -395,10 +417,11 @@ async fn test_edge_case_date_handling() {
         description: Some("Task with past due date".to_string()),
         priority: TaskPriority::Urgent,
         due_date: Some(past_date),
+        ..Default::default()
     };
 
     let past_task = database
-        .create_task(past_request)
+        .create_task(past_request, &WriteContext::default())
         .await
         .expect("Creating task with past due date should succeed");
 
@@ -414,14 +437,13 @@ async fn test_edge_case_date_handling() {
     // Test updating due date
     let new_due_date = Utc::now() + chrono::Duration::days(7);
     let date_update = UpdateTaskRequest {
-        title: None,
-        description: None,
-        status: None,
-        priority: None,
-        due_date: Some(new_due_date),
+        due_date: Patch::Value(new_due_date),
+        ..Default::default()
     };
 
-    let updated_task = database.update_task(future_task.id, date_update).await;
+    let updated_task = database
+        .update_task(future_task.id, date_update, &WriteContext::default())
+        .await;
 
     // Handle case where task might be deleted by other concurrent tests
     let task_id_for_cleanup = future_task.id;
@@ -438,21 +460,33 @@ async fn test_edge_case_date_handling() {
                 "Updated date should be preserved within reasonable precision"
             );
 
-            // Test removing due date - with current API design, due_date: None means "don't change"
-            // To test the behavior, we verify that None doesn't change the existing due_date
-            let preserve_date_update = UpdateTaskRequest {
-                title: None,
-                description: None,
-                status: None,
-                priority: None,
-                due_date: None, // This should preserve the existing due_date
-            };
+            // Leaving the due date out of an update keeps it
+            let preserve_date_update = UpdateTaskRequest::default();
 
-            if let Ok(preserved_task) = database.update_task(task.id, preserve_date_update).await {
+            if let Ok(preserved_task) = database
+                .update_task(task.id, preserve_date_update, &WriteContext::default())
+                .await
+            {
                 // The due_date should be preserved (not changed)
                 assert!(
                     preserved_task.due_date.is_some(),
-                    "Due date should be preserved when update contains None"
+                    "Due date should be preserved when the update leaves it out"
+                );
+            }
+
+            // An explicit null clears it
+            let clear_date_update = UpdateTaskRequest {
+                due_date: Patch::Null,
+                ..Default::default()
+            };
+
+            if let Ok(cleared_task) = database
+                .update_task(task.id, clear_date_update, &WriteContext::default())
+                .await
+            {
+                assert!(
+                    cleared_task.due_date.is_none(),
+                    "Due date should be cleared when the update sets it to null"
                 );
             }
         }
@@ -463,8 +497,12 @@ async fn test_edge_case_date_handling() {
     }
 
     // Cleanup
-    let _ = database.delete_task(task_id_for_cleanup).await;
-    let _ = database.delete_task(past_task.id).await;
+    let _ = database
+        .delete_task(task_id_for_cleanup, &WriteContext::default())
+        .await;
+    let _ = database
+        .delete_task(past_task.id, &WriteContext::default())
+        .await;
 }
 
 #[tokio::test]
@@ -477,10 +515,11 @@ async fn test_edge_case_concurrent_modifications() {
         description: Some("Testing concurrent modifications".to_string()),
         priority: TaskPriority::Medium,
         due_date: None,
+        ..Default::default()
     };
 
     let task = database
-        .create_task(request)
+        .create_task(request, &WriteContext::default())
         .await
         .expect("Creating task should succeed");
 
@@ -491,24 +530,23 @@ async fn test_edge_case_concurrent_modifications() {
 
     let handle1 = tokio::spawn(async move {
         let update1 = UpdateTaskRequest {
-            title: Some("Updated by Thread 1".to_string()),
-            description: None,
-            status: Some(TaskStatus::InProgress),
-            priority: None,
-            due_date: None,
+            title: Patch::Value("Updated by Thread 1".to_string()),
+            status: Patch::Value(TaskStatus::new("InProgress")),
+            ..Default::default()
         };
-        db1.update_task(task_id, update1).await
+        db1.update_task(task_id, update1, &WriteContext::default())
+            .await
     });
 
     let handle2 = tokio::spawn(async move {
         let update2 = UpdateTaskRequest {
-            title: Some("Updated by Thread 2".to_string()),
-            description: None,
-            status: Some(TaskStatus::Completed),
-            priority: Some(TaskPriority::High),
-            due_date: None,
+            title: Patch::Value("Updated by Thread 2".to_string()),
+            status: Patch::Value(TaskStatus::new("Completed")),
+            priority: Patch::Value(TaskPriority::High),
+            ..Default::default()
         };
-        db2.update_task(task_id, update2).await
+        db2.update_task(task_id, update2, &WriteContext::default())
+            .await
     });
 
     // Both updates should succeed (last one wins) - but tasks might be deleted by other tests
@@ -531,7 +569,9 @@ async fn test_edge_case_concurrent_modifications() {
     // If task doesn't exist, it means it was cleaned up by another test, which is fine
 
     // Cleanup
-    let _ = database.delete_task(task_id).await;
+    let _ = database
+        .delete_task(task_id, &WriteContext::default())
+        .await;
 }
 
 #[tokio::test]
@@ -555,18 +595,18 @@ async fn test_edge_case_large_batch_operations() {
                 _ => TaskPriority::Urgent,
             },
             due_date: None,
+            ..Default::default()
         };
 
         let task = database
-            .create_task(request)
+            .create_task(request, &WriteContext::default())
             .await
             .expect("Batch task creation should succeed");
         task_ids.push(task.id);
     }
 
     // Verify we can retrieve our created tasks (other tests may also have tasks)
-    let all_tasks = database
-        .get_tasks(None)
+    let all_tasks = list_all(&database, TaskFilter::default())
         .await
         .expect("Retrieving all tasks should succeed");
 
@@ -585,13 +625,13 @@ async fn test_edge_case_large_batch_operations() {
 
     // Test filtering with large dataset
     let filter = TaskFilter {
-        status: None,
-        priority: Some(TaskPriority::High),
+        status: Vec::new(),
+        priority: vec![TaskPriority::High],
         due_before: None,
         due_after: None,
+        ..Default::default()
     };
-    let high_priority_tasks = database
-        .get_tasks(Some(filter))
+    let high_priority_tasks = list_all(&database, filter)
         .await
         .expect("Filtering large dataset should succeed");
 
@@ -612,14 +652,14 @@ async fn test_edge_case_large_batch_operations() {
     // Batch update some tasks
     for (i, &task_id) in task_ids.iter().enumerate().take(10) {
         let update = UpdateTaskRequest {
-            title: Some(format!("Updated Batch Task {i}")),
-            description: None,
-            status: Some(TaskStatus::Completed),
-            priority: None,
-            due_date: None,
+            title: Patch::Value(format!("Updated Batch Task {i}")),
+            status: Patch::Value(TaskStatus::new("Completed")),
+            ..Default::default()
         };
 
-        let result = database.update_task(task_id, update).await;
+        let result = database
+            .update_task(task_id, update, &WriteContext::default())
+            .await;
         // Allow for tasks that might have been deleted by other concurrent tests
         if result.is_err() {
             // Task might have been deleted by another test - that's ok in concurrent environment
@@ -629,7 +669,9 @@ async fn test_edge_case_large_batch_operations() {
 
     // Batch delete all tasks
     for task_id in task_ids {
-        let delete_result = database.delete_task(task_id).await;
+        let delete_result = database
+            .delete_task(task_id, &WriteContext::default())
+            .await;
         // Allow for tasks that might have been deleted by other concurrent tests
         if delete_result.is_err() {
             // Task might have been deleted by another test - that's ok in concurrent environment
@@ -658,10 +700,11 @@ async fn test_edge_case_database_connection_resilience() {
         description: Some("Testing connection resilience".to_string()),
         priority: TaskPriority::Medium,
         due_date: None,
+        ..Default::default()
     };
 
     let task = database
-        .create_task(request)
+        .create_task(request, &WriteContext::default())
         .await
         .expect("Should be able to create task with good connection");
 
@@ -679,7 +722,9 @@ async fn test_edge_case_database_connection_resilience() {
     }
 
     // Cleanup
-    let _ = database.delete_task(task.id).await;
+    let _ = database
+        .delete_task(task.id, &WriteContext::default())
+        .await;
 }
 
 #[test]
@@ -716,7 +761,9 @@ fn test_error_types_coverage() {
 fn test_common_types_serialization() {
     // Test that all common types can be serialized/deserialized
     use chrono::Utc;
-    use common::{CreateTaskRequest, Task, TaskPriority, TaskStatus};
+    use common::{
+        CreateTaskRequest, StatusCategory, Task, TaskPriority, TaskStatus, DEFAULT_PROJECT_ID,
+    };
     use serde_json;
     use uuid::Uuid;
 
@@ -726,6 +773,7 @@ fn test_common_types_serialization() {
         description: Some("Testing serialization".to_string()),
         priority: TaskPriority::High,
         due_date: None,
+        ..Default::default()
     };
 
     let json = serde_json::to_string(&create_request).expect("Should serialize");
@@ -737,11 +785,29 @@ fn test_common_types_serialization() {
         id: Uuid::new_v4(),
         title: "Serialization Test Task".to_string(),
         description: Some("Testing task serialization".to_string()),
-        status: TaskStatus::InProgress,
+        status: TaskStatus::new("InProgress"),
+        status_category: StatusCategory::Doing,
         priority: TaskPriority::Urgent,
         due_date: None,
         created_at: Utc::now(),
         updated_at: Utc::now(),
+        version: 1,
+        deleted_at: None,
+        comment_count: 0,
+        search: None,
+        tags: Vec::new(),
+        assignee_id: None,
+        project_id: DEFAULT_PROJECT_ID,
+        parent_id: None,
+        subtask_count: 0,
+        completed_subtask_count: 0,
+        is_blocked: false,
+        recurrence: None,
+        rank: String::new(),
+        estimate_minutes: None,
+        logged_minutes: 0,
+        timer_started_at: None,
+        custom_fields: Default::default(),
     };
 
     let task_json = serde_json::to_string(&task).expect("Should serialize task");
@@ -12,6 +12,78 @@ mod error_test_suite {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
+    #[test]
+    fn test_app_error_comment_not_found() {
+        let error = AppError::CommentNotFound;
+        let response = error.into_response();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_app_error_tag_not_found() {
+        let error = AppError::TagNotFound;
+        let response = error.into_response();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_app_error_member_not_found() {
+        let error = AppError::MemberNotFound;
+        let response = error.into_response();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_app_error_project_not_found() {
+        let error = AppError::ProjectNotFound;
+        let response = error.into_response();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_app_error_custom_field_not_found() {
+        let error = AppError::CustomFieldNotFound;
+        let response = error.into_response();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_app_error_saved_view_not_found() {
+        let error = AppError::SavedViewNotFound;
+        let response = error.into_response();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_app_error_dependency_not_found() {
+        let error = AppError::DependencyNotFound;
+        let response = error.into_response();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_app_error_dependency_cycle() {
+        let error = AppError::DependencyCycle;
+        let response = error.into_response();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[test]
+    fn test_app_error_conflict() {
+        let error = AppError::Conflict("Tag already exists: bug".to_string());
+        let response = error.into_response();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
     #[test]
     fn test_app_error_invalid_input() {
         let error = AppError::InvalidInput("Test error message".to_string());
@@ -20,6 +92,30 @@ mod error_test_suite {
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
 
+    #[test]
+    fn test_app_error_attachment_not_found() {
+        let error = AppError::AttachmentNotFound;
+        let response = error.into_response();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_app_error_payload_too_large() {
+        let error = AppError::PayloadTooLarge("Attachments cannot exceed 1024 bytes".to_string());
+        let response = error.into_response();
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[test]
+    fn test_app_error_storage() {
+        let error = AppError::Storage(std::io::Error::other("disk full"));
+        let response = error.into_response();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
     #[test]
     fn test_app_error_internal_error() {
         let error = AppError::InternalError;
@@ -4,12 +4,106 @@
 //! of backend functionality, performance, and edge cases.
 
 // All test modules
+pub mod blob_tests;
 pub mod configuration_tests;
 pub mod database_tests;
 pub mod edge_case_tests;
 pub mod error_tests;
 pub mod handler_tests;
 pub mod integration_tests;
+pub mod search_tests;
+
+use std::{collections::HashMap, sync::RwLock};
+
+use async_trait::async_trait;
+use common::{Task, TaskFilter, TaskSort};
+use tokio::sync::Notify;
+
+use crate::{blob::BlobStore, error::AppError, repository::TaskRepository};
+
+/// Every live task matching `filter`, newest first, as a single page.
+pub async fn list_all(
+    repository: &dyn TaskRepository,
+    filter: TaskFilter,
+) -> Result<Vec<Task>, AppError> {
+    let page = repository
+        .list_tasks(&filter, TaskSort::default(), None, u32::MAX)
+        .await?;
+    Ok(page.items)
+}
+
+/// Blobs in process memory, for tests.
+#[derive(Default)]
+pub struct MemoryBlobStore {
+    blobs: RwLock<HashMap<String, Vec<u8>>>,
+    held_deletes: Option<HeldDeletes>,
+}
+
+/// Lets a test pause a removal between finding a blob unused and removing it.
+#[derive(Default)]
+struct HeldDeletes {
+    reached: Notify,
+    released: Notify,
+}
+
+impl MemoryBlobStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A store whose deletes wait for [`MemoryBlobStore::release_delete`].
+    pub fn holding_deletes() -> Self {
+        Self {
+            held_deletes: Some(HeldDeletes::default()),
+            ..Self::default()
+        }
+    }
+
+    /// Waits until a delete is held.
+    pub async fn delete_reached(&self) {
+        if let Some(held) = &self.held_deletes {
+            held.reached.notified().await;
+        }
+    }
+
+    /// Lets one held delete go on.
+    pub fn release_delete(&self) {
+        if let Some(held) = &self.held_deletes {
+            held.released.notify_one();
+        }
+    }
+
+    /// Whether a blob is stored under `key`.
+    pub fn contains(&self, key: &str) -> bool {
+        self.blobs.read().is_ok_and(|blobs| blobs.contains_key(key))
+    }
+}
+
+#[async_trait]
+impl BlobStore for MemoryBlobStore {
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<(), AppError> {
+        let mut blobs = self.blobs.write().map_err(|_| AppError::InternalError)?;
+        blobs
+            .entry(key.to_string())
+            .or_insert_with(|| bytes.to_vec());
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, AppError> {
+        let blobs = self.blobs.read().map_err(|_| AppError::InternalError)?;
+        blobs.get(key).cloned().ok_or(AppError::AttachmentNotFound)
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        if let Some(held) = &self.held_deletes {
+            held.reached.notify_one();
+            held.released.notified().await;
+        }
+        let mut blobs = self.blobs.write().map_err(|_| AppError::InternalError)?;
+        blobs.remove(key);
+        Ok(())
+    }
+}
 
 #[cfg(test)]
 mod additional_tests {
@@ -22,6 +116,7 @@ mod additional_tests {
             description: Some("Test Description".to_string()),
             priority: common::TaskPriority::Low,
             due_date: None,
+            ..Default::default()
         };
 
         assert_eq!(request.title, "Test Task");
@@ -31,8 +126,9 @@ mod additional_tests {
 
     #[test]
     fn test_task_status_default() {
-        let status = TaskStatus::default();
-        assert_eq!(status, TaskStatus::Todo);
+        let first = &common::default_workflow()[0];
+        assert_eq!(first.name, TaskStatus::new("Todo"));
+        assert_eq!(first.category, common::StatusCategory::Todo);
     }
 
     #[test]
@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod search_test_suite {
+    use crate::search::{parse_highlight, to_tsquery, HIGHLIGHT_START, HIGHLIGHT_STOP};
+    use common::HighlightSpan;
+
+    #[test]
+    fn test_to_tsquery_words_are_anded() {
+        assert_eq!(to_tsquery("login page"), Some("login & page".to_string()));
+        assert_eq!(to_tsquery("  login   "), Some("login".to_string()));
+    }
+
+    #[test]
+    fn test_to_tsquery_phrase_prefix_and_negation() {
+        assert_eq!(
+            to_tsquery("\"login page\""),
+            Some("(login <-> page)".to_string())
+        );
+        assert_eq!(to_tsquery("deploy*"), Some("deploy:*".to_string()));
+        assert_eq!(to_tsquery("-bug"), Some("!bug".to_string()));
+        assert_eq!(
+            to_tsquery("-\"flaky test\" ci"),
+            Some("!(flaky <-> test) & ci".to_string())
+        );
+    }
+
+    #[test]
+    fn test_to_tsquery_strips_operators() {
+        assert_eq!(to_tsquery("a&b|c"), Some("(a <-> b <-> c)".to_string()));
+        assert_eq!(to_tsquery("foo:* !bar"), Some("foo:* & bar".to_string()));
+        assert_eq!(to_tsquery("'); DROP"), Some("DROP".to_string()));
+        assert_eq!(
+            to_tsquery("\"unterminated"),
+            Some("unterminated".to_string())
+        );
+    }
+
+    #[test]
+    fn test_to_tsquery_empty_input() {
+        assert_eq!(to_tsquery(""), None);
+        assert_eq!(to_tsquery("   "), None);
+        assert_eq!(to_tsquery("- \"\" *"), None);
+    }
+
+    #[test]
+    fn test_parse_highlight() {
+        let text = format!("Fix {HIGHLIGHT_START}login{HIGHLIGHT_STOP} page");
+        assert_eq!(
+            parse_highlight(&text),
+            vec![
+                HighlightSpan {
+                    text: "Fix ".to_string(),
+                    matched: false
+                },
+                HighlightSpan {
+                    text: "login".to_string(),
+                    matched: true
+                },
+                HighlightSpan {
+                    text: " page".to_string(),
+                    matched: false
+                },
+            ]
+        );
+        assert!(parse_highlight("").is_empty());
+    }
+}
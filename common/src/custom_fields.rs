@@ -0,0 +1,288 @@
+//! Extra fields a project defines for its tasks, such as a customer, a
+//! component or story points.
+//!
+//! Values are kept on each task keyed by field id and checked against the
+//! field's type on every write. The server and the task form share the
+//! checks, so a value the form accepts is one the server stores.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+#[cfg(feature = "sqlx")]
+use sqlx::Type;
+
+/// A task's custom field values, keyed by field id. Fields without a value
+/// are left out.
+pub type CustomFieldValues = BTreeMap<Uuid, Value>;
+
+/// The kind of value a custom field holds.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "sqlx", derive(Type))]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "custom_field_type", rename_all = "PascalCase")
+)]
+pub enum CustomFieldType {
+    /// Free text, stored as a string.
+    Text,
+    /// Any finite number; whole numbers are stored without a fraction.
+    Number,
+    /// A calendar date, stored as `YYYY-MM-DD`.
+    Date,
+    /// One of the field's options.
+    SingleSelect,
+    /// Any of the field's options, stored as a list in option order.
+    MultiSelect,
+    /// `true` or `false`.
+    Checkbox,
+}
+
+impl CustomFieldType {
+    /// Whether values are picked from the field's options.
+    #[must_use]
+    pub fn has_options(self) -> bool {
+        matches!(
+            self,
+            CustomFieldType::SingleSelect | CustomFieldType::MultiSelect
+        )
+    }
+}
+
+/// A field defined by a project, in addition to the ones every task has.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CustomField {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub field_type: CustomFieldType,
+    /// The choices of a select field, in the order they are offered; empty
+    /// for other types.
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+/// A new custom field, or new settings for one. A field's type cannot
+/// change once it is created.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CustomFieldRequest {
+    pub name: String,
+    pub field_type: CustomFieldType,
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+/// Tasks whose value for a custom field is `value`, or for a multi-select
+/// field, whose options include it. Written in a query string as
+/// `field.<id>=<value>`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CustomFieldFilter {
+    pub field_id: Uuid,
+    pub value: Value,
+}
+
+/// Longest custom field name, in characters.
+pub const MAX_FIELD_NAME_LENGTH: usize = 50;
+
+/// Most options a select field can have.
+pub const MAX_FIELD_OPTIONS: usize = 100;
+
+/// Longest select option, in characters.
+pub const MAX_OPTION_LENGTH: usize = 100;
+
+/// Longest text value, in characters.
+pub const MAX_TEXT_VALUE_LENGTH: usize = 1000;
+
+impl CustomFieldRequest {
+    /// Checks the name and options, trimming them.
+    pub fn normalize(&mut self) -> Result<(), String> {
+        self.name = self.name.trim().to_string();
+        if self.name.is_empty() {
+            return Err("Field name cannot be empty".to_string());
+        }
+        if self.name.chars().count() > MAX_FIELD_NAME_LENGTH {
+            return Err(format!(
+                "Field name cannot exceed {MAX_FIELD_NAME_LENGTH} characters"
+            ));
+        }
+
+        if !self.field_type.has_options() {
+            if !self.options.is_empty() {
+                return Err("Only select fields have options".to_string());
+            }
+            return Ok(());
+        }
+        for option in &mut self.options {
+            *option = option.trim().to_string();
+        }
+        if self.options.is_empty() {
+            return Err("A select field needs at least one option".to_string());
+        }
+        if self.options.len() > MAX_FIELD_OPTIONS {
+            return Err(format!(
+                "A field cannot have more than {MAX_FIELD_OPTIONS} options"
+            ));
+        }
+        for (i, option) in self.options.iter().enumerate() {
+            if option.is_empty() {
+                return Err("Options cannot be empty".to_string());
+            }
+            if option.chars().count() > MAX_OPTION_LENGTH {
+                return Err(format!(
+                    "Options cannot exceed {MAX_OPTION_LENGTH} characters"
+                ));
+            }
+            if self.options[..i].contains(option) {
+                return Err(format!("Duplicate option: {option}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CustomField {
+    /// Checks `value` against the field's type and returns it in the form it
+    /// is stored in.
+    pub fn check_value(&self, value: &Value) -> Result<Value, String> {
+        let name = &self.name;
+        match (self.field_type, value) {
+            (CustomFieldType::Text, Value::String(text)) => {
+                if text.chars().count() > MAX_TEXT_VALUE_LENGTH {
+                    return Err(format!(
+                        "{name} cannot exceed {MAX_TEXT_VALUE_LENGTH} characters"
+                    ));
+                }
+                Ok(value.clone())
+            }
+            (CustomFieldType::Number, Value::Number(number)) => number
+                .as_f64()
+                .and_then(number_value)
+                .ok_or_else(|| format!("{name} must be a number")),
+            (CustomFieldType::Date, Value::String(date)) => {
+                NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                    .map(|date| Value::String(date.format("%Y-%m-%d").to_string()))
+                    .map_err(|_| format!("{name} must be a date such as 2026-01-31"))
+            }
+            (CustomFieldType::SingleSelect, Value::String(option)) => {
+                self.check_option(option)?;
+                Ok(value.clone())
+            }
+            (CustomFieldType::MultiSelect, Value::Array(items)) => {
+                let mut picked = Vec::with_capacity(items.len());
+                for item in items {
+                    let Value::String(option) = item else {
+                        return Err(format!("{name} must be a list of options"));
+                    };
+                    self.check_option(option)?;
+                    picked.push(option.as_str());
+                }
+                // Option order, so the same picks always read the same
+                Ok(Value::Array(
+                    self.options
+                        .iter()
+                        .filter(|option| picked.contains(&option.as_str()))
+                        .map(|option| Value::String(option.clone()))
+                        .collect(),
+                ))
+            }
+            (CustomFieldType::Checkbox, Value::Bool(_)) => Ok(value.clone()),
+            (field_type, _) => Err(format!("{name} must be {}", expected(field_type))),
+        }
+    }
+
+    /// Reads a value typed as text, as in a query string or a form input.
+    /// For a multi-select field this is a single option.
+    pub fn parse_value(&self, raw: &str) -> Result<Value, String> {
+        let raw = raw.trim();
+        let value = match self.field_type {
+            CustomFieldType::Text | CustomFieldType::Date | CustomFieldType::SingleSelect => {
+                Value::String(raw.to_string())
+            }
+            CustomFieldType::Number => {
+                return raw
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(number_value)
+                    .ok_or_else(|| format!("{} must be a number", self.name));
+            }
+            CustomFieldType::MultiSelect => {
+                self.check_option(raw)?;
+                return Ok(Value::String(raw.to_string()));
+            }
+            CustomFieldType::Checkbox => match raw {
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                _ => return Err(format!("{} must be true or false", self.name)),
+            },
+        };
+        self.check_value(&value)
+    }
+
+    fn check_option(&self, option: &str) -> Result<(), String> {
+        if self.options.iter().any(|known| known == option) {
+            Ok(())
+        } else {
+            Err(format!("{} has no option {option}", self.name))
+        }
+    }
+}
+
+/// Whether `value` counts as no value at all: `null`, an empty string or
+/// an empty list.
+#[must_use]
+pub fn is_unset(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(text) => text.trim().is_empty(),
+        Value::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+/// Checks every value against `fields`, the fields of the task's project,
+/// putting each in its stored form and dropping the ones that are unset.
+pub fn check_values(values: &mut CustomFieldValues, fields: &[CustomField]) -> Result<(), String> {
+    values.retain(|_, value| !is_unset(value));
+    for (id, value) in values.iter_mut() {
+        let field = fields
+            .iter()
+            .find(|field| field.id == *id)
+            .ok_or_else(|| format!("Unknown custom field: {id}"))?;
+        *value = field.check_value(value)?;
+    }
+    Ok(())
+}
+
+/// Whether a stored value matches a filter value: equal to it, or for a
+/// multi-select field, listing it.
+#[must_use]
+pub fn value_matches(stored: &Value, wanted: &Value) -> bool {
+    match stored {
+        Value::Array(items) => items.contains(wanted),
+        stored => stored == wanted,
+    }
+}
+
+// Whole numbers are stored as integers, so 3 and 3.0 are the same value
+fn number_value(number: f64) -> Option<Value> {
+    if !number.is_finite() {
+        return None;
+    }
+    if number.fract() == 0.0 && number.abs() < 9_007_199_254_740_992.0 {
+        return Some(Value::from(number as i64));
+    }
+    Some(Value::from(number))
+}
+
+fn expected(field_type: CustomFieldType) -> &'static str {
+    match field_type {
+        CustomFieldType::Text => "text",
+        CustomFieldType::Number => "a number",
+        CustomFieldType::Date => "a date such as 2026-01-31",
+        CustomFieldType::SingleSelect => "one of its options",
+        CustomFieldType::MultiSelect => "a list of options",
+        CustomFieldType::Checkbox => "true or false",
+    }
+}
@@ -1,24 +1,155 @@
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt, str::FromStr};
 use uuid::Uuid;
 
+use crate::custom_fields::{CustomFieldFilter, CustomFieldValues};
+
 #[cfg(feature = "sqlx")]
 use sqlx::Type;
 
+pub mod custom_fields;
+pub mod query;
+pub mod rank;
+pub mod recurrence;
+pub mod saved_views;
+pub mod transition;
+
+use transition::{PriorityRule, TaskField};
+
+/// Name of a state in a project's workflow, such as `InProgress`. Which
+/// names are valid depends on the project the task is in.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(transparent)]
+#[cfg_attr(feature = "sqlx", derive(Type), sqlx(transparent))]
+pub struct TaskStatus(String);
+
+impl TaskStatus {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for TaskStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for TaskStatus {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+/// What a workflow state means for the task, whatever the state is called.
+/// Blockers are checked on leaving `Todo` and a task is finished once it
+/// reaches `Done`.
 #[derive(
     Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default, PartialOrd, Ord,
 )]
 #[cfg_attr(feature = "sqlx", derive(Type))]
 #[cfg_attr(
     feature = "sqlx",
-    sqlx(type_name = "task_status", rename_all = "PascalCase")
+    sqlx(type_name = "status_category", rename_all = "PascalCase")
 )]
-pub enum TaskStatus {
+pub enum StatusCategory {
     #[default]
     Todo,
-    InProgress,
-    Completed,
-    Backlog,
+    Doing,
+    Done,
+}
+
+/// One column of a project's board.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkflowState {
+    pub name: TaskStatus,
+    pub category: StatusCategory,
+    /// Most live tasks the column takes before moves into it are refused or
+    /// warned about; `None` for no limit.
+    #[serde(default)]
+    pub wip_limit: Option<u32>,
+    /// States a task here may move to; `None` allows any.
+    #[serde(default)]
+    pub next: Option<Vec<TaskStatus>>,
+    /// Fields a task must have filled in before it can enter the state.
+    #[serde(default)]
+    pub requires: Vec<TaskField>,
+}
+
+/// The ordered states tasks in a project move through. New tasks start in
+/// the first state.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Workflow {
+    pub project_id: Uuid,
+    pub states: Vec<WorkflowState>,
+    /// Fields a task must have filled in before it can take a priority.
+    #[serde(default)]
+    pub priority_rules: Vec<PriorityRule>,
+}
+
+impl Workflow {
+    /// The state called `status`, if the workflow has one.
+    #[must_use]
+    pub fn state(&self, status: &TaskStatus) -> Option<&WorkflowState> {
+        self.states.iter().find(|state| state.name == *status)
+    }
+
+    /// Category of `status`, or `None` when it is not in the workflow.
+    #[must_use]
+    pub fn category(&self, status: &TaskStatus) -> Option<StatusCategory> {
+        self.state(status).map(|state| state.category)
+    }
+
+    /// The first state of `category`, if there is one.
+    #[must_use]
+    pub fn first_of(&self, category: StatusCategory) -> Option<&WorkflowState> {
+        self.states.iter().find(|state| state.category == category)
+    }
+}
+
+/// The new states of a project's workflow, in board order, and its priority
+/// rules. States that still hold tasks cannot be left out.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkflowRequest {
+    pub states: Vec<WorkflowState>,
+    #[serde(default)]
+    pub priority_rules: Vec<PriorityRule>,
+}
+
+/// Most states a workflow can have.
+pub const MAX_WORKFLOW_STATES: usize = 20;
+
+/// Longest workflow state name, in characters.
+pub const MAX_STATE_NAME_LENGTH: usize = 50;
+
+/// Highest WIP limit a workflow state can have.
+pub const MAX_WIP_LIMIT: u32 = 1000;
+
+/// The states every project starts with, matching the statuses tasks had
+/// before workflows.
+#[must_use]
+pub fn default_workflow() -> Vec<WorkflowState> {
+    [
+        ("Todo", StatusCategory::Todo),
+        ("InProgress", StatusCategory::Doing),
+        ("Completed", StatusCategory::Done),
+        ("Backlog", StatusCategory::Todo),
+    ]
+    .into_iter()
+    .map(|(name, category)| WorkflowState {
+        name: TaskStatus::new(name),
+        category,
+        wip_limit: None,
+        next: None,
+        requires: Vec::new(),
+    })
+    .collect()
 }
 
 #[derive(
@@ -37,41 +168,627 @@ pub enum TaskPriority {
     Urgent,
 }
 
+impl fmt::Display for TaskPriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TaskPriority::Low => "Low",
+            TaskPriority::Medium => "Medium",
+            TaskPriority::High => "High",
+            TaskPriority::Urgent => "Urgent",
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Task {
     pub id: Uuid,
     pub title: String,
     pub description: Option<String>,
     pub status: TaskStatus,
+    /// Category of the task's status in its project's workflow.
+    #[serde(default)]
+    pub status_category: StatusCategory,
     pub priority: TaskPriority,
     pub due_date: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Starts at 1 and goes up on every update. Served as the task's `ETag`
+    /// and checked against `If-Match` to catch concurrent edits.
+    pub version: i64,
+    /// When the task was moved to the trash; `None` for live tasks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Number of comments on the task.
+    #[serde(default)]
+    pub comment_count: i64,
+    /// Labels on the task, ordered by name.
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+    /// The member working on the task, if anyone.
+    #[serde(default)]
+    pub assignee_id: Option<Uuid>,
+    /// The project the task belongs to.
+    #[serde(default = "default_project_id")]
+    pub project_id: Uuid,
+    /// The task this is a subtask of, if any.
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
+    /// Number of live direct subtasks, and how many of them are completed.
+    #[serde(default)]
+    pub subtask_count: i64,
+    #[serde(default)]
+    pub completed_subtask_count: i64,
+    /// Whether a live task that blocks this one is not completed yet.
+    #[serde(default)]
+    pub is_blocked: bool,
+    /// Recurrence rule, such as `FREQ=WEEKLY;BYDAY=MO`. Completing the task
+    /// creates the next occurrence, which carries the rule on.
+    #[serde(default)]
+    pub recurrence: Option<String>,
+    /// Position in its board column; lower ranks come first.
+    #[serde(default)]
+    pub rank: String,
+    /// Expected effort in minutes, if the task has been estimated.
+    #[serde(default)]
+    pub estimate_minutes: Option<u32>,
+    /// Minutes recorded in the task's finished time entries, to set against
+    /// the estimate.
+    #[serde(default)]
+    pub logged_minutes: i64,
+    /// When the earliest timer still running on the task was started;
+    /// `None` when no one is timing it.
+    #[serde(default)]
+    pub timer_started_at: Option<DateTime<Utc>>,
+    /// Values of the custom fields of the task's project, by field id.
+    #[serde(default)]
+    pub custom_fields: CustomFieldValues,
+    /// Highlighted title and snippet, present only on full-text search results.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search: Option<SearchMatch>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Why a task matched a full-text search, as text split into plain and
+/// matched spans so clients can highlight without parsing markup.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SearchMatch {
+    pub title: Vec<HighlightSpan>,
+    pub snippet: Vec<HighlightSpan>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HighlightSpan {
+    pub text: String,
+    pub matched: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CreateTaskRequest {
     pub title: String,
     pub description: Option<String>,
     pub priority: TaskPriority,
     pub due_date: Option<DateTime<Utc>>,
+    /// Names of existing tags to put on the task.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub assignee_id: Option<Uuid>,
+    /// Project to create the task in; the default project when left out.
+    #[serde(default)]
+    pub project_id: Option<Uuid>,
+    /// Creates the task as a subtask of this one, in the same project.
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
+    /// Makes the task repeat; it needs a due date.
+    #[serde(default)]
+    pub recurrence: Option<String>,
+    #[serde(default)]
+    pub estimate_minutes: Option<u32>,
+    /// Values for the custom fields of the task's project, by field id.
+    #[serde(default)]
+    pub custom_fields: CustomFieldValues,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Partial update sent with `PATCH`. Fields left out are unchanged and
+/// `null` clears the optional ones.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub struct UpdateTaskRequest {
-    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub title: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub description: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub status: Patch<TaskStatus>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub priority: Patch<TaskPriority>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub due_date: Patch<DateTime<Utc>>,
+    /// Replaces every tag on the task; `[]` removes them all.
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub tags: Patch<Vec<String>>,
+    /// `null` unassigns the task.
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub assignee_id: Patch<Uuid>,
+    /// Moves the task to another project.
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub project_id: Patch<Uuid>,
+    /// `null` makes the task a top-level task again.
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub parent_id: Patch<Uuid>,
+    /// `null` stops the task repeating.
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub recurrence: Patch<String>,
+    /// `null` clears the estimate.
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub estimate_minutes: Patch<u32>,
+    /// Replaces every custom field value; `null` or `{}` clears them all.
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub custom_fields: Patch<CustomFieldValues>,
+}
+
+/// Full replacement sent with `PUT`. Every field must be present except
+/// `project_id`, which keeps the task where it is when left out; `null` clears
+/// the description, due date, assignee, parent, recurrence or estimate.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReplaceTaskRequest {
+    pub title: String,
+    #[serde(deserialize_with = "Option::deserialize")]
     pub description: Option<String>,
-    pub status: Option<TaskStatus>,
-    pub priority: Option<TaskPriority>,
+    pub status: TaskStatus,
+    pub priority: TaskPriority,
+    #[serde(deserialize_with = "Option::deserialize")]
     pub due_date: Option<DateTime<Utc>>,
+    pub tags: Vec<String>,
+    #[serde(deserialize_with = "Option::deserialize")]
+    pub assignee_id: Option<Uuid>,
+    #[serde(default)]
+    pub project_id: Option<Uuid>,
+    #[serde(deserialize_with = "Option::deserialize")]
+    pub parent_id: Option<Uuid>,
+    #[serde(deserialize_with = "Option::deserialize")]
+    pub recurrence: Option<String>,
+    #[serde(deserialize_with = "Option::deserialize")]
+    pub estimate_minutes: Option<u32>,
+    pub custom_fields: CustomFieldValues,
+}
+
+impl From<ReplaceTaskRequest> for UpdateTaskRequest {
+    fn from(request: ReplaceTaskRequest) -> Self {
+        Self {
+            title: Patch::Value(request.title),
+            description: request.description.into(),
+            status: Patch::Value(request.status),
+            priority: Patch::Value(request.priority),
+            due_date: request.due_date.into(),
+            tags: Patch::Value(request.tags),
+            assignee_id: request.assignee_id.into(),
+            project_id: request.project_id.map_or(Patch::Absent, Patch::Value),
+            parent_id: request.parent_id.into(),
+            recurrence: request.recurrence.into(),
+            estimate_minutes: request.estimate_minutes.into(),
+            custom_fields: Patch::Value(request.custom_fields),
+        }
+    }
+}
+
+/// Drops a task into a board column, between two of its cards. `after_id`
+/// is the card that ends up directly above it and `before_id` the one
+/// directly below; with neither, the task goes to the bottom.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MoveTaskRequest {
+    pub status: TaskStatus,
+    #[serde(default)]
+    pub after_id: Option<Uuid>,
+    #[serde(default)]
+    pub before_id: Option<Uuid>,
+}
+
+/// One field of a partial update: left out, set to `null`, or given a value.
+///
+/// Deserializes `null` as [`Patch::Null`] and a value as [`Patch::Value`];
+/// fields need `#[serde(default)]` so that a missing key becomes
+/// [`Patch::Absent`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Patch<T> {
+    #[default]
+    Absent,
+    Null,
+    Value(T),
+}
+
+impl<T> Patch<T> {
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Patch::Absent)
+    }
+
+    /// `None` when absent, otherwise the new value of a nullable field.
+    pub fn into_change(self) -> Option<Option<T>> {
+        match self {
+            Patch::Absent => None,
+            Patch::Null => Some(None),
+            Patch::Value(value) => Some(Some(value)),
+        }
+    }
+
+    /// Converts the value, if there is one.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Patch<U> {
+        match self {
+            Patch::Absent => Patch::Absent,
+            Patch::Null => Patch::Null,
+            Patch::Value(value) => Patch::Value(f(value)),
+        }
+    }
+}
+
+impl<T> From<Option<T>> for Patch<T> {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Patch::Null, Patch::Value)
+    }
+}
+
+impl<T: Serialize> Serialize for Patch<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Patch::Value(value) => serializer.serialize_some(value),
+            Patch::Absent | Patch::Null => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Patch<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Option::deserialize(deserializer).map(Patch::from)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "sqlx", derive(Type))]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "task_event_kind", rename_all = "PascalCase")
+)]
+pub enum TaskEventKind {
+    Created,
+    Updated,
+    Deleted,
+    Restored,
+}
+
+/// One entry in a task's change history. Updates record one event per
+/// changed field with its old and new value as text; the other kinds have no
+/// field.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TaskEvent {
+    pub id: i64,
+    pub task_id: Uuid,
+    pub kind: TaskEventKind,
+    pub field: Option<String>,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    /// Who made the change, when the client said.
+    pub actor: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A label that can be put on any number of tasks. Names are unique.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Tag {
+    pub id: Uuid,
+    pub name: String,
+    /// Hex colour of the tag's chip, such as `#3b82f6`.
+    pub color: String,
+}
+
+/// A new tag, or the new name and colour of an existing one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagRequest {
+    pub name: String,
+    pub color: String,
 }
 
+/// Longest tag name, in characters.
+pub const MAX_TAG_NAME_LENGTH: usize = 50;
+
+/// A board of tasks, so that teams can keep their work apart.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Project {
+    pub id: Uuid,
+    pub name: String,
+}
+
+/// A new project, or the new name of an existing one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectRequest {
+    pub name: String,
+}
+
+/// Longest project name, in characters.
+pub const MAX_PROJECT_NAME_LENGTH: usize = 100;
+
+/// The live tasks on either side of a task's "blocks / blocked by"
+/// relations, oldest first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TaskDependencies {
+    /// Tasks that must be completed before this one can start.
+    pub blocked_by: Vec<Task>,
+    /// Tasks waiting on this one.
+    pub blocks: Vec<Task>,
+}
+
+/// The project every install starts with. Tasks created without a project
+/// go here, as did the tasks that existed before projects.
+pub const DEFAULT_PROJECT_ID: Uuid = Uuid::from_u128(1);
+
+fn default_project_id() -> Uuid {
+    DEFAULT_PROJECT_ID
+}
+
+/// Deepest a subtask can be nested: a top-level task is at depth 0, its
+/// subtasks at 1 and so on.
+pub const MAX_SUBTASK_DEPTH: usize = 3;
+
+/// Someone tasks can be assigned to. There are no accounts; members are
+/// just names the team picks from.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Member {
+    pub id: Uuid,
+    pub name: String,
+}
+
+/// A new member, or the new name of an existing one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberRequest {
+    pub name: String,
+}
+
+/// Longest member name, in characters.
+pub const MAX_MEMBER_NAME_LENGTH: usize = 100;
+
+/// A message in a task's discussion thread. The body is Markdown and is
+/// rendered by the client.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Comment {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub body: String,
+    /// Who wrote the comment, when the client said.
+    pub author: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Body of a new comment or the replacement body of an edited one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentRequest {
+    pub body: String,
+}
+
+/// Longest comment body, in characters.
+pub const MAX_COMMENT_LENGTH: usize = 10_000;
+
+/// A stretch of time a member spent on a task. `ended_at` is `None` while
+/// the entry is a running timer; each member has at most one running.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TimeEntry {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    /// Who spent the time; `None` once the member has been deleted.
+    pub member_id: Option<Uuid>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+}
+
+/// The member whose timer to start or stop.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimerRequest {
+    pub member_id: Uuid,
+}
+
+/// Time recorded by hand, or the corrected member, times and note of an
+/// existing entry. Correcting a running timer's entry stops it.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeEntryRequest {
+    pub member_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// Longest time entry note, in characters.
+pub const MAX_TIME_ENTRY_NOTE_LENGTH: usize = 1000;
+
+/// Largest estimate a task can have, in minutes: 10,000 hours.
+pub const MAX_ESTIMATE_MINUTES: u32 = 600_000;
+
+/// A file attached to a task. The content is stored once per distinct
+/// `sha256`, however many tasks carry it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Attachment {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    /// The uploaded file's name, without any directories.
+    pub filename: String,
+    /// The type sniffed from the content, not the one the client sent.
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// Hex SHA-256 of the content.
+    pub sha256: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Longest attachment file name, in characters.
+pub const MAX_ATTACHMENT_NAME_LENGTH: usize = 255;
+
+/// Criteria for narrowing a task listing. Empty sets and `None` place no
+/// constraint; date bounds are exclusive.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub struct TaskFilter {
-    pub status: Option<TaskStatus>,
-    pub priority: Option<TaskPriority>,
+    #[serde(default)]
+    pub status: Vec<TaskStatus>,
+    #[serde(default)]
+    pub priority: Vec<TaskPriority>,
     pub due_before: Option<DateTime<Utc>>,
     pub due_after: Option<DateTime<Utc>>,
+    pub has_due_date: Option<bool>,
+    pub created_before: Option<DateTime<Utc>>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub updated_before: Option<DateTime<Utc>>,
+    pub updated_after: Option<DateTime<Utc>>,
+    /// Full-text search over title and description. Words must all match;
+    /// `"quoted words"` match as a phrase, `word*` as a prefix and `-word`
+    /// excludes tasks containing it.
+    pub q: Option<String>,
+    /// Tasks with this tag.
+    pub tag: Option<String>,
+    /// Tasks with at least one of these tags.
+    #[serde(default)]
+    pub any_tag: Vec<String>,
+    /// Tasks with every one of these tags.
+    #[serde(default)]
+    pub all_tags: Vec<String>,
+    /// Tasks assigned to this member.
+    pub assignee: Option<Uuid>,
+    /// Tasks in this project.
+    pub project: Option<Uuid>,
+    /// Tasks matching every one of these custom field values.
+    #[serde(default)]
+    pub custom_fields: Vec<CustomFieldFilter>,
+    /// Tasks matching a query in the [`query`] language. Its free text is
+    /// searched together with `q`.
+    pub query: Option<query::TaskQuery>,
+}
+
+impl TaskFilter {
+    /// All the full-text search of the filter, from `q` and the query.
+    #[must_use]
+    pub fn search_text(&self) -> Option<String> {
+        let text: Vec<String> = self
+            .q
+            .iter()
+            .cloned()
+            .chain(self.query.as_ref().and_then(query::TaskQuery::search_text))
+            .collect();
+        (!text.is_empty()).then(|| text.join(" "))
+    }
+}
+
+/// Largest page the list endpoint will return in one response.
+pub const MAX_PAGE_SIZE: u32 = 200;
+
+/// Page size used when the client does not ask for one.
+pub const DEFAULT_PAGE_SIZE: u32 = 50;
+
+/// One page of a keyset-paginated listing.
+///
+/// `next_cursor` is opaque to clients and is passed back verbatim to fetch
+/// the following page; it is `None` on the last page.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+    pub total_estimate: u64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum TaskSortField {
+    CreatedAt,
+    UpdatedAt,
+    DueDate,
+    Priority,
+    Title,
+    /// Full-text search rank; only valid together with a search query.
+    Relevance,
+}
+
+impl TaskSortField {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TaskSortField::CreatedAt => "created_at",
+            TaskSortField::UpdatedAt => "updated_at",
+            TaskSortField::DueDate => "due_date",
+            TaskSortField::Priority => "priority",
+            TaskSortField::Title => "title",
+            TaskSortField::Relevance => "relevance",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Ordering for a task listing, written on the wire as the field name with a
+/// leading `-` for descending order, e.g. `priority` or `-updated_at`.
+///
+/// Ties are always broken by task id in the same direction so that keyset
+/// cursors stay stable. Tasks without a due date sort last in both directions.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct TaskSort {
+    pub field: TaskSortField,
+    pub direction: SortDirection,
+}
+
+/// Newest first, matching the board's historical ordering.
+impl Default for TaskSort {
+    fn default() -> Self {
+        Self::new(TaskSortField::CreatedAt, SortDirection::Desc)
+    }
+}
+
+impl TaskSort {
+    #[must_use]
+    pub fn new(field: TaskSortField, direction: SortDirection) -> Self {
+        Self { field, direction }
+    }
+}
+
+impl fmt::Display for TaskSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.direction == SortDirection::Desc {
+            f.write_str("-")?;
+        }
+        f.write_str(self.field.as_str())
+    }
+}
+
+impl FromStr for TaskSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (direction, name) = match s.strip_prefix('-') {
+            Some(name) => (SortDirection::Desc, name),
+            None => (SortDirection::Asc, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let field = match name {
+            "created_at" => TaskSortField::CreatedAt,
+            "updated_at" => TaskSortField::UpdatedAt,
+            "due_date" => TaskSortField::DueDate,
+            "priority" => TaskSortField::Priority,
+            "title" => TaskSortField::Title,
+            "relevance" => TaskSortField::Relevance,
+            _ => return Err(format!("Unknown sort field: {name}")),
+        };
+        Ok(Self { field, direction })
+    }
+}
+
+impl TryFrom<String> for TaskSort {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<TaskSort> for String {
+    fn from(sort: TaskSort) -> Self {
+        sort.to_string()
+    }
 }
 
 #[cfg(test)]
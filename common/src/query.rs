@@ -0,0 +1,489 @@
+//! The task query language, e.g.
+//! `status:InProgress priority>=High due<2026-11-01 tag:bug -assignee:none "login page"`.
+//!
+//! A query is a list of terms separated by spaces, all of which a task must
+//! match. A term is either a field condition, `<field><op><value>`, or free
+//! text searched as the `q` filter searches it: a word, a `"quoted phrase"`
+//! or a word ending in `*` for a prefix. A word whose prefix is not a field,
+//! such as `http://example.com` or `10:30`, is free text too. A leading `-`
+//! negates any term.
+//!
+//! | Field | Operators | Values |
+//! | --- | --- | --- |
+//! | `status` | `:` | a workflow state, e.g. `InProgress` |
+//! | `priority` | `:` `<` `<=` `>` `>=` | `Low`, `Medium`, `High` or `Urgent` |
+//! | `due`, `created`, `updated` | `:` `<` `<=` `>` `>=` | a UTC date, `2026-11-01`, or `none` for `due:none` |
+//! | `tag` | `:` | a tag name |
+//! | `assignee` | `:` | a member name, or `none` |
+//!
+//! Field names and the words `none` and priorities ignore case; values with
+//! spaces are quoted, e.g. `assignee:"Ada Lovelace"`.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use crate::{TaskPriority, TaskStatus};
+
+/// Every field a condition can name, in lower case.
+const FIELDS: [&str; 7] = [
+    "status", "priority", "due", "created", "updated", "tag", "assignee",
+];
+
+/// Every priority, lowest first.
+const PRIORITIES: [TaskPriority; 4] = [
+    TaskPriority::Low,
+    TaskPriority::Medium,
+    TaskPriority::High,
+    TaskPriority::Urgent,
+];
+
+/// A parsed query. Its text form is canonical: field names in lower case,
+/// values quoted only where they need to be.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TaskQuery {
+    pub terms: Vec<QueryTerm>,
+}
+
+/// One term of a query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryTerm {
+    /// Whether tasks matching the term are excluded instead.
+    pub negated: bool,
+    pub kind: TermKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TermKind {
+    Condition(Condition),
+    /// Free text, searched as a `q` term is.
+    Text {
+        text: String,
+        quoted: bool,
+    },
+}
+
+/// A condition on one field of a task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Condition {
+    Status(TaskStatus),
+    Priority(Comparison, TaskPriority),
+    /// Compares the UTC day of a date field, so `due:2026-11-01` matches
+    /// the whole day. Tasks without a due date match no comparison.
+    Date(DateField, Comparison, NaiveDate),
+    NoDueDate,
+    Tag(String),
+    /// Tasks assigned to the member with this name, or unassigned ones.
+    Assignee(Option<String>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateField {
+    Due,
+    Created,
+    Updated,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Why a query did not parse, and where.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryError {
+    /// Offset of the offending text in the query, in characters from 0.
+    pub position: usize,
+    pub message: String,
+}
+
+impl QueryTerm {
+    fn text(&self) -> Option<String> {
+        match &self.kind {
+            TermKind::Text { .. } => Some(self.to_string()),
+            TermKind::Condition(_) => None,
+        }
+    }
+}
+
+impl TaskQuery {
+    /// The field conditions, each with whether it is negated.
+    pub fn conditions(&self) -> impl Iterator<Item = (bool, &Condition)> {
+        self.terms.iter().filter_map(|term| match &term.kind {
+            TermKind::Condition(condition) => Some((term.negated, condition)),
+            TermKind::Text { .. } => None,
+        })
+    }
+
+    /// The free text terms in `q` syntax, if there are any.
+    #[must_use]
+    pub fn search_text(&self) -> Option<String> {
+        let text: Vec<String> = self.terms.iter().filter_map(QueryTerm::text).collect();
+        (!text.is_empty()).then(|| text.join(" "))
+    }
+}
+
+impl DateField {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DateField::Due => "due",
+            DateField::Created => "created",
+            DateField::Updated => "updated",
+        }
+    }
+}
+
+impl Comparison {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Comparison::Eq => ":",
+            Comparison::Lt => "<",
+            Comparison::Le => "<=",
+            Comparison::Gt => ">",
+            Comparison::Ge => ">=",
+        }
+    }
+
+    /// The priorities that compare this way with `priority`, lowest first.
+    #[must_use]
+    pub fn priorities(self, priority: TaskPriority) -> Vec<TaskPriority> {
+        PRIORITIES
+            .into_iter()
+            .filter(|other| match self {
+                Comparison::Eq => *other == priority,
+                Comparison::Lt => *other < priority,
+                Comparison::Le => *other <= priority,
+                Comparison::Gt => *other > priority,
+                Comparison::Ge => *other >= priority,
+            })
+            .collect()
+    }
+
+    /// The instants that compare this way with the UTC day `date`, as an
+    /// inclusive start and an exclusive end; `None` leaves that side open.
+    #[must_use]
+    pub fn day_range(self, date: NaiveDate) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+        let start = date.and_time(chrono::NaiveTime::MIN).and_utc();
+        let next = start + Duration::days(1);
+        match self {
+            Comparison::Eq => (Some(start), Some(next)),
+            Comparison::Lt => (None, Some(start)),
+            Comparison::Le => (None, Some(next)),
+            Comparison::Gt => (Some(next), None),
+            Comparison::Ge => (Some(start), None),
+        }
+    }
+}
+
+impl QueryError {
+    fn new(position: usize, message: impl Into<String>) -> Self {
+        Self {
+            position,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at column {}", self.message, self.position + 1)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+/// Walks the query a character at a time, tracking the position errors
+/// report.
+struct Parser {
+    chars: Vec<char>,
+    at: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.at).copied()
+    }
+
+    fn at_space_or_end(&self) -> bool {
+        self.peek().is_none_or(char::is_whitespace)
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.at += 1;
+        }
+    }
+
+    fn term(&mut self) -> Result<QueryTerm, QueryError> {
+        let negated = self.peek() == Some('-');
+        if negated {
+            self.at += 1;
+            if self.at_space_or_end() {
+                return Err(QueryError::new(self.at - 1, "Expected a term after '-'"));
+            }
+        }
+
+        let kind = if self.peek() == Some('"') {
+            TermKind::Text {
+                text: self.quoted()?,
+                quoted: true,
+            }
+        } else {
+            let start = self.at;
+            let name = self.field_name();
+            match self.operator() {
+                Some(comparison) if is_field(&name) => {
+                    TermKind::Condition(self.condition(start, &name, comparison)?)
+                }
+                _ => {
+                    self.at = start;
+                    TermKind::Text {
+                        text: self.word()?,
+                        quoted: false,
+                    }
+                }
+            }
+        };
+
+        if !self.at_space_or_end() {
+            return Err(QueryError::new(self.at, "Expected a space between terms"));
+        }
+        Ok(QueryTerm { negated, kind })
+    }
+
+    fn field_name(&mut self) -> String {
+        let mut name = String::new();
+        while let Some(c) = self.peek().filter(char::is_ascii_alphabetic) {
+            name.push(c);
+            self.at += 1;
+        }
+        name
+    }
+
+    fn operator(&mut self) -> Option<Comparison> {
+        let comparison = match self.peek()? {
+            ':' => Comparison::Eq,
+            '<' => Comparison::Lt,
+            '>' => Comparison::Gt,
+            _ => return None,
+        };
+        self.at += 1;
+        if comparison != Comparison::Eq && self.peek() == Some('=') {
+            self.at += 1;
+            return Some(if comparison == Comparison::Lt {
+                Comparison::Le
+            } else {
+                Comparison::Ge
+            });
+        }
+        Some(comparison)
+    }
+
+    /// A bare word, up to the next space.
+    fn word(&mut self) -> Result<String, QueryError> {
+        let mut word = String::new();
+        while let Some(c) = self.peek().filter(|c| !c.is_whitespace()) {
+            if c == '"' {
+                return Err(QueryError::new(self.at, "Unexpected quote inside a word"));
+            }
+            word.push(c);
+            self.at += 1;
+        }
+        Ok(word)
+    }
+
+    /// Quoted text, without its quotes.
+    fn quoted(&mut self) -> Result<String, QueryError> {
+        let start = self.at;
+        self.at += 1;
+        let mut text = String::new();
+        loop {
+            match self.peek() {
+                Some('"') => break,
+                Some(c) => text.push(c),
+                None => return Err(QueryError::new(start, "Unclosed quote")),
+            }
+            self.at += 1;
+        }
+        self.at += 1;
+        if text.trim().is_empty() {
+            return Err(QueryError::new(start, "Expected text between the quotes"));
+        }
+        Ok(text)
+    }
+
+    fn condition(
+        &mut self,
+        start: usize,
+        name: &str,
+        comparison: Comparison,
+    ) -> Result<Condition, QueryError> {
+        let value_at = self.at;
+        let value = if self.peek() == Some('"') {
+            self.quoted()?
+        } else {
+            self.word()?
+        };
+        if value.is_empty() {
+            return Err(QueryError::new(
+                value_at,
+                format!("Expected a value after '{name}{}'", comparison.as_str()),
+            ));
+        }
+        let is_none = value.eq_ignore_ascii_case("none");
+
+        let field = name.to_ascii_lowercase();
+        let only_equal = |condition: Condition| {
+            if comparison == Comparison::Eq {
+                Ok(condition)
+            } else {
+                Err(QueryError::new(
+                    start,
+                    format!("'{field}' can only be matched with ':'"),
+                ))
+            }
+        };
+        match field.as_str() {
+            "status" => only_equal(Condition::Status(TaskStatus::new(value))),
+            "priority" => {
+                let priority = PRIORITIES
+                    .into_iter()
+                    .find(|priority| format!("{priority:?}").eq_ignore_ascii_case(&value))
+                    .ok_or_else(|| {
+                        QueryError::new(
+                            value_at,
+                            format!(
+                                "Unknown priority '{value}', expected Low, Medium, High or Urgent"
+                            ),
+                        )
+                    })?;
+                Ok(Condition::Priority(comparison, priority))
+            }
+            "due" if is_none => only_equal(Condition::NoDueDate),
+            "due" | "created" | "updated" => {
+                let field = match field.as_str() {
+                    "due" => DateField::Due,
+                    "created" => DateField::Created,
+                    _ => DateField::Updated,
+                };
+                let date = NaiveDate::parse_from_str(&value, "%Y-%m-%d")
+                    .map_err(|_| QueryError::new(value_at, "Expected a date like 2026-11-01"))?;
+                Ok(Condition::Date(field, comparison, date))
+            }
+            "tag" => only_equal(Condition::Tag(value)),
+            "assignee" => only_equal(Condition::Assignee((!is_none).then_some(value))),
+            _ => unreachable!("'{name}' is not one of FIELDS"),
+        }
+    }
+}
+
+fn is_field(name: &str) -> bool {
+    FIELDS.iter().any(|field| field.eq_ignore_ascii_case(name))
+}
+
+impl FromStr for TaskQuery {
+    type Err = QueryError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            chars: input.chars().collect(),
+            at: 0,
+        };
+        let mut terms = Vec::new();
+        parser.skip_spaces();
+        while parser.peek().is_some() {
+            terms.push(parser.term()?);
+            parser.skip_spaces();
+        }
+        Ok(Self { terms })
+    }
+}
+
+/// Quotes a value that holds a space, so it reads back the same.
+fn write_value(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    if value.contains(char::is_whitespace) {
+        write!(f, "\"{value}\"")
+    } else {
+        f.write_str(value)
+    }
+}
+
+impl fmt::Display for QueryTerm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.negated {
+            f.write_str("-")?;
+        }
+        match &self.kind {
+            TermKind::Text { text, quoted: true } => write!(f, "\"{text}\""),
+            TermKind::Text {
+                text,
+                quoted: false,
+            } => f.write_str(text),
+            TermKind::Condition(condition) => write!(f, "{condition}"),
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Condition::Status(status) => {
+                f.write_str("status:")?;
+                write_value(f, status.as_str())
+            }
+            Condition::Priority(comparison, priority) => {
+                write!(f, "priority{}{priority:?}", comparison.as_str())
+            }
+            Condition::Date(field, comparison, date) => write!(
+                f,
+                "{}{}{}",
+                field.as_str(),
+                comparison.as_str(),
+                date.format("%Y-%m-%d")
+            ),
+            Condition::NoDueDate => f.write_str("due:none"),
+            Condition::Tag(name) => {
+                f.write_str("tag:")?;
+                write_value(f, name)
+            }
+            Condition::Assignee(None) => f.write_str("assignee:none"),
+            Condition::Assignee(Some(name)) => {
+                f.write_str("assignee:")?;
+                write_value(f, name)
+            }
+        }
+    }
+}
+
+impl fmt::Display for TaskQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, term) in self.terms.iter().enumerate() {
+            if index > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{term}")?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<String> for TaskQuery {
+    type Error = QueryError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<TaskQuery> for String {
+    fn from(query: TaskQuery) -> Self {
+        query.to_string()
+    }
+}
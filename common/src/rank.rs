@@ -0,0 +1,109 @@
+//! Ranks that order the cards of a board column.
+//!
+//! A rank is a fraction written in base 62 without the leading `0.`, using
+//! digits whose ASCII order matches their value, so ranks sort as plain
+//! bytes. There is always room for another rank between two, which lets a
+//! card move by rewriting its own rank alone. Ranks never end in `0`, as a
+//! trailing zero would leave no room below the rank without it.
+//!
+//! Cards added above the top or below the bottom of a column step the first
+//! [`WHOLE_DIGITS`] digits by one instead of halving the open space, so
+//! adding to the ends of a column keeps ranks the same length.
+
+const DIGITS: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+const BASE: u8 = 62;
+
+/// Digits stepped by a rank at either end of a column, enough for 62^6
+/// cards added one after another before ranks have to grow.
+const WHOLE_DIGITS: usize = 6;
+
+/// A rank after `after` and before `before`, where `None` leaves that end
+/// open. `between(None, None)` is the middle rank, `V`.
+///
+/// A `before` that does not come after `after` is ignored, so the result is
+/// always after `after`.
+pub fn between(after: Option<&str>, before: Option<&str>) -> String {
+    let after = after.filter(|after| !after.is_empty());
+    let before = before.filter(|before| after.unwrap_or_default() < *before);
+
+    let a: Option<Vec<u8>> = after.map(|after| after.bytes().map(value).collect());
+    let b: Option<Vec<u8>> = before.map(|before| before.bytes().map(value).collect());
+
+    let stepped = match (&a, &b) {
+        (Some(a), None) => step(a, true),
+        (None, Some(b)) => step(b, false),
+        _ => None,
+    };
+    stepped
+        .unwrap_or_else(|| midpoint(a.as_deref().unwrap_or_default(), b.as_deref()))
+        .into_iter()
+        .map(|digit| char::from(DIGITS[usize::from(digit)]))
+        .collect()
+}
+
+fn value(byte: u8) -> u8 {
+    DIGITS
+        .iter()
+        .position(|digit| *digit == byte)
+        .map_or(0, |position| position as u8)
+}
+
+/// The first [`WHOLE_DIGITS`] digits of `rank`, padded with zeros, one step
+/// up or down, skipping values that end in zero. `None` when that runs past
+/// either end.
+fn step(rank: &[u8], up: bool) -> Option<Vec<u8>> {
+    let mut whole: Vec<u8> = (0..WHOLE_DIGITS)
+        .map(|i| rank.get(i).copied().unwrap_or(0))
+        .collect();
+    loop {
+        let mut i = WHOLE_DIGITS;
+        loop {
+            i = i.checked_sub(1)?;
+            if up && whole[i] < BASE - 1 {
+                whole[i] += 1;
+                break;
+            } else if !up && whole[i] > 0 {
+                whole[i] -= 1;
+                break;
+            }
+            whole[i] = if up { 0 } else { BASE - 1 };
+        }
+        if whole[WHOLE_DIGITS - 1] != 0 {
+            return Some(whole);
+        }
+    }
+}
+
+/// Digits of a fraction between `a` and `b`, with `None` standing for one.
+fn midpoint(a: &[u8], b: Option<&[u8]>) -> Vec<u8> {
+    if let Some(b) = b {
+        // Keep the common prefix, reading missing digits of `a` as zeros
+        let shared = b
+            .iter()
+            .enumerate()
+            .take_while(|(i, digit)| a.get(*i).copied().unwrap_or(0) == **digit)
+            .count();
+        if shared > 0 {
+            let mut digits = b[..shared].to_vec();
+            digits.extend(midpoint(
+                a.get(shared..).unwrap_or_default(),
+                Some(&b[shared..]),
+            ));
+            return digits;
+        }
+    }
+
+    let digit_a = a.first().copied().unwrap_or(0);
+    let digit_b = b.and_then(|b| b.first().copied()).unwrap_or(BASE);
+    if digit_b > digit_a + 1 {
+        vec![(digit_a + digit_b).div_ceil(2)]
+    } else if let Some(b) = b.filter(|b| b.len() > 1) {
+        // `b` is longer, so its first digit alone is between the two
+        vec![b[0]]
+    } else {
+        let mut digits = vec![digit_a];
+        digits.extend(midpoint(a.get(1..).unwrap_or_default(), None));
+        digits
+    }
+}
@@ -0,0 +1,292 @@
+//! The subset of RFC 5545 recurrence rules that repeating tasks understand:
+//! `FREQ` (`DAILY`, `WEEKLY`, `MONTHLY` or `YEARLY`), `INTERVAL`, `BYDAY`
+//! on weekly rules, and `COUNT` or `UNTIL`, e.g. `FREQ=WEEKLY;BYDAY=MO,TH`.
+//!
+//! Each occurrence is worked out from the one before, keeping its time of
+//! day. As in the RFC, monthly and yearly rules skip months without the
+//! day, so a task due on the 31st repeats on the next 31st.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Utc, Weekday};
+use std::{fmt, str::FromStr};
+
+/// Largest `INTERVAL` accepted.
+pub const MAX_RECURRENCE_INTERVAL: u32 = 1000;
+
+/// Occurrences a preview lists unless asked for more or fewer.
+pub const DEFAULT_OCCURRENCE_PREVIEW: usize = 5;
+
+/// Most occurrences a preview lists.
+pub const MAX_OCCURRENCE_PREVIEW: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// A parsed recurrence rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recurrence {
+    pub frequency: Frequency,
+    /// Repeats every this many days, weeks, months or years.
+    pub interval: u32,
+    /// Days of the week a weekly rule falls on, Monday first; empty for the
+    /// weekday of the occurrence before.
+    pub by_day: Vec<Weekday>,
+    /// Occurrences left in the series, counting the current one.
+    pub count: Option<u32>,
+    /// No occurrence is due after this.
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl Recurrence {
+    /// The occurrence after one due at `due`, with the rule that occurrence
+    /// carries on; `None` once the series is over.
+    #[must_use]
+    pub fn next(&self, due: DateTime<Utc>) -> Option<(DateTime<Utc>, Recurrence)> {
+        if self.count == Some(1) {
+            return None;
+        }
+
+        let next = match self.frequency {
+            Frequency::Daily => Some(due + Duration::days(i64::from(self.interval))),
+            Frequency::Weekly => self.next_weekly(due),
+            Frequency::Monthly => next_month_with_day(due, self.interval),
+            Frequency::Yearly => next_month_with_day(due, self.interval * 12),
+        }?;
+        if self.until.is_some_and(|until| next > until) {
+            return None;
+        }
+
+        let rule = Recurrence {
+            count: self.count.map(|count| count - 1),
+            ..self.clone()
+        };
+        Some((next, rule))
+    }
+
+    /// Up to `limit` due dates following `due`.
+    #[must_use]
+    pub fn upcoming(&self, due: DateTime<Utc>, limit: usize) -> Vec<DateTime<Utc>> {
+        let mut dates = Vec::new();
+        let mut current = (due, self.clone());
+        while dates.len() < limit {
+            let Some(next) = current.1.next(current.0) else {
+                break;
+            };
+            dates.push(next.0);
+            current = next;
+        }
+        dates
+    }
+
+    /// The rule in words, such as "Every 2 weeks on Mon, Thu".
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let unit = match self.frequency {
+            Frequency::Daily => "day",
+            Frequency::Weekly => "week",
+            Frequency::Monthly => "month",
+            Frequency::Yearly => "year",
+        };
+        let mut text = if self.interval == 1 {
+            format!("Every {unit}")
+        } else {
+            format!("Every {} {unit}s", self.interval)
+        };
+        if !self.by_day.is_empty() {
+            let days: Vec<String> = self.by_day.iter().map(ToString::to_string).collect();
+            text.push_str(&format!(" on {}", days.join(", ")));
+        }
+        if let Some(count) = self.count {
+            text.push_str(&format!(", {count} more"));
+        }
+        if let Some(until) = self.until {
+            text.push_str(&format!(" until {}", until.format("%Y-%m-%d")));
+        }
+        text
+    }
+
+    /// The first listed weekday after `due` in a week that is a whole
+    /// number of intervals on from the week of `due`.
+    fn next_weekly(&self, due: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if self.by_day.is_empty() {
+            return Some(due + Duration::weeks(i64::from(self.interval)));
+        }
+
+        let week_start = i64::from(due.weekday().num_days_from_monday());
+        (1..=7 * i64::from(self.interval) + 7)
+            .map(|days| (days, due + Duration::days(days)))
+            .find(|(days, date)| {
+                (week_start + days) / 7 % i64::from(self.interval) == 0
+                    && self.by_day.contains(&date.weekday())
+            })
+            .map(|(_, date)| date)
+    }
+}
+
+/// The first date a whole number of `months` steps after `due` whose month
+/// has the day of `due`.
+fn next_month_with_day(due: DateTime<Utc>, months: u32) -> Option<DateTime<Utc>> {
+    let start = due.year() * 12 + due.month0() as i32;
+    // Every day of the month comes round within eight years of leap days
+    (1..=96).find_map(|step| {
+        let month = start + (months * step) as i32;
+        NaiveDate::from_ymd_opt(
+            month.div_euclid(12),
+            month.rem_euclid(12) as u32 + 1,
+            due.day(),
+        )
+        .map(|date| date.and_time(due.time()).and_utc())
+    })
+}
+
+impl FromStr for Recurrence {
+    type Err = String;
+
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        let rule = rule.trim();
+        let rule = match rule.get(..6) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("RRULE:") => &rule[6..],
+            _ => rule,
+        };
+
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut by_day = Vec::new();
+        let mut count = None;
+        let mut until = None;
+        for part in rule.split(';').filter(|part| !part.is_empty()) {
+            let (name, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("Recurrence rule part needs a value: {part}"))?;
+            match name.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(format!("Unsupported recurrence frequency: {value}")),
+                    })
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse()
+                        .ok()
+                        .filter(|interval| (1..=MAX_RECURRENCE_INTERVAL).contains(interval))
+                        .ok_or_else(|| {
+                            format!(
+                                "INTERVAL must be a whole number from 1 to {MAX_RECURRENCE_INTERVAL}"
+                            )
+                        })?;
+                }
+                "BYDAY" => {
+                    by_day = value
+                        .split(',')
+                        .map(parse_weekday)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    by_day.sort_by_key(Weekday::num_days_from_monday);
+                    by_day.dedup();
+                }
+                "COUNT" => {
+                    count = Some(
+                        value
+                            .parse()
+                            .ok()
+                            .filter(|count| *count > 0)
+                            .ok_or("COUNT must be a positive whole number")?,
+                    );
+                }
+                "UNTIL" => until = Some(parse_until(value)?),
+                _ => return Err(format!("Unsupported recurrence rule part: {name}")),
+            }
+        }
+
+        let frequency = frequency.ok_or("A recurrence rule needs a FREQ")?;
+        if !by_day.is_empty() && frequency != Frequency::Weekly {
+            return Err("BYDAY is only supported on weekly rules".to_string());
+        }
+        if count.is_some() && until.is_some() {
+            return Err("A recurrence rule cannot have both COUNT and UNTIL".to_string());
+        }
+
+        Ok(Recurrence {
+            frequency,
+            interval,
+            by_day,
+            count,
+            until,
+        })
+    }
+}
+
+/// Writes the rule in its canonical form, e.g. `FREQ=WEEKLY;BYDAY=MO,TH`.
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frequency = match self.frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        };
+        write!(f, "FREQ={frequency}")?;
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<&str> = self.by_day.iter().map(|day| weekday_code(*day)).collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={count}")?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%dT%H%M%SZ"))?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_weekday(code: &str) -> Result<Weekday, String> {
+    match code.trim().to_ascii_uppercase().as_str() {
+        "MO" => Ok(Weekday::Mon),
+        "TU" => Ok(Weekday::Tue),
+        "WE" => Ok(Weekday::Wed),
+        "TH" => Ok(Weekday::Thu),
+        "FR" => Ok(Weekday::Fri),
+        "SA" => Ok(Weekday::Sat),
+        "SU" => Ok(Weekday::Sun),
+        _ => Err(format!("Unsupported BYDAY value: {code}")),
+    }
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+/// Reads `UNTIL` as a UTC date-time (`20250131T170000Z`) or a date, which
+/// includes the whole day.
+fn parse_until(value: &str) -> Result<DateTime<Utc>, String> {
+    let invalid = || format!("UNTIL must be a date such as 20250131 or 20250131T170000Z: {value}");
+    if let Some(date_time) = value.strip_suffix('Z') {
+        return chrono::NaiveDateTime::parse_from_str(date_time, "%Y%m%dT%H%M%S")
+            .map(|date_time| date_time.and_utc())
+            .map_err(|_| invalid());
+    }
+
+    let end_of_day = NaiveTime::from_hms_opt(23, 59, 59).unwrap_or_default();
+    NaiveDate::parse_from_str(value, "%Y%m%d")
+        .map(|date| date.and_time(end_of_day).and_utc())
+        .map_err(|_| invalid())
+}
@@ -0,0 +1,98 @@
+//! Named board setups, so a filter combination outlives the page it was
+//! picked on.
+//!
+//! A view keeps everything the board shows apart from the tasks: the filter,
+//! the order of cards, how columns split into lanes and which columns are
+//! shown. Views are shared by everyone using the tracker.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{TaskFilter, TaskSort, TaskSortField, TaskStatus};
+
+#[cfg(feature = "sqlx")]
+use sqlx::Type;
+
+/// How the board splits each column into lanes.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "sqlx", derive(Type))]
+#[cfg_attr(feature = "sqlx", sqlx(type_name = "view_grouping"))]
+pub enum ViewGrouping {
+    /// One lane per column.
+    #[default]
+    None,
+    /// A lane per priority, most urgent first.
+    Priority,
+    /// A lane per assignee, unassigned tasks last.
+    Assignee,
+}
+
+/// A saved board setup.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SavedView {
+    pub id: Uuid,
+    pub name: String,
+    /// What tasks the board loads; its `project` names the board the view
+    /// opens.
+    pub filter: TaskFilter,
+    /// Order of cards within each column; `None` keeps the order they were
+    /// dragged into.
+    pub sort: Option<TaskSort>,
+    #[serde(default)]
+    pub grouping: ViewGrouping,
+    /// States whose columns are shown, in any order; empty shows them all.
+    #[serde(default)]
+    pub columns: Vec<TaskStatus>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A new saved view, or the new settings of one.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SavedViewRequest {
+    pub name: String,
+    #[serde(default)]
+    pub filter: TaskFilter,
+    pub sort: Option<TaskSort>,
+    #[serde(default)]
+    pub grouping: ViewGrouping,
+    #[serde(default)]
+    pub columns: Vec<TaskStatus>,
+}
+
+/// Longest saved view name, in characters.
+pub const MAX_VIEW_NAME_LENGTH: usize = 100;
+
+impl SavedViewRequest {
+    /// Checks the name, sort and columns, trimming the name and dropping
+    /// repeated columns. Cards are sorted on the board, where there is no
+    /// search rank, so views cannot sort by relevance.
+    pub fn normalize(&mut self) -> Result<(), String> {
+        self.name = self.name.trim().to_string();
+        if self.name.is_empty() {
+            return Err("View name cannot be empty".to_string());
+        }
+        if self.name.chars().count() > MAX_VIEW_NAME_LENGTH {
+            return Err(format!(
+                "View name cannot exceed {MAX_VIEW_NAME_LENGTH} characters"
+            ));
+        }
+
+        if self
+            .sort
+            .is_some_and(|sort| sort.field == TaskSortField::Relevance)
+        {
+            return Err("Views cannot sort by relevance".to_string());
+        }
+
+        let mut columns: Vec<TaskStatus> = Vec::with_capacity(self.columns.len());
+        for column in self.columns.drain(..) {
+            if !columns.contains(&column) {
+                columns.push(column);
+            }
+        }
+        self.columns = columns;
+        Ok(())
+    }
+}
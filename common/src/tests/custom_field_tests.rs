@@ -0,0 +1,136 @@
+#[cfg(test)]
+mod custom_field_rule_tests {
+    use crate::custom_fields::*;
+    use crate::DEFAULT_PROJECT_ID;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn field(field_type: CustomFieldType, options: &[&str]) -> CustomField {
+        CustomField {
+            id: Uuid::new_v4(),
+            project_id: DEFAULT_PROJECT_ID,
+            name: "Severity".to_string(),
+            field_type,
+            options: options.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn test_requests_are_trimmed_and_checked() {
+        let mut request = CustomFieldRequest {
+            name: "  Component ".to_string(),
+            field_type: CustomFieldType::MultiSelect,
+            options: vec![" API".to_string(), "UI ".to_string()],
+        };
+        request.normalize().unwrap();
+        assert_eq!(request.name, "Component");
+        assert_eq!(request.options, ["API", "UI"]);
+
+        request.options.push("API".to_string());
+        assert_eq!(request.normalize().unwrap_err(), "Duplicate option: API");
+
+        let mut select = CustomFieldRequest {
+            name: "Severity".to_string(),
+            field_type: CustomFieldType::SingleSelect,
+            options: Vec::new(),
+        };
+        assert!(select.normalize().is_err());
+
+        let mut text = CustomFieldRequest {
+            name: "Customer".to_string(),
+            field_type: CustomFieldType::Text,
+            options: vec!["Acme".to_string()],
+        };
+        assert!(text.normalize().is_err());
+        text.options.clear();
+        text.name = " ".to_string();
+        assert!(text.normalize().is_err());
+    }
+
+    #[test]
+    fn test_values_are_checked_against_the_field_type() {
+        let number = field(CustomFieldType::Number, &[]);
+        assert_eq!(number.check_value(&json!(3.0)).unwrap(), json!(3));
+        assert_eq!(number.check_value(&json!(2.5)).unwrap(), json!(2.5));
+        assert!(number.check_value(&json!("3")).is_err());
+
+        let date = field(CustomFieldType::Date, &[]);
+        assert_eq!(
+            date.check_value(&json!("2026-1-5")).unwrap(),
+            json!("2026-01-05")
+        );
+        assert!(date.check_value(&json!("next week")).is_err());
+
+        let severity = field(CustomFieldType::SingleSelect, &["Minor", "Major"]);
+        assert_eq!(
+            severity.check_value(&json!("Major")).unwrap(),
+            json!("Major")
+        );
+        assert_eq!(
+            severity.check_value(&json!("Blocker")).unwrap_err(),
+            "Severity has no option Blocker"
+        );
+
+        let components = field(CustomFieldType::MultiSelect, &["API", "UI", "Docs"]);
+        assert_eq!(
+            components
+                .check_value(&json!(["Docs", "API", "Docs"]))
+                .unwrap(),
+            json!(["API", "Docs"])
+        );
+        assert!(components.check_value(&json!("API")).is_err());
+
+        let checkbox = field(CustomFieldType::Checkbox, &[]);
+        assert_eq!(checkbox.check_value(&json!(true)).unwrap(), json!(true));
+        assert_eq!(
+            checkbox.check_value(&json!("yes")).unwrap_err(),
+            "Severity must be true or false"
+        );
+
+        let text = field(CustomFieldType::Text, &[]);
+        let long = "x".repeat(MAX_TEXT_VALUE_LENGTH + 1);
+        assert!(text.check_value(&json!(long)).is_err());
+    }
+
+    #[test]
+    fn test_typed_values_are_parsed() {
+        let number = field(CustomFieldType::Number, &[]);
+        assert_eq!(number.parse_value(" 5 ").unwrap(), json!(5));
+        assert!(number.parse_value("NaN").is_err());
+
+        let components = field(CustomFieldType::MultiSelect, &["API", "UI"]);
+        assert_eq!(components.parse_value("UI").unwrap(), json!("UI"));
+        assert!(components.parse_value("Docs").is_err());
+
+        let checkbox = field(CustomFieldType::Checkbox, &[]);
+        assert_eq!(checkbox.parse_value("false").unwrap(), json!(false));
+    }
+
+    #[test]
+    fn test_value_sets_drop_unset_values_and_unknown_fields_fail() {
+        let points = field(CustomFieldType::Number, &[]);
+        let customer = field(CustomFieldType::Text, &[]);
+        let fields = [points.clone(), customer.clone()];
+
+        let mut values = CustomFieldValues::new();
+        values.insert(points.id, json!(8.0));
+        values.insert(customer.id, json!(""));
+        check_values(&mut values, &fields).unwrap();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[&points.id], json!(8));
+
+        values.insert(Uuid::new_v4(), json!("stray"));
+        assert!(check_values(&mut values, &fields)
+            .unwrap_err()
+            .starts_with("Unknown custom field"));
+    }
+
+    #[test]
+    fn test_filters_match_single_values_and_list_members() {
+        assert!(value_matches(&json!("Major"), &json!("Major")));
+        assert!(!value_matches(&json!("Minor"), &json!("Major")));
+        assert!(value_matches(&json!(["API", "UI"]), &json!("UI")));
+        assert!(!value_matches(&json!(["API"]), &json!("UI")));
+        assert!(value_matches(&json!(3), &json!(3)));
+    }
+}
@@ -7,22 +7,24 @@ mod tests {
 
     #[test]
     fn test_task_status_default() {
-        let status = TaskStatus::default();
-        assert_eq!(status, TaskStatus::Todo);
+        let workflow = default_workflow();
+        assert_eq!(workflow[0].name, TaskStatus::new("Todo"));
+        assert_eq!(workflow[0].category, StatusCategory::Todo);
+        assert_eq!(StatusCategory::default(), StatusCategory::Todo);
     }
 
     #[test]
     fn test_task_status_serialization() {
         assert_eq!(
-            serde_json::to_string(&TaskStatus::Todo).unwrap(),
+            serde_json::to_string(&TaskStatus::new("Todo")).unwrap(),
             "\"Todo\""
         );
         assert_eq!(
-            serde_json::to_string(&TaskStatus::InProgress).unwrap(),
+            serde_json::to_string(&TaskStatus::new("InProgress")).unwrap(),
             "\"InProgress\""
         );
         assert_eq!(
-            serde_json::to_string(&TaskStatus::Completed).unwrap(),
+            serde_json::to_string(&TaskStatus::new("Completed")).unwrap(),
             "\"Completed\""
         );
     }
@@ -31,22 +33,27 @@ mod tests {
     fn test_task_status_deserialization() {
         assert_eq!(
             serde_json::from_str::<TaskStatus>("\"Todo\"").unwrap(),
-            TaskStatus::Todo
+            TaskStatus::new("Todo")
         );
         assert_eq!(
             serde_json::from_str::<TaskStatus>("\"InProgress\"").unwrap(),
-            TaskStatus::InProgress
+            TaskStatus::new("InProgress")
         );
         assert_eq!(
             serde_json::from_str::<TaskStatus>("\"Completed\"").unwrap(),
-            TaskStatus::Completed
+            TaskStatus::new("Completed")
         );
     }
 
     #[test]
     fn test_task_status_invalid_deserialization() {
-        assert!(serde_json::from_str::<TaskStatus>("\"Invalid\"").is_err());
-        assert!(serde_json::from_str::<TaskStatus>("\"todo\"").is_err()); // case sensitive
+        // Any name parses; the project's workflow decides which are valid
+        assert_eq!(
+            serde_json::from_str::<TaskStatus>("\"todo\"").unwrap(),
+            TaskStatus::new("todo")
+        );
+        assert!(serde_json::from_str::<TaskStatus>("42").is_err());
+        assert!(serde_json::from_str::<TaskStatus>("null").is_err());
     }
 
     #[test]
@@ -60,11 +67,29 @@ mod tests {
             id: task_id,
             title: "Test Task".to_string(),
             description: Some("Test Description".to_string()),
-            status: TaskStatus::InProgress,
+            status: TaskStatus::new("InProgress"),
+            status_category: StatusCategory::Doing,
             priority: TaskPriority::High,
             due_date: Some(due_date),
             created_at,
             updated_at,
+            version: 1,
+            deleted_at: None,
+            comment_count: 0,
+            search: None,
+            tags: Vec::new(),
+            assignee_id: None,
+            project_id: DEFAULT_PROJECT_ID,
+            parent_id: None,
+            subtask_count: 0,
+            completed_subtask_count: 0,
+            is_blocked: false,
+            recurrence: None,
+            rank: String::new(),
+            estimate_minutes: None,
+            logged_minutes: 0,
+            timer_started_at: None,
+            custom_fields: Default::default(),
         };
 
         let json = serde_json::to_string(&task).unwrap();
@@ -90,11 +115,29 @@ mod tests {
             id: task_id,
             title: "Minimal Task".to_string(),
             description: None,
-            status: TaskStatus::Todo,
+            status: TaskStatus::new("Todo"),
+            status_category: StatusCategory::Todo,
             priority: TaskPriority::Low,
             due_date: None,
             created_at,
             updated_at,
+            version: 1,
+            deleted_at: None,
+            comment_count: 0,
+            search: None,
+            tags: Vec::new(),
+            assignee_id: None,
+            project_id: DEFAULT_PROJECT_ID,
+            parent_id: None,
+            subtask_count: 0,
+            completed_subtask_count: 0,
+            is_blocked: false,
+            recurrence: None,
+            rank: String::new(),
+            estimate_minutes: None,
+            logged_minutes: 0,
+            timer_started_at: None,
+            custom_fields: Default::default(),
         };
 
         let json = serde_json::to_string(&task).unwrap();
@@ -111,6 +154,7 @@ mod tests {
             description: Some("Task description".to_string()),
             priority: TaskPriority::High,
             due_date: Some(Utc::now() + chrono::Duration::days(7)),
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -129,6 +173,7 @@ mod tests {
             description: None,
             priority: TaskPriority::Medium,
             due_date: None,
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -143,11 +188,18 @@ mod tests {
     #[test]
     fn test_update_task_request() {
         let request = UpdateTaskRequest {
-            title: Some("Updated Title".to_string()),
-            description: Some("Updated Description".to_string()),
-            status: Some(TaskStatus::Completed),
-            priority: Some(TaskPriority::Urgent),
-            due_date: Some(Utc::now() + chrono::Duration::days(3)),
+            title: Patch::Value("Updated Title".to_string()),
+            description: Patch::Value("Updated Description".to_string()),
+            status: Patch::Value(TaskStatus::new("Completed")),
+            priority: Patch::Value(TaskPriority::Urgent),
+            due_date: Patch::Value(Utc::now() + chrono::Duration::days(3)),
+            tags: Patch::Absent,
+            assignee_id: Patch::Absent,
+            project_id: Patch::Absent,
+            parent_id: Patch::Absent,
+            recurrence: Patch::Absent,
+            estimate_minutes: Patch::Absent,
+            custom_fields: Patch::Absent,
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -163,30 +215,96 @@ mod tests {
     #[test]
     fn test_update_task_request_all_none() {
         let request = UpdateTaskRequest {
-            title: None,
-            description: None,
-            status: None,
-            priority: None,
-            due_date: None,
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(json, "{}");
         let deserialized: UpdateTaskRequest = serde_json::from_str(&json).unwrap();
 
-        assert_eq!(deserialized.title, None);
-        assert_eq!(deserialized.description, None);
-        assert_eq!(deserialized.status, None);
-        assert_eq!(deserialized.priority, None);
-        assert_eq!(deserialized.due_date, None);
+        assert_eq!(deserialized.title, Patch::Absent);
+        assert_eq!(deserialized.description, Patch::Absent);
+        assert_eq!(deserialized.status, Patch::Absent);
+        assert_eq!(deserialized.priority, Patch::Absent);
+        assert_eq!(deserialized.due_date, Patch::Absent);
+    }
+
+    #[test]
+    fn test_update_task_request_distinguishes_null_from_absent() {
+        let request: UpdateTaskRequest =
+            serde_json::from_str(r#"{"description": null, "status": "Completed"}"#).unwrap();
+
+        assert_eq!(request.title, Patch::Absent);
+        assert_eq!(request.description, Patch::Null);
+        assert_eq!(request.status, Patch::Value(TaskStatus::new("Completed")));
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"description": null, "status": "Completed"})
+        );
+    }
+
+    #[test]
+    fn test_replace_task_request_requires_every_field() {
+        let json = r#"{
+            "title": "Replaced",
+            "description": null,
+            "status": "InProgress",
+            "priority": "High",
+            "due_date": null,
+            "tags": [],
+            "assignee_id": null,
+            "parent_id": null,
+            "recurrence": null,
+            "estimate_minutes": null,
+            "custom_fields": {}
+        }"#;
+        let request: ReplaceTaskRequest = serde_json::from_str(json).unwrap();
+
+        let update = UpdateTaskRequest::from(request);
+        assert_eq!(update.title, Patch::Value("Replaced".to_string()));
+        assert_eq!(update.description, Patch::Null);
+        assert_eq!(update.status, Patch::Value(TaskStatus::new("InProgress")));
+        assert_eq!(update.due_date, Patch::Null);
+
+        let missing_due_date = r#"{
+            "title": "Replaced",
+            "description": null,
+            "status": "InProgress",
+            "priority": "High",
+            "tags": [],
+            "assignee_id": null,
+            "parent_id": null,
+            "recurrence": null,
+            "estimate_minutes": null,
+            "custom_fields": {}
+        }"#;
+        assert!(serde_json::from_str::<ReplaceTaskRequest>(missing_due_date).is_err());
+
+        let missing_estimate = r#"{
+            "title": "Replaced",
+            "description": null,
+            "status": "InProgress",
+            "priority": "High",
+            "due_date": null,
+            "tags": [],
+            "assignee_id": null,
+            "parent_id": null,
+            "recurrence": null,
+            "custom_fields": {}
+        }"#;
+        assert!(serde_json::from_str::<ReplaceTaskRequest>(missing_estimate).is_err());
     }
 
     #[test]
     fn test_task_filter() {
         let filter = TaskFilter {
-            status: Some(TaskStatus::InProgress),
-            priority: Some(TaskPriority::High),
+            status: vec![TaskStatus::new("InProgress")],
+            priority: vec![TaskPriority::High],
             due_before: Some(Utc::now() + chrono::Duration::days(7)),
             due_after: Some(Utc::now() - chrono::Duration::days(1)),
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&filter).unwrap();
@@ -201,17 +319,18 @@ mod tests {
     #[test]
     fn test_task_filter_empty() {
         let filter = TaskFilter {
-            status: None,
-            priority: None,
+            status: Vec::new(),
+            priority: Vec::new(),
             due_before: None,
             due_after: None,
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&filter).unwrap();
         let deserialized: TaskFilter = serde_json::from_str(&json).unwrap();
 
-        assert_eq!(deserialized.status, None);
-        assert_eq!(deserialized.priority, None);
+        assert!(deserialized.status.is_empty());
+        assert!(deserialized.priority.is_empty());
         assert_eq!(deserialized.due_before, None);
         assert_eq!(deserialized.due_after, None);
     }
@@ -226,22 +345,58 @@ mod tests {
             id: task_id,
             title: "Task".to_string(),
             description: Some("Description".to_string()),
-            status: TaskStatus::Todo,
+            status: TaskStatus::new("Todo"),
+            status_category: StatusCategory::Todo,
             priority: TaskPriority::Medium,
             due_date: None,
             created_at,
             updated_at,
+            version: 1,
+            deleted_at: None,
+            comment_count: 0,
+            search: None,
+            tags: Vec::new(),
+            assignee_id: None,
+            project_id: DEFAULT_PROJECT_ID,
+            parent_id: None,
+            subtask_count: 0,
+            completed_subtask_count: 0,
+            is_blocked: false,
+            recurrence: None,
+            rank: String::new(),
+            estimate_minutes: None,
+            logged_minutes: 0,
+            timer_started_at: None,
+            custom_fields: Default::default(),
         };
 
         let task2 = Task {
             id: task_id,
             title: "Task".to_string(),
             description: Some("Description".to_string()),
-            status: TaskStatus::Todo,
+            status: TaskStatus::new("Todo"),
+            status_category: StatusCategory::Todo,
             priority: TaskPriority::Medium,
             due_date: None,
             created_at,
             updated_at,
+            version: 1,
+            deleted_at: None,
+            comment_count: 0,
+            search: None,
+            tags: Vec::new(),
+            assignee_id: None,
+            project_id: DEFAULT_PROJECT_ID,
+            parent_id: None,
+            subtask_count: 0,
+            completed_subtask_count: 0,
+            is_blocked: false,
+            recurrence: None,
+            rank: String::new(),
+            estimate_minutes: None,
+            logged_minutes: 0,
+            timer_started_at: None,
+            custom_fields: Default::default(),
         };
 
         assert_eq!(task1, task2);
@@ -258,22 +413,58 @@ mod tests {
             id: task_id1,
             title: "Task".to_string(),
             description: Some("Description".to_string()),
-            status: TaskStatus::Todo,
+            status: TaskStatus::new("Todo"),
+            status_category: StatusCategory::Todo,
             priority: TaskPriority::Medium,
             due_date: None,
             created_at,
             updated_at,
+            version: 1,
+            deleted_at: None,
+            comment_count: 0,
+            search: None,
+            tags: Vec::new(),
+            assignee_id: None,
+            project_id: DEFAULT_PROJECT_ID,
+            parent_id: None,
+            subtask_count: 0,
+            completed_subtask_count: 0,
+            is_blocked: false,
+            recurrence: None,
+            rank: String::new(),
+            estimate_minutes: None,
+            logged_minutes: 0,
+            timer_started_at: None,
+            custom_fields: Default::default(),
         };
 
         let task2 = Task {
             id: task_id2,
             title: "Task".to_string(),
             description: Some("Description".to_string()),
-            status: TaskStatus::Todo,
+            status: TaskStatus::new("Todo"),
+            status_category: StatusCategory::Todo,
             priority: TaskPriority::Medium,
             due_date: None,
             created_at,
             updated_at,
+            version: 1,
+            deleted_at: None,
+            comment_count: 0,
+            search: None,
+            tags: Vec::new(),
+            assignee_id: None,
+            project_id: DEFAULT_PROJECT_ID,
+            parent_id: None,
+            subtask_count: 0,
+            completed_subtask_count: 0,
+            is_blocked: false,
+            recurrence: None,
+            rank: String::new(),
+            estimate_minutes: None,
+            logged_minutes: 0,
+            timer_started_at: None,
+            custom_fields: Default::default(),
         };
 
         assert_ne!(task1, task2);
@@ -282,8 +473,8 @@ mod tests {
     #[test]
     fn test_enum_derive_traits() {
         // Test Clone/Copy
-        let status = TaskStatus::InProgress;
-        let status_clone = status; // Use copy instead of clone for Copy types
+        let status = TaskStatus::new("InProgress");
+        let status_clone = status.clone();
         assert_eq!(status, status_clone);
 
         let priority = TaskPriority::High;
@@ -291,15 +482,12 @@ mod tests {
         assert_eq!(priority, priority_clone);
 
         // Test Copy
-        let status_copy = status;
-        assert_eq!(status, status_copy);
-
         let priority_copy = priority;
         assert_eq!(priority, priority_copy);
 
         // Test Debug
-        let debug_str = format!("{:?}", TaskStatus::Completed);
-        assert_eq!(debug_str, "Completed");
+        let debug_str = format!("{:?}", TaskStatus::new("Completed"));
+        assert_eq!(debug_str, "TaskStatus(\"Completed\")");
 
         let debug_str = format!("{:?}", TaskPriority::Urgent);
         assert_eq!(debug_str, "Urgent");
@@ -315,11 +503,29 @@ mod tests {
             id: task_id,
             title: "Debug Test".to_string(),
             description: None,
-            status: TaskStatus::Todo,
+            status: TaskStatus::new("Todo"),
+            status_category: StatusCategory::Todo,
             priority: TaskPriority::Low,
             due_date: None,
             created_at,
             updated_at,
+            version: 1,
+            deleted_at: None,
+            comment_count: 0,
+            search: None,
+            tags: Vec::new(),
+            assignee_id: None,
+            project_id: DEFAULT_PROJECT_ID,
+            parent_id: None,
+            subtask_count: 0,
+            completed_subtask_count: 0,
+            is_blocked: false,
+            recurrence: None,
+            rank: String::new(),
+            estimate_minutes: None,
+            logged_minutes: 0,
+            timer_started_at: None,
+            custom_fields: Default::default(),
         };
 
         // Test Debug
@@ -336,7 +542,7 @@ mod tests {
     #[test]
     fn test_invalid_json_handling() {
         // Test invalid JSON for enums
-        assert!(serde_json::from_str::<TaskStatus>("\"InvalidStatus\"").is_err());
+        assert!(serde_json::from_str::<TaskStatus>("[\"Todo\"]").is_err());
         assert!(serde_json::from_str::<TaskPriority>("\"InvalidPriority\"").is_err());
 
         // Test malformed JSON
@@ -361,4 +567,274 @@ mod tests {
 
         assert_eq!(uuid, deserialized);
     }
+
+    #[test]
+    fn test_task_sort_wire_format() {
+        let sort: TaskSort = "-priority".parse().unwrap();
+        assert_eq!(
+            sort,
+            TaskSort::new(TaskSortField::Priority, SortDirection::Desc)
+        );
+        assert_eq!(sort.to_string(), "-priority");
+
+        let sort: TaskSort = "due_date".parse().unwrap();
+        assert_eq!(
+            sort,
+            TaskSort::new(TaskSortField::DueDate, SortDirection::Asc)
+        );
+        assert_eq!(serde_json::to_string(&sort).unwrap(), "\"due_date\"");
+        assert_eq!(
+            serde_json::from_str::<TaskSort>("\"-title\"").unwrap(),
+            TaskSort::new(TaskSortField::Title, SortDirection::Desc)
+        );
+
+        assert!("colour".parse::<TaskSort>().is_err());
+        assert!(serde_json::from_str::<TaskSort>("\"-\"").is_err());
+        assert_eq!(TaskSort::default().to_string(), "-created_at");
+    }
+
+    #[test]
+    fn test_page_serialization() {
+        let page = Page {
+            items: vec![1, 2, 3],
+            next_cursor: Some("abc".to_string()),
+            total_estimate: 10,
+        };
+
+        let json = serde_json::to_string(&page).unwrap();
+        let deserialized: Page<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, page);
+
+        let last: Page<i32> =
+            serde_json::from_str(r#"{"items":[],"next_cursor":null,"total_estimate":0}"#).unwrap();
+        assert!(last.items.is_empty());
+        assert!(last.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_comment_serialization() {
+        let comment = Comment {
+            id: Uuid::new_v4(),
+            task_id: Uuid::new_v4(),
+            body: "Looks **good**".to_string(),
+            author: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let json = serde_json::to_string(&comment).unwrap();
+        let deserialized: Comment = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, comment);
+
+        // Tasks serialized before comments existed have none
+        let task = serde_json::json!({
+            "id": Uuid::new_v4(),
+            "title": "Old",
+            "description": null,
+            "status": "Todo",
+            "priority": "Low",
+            "due_date": null,
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z",
+            "version": 1
+        });
+        let task: Task = serde_json::from_value(task).unwrap();
+        assert_eq!(task.comment_count, 0);
+    }
+
+    #[test]
+    fn test_task_tags_serialization() {
+        // Tag names are enough to label a task; missing tags mean none
+        let request: CreateTaskRequest = serde_json::from_value(serde_json::json!({
+            "title": "Labelled",
+            "description": null,
+            "priority": "Low",
+            "due_date": null,
+            "tags": ["bug", "ui"]
+        }))
+        .unwrap();
+        assert_eq!(request.tags, ["bug", "ui"]);
+
+        // A replacement lists every tag, so leaving them out is refused
+        let mut replace = serde_json::json!({
+            "title": "Labelled",
+            "description": null,
+            "status": "Todo",
+            "priority": "Low",
+            "due_date": null,
+            "assignee_id": null,
+            "parent_id": null,
+            "recurrence": null,
+            "estimate_minutes": null,
+            "custom_fields": {}
+        });
+        assert!(serde_json::from_value::<ReplaceTaskRequest>(replace.clone()).is_err());
+        replace["tags"] = serde_json::json!([]);
+        let replace: ReplaceTaskRequest = serde_json::from_value(replace).unwrap();
+        assert_eq!(
+            UpdateTaskRequest::from(replace).tags,
+            Patch::Value(Vec::new())
+        );
+
+        let update: UpdateTaskRequest =
+            serde_json::from_value(serde_json::json!({ "title": "Renamed" })).unwrap();
+        assert!(update.tags.is_absent());
+
+        let tag = Tag {
+            id: Uuid::new_v4(),
+            name: "bug".to_string(),
+            color: "#ef4444".to_string(),
+        };
+        let json = serde_json::to_string(&tag).unwrap();
+        let deserialized: Tag = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, tag);
+    }
+
+    #[test]
+    fn test_task_assignee_serialization() {
+        let member = Member {
+            id: Uuid::new_v4(),
+            name: "Sarah".to_string(),
+        };
+        let json = serde_json::to_string(&member).unwrap();
+        let deserialized: Member = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, member);
+
+        let update: UpdateTaskRequest =
+            serde_json::from_value(serde_json::json!({ "assignee_id": null })).unwrap();
+        assert_eq!(update.assignee_id, Patch::Null);
+        let update: UpdateTaskRequest =
+            serde_json::from_value(serde_json::json!({ "assignee_id": member.id })).unwrap();
+        assert_eq!(update.assignee_id, Patch::Value(member.id));
+
+        // Tasks serialized before assignees existed are unassigned
+        let task = serde_json::json!({
+            "id": Uuid::new_v4(),
+            "title": "Old",
+            "description": null,
+            "status": "Todo",
+            "priority": "Low",
+            "due_date": null,
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z",
+            "version": 1
+        });
+        let task: Task = serde_json::from_value(task).unwrap();
+        assert_eq!(task.assignee_id, None);
+    }
+
+    #[test]
+    fn test_task_project_serialization() {
+        assert_eq!(
+            DEFAULT_PROJECT_ID.to_string(),
+            "00000000-0000-0000-0000-000000000001"
+        );
+
+        // Tasks serialized before projects existed are in the default project
+        let task = serde_json::json!({
+            "id": Uuid::new_v4(),
+            "title": "Old",
+            "description": null,
+            "status": "Todo",
+            "priority": "Low",
+            "due_date": null,
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z",
+            "version": 1
+        });
+        let task: Task = serde_json::from_value(task).unwrap();
+        assert_eq!(task.project_id, DEFAULT_PROJECT_ID);
+
+        // A replacement without a project leaves the task where it is
+        let replace: ReplaceTaskRequest = serde_json::from_value(serde_json::json!({
+            "title": "Moved",
+            "description": null,
+            "status": "Todo",
+            "priority": "Low",
+            "due_date": null,
+            "tags": [],
+            "assignee_id": null,
+            "parent_id": null,
+            "recurrence": null,
+            "estimate_minutes": null,
+            "custom_fields": {}
+        }))
+        .unwrap();
+        assert_eq!(UpdateTaskRequest::from(replace).project_id, Patch::Absent);
+    }
+
+    #[test]
+    fn test_task_subtask_serialization() {
+        // Tasks serialized before subtasks existed are top-level with no roll-up
+        let task = serde_json::json!({
+            "id": Uuid::new_v4(),
+            "title": "Old",
+            "description": null,
+            "status": "Todo",
+            "priority": "Low",
+            "due_date": null,
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z",
+            "version": 1
+        });
+        let task: Task = serde_json::from_value(task).unwrap();
+        assert_eq!(task.parent_id, None);
+        assert_eq!((task.completed_subtask_count, task.subtask_count), (0, 0));
+        assert!(!task.is_blocked);
+
+        // Unlike the project, a replacement must name the parent; null clears it
+        let mut replace = serde_json::json!({
+            "title": "Lifted",
+            "description": null,
+            "status": "Todo",
+            "priority": "Low",
+            "due_date": null,
+            "tags": [],
+            "assignee_id": null,
+            "recurrence": null,
+            "estimate_minutes": null,
+            "custom_fields": {}
+        });
+        assert!(serde_json::from_value::<ReplaceTaskRequest>(replace.clone()).is_err());
+        replace["parent_id"] = serde_json::Value::Null;
+        let replace: ReplaceTaskRequest = serde_json::from_value(replace).unwrap();
+        assert_eq!(UpdateTaskRequest::from(replace).parent_id, Patch::Null);
+
+        let update: UpdateTaskRequest =
+            serde_json::from_value(serde_json::json!({ "parent_id": null })).unwrap();
+        assert_eq!(update.parent_id, Patch::Null);
+    }
+
+    #[test]
+    fn test_task_time_tracking_serialization() {
+        // Tasks serialized before time tracking have no estimate or time logged
+        let task = serde_json::json!({
+            "id": Uuid::new_v4(),
+            "title": "Old",
+            "description": null,
+            "status": "Todo",
+            "priority": "Low",
+            "due_date": null,
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z",
+            "version": 1
+        });
+        let task: Task = serde_json::from_value(task).unwrap();
+        assert_eq!(task.estimate_minutes, None);
+        assert_eq!(task.logged_minutes, 0);
+        assert_eq!(task.timer_started_at, None);
+
+        let update: UpdateTaskRequest =
+            serde_json::from_value(serde_json::json!({ "estimate_minutes": null })).unwrap();
+        assert_eq!(update.estimate_minutes, Patch::Null);
+
+        let entry: TimeEntryRequest = serde_json::from_value(serde_json::json!({
+            "member_id": Uuid::nil(),
+            "started_at": "2024-01-01T09:00:00Z",
+            "ended_at": "2024-01-01T10:30:00Z"
+        }))
+        .unwrap();
+        assert_eq!(entry.note, None);
+        assert_eq!((entry.ended_at - entry.started_at).num_minutes(), 90);
+    }
 }
@@ -1,3 +1,9 @@
+pub mod custom_field_tests;
 #[cfg(test)]
 pub mod data_structures;
+pub mod query_tests;
+pub mod rank_tests;
+pub mod recurrence_tests;
+pub mod saved_view_tests;
+pub mod transition_tests;
 pub mod validation_tests;
@@ -0,0 +1,216 @@
+#[cfg(test)]
+mod query_language_tests {
+    use crate::query::*;
+    use crate::{TaskFilter, TaskPriority, TaskStatus};
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn condition(negated: bool, condition: Condition) -> QueryTerm {
+        QueryTerm {
+            negated,
+            kind: TermKind::Condition(condition),
+        }
+    }
+
+    fn error(text: &str) -> QueryError {
+        text.parse::<TaskQuery>().unwrap_err()
+    }
+
+    #[test]
+    fn test_parses_every_kind_of_term() {
+        let query: TaskQuery =
+            r#"status:InProgress priority>=High due<2026-11-01 tag:bug -assignee:none "login page""#
+                .parse()
+                .unwrap();
+
+        assert_eq!(
+            query.terms,
+            vec![
+                condition(false, Condition::Status(TaskStatus::new("InProgress"))),
+                condition(
+                    false,
+                    Condition::Priority(Comparison::Ge, TaskPriority::High)
+                ),
+                condition(
+                    false,
+                    Condition::Date(DateField::Due, Comparison::Lt, date(2026, 11, 1))
+                ),
+                condition(false, Condition::Tag("bug".to_string())),
+                condition(true, Condition::Assignee(None)),
+                QueryTerm {
+                    negated: false,
+                    kind: TermKind::Text {
+                        text: "login page".to_string(),
+                        quoted: true,
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fields_and_keywords_ignore_case() {
+        let query: TaskQuery =
+            "Priority:urgent DUE:None Assignee:\"Ada Lovelace\" created>=2026-01-02"
+                .parse()
+                .unwrap();
+
+        assert_eq!(
+            query
+                .conditions()
+                .map(|(_, c)| c.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                Condition::Priority(Comparison::Eq, TaskPriority::Urgent),
+                Condition::NoDueDate,
+                Condition::Assignee(Some("Ada Lovelace".to_string())),
+                Condition::Date(DateField::Created, Comparison::Ge, date(2026, 1, 2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_words_without_a_field_are_free_text() {
+        let query: TaskQuery = "  login  -crash pag* v1.2 ".parse().unwrap();
+
+        assert_eq!(query.conditions().count(), 0);
+        assert_eq!(
+            query.search_text().as_deref(),
+            Some("login -crash pag* v1.2")
+        );
+
+        // Only a known field name before an operator makes a condition
+        let query: TaskQuery = "http://example.com 10:30 colour:red a<b tag:bug"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            query
+                .conditions()
+                .map(|(_, c)| c.clone())
+                .collect::<Vec<_>>(),
+            vec![Condition::Tag("bug".to_string())]
+        );
+        assert_eq!(
+            query.search_text().as_deref(),
+            Some("http://example.com 10:30 colour:red a<b")
+        );
+        assert_eq!(query.to_string().parse::<TaskQuery>().unwrap(), query);
+        assert_eq!("".parse::<TaskQuery>().unwrap(), TaskQuery::default());
+        assert_eq!(TaskQuery::default().search_text(), None);
+    }
+
+    #[test]
+    fn test_errors_point_at_the_offending_text() {
+        let cases = [
+            ("tag:bug tag>bug", 8, "'tag' can only be matched with ':'"),
+            ("status>Todo", 0, "'status' can only be matched with ':'"),
+            ("due>none", 0, "'due' can only be matched with ':'"),
+            ("priority:", 9, "Expected a value after 'priority:'"),
+            (
+                "priority>=Highest",
+                10,
+                "Unknown priority 'Highest', expected Low, Medium, High or Urgent",
+            ),
+            ("due:2026-13-01", 4, "Expected a date like 2026-11-01"),
+            ("bug \"login page", 4, "Unclosed quote"),
+            ("\"\"", 0, "Expected text between the quotes"),
+            ("log\"in", 3, "Unexpected quote inside a word"),
+            ("\"login\"page", 7, "Expected a space between terms"),
+            ("bug - crash", 4, "Expected a term after '-'"),
+        ];
+        for (text, position, message) in cases {
+            assert_eq!(
+                error(text),
+                QueryError {
+                    position,
+                    message: message.to_string(),
+                },
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_error_positions_count_characters() {
+        let err = error("émoji café status>Todo");
+
+        assert_eq!(err.position, 11);
+        assert_eq!(
+            err.to_string(),
+            "'status' can only be matched with ':' at column 12"
+        );
+    }
+
+    #[test]
+    fn test_text_form_round_trips() {
+        let text = r#"status:"In review" priority<=Medium updated>2026-03-04 due:none tag:"good first issue" -assignee:Ada -"login page" crash*"#;
+        let query: TaskQuery = text.parse().unwrap();
+
+        assert_eq!(query.to_string(), text);
+        assert_eq!(query.to_string().parse::<TaskQuery>().unwrap(), query);
+        assert_eq!(
+            "PRIORITY:high  Due:NONE"
+                .parse::<TaskQuery>()
+                .unwrap()
+                .to_string(),
+            "priority:High due:none"
+        );
+    }
+
+    #[test]
+    fn test_serializes_as_its_text() {
+        let query: TaskQuery = "tag:bug -status:Done".parse().unwrap();
+
+        let json = serde_json::to_string(&query).unwrap();
+        assert_eq!(json, "\"tag:bug -status:Done\"");
+        assert_eq!(serde_json::from_str::<TaskQuery>(&json).unwrap(), query);
+        assert!(serde_json::from_str::<TaskQuery>("\"status>Done\"").is_err());
+    }
+
+    #[test]
+    fn test_priority_comparisons() {
+        use TaskPriority::*;
+
+        assert_eq!(Comparison::Eq.priorities(High), vec![High]);
+        assert_eq!(Comparison::Lt.priorities(High), vec![Low, Medium]);
+        assert_eq!(Comparison::Le.priorities(Medium), vec![Low, Medium]);
+        assert_eq!(Comparison::Gt.priorities(Medium), vec![High, Urgent]);
+        assert_eq!(Comparison::Ge.priorities(Urgent), vec![Urgent]);
+        assert!(Comparison::Gt.priorities(Urgent).is_empty());
+    }
+
+    #[test]
+    fn test_day_ranges_cover_whole_days() {
+        let day = date(2026, 11, 1);
+        let start = Utc.with_ymd_and_hms(2026, 11, 1, 0, 0, 0).unwrap();
+        let next = Utc.with_ymd_and_hms(2026, 11, 2, 0, 0, 0).unwrap();
+
+        assert_eq!(Comparison::Eq.day_range(day), (Some(start), Some(next)));
+        assert_eq!(Comparison::Lt.day_range(day), (None, Some(start)));
+        assert_eq!(Comparison::Le.day_range(day), (None, Some(next)));
+        assert_eq!(Comparison::Gt.day_range(day), (Some(next), None));
+        assert_eq!(Comparison::Ge.day_range(day), (Some(start), None));
+    }
+
+    #[test]
+    fn test_filter_search_text_joins_q_and_query_text() {
+        let mut filter = TaskFilter {
+            q: Some("crash".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(filter.search_text().as_deref(), Some("crash"));
+
+        filter.query = Some("tag:bug -\"login page\"".parse().unwrap());
+        assert_eq!(
+            filter.search_text().as_deref(),
+            Some("crash -\"login page\"")
+        );
+
+        filter.q = None;
+        filter.query = Some("tag:bug".parse().unwrap());
+        assert_eq!(filter.search_text(), None);
+    }
+}
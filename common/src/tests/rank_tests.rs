@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod rank_ordering_tests {
+    use crate::rank::between;
+
+    #[test]
+    fn test_first_rank_is_the_middle() {
+        assert_eq!(between(None, None), "V");
+        assert!(between(None, Some("V")).as_str() < "V");
+        assert!(between(Some("V"), None).as_str() > "V");
+    }
+
+    #[test]
+    fn test_rank_falls_between_neighbours() {
+        for (after, before) in [
+            ("V", "W"),
+            ("1", "2"),
+            ("0001", "0002"),
+            ("V", "V1"),
+            ("Vz", "W"),
+            ("zz", "zzV"),
+            ("00000001V", "00000002V"),
+        ] {
+            let rank = between(Some(after), Some(before));
+            assert!(after < rank.as_str(), "{after} < {rank}");
+            assert!(rank.as_str() < before, "{rank} < {before}");
+            assert!(!rank.ends_with('0'), "{rank} ends in 0");
+        }
+    }
+
+    #[test]
+    fn test_repeated_inserts_keep_order() {
+        // Always dropping just below the top card
+        let top = between(None, None);
+        let mut next = between(Some(&top), None);
+        for _ in 0..200 {
+            let rank = between(Some(&top), Some(&next));
+            assert!(top < rank && rank < next);
+            next = rank;
+        }
+
+        // Always dropping just above the bottom card
+        let bottom = between(None, None);
+        let mut previous = between(None, Some(&bottom));
+        for _ in 0..200 {
+            let rank = between(Some(&previous), Some(&bottom));
+            assert!(previous < rank && rank < bottom);
+            previous = rank;
+        }
+    }
+
+    #[test]
+    fn test_adding_at_the_ends_keeps_ranks_short() {
+        let mut bottom = between(None, None);
+        let mut top = bottom.clone();
+        for _ in 0..10_000 {
+            let rank = between(Some(&bottom), None);
+            assert!(rank > bottom && !rank.ends_with('0'));
+            bottom = rank;
+
+            let rank = between(None, Some(&top));
+            assert!(rank < top && !rank.ends_with('0'));
+            top = rank;
+        }
+        assert_eq!(bottom.len(), 6);
+        assert_eq!(top.len(), 6);
+
+        // Ranks from before the whole digits were stepped still come first
+        assert!(between(Some("00000001V"), None).as_str() > "00000001V");
+        assert!(between(Some("zzV"), None).as_str() > "zzV");
+        assert!(between(Some("zzzzzz"), None).as_str() > "zzzzzz");
+        assert!(between(None, Some("000001")).as_str() < "000001");
+    }
+
+    #[test]
+    fn test_out_of_order_bound_is_ignored() {
+        let rank = between(Some("W"), Some("V"));
+        assert!(rank.as_str() > "W");
+        let rank = between(Some("V"), Some("V"));
+        assert!(rank.as_str() > "V");
+    }
+}
@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod recurrence_rule_tests {
+    use crate::recurrence::*;
+    use chrono::{DateTime, TimeZone, Utc, Weekday};
+
+    fn at(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, 9, 30, 0).unwrap()
+    }
+
+    fn rule(text: &str) -> Recurrence {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn test_rules_parse_into_canonical_form() {
+        let recurrence = rule("rrule:freq=weekly;byday=th,mo,th;interval=2;count=3");
+        assert_eq!(recurrence.frequency, Frequency::Weekly);
+        assert_eq!(recurrence.interval, 2);
+        assert_eq!(recurrence.by_day, vec![Weekday::Mon, Weekday::Thu]);
+        assert_eq!(recurrence.count, Some(3));
+        assert_eq!(
+            recurrence.to_string(),
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH;COUNT=3"
+        );
+
+        // A date-only UNTIL covers the whole day
+        let recurrence = rule("FREQ=DAILY;UNTIL=20250131");
+        assert_eq!(
+            recurrence.until,
+            Some(Utc.with_ymd_and_hms(2025, 1, 31, 23, 59, 59).unwrap())
+        );
+        assert_eq!(recurrence.to_string(), "FREQ=DAILY;UNTIL=20250131T235959Z");
+        assert_eq!(rule(&recurrence.to_string()), recurrence);
+    }
+
+    #[test]
+    fn test_unsupported_rules_are_refused() {
+        for text in [
+            "",
+            "INTERVAL=2",
+            "FREQ=HOURLY",
+            "FREQ=DAILY;INTERVAL=0",
+            "FREQ=DAILY;INTERVAL=1001",
+            "FREQ=DAILY;BYDAY=MO",
+            "FREQ=WEEKLY;BYDAY=XX",
+            "FREQ=DAILY;COUNT=0",
+            "FREQ=DAILY;COUNT=2;UNTIL=20250131",
+            "FREQ=DAILY;UNTIL=tomorrow",
+            "FREQ=DAILY;BYMONTH=1",
+            "FREQ",
+        ] {
+            assert!(text.parse::<Recurrence>().is_err(), "{text} should fail");
+        }
+    }
+
+    #[test]
+    fn test_daily_and_weekly_rules_keep_the_time_of_day() {
+        assert_eq!(
+            rule("FREQ=DAILY;INTERVAL=3").upcoming(at(2025, 1, 30), 2),
+            vec![at(2025, 2, 2), at(2025, 2, 5)]
+        );
+        assert_eq!(
+            rule("FREQ=WEEKLY").upcoming(at(2025, 1, 6), 2),
+            vec![at(2025, 1, 13), at(2025, 1, 20)]
+        );
+    }
+
+    #[test]
+    fn test_weekly_rules_with_days_skip_weeks_between_intervals() {
+        // 2025-01-06 is a Monday
+        assert_eq!(
+            rule("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH").upcoming(at(2025, 1, 6), 4),
+            vec![
+                at(2025, 1, 9),
+                at(2025, 1, 20),
+                at(2025, 1, 23),
+                at(2025, 2, 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_monthly_and_yearly_rules_skip_missing_days() {
+        assert_eq!(
+            rule("FREQ=MONTHLY").upcoming(at(2025, 1, 31), 3),
+            vec![at(2025, 3, 31), at(2025, 5, 31), at(2025, 7, 31)]
+        );
+        assert_eq!(
+            rule("FREQ=YEARLY").upcoming(at(2024, 2, 29), 1),
+            vec![at(2028, 2, 29)]
+        );
+    }
+
+    #[test]
+    fn test_series_end_after_count_or_until() {
+        let (due, next) = rule("FREQ=DAILY;COUNT=2").next(at(2025, 1, 1)).unwrap();
+        assert_eq!(due, at(2025, 1, 2));
+        assert_eq!(next.count, Some(1));
+        assert_eq!(next.next(due), None);
+
+        assert_eq!(
+            rule("FREQ=DAILY;UNTIL=20250103").upcoming(at(2025, 1, 1), 10),
+            vec![at(2025, 1, 2), at(2025, 1, 3)]
+        );
+    }
+
+    #[test]
+    fn test_rules_are_described_in_words() {
+        assert_eq!(rule("FREQ=DAILY").describe(), "Every day");
+        assert_eq!(
+            rule("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH;COUNT=3").describe(),
+            "Every 2 weeks on Mon, Thu, 3 more"
+        );
+        assert_eq!(
+            rule("FREQ=MONTHLY;UNTIL=20251231").describe(),
+            "Every month until 2025-12-31"
+        );
+    }
+}
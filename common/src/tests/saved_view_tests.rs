@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod saved_view_rule_tests {
+    use crate::saved_views::*;
+    use crate::{SortDirection, TaskFilter, TaskSort, TaskSortField, TaskStatus};
+
+    #[test]
+    fn test_requests_are_trimmed_and_checked() {
+        let mut request = SavedViewRequest {
+            name: "  Urgent bugs ".to_string(),
+            columns: vec![
+                TaskStatus::new("Todo"),
+                TaskStatus::new("InProgress"),
+                TaskStatus::new("Todo"),
+            ],
+            ..Default::default()
+        };
+        request.normalize().unwrap();
+        assert_eq!(request.name, "Urgent bugs");
+        assert_eq!(
+            request.columns,
+            [TaskStatus::new("Todo"), TaskStatus::new("InProgress")]
+        );
+
+        request.name = " ".to_string();
+        assert_eq!(
+            request.normalize().unwrap_err(),
+            "View name cannot be empty"
+        );
+        request.name = "x".repeat(MAX_VIEW_NAME_LENGTH + 1);
+        assert!(request.normalize().is_err());
+
+        let mut by_relevance = SavedViewRequest {
+            name: "Search".to_string(),
+            sort: Some(TaskSort::new(TaskSortField::Relevance, SortDirection::Desc)),
+            ..Default::default()
+        };
+        assert_eq!(
+            by_relevance.normalize().unwrap_err(),
+            "Views cannot sort by relevance"
+        );
+    }
+
+    #[test]
+    fn test_requests_read_with_defaults() {
+        let request: SavedViewRequest = serde_json::from_str(
+            r#"{"name": "Mine", "sort": "-due_date", "filter": {"priority": ["High"]}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            request.sort,
+            Some(TaskSort::new(TaskSortField::DueDate, SortDirection::Desc))
+        );
+        assert_eq!(request.grouping, ViewGrouping::None);
+        assert!(request.columns.is_empty());
+        assert_eq!(
+            request.filter,
+            TaskFilter {
+                priority: vec![crate::TaskPriority::High],
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,155 @@
+#[cfg(test)]
+mod transition_rule_tests {
+    use crate::transition::*;
+    use crate::{
+        default_workflow, StatusCategory, Task, TaskPriority, TaskStatus, Workflow,
+        DEFAULT_PROJECT_ID,
+    };
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    /// The default workflow where Todo only leads to InProgress, which needs
+    /// a description, and urgent tasks need a due date.
+    fn guarded_workflow() -> Workflow {
+        let mut states = default_workflow();
+        states[0].next = Some(vec![TaskStatus::new("InProgress")]);
+        states[1].requires = vec![TaskField::Description];
+        Workflow {
+            project_id: DEFAULT_PROJECT_ID,
+            states,
+            priority_rules: vec![PriorityRule {
+                priority: TaskPriority::Urgent,
+                requires: vec![TaskField::DueDate],
+            }],
+        }
+    }
+
+    fn task(status: &str, description: Option<&str>) -> Task {
+        Task {
+            id: Uuid::new_v4(),
+            title: "Task".to_string(),
+            description: description.map(ToString::to_string),
+            status: TaskStatus::new(status),
+            status_category: StatusCategory::Todo,
+            priority: TaskPriority::Medium,
+            due_date: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            version: 1,
+            deleted_at: None,
+            comment_count: 0,
+            tags: Vec::new(),
+            assignee_id: None,
+            project_id: DEFAULT_PROJECT_ID,
+            parent_id: None,
+            subtask_count: 0,
+            completed_subtask_count: 0,
+            is_blocked: false,
+            recurrence: None,
+            rank: String::new(),
+            estimate_minutes: None,
+            logged_minutes: 0,
+            timer_started_at: None,
+            custom_fields: Default::default(),
+            search: None,
+        }
+    }
+
+    #[test]
+    fn test_states_without_a_next_list_allow_any_move() {
+        let workflow = guarded_workflow();
+        let status = TaskStatus::new;
+        assert!(workflow.allows(&status("Todo"), &status("InProgress")));
+        assert!(!workflow.allows(&status("Todo"), &status("Completed")));
+        assert!(workflow.allows(&status("Todo"), &status("Todo")));
+        assert!(workflow.allows(&status("Completed"), &status("Todo")));
+    }
+
+    #[test]
+    fn test_refused_moves_name_the_allowed_ones() {
+        let workflow = guarded_workflow();
+        let from = TaskStatus::new("Todo");
+        let error = workflow
+            .check_transition(Some(&from), &workflow.states[2], FilledFields::default())
+            .unwrap_err();
+        assert_eq!(
+            error,
+            TransitionError::TransitionNotAllowed {
+                from: from.clone(),
+                to: TaskStatus::new("Completed"),
+                allowed: vec![TaskStatus::new("InProgress")],
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Cannot move a task from Todo to Completed"
+        );
+
+        // A task from elsewhere only meets the guard
+        assert!(workflow
+            .check_transition(None, &workflow.states[2], FilledFields::default())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_guards_need_their_fields_filled_in() {
+        let workflow = guarded_workflow();
+        let from = TaskStatus::new("Todo");
+        let error = workflow
+            .check_transition(Some(&from), &workflow.states[1], FilledFields::default())
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Moving to InProgress requires: description"
+        );
+
+        let fields = FilledFields::of(&task("Todo", Some("   ")));
+        assert!(!fields.has(TaskField::Description));
+        let fields = FilledFields::of(&task("Todo", Some("Steps to reproduce")));
+        assert!(workflow
+            .check_transition(Some(&from), &workflow.states[1], fields)
+            .is_ok());
+
+        let error = workflow
+            .check_priority(TaskPriority::Urgent, fields)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Urgent priority requires: due date");
+        assert!(workflow.check_priority(TaskPriority::High, fields).is_ok());
+    }
+
+    #[test]
+    fn test_allowed_next_states_follow_rules_and_guards() {
+        let workflow = guarded_workflow();
+        let names = |task: &Task| -> Vec<String> {
+            workflow
+                .allowed_next(task)
+                .iter()
+                .map(|state| state.name.to_string())
+                .collect()
+        };
+        assert!(names(&task("Todo", None)).is_empty());
+        assert_eq!(names(&task("Todo", Some("Details"))), ["InProgress"]);
+        assert_eq!(names(&task("Completed", None)), ["Todo", "Backlog"]);
+    }
+
+    #[test]
+    fn test_errors_serialize_with_a_code() {
+        let error = TransitionError::StateRequiresFields {
+            status: TaskStatus::new("InProgress"),
+            missing: vec![TaskField::Description, TaskField::DueDate],
+        };
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["code"], "state_requires_fields");
+        assert_eq!(
+            json["missing"],
+            serde_json::json!(["Description", "DueDate"])
+        );
+        assert_eq!(
+            serde_json::from_value::<TransitionError>(json).unwrap(),
+            error
+        );
+
+        assert_eq!("DueDate".parse::<TaskField>(), Ok(TaskField::DueDate));
+        assert!("Title".parse::<TaskField>().is_err());
+    }
+}
@@ -11,15 +11,33 @@ mod task_validation_tests {
             id: Uuid::new_v4(),
             title: "Builder Test".to_string(),
             description: None,
-            status: TaskStatus::Todo,
+            status: TaskStatus::new("Todo"),
+            status_category: StatusCategory::Todo,
             priority: TaskPriority::Low,
             due_date: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            version: 1,
+            deleted_at: None,
+            comment_count: 0,
+            search: None,
+            tags: Vec::new(),
+            assignee_id: None,
+            project_id: DEFAULT_PROJECT_ID,
+            parent_id: None,
+            subtask_count: 0,
+            completed_subtask_count: 0,
+            is_blocked: false,
+            recurrence: None,
+            rank: String::new(),
+            estimate_minutes: None,
+            logged_minutes: 0,
+            timer_started_at: None,
+            custom_fields: Default::default(),
         };
 
         assert_eq!(task.title, "Builder Test");
-        assert_eq!(task.status, TaskStatus::Todo);
+        assert_eq!(task.status, TaskStatus::new("Todo"));
         assert_eq!(task.priority, TaskPriority::Low);
     }
 
@@ -30,16 +48,34 @@ mod task_validation_tests {
             id: Uuid::new_v4(),
             title: "Complete Task".to_string(),
             description: Some("Full description".to_string()),
-            status: TaskStatus::InProgress,
+            status: TaskStatus::new("InProgress"),
+            status_category: StatusCategory::Doing,
             priority: TaskPriority::Urgent,
             due_date: Some(due_date),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            version: 1,
+            deleted_at: None,
+            comment_count: 0,
+            search: None,
+            tags: Vec::new(),
+            assignee_id: None,
+            project_id: DEFAULT_PROJECT_ID,
+            parent_id: None,
+            subtask_count: 0,
+            completed_subtask_count: 0,
+            is_blocked: false,
+            recurrence: None,
+            rank: String::new(),
+            estimate_minutes: None,
+            logged_minutes: 0,
+            timer_started_at: None,
+            custom_fields: Default::default(),
         };
 
         assert!(!task.title.is_empty());
         assert!(task.description.is_some());
-        assert_eq!(task.status, TaskStatus::InProgress);
+        assert_eq!(task.status, TaskStatus::new("InProgress"));
         assert_eq!(task.priority, TaskPriority::Urgent);
         assert!(task.due_date.is_some());
     }
@@ -51,6 +87,7 @@ mod task_validation_tests {
             description: Some("Valid description".to_string()),
             priority: TaskPriority::High,
             due_date: None,
+            ..Default::default()
         };
 
         assert!(!valid_request.title.is_empty());
@@ -65,6 +102,7 @@ mod task_validation_tests {
             description: None,
             priority: TaskPriority::Medium,
             due_date: None,
+            ..Default::default()
         };
 
         assert_eq!(minimal_request.title, "Minimal");
@@ -75,35 +113,43 @@ mod task_validation_tests {
     #[test]
     fn test_update_task_request_partial() {
         let partial_update = UpdateTaskRequest {
-            title: Some("Updated Title".to_string()),
-            description: None,
-            status: None,
-            priority: Some(TaskPriority::Low),
-            due_date: None,
+            title: Patch::Value("Updated Title".to_string()),
+            priority: Patch::Value(TaskPriority::Low),
+            ..Default::default()
         };
 
-        assert_eq!(partial_update.title, Some("Updated Title".to_string()));
-        assert!(partial_update.description.is_none());
-        assert!(partial_update.status.is_none());
-        assert_eq!(partial_update.priority, Some(TaskPriority::Low));
+        assert_eq!(
+            partial_update.title,
+            Patch::Value("Updated Title".to_string())
+        );
+        assert!(partial_update.description.is_absent());
+        assert!(partial_update.status.is_absent());
+        assert_eq!(partial_update.priority, Patch::Value(TaskPriority::Low));
     }
 
     #[test]
     fn test_update_task_request_complete() {
         let due_date = Utc.with_ymd_and_hms(2024, 6, 15, 10, 0, 0).unwrap();
         let complete_update = UpdateTaskRequest {
-            title: Some("Completely Updated".to_string()),
-            description: Some("New description".to_string()),
-            status: Some(TaskStatus::Completed),
-            priority: Some(TaskPriority::Urgent),
-            due_date: Some(due_date),
+            title: Patch::Value("Completely Updated".to_string()),
+            description: Patch::Value("New description".to_string()),
+            status: Patch::Value(TaskStatus::new("Completed")),
+            priority: Patch::Value(TaskPriority::Urgent),
+            due_date: Patch::Value(due_date),
+            tags: Patch::Absent,
+            assignee_id: Patch::Absent,
+            project_id: Patch::Absent,
+            parent_id: Patch::Absent,
+            recurrence: Patch::Absent,
+            estimate_minutes: Patch::Absent,
+            custom_fields: Patch::Absent,
         };
 
-        assert!(complete_update.title.is_some());
-        assert!(complete_update.description.is_some());
-        assert!(complete_update.status.is_some());
-        assert!(complete_update.priority.is_some());
-        assert!(complete_update.due_date.is_some());
+        assert!(!complete_update.title.is_absent());
+        assert!(!complete_update.description.is_absent());
+        assert!(!complete_update.status.is_absent());
+        assert!(!complete_update.priority.is_absent());
+        assert!(!complete_update.due_date.is_absent());
     }
 
     #[test]
@@ -112,14 +158,15 @@ mod task_validation_tests {
         let due_after = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
 
         let filter = TaskFilter {
-            status: Some(TaskStatus::InProgress),
-            priority: Some(TaskPriority::High),
+            status: vec![TaskStatus::new("InProgress")],
+            priority: vec![TaskPriority::High],
             due_before: Some(due_before),
             due_after: Some(due_after),
+            ..Default::default()
         };
 
-        assert_eq!(filter.status, Some(TaskStatus::InProgress));
-        assert_eq!(filter.priority, Some(TaskPriority::High));
+        assert_eq!(filter.status, vec![TaskStatus::new("InProgress")]);
+        assert_eq!(filter.priority, vec![TaskPriority::High]);
         assert!(filter.due_before.is_some());
         assert!(filter.due_after.is_some());
     }
@@ -127,14 +174,15 @@ mod task_validation_tests {
     #[test]
     fn test_task_filter_single_field() {
         let status_filter = TaskFilter {
-            status: Some(TaskStatus::Todo),
-            priority: None,
+            status: vec![TaskStatus::new("Todo")],
+            priority: Vec::new(),
             due_before: None,
             due_after: None,
+            ..Default::default()
         };
 
-        assert_eq!(status_filter.status, Some(TaskStatus::Todo));
-        assert!(status_filter.priority.is_none());
+        assert_eq!(status_filter.status, vec![TaskStatus::new("Todo")]);
+        assert!(status_filter.priority.is_empty());
         assert!(status_filter.due_before.is_none());
         assert!(status_filter.due_after.is_none());
     }
@@ -167,9 +215,9 @@ mod task_validation_tests {
 
     #[test]
     fn test_task_status_equality() {
-        let status1 = TaskStatus::Todo;
-        let status2 = TaskStatus::Todo;
-        let status3 = TaskStatus::InProgress;
+        let status1 = TaskStatus::new("Todo");
+        let status2 = TaskStatus::new("Todo");
+        let status3 = TaskStatus::new("InProgress");
 
         assert_eq!(status1, status2);
         assert_ne!(status1, status3);
@@ -181,11 +229,29 @@ mod task_validation_tests {
             id: Uuid::new_v4(),
             title: "JSON Test".to_string(),
             description: Some("Test serialization".to_string()),
-            status: TaskStatus::InProgress,
+            status: TaskStatus::new("InProgress"),
+            status_category: StatusCategory::Doing,
             priority: TaskPriority::High,
             due_date: Some(Utc::now()),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            version: 1,
+            deleted_at: None,
+            comment_count: 0,
+            search: None,
+            tags: Vec::new(),
+            assignee_id: None,
+            project_id: DEFAULT_PROJECT_ID,
+            parent_id: None,
+            subtask_count: 0,
+            completed_subtask_count: 0,
+            is_blocked: false,
+            recurrence: None,
+            rank: String::new(),
+            estimate_minutes: None,
+            logged_minutes: 0,
+            timer_started_at: None,
+            custom_fields: Default::default(),
         };
 
         let json = serde_json::to_string(&original_task).unwrap();
@@ -204,6 +270,7 @@ mod task_validation_tests {
             description: Some("Create via JSON".to_string()),
             priority: TaskPriority::Medium,
             due_date: None,
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -217,11 +284,9 @@ mod task_validation_tests {
     #[test]
     fn test_update_request_json_serialization() {
         let request = UpdateTaskRequest {
-            title: Some("Updated via JSON".to_string()),
-            description: None,
-            status: Some(TaskStatus::Completed),
-            priority: None,
-            due_date: None,
+            title: Patch::Value("Updated via JSON".to_string()),
+            status: Patch::Value(TaskStatus::new("Completed")),
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -234,10 +299,11 @@ mod task_validation_tests {
     #[test]
     fn test_task_filter_json_serialization() {
         let filter = TaskFilter {
-            status: Some(TaskStatus::Todo),
-            priority: Some(TaskPriority::Urgent),
+            status: vec![TaskStatus::new("Todo")],
+            priority: vec![TaskPriority::Urgent],
             due_before: None,
             due_after: None,
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&filter).unwrap();
@@ -250,16 +316,17 @@ mod task_validation_tests {
     #[test]
     fn test_enum_string_representation() {
         // Test TaskStatus string representation
-        assert_eq!(format!("{:?}", TaskStatus::Todo), "Todo");
-        assert_eq!(format!("{:?}", TaskStatus::InProgress), "InProgress");
-        assert_eq!(format!("{:?}", TaskStatus::Completed), "Completed");
-        assert_eq!(format!("{:?}", TaskStatus::Backlog), "Backlog");
+        assert_eq!(TaskStatus::new("Todo").to_string(), "Todo");
+        assert_eq!(TaskStatus::new("InProgress").to_string(), "InProgress");
+        assert_eq!(TaskStatus::new("In Review").to_string(), "In Review");
+        assert_eq!(TaskStatus::new("Backlog").to_string(), "Backlog");
 
         // Test TaskPriority string representation
         assert_eq!(format!("{:?}", TaskPriority::Low), "Low");
         assert_eq!(format!("{:?}", TaskPriority::Medium), "Medium");
         assert_eq!(format!("{:?}", TaskPriority::High), "High");
         assert_eq!(format!("{:?}", TaskPriority::Urgent), "Urgent");
+        assert_eq!(TaskPriority::Urgent.to_string(), "Urgent");
     }
 
     #[test]
@@ -268,11 +335,29 @@ mod task_validation_tests {
             id: Uuid::new_v4(),
             title: "Clone Test".to_string(),
             description: Some("Test cloning".to_string()),
-            status: TaskStatus::Todo,
+            status: TaskStatus::new("Todo"),
+            status_category: StatusCategory::Todo,
             priority: TaskPriority::Medium,
             due_date: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            version: 1,
+            deleted_at: None,
+            comment_count: 0,
+            search: None,
+            tags: Vec::new(),
+            assignee_id: None,
+            project_id: DEFAULT_PROJECT_ID,
+            parent_id: None,
+            subtask_count: 0,
+            completed_subtask_count: 0,
+            is_blocked: false,
+            recurrence: None,
+            rank: String::new(),
+            estimate_minutes: None,
+            logged_minutes: 0,
+            timer_started_at: None,
+            custom_fields: Default::default(),
         };
 
         let cloned = original.clone();
@@ -290,11 +375,29 @@ mod task_validation_tests {
             id: Uuid::new_v4(),
             title: "Test".to_string(),
             description: None,
-            status: TaskStatus::Todo,
+            status: TaskStatus::new("Todo"),
+            status_category: StatusCategory::Todo,
             priority: TaskPriority::Low,
             due_date: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            version: 1,
+            deleted_at: None,
+            comment_count: 0,
+            search: None,
+            tags: Vec::new(),
+            assignee_id: None,
+            project_id: DEFAULT_PROJECT_ID,
+            parent_id: None,
+            subtask_count: 0,
+            completed_subtask_count: 0,
+            is_blocked: false,
+            recurrence: None,
+            rank: String::new(),
+            estimate_minutes: None,
+            logged_minutes: 0,
+            timer_started_at: None,
+            custom_fields: Default::default(),
         };
 
         let task2 = task1.clone();
@@ -314,22 +417,58 @@ mod task_validation_tests {
             id: Uuid::new_v4(),
             title: "Future Task".to_string(),
             description: None,
-            status: TaskStatus::Todo,
+            status: TaskStatus::new("Todo"),
+            status_category: StatusCategory::Todo,
             priority: TaskPriority::Low,
             due_date: Some(far_future),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            version: 1,
+            deleted_at: None,
+            comment_count: 0,
+            search: None,
+            tags: Vec::new(),
+            assignee_id: None,
+            project_id: DEFAULT_PROJECT_ID,
+            parent_id: None,
+            subtask_count: 0,
+            completed_subtask_count: 0,
+            is_blocked: false,
+            recurrence: None,
+            rank: String::new(),
+            estimate_minutes: None,
+            logged_minutes: 0,
+            timer_started_at: None,
+            custom_fields: Default::default(),
         };
 
         let past_task = Task {
             id: Uuid::new_v4(),
             title: "Past Task".to_string(),
             description: None,
-            status: TaskStatus::Completed,
+            status: TaskStatus::new("Completed"),
+            status_category: StatusCategory::Done,
             priority: TaskPriority::Low,
             due_date: Some(far_past),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            version: 1,
+            deleted_at: None,
+            comment_count: 0,
+            search: None,
+            tags: Vec::new(),
+            assignee_id: None,
+            project_id: DEFAULT_PROJECT_ID,
+            parent_id: None,
+            subtask_count: 0,
+            completed_subtask_count: 0,
+            is_blocked: false,
+            recurrence: None,
+            rank: String::new(),
+            estimate_minutes: None,
+            logged_minutes: 0,
+            timer_started_at: None,
+            custom_fields: Default::default(),
         };
 
         assert!(future_task.due_date.unwrap() > Utc::now());
@@ -0,0 +1,249 @@
+//! Rules on how tasks move through a project's workflow: the states a task
+//! may go to from each state, and guards naming the fields a task must have
+//! filled in before it enters a state or takes a priority.
+//!
+//! The server checks them on every write; clients use the same checks to
+//! offer only the moves that will succeed. Guards are checked on the way in,
+//! so clearing a field later does not move the task back out.
+
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use crate::{Task, TaskPriority, TaskStatus, Workflow, WorkflowState};
+
+/// A task field a guard can require.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum TaskField {
+    Description,
+    DueDate,
+    Assignee,
+}
+
+impl TaskField {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TaskField::Description => "Description",
+            TaskField::DueDate => "DueDate",
+            TaskField::Assignee => "Assignee",
+        }
+    }
+
+    /// The field as it reads in a sentence.
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            TaskField::Description => "description",
+            TaskField::DueDate => "due date",
+            TaskField::Assignee => "assignee",
+        }
+    }
+}
+
+impl FromStr for TaskField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Description" => Ok(TaskField::Description),
+            "DueDate" => Ok(TaskField::DueDate),
+            "Assignee" => Ok(TaskField::Assignee),
+            _ => Err(format!("Unknown task field: {s}")),
+        }
+    }
+}
+
+/// Which guarded fields a task has filled in, as it is or as a write would
+/// leave it. A description of only spaces does not count.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FilledFields {
+    pub description: bool,
+    pub due_date: bool,
+    pub assignee: bool,
+}
+
+impl FilledFields {
+    #[must_use]
+    pub fn of(task: &Task) -> Self {
+        Self {
+            description: has_text(task.description.as_deref()),
+            due_date: task.due_date.is_some(),
+            assignee: task.assignee_id.is_some(),
+        }
+    }
+
+    #[must_use]
+    pub fn has(self, field: TaskField) -> bool {
+        match field {
+            TaskField::Description => self.description,
+            TaskField::DueDate => self.due_date,
+            TaskField::Assignee => self.assignee,
+        }
+    }
+
+    /// The fields of `required` this leaves empty.
+    #[must_use]
+    pub fn missing(self, required: &[TaskField]) -> Vec<TaskField> {
+        required
+            .iter()
+            .copied()
+            .filter(|field| !self.has(*field))
+            .collect()
+    }
+}
+
+/// Whether an optional text field counts as filled in.
+#[must_use]
+pub fn has_text(text: Option<&str>) -> bool {
+    text.is_some_and(|text| !text.trim().is_empty())
+}
+
+/// Fields a task needs before it can take `priority`, such as a due date
+/// for `Urgent`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PriorityRule {
+    pub priority: TaskPriority,
+    pub requires: Vec<TaskField>,
+}
+
+/// Why the workflow refused a change. The server sends it as the body of a
+/// `422 Unprocessable Entity`, next to the usual `error` message.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum TransitionError {
+    /// The workflow has no move from `from` to `to`; `allowed` lists the
+    /// states it does allow from `from`.
+    TransitionNotAllowed {
+        from: TaskStatus,
+        to: TaskStatus,
+        allowed: Vec<TaskStatus>,
+    },
+    /// Entering `status` needs fields the task lacks.
+    StateRequiresFields {
+        status: TaskStatus,
+        missing: Vec<TaskField>,
+    },
+    /// Taking `priority` needs fields the task lacks.
+    PriorityRequiresFields {
+        priority: TaskPriority,
+        missing: Vec<TaskField>,
+    },
+}
+
+impl fmt::Display for TransitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransitionError::TransitionNotAllowed { from, to, .. } => {
+                write!(f, "Cannot move a task from {from} to {to}")
+            }
+            TransitionError::StateRequiresFields { status, missing } => {
+                write!(f, "Moving to {status} requires: {}", labels(missing))
+            }
+            TransitionError::PriorityRequiresFields { priority, missing } => {
+                write!(f, "{priority:?} priority requires: {}", labels(missing))
+            }
+        }
+    }
+}
+
+impl std::error::Error for TransitionError {}
+
+fn labels(fields: &[TaskField]) -> String {
+    fields
+        .iter()
+        .map(|field| field.label())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl Workflow {
+    /// Whether a task may move straight from `from` to `to`. Staying put is
+    /// always allowed, and so is leaving a state without a `next` list.
+    #[must_use]
+    pub fn allows(&self, from: &TaskStatus, to: &TaskStatus) -> bool {
+        from == to
+            || self
+                .state(from)
+                .and_then(|state| state.next.as_ref())
+                .is_none_or(|next| next.contains(to))
+    }
+
+    /// The rule for `priority`, if the workflow has one.
+    #[must_use]
+    pub fn priority_rule(&self, priority: TaskPriority) -> Option<&PriorityRule> {
+        self.priority_rules
+            .iter()
+            .find(|rule| rule.priority == priority)
+    }
+
+    /// Checks a task with `fields` entering `to`. `from` is the state it
+    /// leaves in this workflow, or `None` for a task that is new or comes
+    /// from another project, which only the guard of `to` applies to.
+    pub fn check_transition(
+        &self,
+        from: Option<&TaskStatus>,
+        to: &WorkflowState,
+        fields: FilledFields,
+    ) -> Result<(), TransitionError> {
+        if let Some(from) = from {
+            if from == &to.name {
+                return Ok(());
+            }
+            if !self.allows(from, &to.name) {
+                return Err(TransitionError::TransitionNotAllowed {
+                    from: from.clone(),
+                    to: to.name.clone(),
+                    allowed: self.next_states(from).cloned().collect(),
+                });
+            }
+        }
+
+        let missing = fields.missing(&to.requires);
+        if !missing.is_empty() {
+            return Err(TransitionError::StateRequiresFields {
+                status: to.name.clone(),
+                missing,
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks a task with `fields` taking `priority`.
+    pub fn check_priority(
+        &self,
+        priority: TaskPriority,
+        fields: FilledFields,
+    ) -> Result<(), TransitionError> {
+        let Some(rule) = self.priority_rule(priority) else {
+            return Ok(());
+        };
+        let missing = fields.missing(&rule.requires);
+        if !missing.is_empty() {
+            return Err(TransitionError::PriorityRequiresFields { priority, missing });
+        }
+        Ok(())
+    }
+
+    /// The states `task` can move to right now, in board order: those the
+    /// workflow allows from its status whose guards it meets.
+    #[must_use]
+    pub fn allowed_next(&self, task: &Task) -> Vec<&WorkflowState> {
+        let fields = FilledFields::of(task);
+        self.states
+            .iter()
+            .filter(|state| state.name != task.status)
+            .filter(|state| {
+                self.check_transition(Some(&task.status), state, fields)
+                    .is_ok()
+            })
+            .collect()
+    }
+
+    /// Names of the states the workflow allows from `from`, in board order.
+    fn next_states<'a>(&'a self, from: &'a TaskStatus) -> impl Iterator<Item = &'a TaskStatus> {
+        self.states
+            .iter()
+            .map(|state| &state.name)
+            .filter(move |to| *to != from && self.allows(from, to))
+    }
+}
@@ -1,5 +1,14 @@
-use common::{CreateTaskRequest, Task, TaskFilter, UpdateTaskRequest};
-use gloo_net::http::Request;
+use chrono::{DateTime, SecondsFormat, Utc};
+use common::{
+    custom_fields::CustomField,
+    saved_views::{SavedView, SavedViewRequest},
+    Attachment, Comment, CommentRequest, CreateTaskRequest, Member, MoveTaskRequest, Page, Project,
+    Tag, Task, TaskEvent, TaskFilter, TaskSort, TimeEntry, TimerRequest, UpdateTaskRequest,
+    Workflow, MAX_PAGE_SIZE,
+};
+use gloo_net::http::{Request, Response};
+use serde::Deserialize;
+use std::fmt;
 use uuid::Uuid;
 
 // API base URL - function to get it dynamically
@@ -9,34 +18,96 @@ fn api_base() -> String {
     "http://localhost:8080/api".to_string()
 }
 
-pub async fn fetch_tasks(filter: Option<TaskFilter>) -> Result<Vec<Task>, String> {
-    let mut url = format!("{}/tasks", api_base());
+pub async fn fetch_tasks(
+    filter: Option<TaskFilter>,
+    sort: TaskSort,
+    cursor: Option<&str>,
+    limit: u32,
+) -> Result<Page<Task>, String> {
+    // A project's board is read through its nested task listing
+    let mut url = match filter.as_ref().and_then(|filter| filter.project) {
+        Some(project) => format!("{}/projects/{project}/tasks", api_base()),
+        None => format!("{}/tasks", api_base()),
+    };
+    let mut params = vec![format!("sort={sort}"), format!("limit={limit}")];
 
-    if let Some(filter) = filter {
-        let mut params = Vec::new();
+    if let Some(cursor) = cursor {
+        params.push(format!("cursor={cursor}"));
+    }
 
-        if let Some(status) = filter.status {
-            params.push(format!("status={status:?}"));
+    if let Some(filter) = filter {
+        if !filter.status.is_empty() {
+            params.push(format!("status={}", comma_join(&filter.status)));
         }
 
-        if let Some(priority) = filter.priority {
-            params.push(format!("priority={priority:?}"));
+        if !filter.priority.is_empty() {
+            params.push(format!("priority={}", comma_join(&filter.priority)));
         }
 
         if let Some(due_before) = filter.due_before {
-            params.push(format!("due_before={}", due_before.to_rfc3339()));
+            params.push(format!("due_before={}", query_date(due_before)));
         }
 
         if let Some(due_after) = filter.due_after {
-            params.push(format!("due_after={}", due_after.to_rfc3339()));
+            params.push(format!("due_after={}", query_date(due_after)));
+        }
+
+        if let Some(has_due_date) = filter.has_due_date {
+            params.push(format!("has_due_date={has_due_date}"));
+        }
+
+        let ranges = [
+            ("created_before", filter.created_before),
+            ("created_after", filter.created_after),
+            ("updated_before", filter.updated_before),
+            ("updated_after", filter.updated_after),
+        ];
+        for (name, bound) in ranges {
+            if let Some(bound) = bound {
+                params.push(format!("{name}={}", query_date(bound)));
+            }
+        }
+
+        if let Some(q) = filter.q.filter(|q| !q.trim().is_empty()) {
+            params.push(format!("q={}", urlencoding::encode(&q)));
+        }
+
+        if let Some(query) = filter.query.filter(|query| !query.terms.is_empty()) {
+            params.push(format!("query={}", urlencoding::encode(&query.to_string())));
         }
 
-        if !params.is_empty() {
-            url.push('?');
-            url.push_str(&params.join("&"));
+        if let Some(assignee) = filter.assignee {
+            params.push(format!("assignee={assignee}"));
+        }
+
+        if let Some(tag) = filter.tag {
+            params.push(format!("tag={}", urlencoding::encode(&tag)));
+        }
+
+        let tag_lists = [("any_tag", filter.any_tag), ("all_tags", filter.all_tags)];
+        for (name, tags) in tag_lists {
+            if !tags.is_empty() {
+                params.push(format!("{name}={}", encode_tag_list(&tags)));
+            }
+        }
+
+        // Values go as typed text, which the server reads by the field's type
+        for wanted in filter.custom_fields {
+            let value = match wanted.value {
+                serde_json::Value::String(text) => text,
+                value => value.to_string(),
+            };
+            params.push(format!(
+                "field.{}={}",
+                wanted.field_id,
+                urlencoding::encode(&value)
+            ));
         }
     }
 
+    url.push('?');
+    url.push_str(&params.join("&"));
+
     let response = Request::get(&url)
         .send()
         .await
@@ -47,11 +118,52 @@ pub async fn fetch_tasks(filter: Option<TaskFilter>) -> Result<Vec<Task>, String
     }
 
     response
-        .json::<Vec<Task>>()
+        .json::<Page<Task>>()
         .await
         .map_err(|e| format!("Failed to parse response: {e}"))
 }
 
+// The board shows every task, so follow the cursors until the last page
+pub async fn fetch_all_tasks(
+    filter: Option<TaskFilter>,
+    sort: TaskSort,
+) -> Result<Vec<Task>, String> {
+    let mut tasks = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let page = fetch_tasks(filter.clone(), sort, cursor.as_deref(), MAX_PAGE_SIZE).await?;
+        tasks.extend(page.items);
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => return Ok(tasks),
+        }
+    }
+}
+
+// Use the `Z` suffix so no `+` offset gets decoded as a space on the server
+fn query_date(date: DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+// Multi-value filters are sent as a single comma-separated parameter. State
+// names cannot contain commas, so each value is encoded and the commas kept
+fn comma_join<T: std::fmt::Display>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(|value| urlencoding::encode(&value.to_string()).into_owned())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+// Tag names cannot contain commas, so each is encoded and the commas kept
+fn encode_tag_list(tags: &[String]) -> String {
+    tags.iter()
+        .map(|tag| urlencoding::encode(tag).into_owned())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 pub async fn create_task(request: CreateTaskRequest) -> Result<Task, String> {
     let response = Request::post(&format!("{}/tasks", api_base()))
         .json(&request)
@@ -70,8 +182,214 @@ pub async fn create_task(request: CreateTaskRequest) -> Result<Task, String> {
         .map_err(|e| format!("Failed to parse response: {e}"))
 }
 
-pub async fn update_task(id: Uuid, request: UpdateTaskRequest) -> Result<Task, String> {
-    let response = Request::put(&format!("{}/tasks/{id}", api_base()))
+/// Why a write to a task failed. Writes carry the version the client last
+/// saw, so a concurrent edit is reported separately from other failures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WriteError {
+    /// The task was changed by someone else since it was loaded.
+    Conflict,
+    /// The server refused the change, such as a move into a column at its
+    /// WIP limit or one the workflow does not allow, with its reason.
+    Rejected(String),
+    Failed(String),
+}
+
+impl fmt::Display for WriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WriteError::Conflict => f.write_str("Task was changed by someone else"),
+            WriteError::Rejected(message) | WriteError::Failed(message) => f.write_str(message),
+        }
+    }
+}
+
+/// The `{"error": ...}` body of a failed request.
+#[derive(Deserialize)]
+struct ErrorBody {
+    error: String,
+}
+
+/// Reads the reason out of a 409 or 422 response, falling back to the
+/// status.
+async fn rejection(response: Response) -> WriteError {
+    match response.json::<ErrorBody>().await {
+        Ok(body) => WriteError::Rejected(body.error),
+        Err(_) => WriteError::Failed(format!("HTTP error: {}", response.status())),
+    }
+}
+
+// Matches the `ETag` the backend sends for a task
+fn if_match(version: i64) -> String {
+    format!("\"{version}\"")
+}
+
+pub async fn update_task(
+    id: Uuid,
+    request: UpdateTaskRequest,
+    version: i64,
+    override_blockers: bool,
+) -> Result<Task, WriteError> {
+    let mut builder = Request::patch(&format!("{}/tasks/{id}", api_base()))
+        .header("If-Match", &if_match(version));
+    if override_blockers {
+        builder = builder.header("X-Override-Blockers", "true");
+    }
+    let response = builder
+        .json(&request)
+        .map_err(|e| WriteError::Failed(format!("Failed to serialize request: {e}")))?
+        .send()
+        .await
+        .map_err(|e| WriteError::Failed(format!("Request failed: {e}")))?;
+
+    if response.status() == 412 {
+        return Err(WriteError::Conflict);
+    }
+    if matches!(response.status(), 409 | 422) {
+        return Err(rejection(response).await);
+    }
+    if !response.ok() {
+        return Err(WriteError::Failed(format!(
+            "HTTP error: {}",
+            response.status()
+        )));
+    }
+
+    response
+        .json::<Task>()
+        .await
+        .map_err(|e| WriteError::Failed(format!("Failed to parse response: {e}")))
+}
+
+/// Drops a task into a column between two of its cards.
+pub async fn move_task(
+    id: Uuid,
+    request: MoveTaskRequest,
+    version: i64,
+    override_blockers: bool,
+) -> Result<Task, WriteError> {
+    let mut builder = Request::post(&format!("{}/tasks/{id}/move", api_base()))
+        .header("If-Match", &if_match(version));
+    if override_blockers {
+        builder = builder.header("X-Override-Blockers", "true");
+    }
+    let response = builder
+        .json(&request)
+        .map_err(|e| WriteError::Failed(format!("Failed to serialize request: {e}")))?
+        .send()
+        .await
+        .map_err(|e| WriteError::Failed(format!("Request failed: {e}")))?;
+
+    if response.status() == 412 {
+        return Err(WriteError::Conflict);
+    }
+    if matches!(response.status(), 409 | 422) {
+        return Err(rejection(response).await);
+    }
+    if !response.ok() {
+        return Err(WriteError::Failed(format!(
+            "HTTP error: {}",
+            response.status()
+        )));
+    }
+
+    response
+        .json::<Task>()
+        .await
+        .map_err(|e| WriteError::Failed(format!("Failed to parse response: {e}")))
+}
+
+pub async fn delete_task(id: Uuid, version: i64) -> Result<(), WriteError> {
+    let response = Request::delete(&format!("{}/tasks/{id}", api_base()))
+        .header("If-Match", &if_match(version))
+        .send()
+        .await
+        .map_err(|e| WriteError::Failed(format!("Request failed: {e}")))?;
+
+    if response.status() == 412 {
+        return Err(WriteError::Conflict);
+    }
+    if !response.ok() {
+        return Err(WriteError::Failed(format!(
+            "HTTP error: {}",
+            response.status()
+        )));
+    }
+
+    Ok(())
+}
+
+pub async fn fetch_task_history(id: Uuid) -> Result<Vec<TaskEvent>, String> {
+    let response = Request::get(&format!("{}/tasks/{id}/history", api_base()))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+
+    if !response.ok() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    response
+        .json::<Vec<TaskEvent>>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {e}"))
+}
+
+pub async fn fetch_children(id: Uuid) -> Result<Vec<Task>, String> {
+    let response = Request::get(&format!("{}/tasks/{id}/children", api_base()))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+
+    if !response.ok() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    response
+        .json::<Vec<Task>>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {e}"))
+}
+
+pub async fn fetch_comments(task_id: Uuid) -> Result<Vec<Comment>, String> {
+    let response = Request::get(&format!("{}/tasks/{task_id}/comments", api_base()))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+
+    if !response.ok() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    response
+        .json::<Vec<Comment>>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {e}"))
+}
+
+pub async fn create_comment(task_id: Uuid, request: CommentRequest) -> Result<Comment, String> {
+    let response = Request::post(&format!("{}/tasks/{task_id}/comments", api_base()))
+        .json(&request)
+        .map_err(|e| format!("Failed to serialize request: {e}"))?
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+
+    if !response.ok() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    response
+        .json::<Comment>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {e}"))
+}
+
+pub async fn update_comment(
+    task_id: Uuid,
+    id: Uuid,
+    request: CommentRequest,
+) -> Result<Comment, String> {
+    let response = Request::put(&format!("{}/tasks/{task_id}/comments/{id}", api_base()))
         .json(&request)
         .map_err(|e| format!("Failed to serialize request: {e}"))?
         .send()
@@ -82,14 +400,284 @@ pub async fn update_task(id: Uuid, request: UpdateTaskRequest) -> Result<Task, S
         return Err(format!("HTTP error: {}", response.status()));
     }
 
+    response
+        .json::<Comment>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {e}"))
+}
+
+pub async fn delete_comment(task_id: Uuid, id: Uuid) -> Result<(), String> {
+    let response = Request::delete(&format!("{}/tasks/{task_id}/comments/{id}", api_base()))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+
+    if !response.ok() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    Ok(())
+}
+
+pub async fn fetch_attachments(task_id: Uuid) -> Result<Vec<Attachment>, String> {
+    let response = Request::get(&format!("{}/tasks/{task_id}/attachments", api_base()))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+
+    if !response.ok() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    response
+        .json::<Vec<Attachment>>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {e}"))
+}
+
+/// Uploads `files` to a task as one multipart request. A file over the size
+/// limit fails the request with the server's reason.
+pub async fn upload_attachments(
+    task_id: Uuid,
+    files: Vec<web_sys::File>,
+) -> Result<Vec<Attachment>, String> {
+    let form = web_sys::FormData::new().map_err(|_| "Failed to build upload".to_string())?;
+    for file in &files {
+        form.append_with_blob_and_filename("file", file, &file.name())
+            .map_err(|_| "Failed to build upload".to_string())?;
+    }
+
+    let response = Request::post(&format!("{}/tasks/{task_id}/attachments", api_base()))
+        .body(form)
+        .map_err(|e| format!("Failed to build upload: {e}"))?
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+
+    if !response.ok() {
+        let status = response.status();
+        return Err(match response.json::<ErrorBody>().await {
+            Ok(body) => body.error,
+            Err(_) => format!("HTTP error: {status}"),
+        });
+    }
+
+    response
+        .json::<Vec<Attachment>>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {e}"))
+}
+
+/// Where an attachment downloads from, for links and image previews.
+pub fn attachment_url(task_id: Uuid, id: Uuid) -> String {
+    format!("{}/tasks/{task_id}/attachments/{id}", api_base())
+}
+
+pub async fn delete_attachment(task_id: Uuid, id: Uuid) -> Result<(), String> {
+    let response = Request::delete(&attachment_url(task_id, id))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+
+    if !response.ok() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    Ok(())
+}
+
+/// Starts `member_id`'s timer on a task, stopping any other timer they have
+/// running. `stop` ends it instead.
+pub async fn toggle_timer(task_id: Uuid, member_id: Uuid, stop: bool) -> Result<TimeEntry, String> {
+    let action = if stop { "stop" } else { "start" };
+    let response = Request::post(&format!("{}/tasks/{task_id}/timer/{action}", api_base()))
+        .json(&TimerRequest { member_id })
+        .map_err(|e| format!("Failed to serialize request: {e}"))?
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+
+    if !response.ok() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    response
+        .json::<TimeEntry>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {e}"))
+}
+
+pub async fn fetch_trash() -> Result<Vec<Task>, String> {
+    let response = Request::get(&format!("{}/trash", api_base()))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+
+    if !response.ok() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    response
+        .json::<Vec<Task>>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {e}"))
+}
+
+pub async fn restore_task(id: Uuid) -> Result<Task, String> {
+    let response = Request::post(&format!("{}/tasks/{id}/restore", api_base()))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+
+    if !response.ok() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
     response
         .json::<Task>()
         .await
         .map_err(|e| format!("Failed to parse response: {e}"))
 }
 
-pub async fn delete_task(id: Uuid) -> Result<(), String> {
-    let response = Request::delete(&format!("{}/tasks/{id}", api_base()))
+pub async fn purge_task(id: Uuid) -> Result<(), String> {
+    let response = Request::delete(&format!("{}/trash/{id}", api_base()))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+
+    if !response.ok() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    Ok(())
+}
+
+pub async fn fetch_tags() -> Result<Vec<Tag>, String> {
+    let response = Request::get(&format!("{}/tags", api_base()))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+
+    if !response.ok() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    response
+        .json::<Vec<Tag>>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {e}"))
+}
+
+pub async fn fetch_members() -> Result<Vec<Member>, String> {
+    let response = Request::get(&format!("{}/members", api_base()))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+
+    if !response.ok() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    response
+        .json::<Vec<Member>>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {e}"))
+}
+
+pub async fn fetch_projects() -> Result<Vec<Project>, String> {
+    let response = Request::get(&format!("{}/projects", api_base()))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+
+    if !response.ok() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    response
+        .json::<Vec<Project>>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {e}"))
+}
+
+pub async fn fetch_workflow(project_id: Uuid) -> Result<Workflow, String> {
+    let response = Request::get(&format!("{}/projects/{project_id}/workflow", api_base()))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+
+    if !response.ok() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    response
+        .json::<Workflow>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {e}"))
+}
+
+pub async fn fetch_custom_fields(project_id: Uuid) -> Result<Vec<CustomField>, String> {
+    let response = Request::get(&format!("{}/projects/{project_id}/fields", api_base()))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+
+    if !response.ok() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    response
+        .json::<Vec<CustomField>>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {e}"))
+}
+
+pub async fn fetch_saved_views() -> Result<Vec<SavedView>, String> {
+    let response = Request::get(&format!("{}/views", api_base()))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+
+    if !response.ok() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    response
+        .json::<Vec<SavedView>>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {e}"))
+}
+
+/// Saves a view, replacing the settings of view `id` when given. Fails with
+/// the server's reason, such as an empty name.
+pub async fn save_view(id: Option<Uuid>, request: SavedViewRequest) -> Result<SavedView, String> {
+    let builder = match id {
+        Some(id) => Request::put(&format!("{}/views/{id}", api_base())),
+        None => Request::post(&format!("{}/views", api_base())),
+    };
+    let response = builder
+        .json(&request)
+        .map_err(|e| format!("Failed to serialize request: {e}"))?
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+
+    if !response.ok() {
+        let status = response.status();
+        return Err(match response.json::<ErrorBody>().await {
+            Ok(body) => body.error,
+            Err(_) => format!("HTTP error: {status}"),
+        });
+    }
+
+    response
+        .json::<SavedView>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {e}"))
+}
+
+pub async fn delete_saved_view(id: Uuid) -> Result<(), String> {
+    let response = Request::delete(&format!("{}/views/{id}", api_base()))
         .send()
         .await
         .map_err(|e| format!("Request failed: {e}"))?;
@@ -0,0 +1,143 @@
+use crate::api;
+use crate::logic::attachment_logic::{format_size, is_image};
+use common::Attachment;
+use leptos::*;
+use uuid::Uuid;
+use web_sys::{File, FileList, HtmlInputElement};
+
+// Every file in a picker or drop, in the order given
+fn files_of(list: &FileList) -> Vec<File> {
+    (0..list.length()).filter_map(|i| list.get(i)).collect()
+}
+
+#[component]
+#[allow(non_snake_case)]
+pub fn AttachmentList(task_id: Uuid) -> impl IntoView {
+    let (attachments, set_attachments) = create_signal(Vec::<Attachment>::new());
+    let (drag_over, set_drag_over) = create_signal(false);
+    let (error, set_error) = create_signal(None::<String>);
+
+    let load_attachments = create_action(move |_: &()| api::fetch_attachments(task_id));
+
+    let upload = create_action(move |files: &Vec<File>| {
+        let files = files.clone();
+        async move { api::upload_attachments(task_id, files).await }
+    });
+
+    let delete_attachment = create_action(move |id: &Uuid| {
+        let id = *id;
+        async move { api::delete_attachment(task_id, id).await.map(|()| id) }
+    });
+
+    load_attachments.dispatch(());
+
+    create_effect(move |_| {
+        if let Some(result) = load_attachments.value().get() {
+            match result {
+                Ok(loaded) => set_attachments.set(loaded),
+                Err(e) => set_error.set(Some(e)),
+            }
+        }
+    });
+
+    create_effect(move |_| {
+        if let Some(result) = upload.value().get() {
+            match result {
+                Ok(uploaded) => set_attachments.update(|attachments| attachments.extend(uploaded)),
+                Err(e) => set_error.set(Some(e)),
+            }
+        }
+    });
+
+    create_effect(move |_| {
+        if let Some(result) = delete_attachment.value().get() {
+            match result {
+                Ok(id) => set_attachments.update(|attachments| attachments.retain(|a| a.id != id)),
+                Err(e) => set_error.set(Some(e)),
+            }
+        }
+    });
+
+    let upload_files = move |files: Vec<File>| {
+        if files.is_empty() {
+            return;
+        }
+        set_error.set(None);
+        upload.dispatch(files);
+    };
+
+    let render_attachment = move |attachment: Attachment| {
+        let id = attachment.id;
+        let url = api::attachment_url(task_id, id);
+
+        view! {
+            <li class="flex items-center gap-3 rounded-lg bg-gray-900 border border-gray-700 p-2">
+                {is_image(&attachment).then(|| view! {
+                    <img src=url.clone() alt=attachment.filename.clone() class="h-10 w-10 rounded object-cover" />
+                })}
+                <a href=url class="flex-1 truncate text-blue-300 hover:text-blue-200" download=attachment.filename.clone()>
+                    {attachment.filename.clone()}
+                </a>
+                <span class="text-xs text-gray-500">{format_size(attachment.size_bytes)}</span>
+                <button
+                    class="text-xs text-gray-500 hover:text-red-300"
+                    on:click=move |_| { delete_attachment.dispatch(id); }
+                >
+                    "Remove"
+                </button>
+            </li>
+        }
+    };
+
+    view! {
+        <div class="space-y-3">
+            {move || error.get().map(|err| view! { <p class="text-red-300">{err}</p> })}
+
+            <ul class="space-y-2 max-h-64 overflow-y-auto">
+                <For
+                    each=move || attachments.get()
+                    key=|attachment| attachment.id
+                    children=render_attachment
+                />
+            </ul>
+
+            <label
+                class={move || format!(
+                    "block rounded-lg border-2 border-dashed p-4 text-center text-xs cursor-pointer transition-colors {}",
+                    if drag_over.get() { "border-blue-400 bg-blue-900/20 text-blue-200" } else { "border-gray-600 text-gray-500 hover:border-gray-500" }
+                )}
+                on:dragover=move |ev| {
+                    ev.prevent_default();
+                    set_drag_over.set(true);
+                }
+                on:dragleave=move |_| set_drag_over.set(false)
+                on:drop=move |ev| {
+                    ev.prevent_default();
+                    set_drag_over.set(false);
+                    if let Some(list) = ev.data_transfer().and_then(|data| data.files()) {
+                        upload_files(files_of(&list));
+                    }
+                }
+            >
+                {move || if upload.pending().get() {
+                    "Uploading..."
+                } else {
+                    "Drop files here or click to attach"
+                }}
+                <input
+                    type="file"
+                    multiple
+                    class="hidden"
+                    on:change=move |ev| {
+                        let input = event_target::<HtmlInputElement>(&ev);
+                        if let Some(list) = input.files() {
+                            upload_files(files_of(&list));
+                        }
+                        // Lets the same file be picked again after a failed upload
+                        input.set_value("");
+                    }
+                />
+            </label>
+        </div>
+    }
+}
@@ -1,21 +1,42 @@
-use common::{Task, TaskPriority};
+use crate::api;
+use crate::logic::recurrence_logic::recurrence_label;
+use crate::logic::subtask_logic::{progress_label, progress_percent};
+use crate::logic::task_list_logic::DropTarget;
+use crate::logic::time_logic::{effort_label, format_minutes, over_estimate, running_minutes};
+use common::{HighlightSpan, StatusCategory, Task, TaskPriority};
 use leptos::logging::log;
 use leptos::*;
+use wasm_bindgen::JsCast;
 
 #[component]
-pub fn Card(task: Task, set_dragging_task_id: WriteSignal<Option<uuid::Uuid>>) -> impl IntoView {
+pub fn Card(
+    task: Task,
+    set_dragging_task_id: WriteSignal<Option<uuid::Uuid>>,
+    #[prop(optional)] set_selected_task_id: Option<WriteSignal<Option<uuid::Uuid>>>,
+    #[prop(optional)] set_drop_target: Option<WriteSignal<Option<DropTarget>>>,
+    /// Called once the timer control has started or stopped a timer.
+    #[prop(optional)]
+    on_timer_change: Option<Callback<()>>,
+) -> impl IntoView {
     // Card for displaying a task
-    let task_name = task.title.clone();
+    let search = task.search.clone();
+    let task_name = match &search {
+        Some(search) => render_highlight(search.title.clone()),
+        None => task.title.clone().into_view(),
+    };
 
     let task_description = task.description.clone().unwrap_or_default();
 
-    // Limit description to 15 words for display
-    let display_description = {
-        let words: Vec<&str> = task_description.split_whitespace().collect();
-        if words.len() > 15 {
-            format!("{}...", words[..15].join(" "))
-        } else {
-            task_description.clone()
+    // Limit description to 15 words for display, preferring the search snippet
+    let display_description = match search {
+        Some(search) if !search.snippet.is_empty() => render_highlight(search.snippet),
+        _ => {
+            let words: Vec<&str> = task_description.split_whitespace().collect();
+            if words.len() > 15 {
+                format!("{}...", words[..15].join(" ")).into_view()
+            } else {
+                task_description.clone().into_view()
+            }
         }
     };
 
@@ -23,6 +44,45 @@ pub fn Card(task: Task, set_dragging_task_id: WriteSignal<Option<uuid::Uuid>>) -
         .due_date
         .map(|d| d.format("%Y %b %d").to_string())
         .unwrap_or_default();
+    let comment_count = task.comment_count;
+    let is_blocked = task.is_blocked;
+    let repeats = recurrence_label(&task);
+    let progress = progress_label(&task);
+    let percent = progress_percent(&task);
+    let effort = effort_label(&task);
+    let over = over_estimate(&task);
+    let running = running_minutes(&task, chrono::Utc::now());
+
+    // The timer control acts for the assignee, so unassigned tasks have none
+    let assignee_id = task.assignee_id;
+    let timed_task_id = task.id;
+    let toggle_timer = create_action(move |(member_id, stop): &(uuid::Uuid, bool)| {
+        api::toggle_timer(timed_task_id, *member_id, *stop)
+    });
+    create_effect(move |_| match toggle_timer.value().get() {
+        Some(Ok(_)) => {
+            if let Some(callback) = on_timer_change {
+                callback.call(());
+            }
+        }
+        Some(Err(e)) => log!("Timer change failed: {}", e),
+        None => {}
+    });
+
+    // Subtasks are only fetched once the card is expanded
+    let (expanded, set_expanded) = create_signal(false);
+    let task_id = task.id;
+    let children = create_local_resource(
+        move || expanded.get(),
+        move |open| async move {
+            if open {
+                Some(api::fetch_children(task_id).await)
+            } else {
+                None
+            }
+        },
+    );
+    let tags = task.tags.clone();
     let priority_label = match task.priority {
         TaskPriority::Low => "Low",
         TaskPriority::Medium => "Mid",
@@ -33,6 +93,11 @@ pub fn Card(task: Task, set_dragging_task_id: WriteSignal<Option<uuid::Uuid>>) -
         <div
             class="bg-white dark:bg-gray-800 rounded-xl shadow-lg border-2 border-gray-300 dark:border-gray-600 p-4 mb-4 w-full max-w-sm h-48 flex flex-col select-none cursor-grab active:cursor-grabbing pointer-events-auto hover:border-gray-400 dark:hover:border-gray-500 transition-colors"
             draggable="true"
+            on:click=move |_| {
+                if let Some(setter) = set_selected_task_id {
+                    setter.set(Some(task.id));
+                }
+            }
             on:dragstart=move |_ev| {
                 log!("Drag started for task {}", task.id);
                 set_dragging_task_id.set(Some(task.id));
@@ -48,6 +113,9 @@ pub fn Card(task: Task, set_dragging_task_id: WriteSignal<Option<uuid::Uuid>>) -
                 set_timeout(
                     move || {
                         set_dragging_task_id.set(None);
+                        if let Some(setter) = set_drop_target {
+                            setter.set(None);
+                        }
                         log!("Cleared dragging task ID after timeout");
                     },
                     std::time::Duration::from_millis(50)
@@ -57,6 +125,17 @@ pub fn Card(task: Task, set_dragging_task_id: WriteSignal<Option<uuid::Uuid>>) -
                 // Allow the card to be dragged over and let the event bubble up to the drop zone
                 ev.prevent_default();
                 // Don't stop propagation - let it bubble up to the column drop zone
+                if let Some(setter) = set_drop_target {
+                    // Over the lower half, the dropped card goes below this one
+                    let below = ev
+                        .current_target()
+                        .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+                        .is_some_and(|card| {
+                            let rect = card.get_bounding_client_rect();
+                            f64::from(ev.client_y()) > rect.top() + rect.height() / 2.0
+                        });
+                    setter.set(Some(DropTarget { task_id: task.id, below }));
+                }
             }
             on:drop=move |_ev| {
                 // Allow drop events to bubble up to the parent drop zone
@@ -71,31 +150,152 @@ pub fn Card(task: Task, set_dragging_task_id: WriteSignal<Option<uuid::Uuid>>) -
                     TaskPriority::High => "bg-orange-100 text-orange-800 dark:bg-orange-900 dark:text-orange-300",
                     TaskPriority::Urgent => "bg-red-100 text-red-800 dark:bg-red-900 dark:text-red-300",
                 })}>{priority_label}</span>
+                {is_blocked.then(|| view! {
+                    <span class="flex-shrink-0 mr-1 text-red-500 dark:text-red-400" title="Blocked by unfinished tasks">
+                        <svg xmlns="http://www.w3.org/2000/svg" class="h-4 w-4" fill="none" viewBox="0 0 24 24" stroke="currentColor"><path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M12 15v2m-6 4h12a2 2 0 002-2v-6a2 2 0 00-2-2H6a2 2 0 00-2 2v6a2 2 0 002 2zm10-10V7a4 4 0 00-8 0v4h8z" /></svg>
+                    </span>
+                })}
+                {repeats.map(|repeats| view! {
+                    <span class="flex-shrink-0 mr-1 text-blue-500 dark:text-blue-400" title=repeats>
+                        <svg xmlns="http://www.w3.org/2000/svg" class="h-4 w-4" fill="none" viewBox="0 0 24 24" stroke="currentColor"><path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M4 4v5h.582m15.356 2A8.001 8.001 0 004.582 9m0 0H9m11 11v-5h-.581m0 0a8.003 8.003 0 01-15.357-2m15.357 2H15" /></svg>
+                    </span>
+                })}
                 <div class="flex-1 min-w-0 mr-2">
                     <div class="font-semibold text-gray-900 dark:text-gray-100 leading-tight text-sm overflow-hidden" style="display: -webkit-box; -webkit-line-clamp: 2; -webkit-box-orient: vertical;">
                         {task_name}
                     </div>
                 </div>
+                {assignee_id.map(|member_id| {
+                    let stop = running.is_some();
+                    view! {
+                        <button
+                            class="flex-shrink-0 mr-1 text-gray-400 hover:text-gray-600 dark:hover:text-gray-300"
+                            title=if stop { "Stop timer" } else { "Start timer" }
+                            disabled=move || toggle_timer.pending().get()
+                            on:click=move |ev| {
+                                // Timing a task must not open it
+                                ev.stop_propagation();
+                                toggle_timer.dispatch((member_id, stop));
+                            }
+                        >
+                            {if stop {
+                                view! { <svg xmlns="http://www.w3.org/2000/svg" class="h-5 w-5" fill="none" viewBox="0 0 24 24" stroke="currentColor"><path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M21 12a9 9 0 11-18 0 9 9 0 0118 0z" /><path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M9 10h6v4H9z" /></svg> }
+                            } else {
+                                view! { <svg xmlns="http://www.w3.org/2000/svg" class="h-5 w-5" fill="none" viewBox="0 0 24 24" stroke="currentColor"><path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M14.752 11.168l-3.197-2.132A1 1 0 0010 9.87v4.263a1 1 0 001.555.832l3.197-2.132a1 1 0 000-1.664z" /><path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M21 12a9 9 0 11-18 0 9 9 0 0118 0z" /></svg> }
+                            }}
+                        </button>
+                    }
+                })}
                 <button class="flex-shrink-0 text-gray-400 hover:text-gray-600 dark:hover:text-gray-300" title="Follow">
                     <svg xmlns="http://www.w3.org/2000/svg" class="h-5 w-5" fill="none" viewBox="0 0 24 24" stroke="currentColor"><path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M18 9v6m3-3h-6" /></svg>
                 </button>
             </div>
+            {(!tags.is_empty()).then(|| view! {
+                <div class="flex flex-wrap gap-1 mb-2 overflow-hidden max-h-5">
+                    {tags.into_iter().map(|tag| view! {
+                        <span
+                            class="px-1.5 rounded text-xs font-medium text-white leading-5"
+                            style=format!("background-color: {}", tag.color)
+                        >
+                            {tag.name}
+                        </span>
+                    }).collect_view()}
+                </div>
+            })}
             <div class="bg-gray-50 dark:bg-gray-900 rounded-lg p-3 mb-3 border border-gray-100 dark:border-gray-800 flex-1 overflow-hidden">
                 <div class="text-sm text-gray-600 dark:text-gray-300 overflow-hidden mb-2" style="display: -webkit-box; -webkit-line-clamp: 4; -webkit-box-orient: vertical;">
                     {display_description}
                 </div>
             </div>
             {
-                if date.is_empty() {
+                if date.is_empty()
+                    && comment_count == 0
+                    && progress.is_none()
+                    && effort.is_none()
+                    && running.is_none()
+                {
                     view! { <div></div> }.into_view()
                 } else {
                     view! {
                         <div class="flex justify-between items-center text-xs text-gray-500 dark:text-gray-400 mt-auto">
-                            <span>"Due: " {date}</span>
+                            <span>{(!date.is_empty()).then(|| format!("Due: {date}"))}</span>
+                            {progress.clone().map(|progress| view! {
+                                <button
+                                    class="flex items-center hover:text-gray-700 dark:hover:text-gray-200"
+                                    title="Subtasks"
+                                    on:click=move |ev| {
+                                        // Expanding must not open the task itself
+                                        ev.stop_propagation();
+                                        set_expanded.update(|open| *open = !*open);
+                                    }
+                                >
+                                    <span class="w-10 h-1.5 rounded bg-gray-200 dark:bg-gray-700 mr-1 overflow-hidden">
+                                        <span class="block h-full bg-green-500" style=format!("width: {percent}%")></span>
+                                    </span>
+                                    {progress}
+                                </button>
+                            })}
+                            {running.map(|minutes| view! {
+                                <span class="flex items-center text-red-600 dark:text-red-400" title="Timer running">
+                                    <span class="w-2 h-2 rounded-full bg-red-500 animate-pulse mr-1"></span>
+                                    {format_minutes(minutes)}
+                                </span>
+                            })}
+                            {effort.clone().map(|effort| view! {
+                                <span
+                                    class="flex items-center"
+                                    class=("text-red-600", over)
+                                    title="Logged / estimated"
+                                >
+                                    {effort}
+                                </span>
+                            })}
+                            {(comment_count > 0).then(|| view! {
+                                <span class="flex items-center" title="Comments">
+                                    <svg xmlns="http://www.w3.org/2000/svg" class="h-4 w-4 mr-1" fill="none" viewBox="0 0 24 24" stroke="currentColor"><path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M8 10h.01M12 10h.01M16 10h.01M21 12c0 4.418-4.03 8-9 8a9.86 9.86 0 01-4-.8L3 20l1.3-3.9A7.96 7.96 0 013 12c0-4.418 4.03-8 9-8s9 3.582 9 8z" /></svg>
+                                    {comment_count}
+                                </span>
+                            })}
                         </div>
                     }.into_view()
                 }
             }
         </div>
+        <Show when=move || expanded.get()>
+            <ul class="w-full max-w-sm -mt-3 mb-4 ml-4 pl-3 border-l-2 border-gray-300 dark:border-gray-600 space-y-1 text-sm">
+                {move || children.get().flatten().map(|result| match result {
+                    Ok(children) => children.into_iter().map(|child| {
+                        let done = child.status_category == StatusCategory::Done;
+                        view! {
+                            <li
+                                class="px-2 py-1 rounded bg-white dark:bg-gray-800 text-gray-700 dark:text-gray-300"
+                                class=("line-through", done)
+                            >
+                                {child.title.clone()}
+                                {progress_label(&child).map(|progress| view! {
+                                    <span class="ml-2 text-xs text-gray-500">{progress}</span>
+                                })}
+                            </li>
+                        }
+                    }).collect_view(),
+                    Err(e) => view! { <li class="text-red-500">{e}</li> }.into_view(),
+                })}
+            </ul>
+        </Show>
     }
 }
+
+// Render search highlight spans, marking the words that matched the query
+fn render_highlight(spans: Vec<HighlightSpan>) -> View {
+    spans
+        .into_iter()
+        .map(|span| {
+            if span.matched {
+                view! { <mark class="bg-yellow-200 text-gray-900 rounded px-0.5">{span.text}</mark> }
+                    .into_view()
+            } else {
+                span.text.into_view()
+            }
+        })
+        .collect_view()
+}
@@ -0,0 +1,196 @@
+use crate::api;
+use crate::logic::comment_logic::render_markdown;
+use common::{Comment, CommentRequest};
+use leptos::*;
+use uuid::Uuid;
+
+#[component]
+#[allow(non_snake_case)]
+pub fn CommentThread<F>(task_id: Uuid, on_count_change: F) -> impl IntoView
+where
+    F: Fn(usize) + 'static + Copy,
+{
+    let (comments, set_comments) = create_signal(Vec::<Comment>::new());
+    let (draft, set_draft) = create_signal(String::new());
+    let (editing, set_editing) = create_signal(None::<(Uuid, String)>);
+    let (error, set_error) = create_signal(None::<String>);
+
+    let load_comments = create_action(move |_: &()| api::fetch_comments(task_id));
+
+    let post_comment = create_action(move |body: &String| {
+        let request = CommentRequest { body: body.clone() };
+        async move { api::create_comment(task_id, request).await }
+    });
+
+    let save_comment = create_action(move |(id, body): &(Uuid, String)| {
+        let id = *id;
+        let request = CommentRequest { body: body.clone() };
+        async move { api::update_comment(task_id, id, request).await }
+    });
+
+    let delete_comment = create_action(move |id: &Uuid| {
+        let id = *id;
+        async move { api::delete_comment(task_id, id).await.map(|()| id) }
+    });
+
+    load_comments.dispatch(());
+
+    create_effect(move |_| {
+        if let Some(result) = load_comments.value().get() {
+            match result {
+                Ok(loaded) => set_comments.set(loaded),
+                Err(e) => set_error.set(Some(e)),
+            }
+        }
+    });
+
+    create_effect(move |_| {
+        if let Some(result) = post_comment.value().get() {
+            match result {
+                Ok(comment) => {
+                    set_draft.set(String::new());
+                    set_comments.update(|comments| comments.push(comment));
+                    on_count_change(comments.with_untracked(Vec::len));
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        }
+    });
+
+    create_effect(move |_| {
+        if let Some(result) = save_comment.value().get() {
+            match result {
+                Ok(saved) => {
+                    set_editing.set(None);
+                    set_comments.update(|comments| {
+                        if let Some(comment) = comments.iter_mut().find(|c| c.id == saved.id) {
+                            *comment = saved;
+                        }
+                    });
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        }
+    });
+
+    create_effect(move |_| {
+        if let Some(result) = delete_comment.value().get() {
+            match result {
+                Ok(id) => {
+                    set_comments.update(|comments| comments.retain(|c| c.id != id));
+                    on_count_change(comments.with_untracked(Vec::len));
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        }
+    });
+
+    let on_post = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        let body = draft.get();
+        if body.trim().is_empty() {
+            return;
+        }
+        set_error.set(None);
+        post_comment.dispatch(body);
+    };
+
+    let render_comment = move |comment: Comment| {
+        let id = comment.id;
+        let body = comment.body.clone();
+        let is_editing =
+            move || editing.with(|editing| matches!(editing, Some((edit_id, _)) if *edit_id == id));
+
+        view! {
+            <li class="rounded-lg bg-gray-900 border border-gray-700 p-3">
+                <div class="flex justify-between items-center text-xs text-gray-500 mb-1">
+                    <span>
+                        {comment.author.clone().unwrap_or_else(|| "Someone".to_string())}
+                        " · "
+                        {comment.created_at.format("%Y %b %d %H:%M").to_string()}
+                        {(comment.updated_at != comment.created_at).then_some(" (edited)")}
+                    </span>
+                    <span class="space-x-2">
+                        <button
+                            class="hover:text-gray-300"
+                            on:click=move |_| set_editing.set(Some((id, body.clone())))
+                        >
+                            "Edit"
+                        </button>
+                        <button
+                            class="hover:text-red-300"
+                            on:click=move |_| { delete_comment.dispatch(id); }
+                        >
+                            "Delete"
+                        </button>
+                    </span>
+                </div>
+                {move || if is_editing() {
+                    view! {
+                        <div class="space-y-2">
+                            <textarea
+                                rows="3"
+                                class="w-full px-2 py-1 text-sm bg-gray-800 border border-gray-600 rounded text-gray-100"
+                                prop:value=move || editing.get().map(|(_, body)| body).unwrap_or_default()
+                                on:input=move |ev| set_editing.set(Some((id, event_target_value(&ev))))
+                            ></textarea>
+                            <div class="flex justify-end space-x-2 text-xs">
+                                <button class="text-gray-400 hover:text-gray-200" on:click=move |_| set_editing.set(None)>
+                                    "Cancel"
+                                </button>
+                                <button
+                                    class="px-2 py-1 rounded bg-blue-600 text-white hover:bg-blue-700"
+                                    on:click=move |_| {
+                                        if let Some(edit) = editing.get() {
+                                            save_comment.dispatch(edit);
+                                        }
+                                    }
+                                >
+                                    "Save"
+                                </button>
+                            </div>
+                        </div>
+                    }.into_view()
+                } else {
+                    let html = comments.with(|comments| {
+                        comments.iter().find(|c| c.id == id).map(|c| render_markdown(&c.body))
+                    });
+                    view! { <div class="prose prose-invert prose-sm max-w-none" inner_html=html></div> }.into_view()
+                }}
+            </li>
+        }
+    };
+
+    view! {
+        <div class="space-y-3">
+            {move || error.get().map(|err| view! { <p class="text-red-300">{err}</p> })}
+
+            <ul class="space-y-2 max-h-64 overflow-y-auto">
+                <For
+                    each=move || comments.get()
+                    key=|comment| (comment.id, comment.updated_at)
+                    children=render_comment
+                />
+            </ul>
+
+            <form on:submit=on_post class="space-y-2">
+                <textarea
+                    rows="3"
+                    placeholder="Add a comment. Markdown is supported."
+                    class="w-full px-3 py-2 text-sm bg-gray-700 border border-gray-600 rounded-md text-gray-100 placeholder-gray-400 focus:outline-none focus:ring-2 focus:ring-blue-500"
+                    prop:value=draft
+                    on:input=move |ev| set_draft.set(event_target_value(&ev))
+                ></textarea>
+                <div class="flex justify-end">
+                    <button
+                        type="submit"
+                        disabled=move || post_comment.pending().get() || draft.with(|draft| draft.trim().is_empty())
+                        class="px-3 py-1.5 text-sm font-medium rounded-md bg-blue-600 text-white hover:bg-blue-700 disabled:opacity-50"
+                    >
+                        "Comment"
+                    </button>
+                </div>
+            </form>
+        </div>
+    }
+}
@@ -1,14 +1,20 @@
+pub mod attachment_list;
 pub mod card;
+pub mod comment_thread;
 pub mod modal;
 pub mod navbar;
 pub mod sidebar;
+pub mod task_detail;
 pub mod task_form;
 pub mod task_item;
 pub mod task_list;
 
+pub use attachment_list::*;
 pub use card::*;
+pub use comment_thread::*;
 pub use modal::*;
 pub use navbar::*;
 pub use sidebar::*;
+pub use task_detail::*;
 pub use task_form::*;
 pub use task_list::*;
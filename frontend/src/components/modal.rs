@@ -3,7 +3,7 @@ use leptos::*;
 #[component]
 #[allow(non_snake_case)]
 pub fn Modal<F>(
-    show: ReadSignal<bool>,
+    #[prop(into)] show: Signal<bool>,
     on_close: F,
     title: String,
     children: Children,
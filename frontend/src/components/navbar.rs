@@ -1,22 +1,166 @@
-use common::TaskPriority;
+use crate::api;
+use crate::logic::board_state::BoardState;
+use crate::logic::query_logic::split_at_error;
+use crate::logic::saved_view_logic::{existing_view, toggle_column, view_request, SORT_CHOICES};
+use crate::logic::tag_logic::{pick_tag, tag_suggestions};
+use crate::logic::workflow_logic::state_label;
+use common::{
+    query::{QueryError, TaskQuery},
+    saved_views::ViewGrouping,
+    TaskPriority, Workflow,
+};
 use leptos::*;
+use uuid::Uuid;
+
+// Wait for a pause in typing before searching
+const SEARCH_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(300);
 
 #[component]
 pub fn Navbar<F>(
-    filter_priority: ReadSignal<Option<TaskPriority>>,
-    set_filter_priority: WriteSignal<Option<TaskPriority>>,
+    board: BoardState,
+    /// The project whose board is open, which a saved view opens.
+    project_id: Signal<Uuid>,
+    /// The project's workflow, whose columns can be hidden.
+    workflow: Signal<Workflow>,
     on_add_task: F,
 ) -> impl IntoView
 where
     F: Fn() + 'static + Copy,
 {
+    let BoardState {
+        filter_priority,
+        search_query,
+        filter_tags,
+        filter_assignee,
+        sort,
+        grouping,
+        columns,
+        views_changed,
+    } = board;
+
+    let (search_text, set_search_text) = create_signal(String::new());
+    // Why the typed query did not parse; the board keeps the last good one
+    let (query_error, set_query_error) = create_signal(None::<QueryError>);
+    // Bumped on every keystroke so only the latest pending search fires
+    let search_generation = store_value(0_u32);
+
+    // A restored view brings its own search, which replaces any pending one
+    create_effect(move |_| {
+        let query = search_query.get();
+        if search_text.get_untracked().trim() != query {
+            search_generation.update_value(|generation| *generation += 1);
+            set_search_text.set(query);
+            set_query_error.set(None);
+        }
+    });
+
+    let on_search_input = move |ev| {
+        let value = event_target_value(&ev);
+        set_search_text.set(value.clone());
+        search_generation.update_value(|generation| *generation += 1);
+        let generation = search_generation.get_value();
+
+        set_timeout(
+            move || {
+                if search_generation.get_value() != generation {
+                    return;
+                }
+                match value.parse::<TaskQuery>() {
+                    Ok(_) => {
+                        set_query_error.set(None);
+                        search_query.set(value.trim().to_string());
+                    }
+                    Err(err) => set_query_error.set(Some(err)),
+                }
+            },
+            SEARCH_DEBOUNCE,
+        );
+    };
+
+    // The query with the word the error points at underlined
+    let query_error_view = move || {
+        query_error.get().map(|err| {
+            let (before, word, after) =
+                search_text.with_untracked(|text| split_at_error(text, &err));
+            view! {
+                <div class="absolute top-full left-0 mt-1 z-20 w-72 rounded border border-red-500 bg-gray-900 px-2 py-1 text-xs text-gray-100 shadow-lg">
+                    <p class="font-mono whitespace-pre-wrap break-all">
+                        {before}
+                        <span class="text-red-400 underline decoration-wavy">
+                            {if word.is_empty() { "␣".to_string() } else { word }}
+                        </span>
+                        {after}
+                    </p>
+                    <p class="mt-1 text-red-400">{err.to_string()}</p>
+                </div>
+            }
+        })
+    };
+
+    let (tag_text, set_tag_text) = create_signal(String::new());
+    // Refetched on focus so tags created elsewhere are offered too
+    let load_tags = create_action(|_: &()| api::fetch_tags());
+    load_tags.dispatch(());
+    let tags = move || {
+        load_tags
+            .value()
+            .get()
+            .and_then(Result::ok)
+            .unwrap_or_default()
+    };
+
+    let members = create_local_resource(|| (), |_| api::fetch_members());
+
+    let on_tag_change = move |ev| {
+        let value = event_target_value(&ev);
+        if let Some(name) = filter_tags.with(|picked| pick_tag(&tags(), picked, &value)) {
+            filter_tags.update(|picked| picked.push(name));
+            set_tag_text.set(String::new());
+        }
+    };
+
+    let clear_filters = move |_| {
+        filter_priority.set(None);
+        filter_tags.set(Vec::new());
+        filter_assignee.set(None);
+        set_tag_text.set(String::new());
+        search_generation.update_value(|generation| *generation += 1);
+        set_search_text.set(String::new());
+        set_query_error.set(None);
+        search_query.set(String::new());
+    };
+
+    // Saving under the name of an existing view replaces its settings
+    let save_view = create_action(move |name: &String| {
+        let request = view_request(name, &board.snapshot(), project_id.get_untracked());
+        async move {
+            let views = api::fetch_saved_views().await?;
+            let id = existing_view(&views, &request.name);
+            api::save_view(id, request).await
+        }
+    });
+    create_effect(move |_| match save_view.value().get() {
+        Some(Ok(_)) => views_changed.notify(),
+        Some(Err(message)) => {
+            let _ = window().alert_with_message(&message);
+        }
+        None => {}
+    });
+    let on_save_view = move |_| {
+        if let Ok(Some(name)) = window().prompt_with_message("Save the current view as:") {
+            if !name.trim().is_empty() {
+                save_view.dispatch(name);
+            }
+        }
+    };
+
     view! {
         <nav class="bg-gray-800 dark:bg-gray-900 border-b border-gray-700 dark:border-gray-600 px-4 py-1 mb-2">
             <div class="flex items-center justify-between">
                 <div class="flex items-center space-x-3">
                     // Clear filters button
                     <button
-                        on:click=move |_| set_filter_priority.set(None)
+                        on:click=clear_filters
                         class="bg-gray-600 hover:bg-gray-500 text-gray-200 px-2 py-1 rounded text-xs transition-colors"
                     >
                         "Clear Filters"
@@ -33,7 +177,7 @@ where
                                 "Urgent" => Some(TaskPriority::Urgent),
                                 _ => None,
                             };
-                            set_filter_priority.set(priority);
+                            filter_priority.set(priority);
                         }
                         class="bg-gray-700 dark:bg-gray-800 text-gray-100 dark:text-gray-100 border border-gray-600 dark:border-gray-600 rounded px-2 py-1 text-xs focus:outline-none focus:ring-2 focus:ring-blue-500 focus:border-transparent w-32 appearance-none"
                         style="background-image: none;"
@@ -45,44 +189,187 @@ where
                         <option value="Urgent" selected=move || filter_priority.get() == Some(TaskPriority::Urgent)>"Urgent"</option>
                     </select>
 
-                    // Status dropdown placeholder (no label, no arrow)
+                    // Assignee filter (no label, no arrow)
                     <select
+                        on:change=move |ev| {
+                            filter_assignee.set(event_target_value(&ev).parse().ok());
+                        }
                         class="bg-gray-700 dark:bg-gray-800 text-gray-100 dark:text-gray-100 border border-gray-600 dark:border-gray-600 rounded px-2 py-1 text-xs focus:outline-none focus:ring-2 focus:ring-blue-500 focus:border-transparent w-32 appearance-none"
                         style="background-image: none;"
                     >
-                        <option selected>"All Status"</option>
-                        <option>"Todo"</option>
-                        <option>"In Progress"</option>
-                        <option>"Completed"</option>
+                        <option value="" selected=move || filter_assignee.get().is_none()>"All Users"</option>
+                        {move || {
+                            members
+                                .get()
+                                .and_then(Result::ok)
+                                .unwrap_or_default()
+                                .into_iter()
+                                .map(|member| {
+                                    let id = member.id;
+                                    view! {
+                                        <option
+                                            value=id.to_string()
+                                            selected=move || filter_assignee.get() == Some(id)
+                                        >
+                                            {member.name}
+                                        </option>
+                                    }
+                                })
+                                .collect_view()
+                        }}
                     </select>
 
-                    // Assignee dropdown placeholder (no label, no arrow)
+                    // Order of cards within each column
                     <select
+                        on:change=move |ev| sort.set(event_target_value(&ev).parse().ok())
                         class="bg-gray-700 dark:bg-gray-800 text-gray-100 dark:text-gray-100 border border-gray-600 dark:border-gray-600 rounded px-2 py-1 text-xs focus:outline-none focus:ring-2 focus:ring-blue-500 focus:border-transparent w-32 appearance-none"
                         style="background-image: none;"
+                        title="Sort cards"
                     >
-                        <option selected>"All Users"</option>
-                        <option>"Pratham"</option>
-                        <option>"Marko"</option>
-                        <option>"Sarah"</option>
-                        <option>"John"</option>
+                        {SORT_CHOICES
+                            .into_iter()
+                            .map(|(value, label)| {
+                                let picked = move || {
+                                    sort.get().map(|sort| sort.to_string()).unwrap_or_default() == value
+                                };
+                                view! { <option value=value selected=picked>{label}</option> }
+                            })
+                            .collect_view()}
                     </select>
+
+                    // Lanes within each column
+                    <select
+                        on:change=move |ev| {
+                            let value = event_target_value(&ev);
+                            grouping.set(match value.as_str() {
+                                "Priority" => ViewGrouping::Priority,
+                                "Assignee" => ViewGrouping::Assignee,
+                                _ => ViewGrouping::None,
+                            });
+                        }
+                        class="bg-gray-700 dark:bg-gray-800 text-gray-100 dark:text-gray-100 border border-gray-600 dark:border-gray-600 rounded px-2 py-1 text-xs focus:outline-none focus:ring-2 focus:ring-blue-500 focus:border-transparent w-32 appearance-none"
+                        style="background-image: none;"
+                        title="Group cards"
+                    >
+                        <option value="None" selected=move || grouping.get() == ViewGrouping::None>"No Lanes"</option>
+                        <option value="Priority" selected=move || grouping.get() == ViewGrouping::Priority>"By Priority"</option>
+                        <option value="Assignee" selected=move || grouping.get() == ViewGrouping::Assignee>"By Assignee"</option>
+                    </select>
+
+                    // Columns shown, one checkbox per workflow state
+                    <details class="relative">
+                        <summary class="bg-gray-700 text-gray-100 border border-gray-600 rounded px-2 py-1 text-xs cursor-pointer list-none">
+                            "Columns"
+                        </summary>
+                        <div class="absolute z-10 mt-1 bg-gray-800 border border-gray-600 rounded p-2 space-y-1 w-40">
+                            {move || {
+                                workflow
+                                    .get()
+                                    .states
+                                    .into_iter()
+                                    .map(|state| {
+                                        let name = store_value(state.name);
+                                        let shown = move || {
+                                            columns.with(|columns| {
+                                                name.with_value(|name| columns.is_empty() || columns.contains(name))
+                                            })
+                                        };
+                                        view! {
+                                            <label class="flex items-center text-xs text-gray-100">
+                                                <input
+                                                    type="checkbox"
+                                                    class="mr-2"
+                                                    prop:checked=shown
+                                                    on:change=move |ev| {
+                                                        let ticked = event_target_checked(&ev);
+                                                        columns.update(|columns| {
+                                                            *columns = workflow.with_untracked(|workflow| {
+                                                                name.with_value(|name| {
+                                                                    toggle_column(&workflow.states, columns, name, ticked)
+                                                                })
+                                                            });
+                                                        });
+                                                    }
+                                                />
+                                                {name.with_value(state_label)}
+                                            </label>
+                                        }
+                                    })
+                                    .collect_view()
+                            }}
+                        </div>
+                    </details>
                 </div>
 
                 <div class="flex items-center space-x-3">
-                    // Search textbox (no label)
-                    <input
-                        type="text"
-                        placeholder="Search tasks..."
-                        class="bg-gray-700 dark:bg-gray-800 text-gray-100 dark:text-gray-100 border border-gray-600 dark:border-gray-600 rounded px-2 py-1 text-xs focus:outline-none focus:ring-2 focus:ring-blue-500 focus:border-transparent w-32"
+                    // Search textbox (no label); takes words or a task query
+                    <div class="relative">
+                        <input
+                            type="search"
+                            placeholder="Search or tag:bug priority>=High..."
+                            title="Search words, or filter with status:, priority:, due:, created:, updated:, tag: and assignee:"
+                            prop:value=search_text
+                            on:input=on_search_input
+                            class="bg-gray-700 dark:bg-gray-800 text-gray-100 dark:text-gray-100 border border-gray-600 dark:border-gray-600 rounded px-2 py-1 text-xs focus:outline-none focus:ring-2 focus:ring-blue-500 focus:border-transparent w-56"
+                            class=("ring-2", move || query_error.with(Option::is_some))
+                            class=("ring-red-500", move || query_error.with(Option::is_some))
+                        />
+                        {query_error_view}
+                    </div>
+
+                    // Picked tags; tasks must carry all of them
+                    <For
+                        each=move || filter_tags.get()
+                        key=|name| name.clone()
+                        children=move |name| {
+                            let remove = name.clone();
+                            view! {
+                                <span class="flex items-center bg-gray-600 text-gray-100 rounded px-2 py-0.5 text-xs">
+                                    {name}
+                                    <button
+                                        on:click=move |_| {
+                                            filter_tags.update(|picked| picked.retain(|tag| *tag != remove));
+                                        }
+                                        class="ml-1 text-gray-300 hover:text-white"
+                                        title="Remove tag filter"
+                                    >
+                                        "×"
+                                    </button>
+                                </span>
+                            }
+                        }
                     />
 
-                    // Additional textbox (no label)
+                    // Tag filter, autocompleted from the existing tags
                     <input
                         type="text"
                         placeholder="Filter by tags..."
+                        list="tag-suggestions"
+                        prop:value=tag_text
+                        on:focus=move |_| load_tags.dispatch(())
+                        on:input=move |ev| set_tag_text.set(event_target_value(&ev))
+                        on:change=on_tag_change
                         class="bg-gray-700 dark:bg-gray-800 text-gray-100 dark:text-gray-100 border border-gray-600 dark:border-gray-600 rounded px-2 py-1 text-xs focus:outline-none focus:ring-2 focus:ring-blue-500 focus:border-transparent w-32"
                     />
+                    <datalist id="tag-suggestions">
+                        {move || {
+                            let tags = tags();
+                            filter_tags.with(|picked| {
+                                tag_suggestions(&tags, picked, &tag_text.get())
+                                    .into_iter()
+                                    .map(|tag| view! { <option value=tag.name.clone()></option> })
+                                    .collect_view()
+                            })
+                        }}
+                    </datalist>
+
+                    // Keeps the filters, order, lanes and columns for the sidebar
+                    <button
+                        on:click=on_save_view
+                        class="bg-gray-600 hover:bg-gray-500 text-gray-200 px-2 py-1 rounded text-xs transition-colors"
+                    >
+                        "Save View"
+                    </button>
 
                     // Add Task Button
                     <button
@@ -1,7 +1,37 @@
+use crate::{
+    api,
+    logic::{
+        board_state::BoardState,
+        project_logic::board_path,
+        saved_view_logic::{board_from_view, view_project},
+    },
+};
 use leptos::*;
+use leptos_router::{use_location, use_navigate, NavigateOptions};
+use uuid::Uuid;
 
 #[component]
 pub fn Sidebar() -> impl IntoView {
+    let projects = create_local_resource(|| (), |_| api::fetch_projects());
+    let location = use_location();
+    let navigate = use_navigate();
+
+    // Listed again whenever the navbar saves one
+    let board = expect_context::<BoardState>();
+    let views = create_local_resource(|| (), |_| api::fetch_saved_views());
+    create_effect(move |loaded: Option<()>| {
+        board.views_changed.track();
+        if loaded.is_some() {
+            views.refetch();
+        }
+    });
+    let delete_view = create_action(|id: &Uuid| api::delete_saved_view(*id));
+    create_effect(move |_| {
+        if let Some(Ok(())) = delete_view.value().get() {
+            views.refetch();
+        }
+    });
+
     view! {
         <aside class="w-64 fixed top-0 left-0 h-full bg-gray-800 border-r border-gray-700 flex flex-col z-20">
             <div class="p-4 font-semibold text-lg border-b border-gray-700 text-white">RustTracker</div>
@@ -11,6 +41,75 @@ pub fn Sidebar() -> impl IntoView {
                 <a href="/kanban" class="block px-4 py-2 rounded hover:bg-gray-700 text-gray-200 hover:text-white font-medium transition-colors">Kanban</a>
                 <a href="/reports" class="block px-4 py-2 rounded hover:bg-gray-700 text-gray-200 hover:text-white font-medium transition-colors">Reports</a>
                 <a href="/settings" class="block px-4 py-2 rounded hover:bg-gray-700 text-gray-200 hover:text-white font-medium transition-colors">Settings</a>
+                <a href="/trash" class="block px-4 py-2 rounded hover:bg-gray-700 text-gray-200 hover:text-white font-medium transition-colors">Trash</a>
+
+                // Project switcher: each project's board
+                <div class="pt-4 mt-4 border-t border-gray-700">
+                    <div class="px-4 pb-2 text-xs font-semibold uppercase tracking-wide text-gray-400">"Projects"</div>
+                    {move || {
+                        projects
+                            .get()
+                            .and_then(Result::ok)
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|project| {
+                                let path = board_path(project.id);
+                                let is_current = {
+                                    let path = path.clone();
+                                    move || location.pathname.get() == path
+                                };
+                                view! {
+                                    <a
+                                        href=path
+                                        class="block px-4 py-2 rounded hover:bg-gray-700 text-gray-200 hover:text-white transition-colors"
+                                        class:bg-gray-700=is_current
+                                    >
+                                        {project.name}
+                                    </a>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </div>
+
+                // Saved views: picking one restores the board it was saved from
+                <div class="pt-4 mt-4 border-t border-gray-700">
+                    <div class="px-4 pb-2 text-xs font-semibold uppercase tracking-wide text-gray-400">"Views"</div>
+                    {move || {
+                        views
+                            .get()
+                            .and_then(Result::ok)
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|view| {
+                                let id = view.id;
+                                let name = view.name.clone();
+                                let navigate = navigate.clone();
+                                let open = move |_| {
+                                    board.restore(board_from_view(&view));
+                                    navigate(&board_path(view_project(&view)), NavigateOptions::default());
+                                };
+                                view! {
+                                    <div class="flex items-center rounded hover:bg-gray-700">
+                                        <button
+                                            on:click=open
+                                            class="flex-1 text-left px-4 py-2 text-gray-200 hover:text-white transition-colors"
+                                        >
+                                            {name}
+                                        </button>
+                                        <button
+                                            on:click=move |_| delete_view.dispatch(id)
+                                            class="px-2 text-gray-400 hover:text-white"
+                                            title="Delete view"
+                                        >
+                                            "×"
+                                        </button>
+                                    </div>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </div>
             </nav>
             <div class="p-4 border-t border-gray-700 text-sm text-gray-400">Sidebar navigation</div>
         </aside>
@@ -0,0 +1,100 @@
+use crate::api;
+use crate::components::{AttachmentList, CommentThread};
+use crate::logic::custom_field_logic::field_rows;
+use crate::logic::task_history_logic::describe_event;
+use crate::logic::workflow_logic::state_label;
+use common::Task;
+use leptos::*;
+
+#[component]
+#[allow(non_snake_case)]
+pub fn TaskDetail<F>(task: Task, on_comment_count: F) -> impl IntoView
+where
+    F: Fn(usize) + 'static + Copy,
+{
+    // Reloaded each time the detail opens, so it includes the latest changes
+    let history = create_resource(move || task.id, api::fetch_task_history);
+    let fields = create_resource(move || task.project_id, api::fetch_custom_fields);
+    let custom_values = task.custom_fields.clone();
+
+    let due_date = task
+        .due_date
+        .map_or_else(|| "None".to_string(), |d| d.format("%Y %b %d").to_string());
+
+    view! {
+        <div class="space-y-4 text-sm text-gray-300">
+            <div>
+                <h4 class="text-base font-semibold text-gray-100">{task.title.clone()}</h4>
+                {task.description.clone().map(|description| view! {
+                    <p class="mt-1 whitespace-pre-wrap">{description}</p>
+                })}
+            </div>
+
+            <dl class="grid grid-cols-3 gap-2">
+                <div>
+                    <dt class="text-xs text-gray-500">"Status"</dt>
+                    <dd>{state_label(&task.status)}</dd>
+                </div>
+                <div>
+                    <dt class="text-xs text-gray-500">"Priority"</dt>
+                    <dd>{format!("{:?}", task.priority)}</dd>
+                </div>
+                <div>
+                    <dt class="text-xs text-gray-500">"Due"</dt>
+                    <dd>{due_date}</dd>
+                </div>
+            </dl>
+
+            {move || {
+                let rows = fields
+                    .get()
+                    .and_then(Result::ok)
+                    .map(|fields| field_rows(&fields, &custom_values))
+                    .unwrap_or_default();
+                (!rows.is_empty()).then(|| view! {
+                    <dl class="grid grid-cols-3 gap-2">
+                        {rows.into_iter().map(|(name, value)| view! {
+                            <div>
+                                <dt class="text-xs text-gray-500">{name}</dt>
+                                <dd>{value}</dd>
+                            </div>
+                        }).collect_view()}
+                    </dl>
+                })
+            }}
+
+            <div>
+                <h4 class="text-xs font-semibold uppercase tracking-wide text-gray-500 mb-2">"Attachments"</h4>
+                <AttachmentList task_id=task.id />
+            </div>
+
+            <div>
+                <h4 class="text-xs font-semibold uppercase tracking-wide text-gray-500 mb-2">"Comments"</h4>
+                <CommentThread task_id=task.id on_count_change=on_comment_count />
+            </div>
+
+            <div>
+                <h4 class="text-xs font-semibold uppercase tracking-wide text-gray-500 mb-2">"History"</h4>
+                <Suspense fallback=move || view! { <p class="text-gray-500">"Loading history..."</p> }>
+                    {move || history.get().map(|result| match result {
+                        Ok(events) => view! {
+                            <ol class="border-l border-gray-600 pl-4 space-y-3 max-h-64 overflow-y-auto">
+                                {events.into_iter().rev().map(|event| view! {
+                                    <li class="relative">
+                                        <span class="absolute -left-[21px] top-1.5 h-2 w-2 rounded-full bg-blue-400"></span>
+                                        <p class="text-gray-200">{describe_event(&event)}</p>
+                                        <p class="text-xs text-gray-500">
+                                            {event.created_at.format("%Y %b %d %H:%M").to_string()}
+                                            {event.actor.map(|actor| format!(" by {actor}"))}
+                                        </p>
+                                    </li>
+                                }).collect_view()}
+                            </ol>
+                        }.into_view(),
+                        Err(e) => view! { <p class="text-red-300">{e}</p> }.into_view(),
+                    })}
+                </Suspense>
+            </div>
+        </div>
+    }
+}
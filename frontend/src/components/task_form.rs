@@ -1,10 +1,20 @@
 use crate::api;
+use crate::logic::custom_field_logic::{form_values, input_text, toggle_option, value_lists};
+use crate::logic::time_logic::parse_minutes;
+use common::custom_fields::{CustomField, CustomFieldType, CustomFieldValues};
 use common::{CreateTaskRequest, TaskPriority};
 use leptos::*;
+use serde_json::Value;
+
+const INPUT_CLASS: &str = "w-full px-3 py-2 border border-gray-600 dark:border-gray-600 bg-gray-800 dark:bg-gray-800 text-gray-100 dark:text-gray-100 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500 focus:border-transparent";
 
 #[component]
 #[allow(non_snake_case)]
-pub fn TaskForm<F, G>(on_submit: F, on_close: Option<G>) -> impl IntoView
+pub fn TaskForm<F, G>(
+    #[prop(into)] project_id: Signal<uuid::Uuid>,
+    on_submit: F,
+    on_close: Option<G>,
+) -> impl IntoView
 where
     F: Fn() + 'static + Copy,
     G: Fn() + 'static + Copy,
@@ -12,6 +22,11 @@ where
     let (title, set_title) = create_signal(String::new());
     let (description, set_description) = create_signal(String::new());
     let (priority, set_priority) = create_signal(TaskPriority::Medium);
+    let (assignee_id, set_assignee_id) = create_signal(None::<uuid::Uuid>);
+    let (estimate, set_estimate) = create_signal(String::new());
+    let (custom_values, set_custom_values) = create_signal(CustomFieldValues::new());
+    let members = create_local_resource(|| (), |_| api::fetch_members());
+    let fields = create_local_resource(move || project_id.get(), api::fetch_custom_fields);
     let (is_submitting, set_is_submitting) = create_signal(false);
     let (error, set_error) = create_signal(None::<String>);
 
@@ -37,11 +52,39 @@ where
             Some(description_value.to_string())
         };
 
+        let estimate_value = estimate.get();
+        let estimate_minutes = if estimate_value.trim().is_empty() {
+            None
+        } else if let Some(minutes) = parse_minutes(&estimate_value) {
+            Some(minutes)
+        } else {
+            set_error.set(Some(
+                "Estimate must read like 1h 30m, 2h or 45m".to_string(),
+            ));
+            return;
+        };
+
+        let project_fields = fields.get().and_then(Result::ok).unwrap_or_default();
+        let custom_fields = match form_values(&project_fields, &custom_values.get()) {
+            Ok(values) => values,
+            Err(e) => {
+                set_error.set(Some(e));
+                return;
+            }
+        };
+
         let request = CreateTaskRequest {
             title: title_value,
             description: description_value,
             priority: priority.get(),
-            due_date: None, // TODO: Add date picker
+            due_date: None, // TODO: Add date picker,
+            tags: Vec::new(),
+            assignee_id: assignee_id.get(),
+            project_id: Some(project_id.get()),
+            parent_id: None,
+            recurrence: None,
+            estimate_minutes,
+            custom_fields,
         };
 
         set_error.set(None);
@@ -57,6 +100,9 @@ where
                     set_title.set(String::new());
                     set_description.set(String::new());
                     set_priority.set(TaskPriority::Medium);
+                    set_assignee_id.set(None);
+                    set_estimate.set(String::new());
+                    set_custom_values.set(CustomFieldValues::new());
                     on_submit();
                     if let Some(close_fn) = on_close {
                         close_fn();
@@ -123,6 +169,60 @@ where
                 </select>
             </div>
 
+            <div>
+                <label class="block text-sm font-medium text-gray-300 dark:text-gray-300 mb-2">
+                    "Assignee"
+                </label>
+                <select
+                    on:change=move |ev| set_assignee_id.set(event_target_value(&ev).parse().ok())
+                    class="w-full px-3 py-2 border border-gray-600 dark:border-gray-600 bg-gray-800 dark:bg-gray-800 text-gray-100 dark:text-gray-100 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500 focus:border-transparent"
+                >
+                    <option value="" selected=move || assignee_id.get().is_none()>"Unassigned"</option>
+                    {move || {
+                        members
+                            .get()
+                            .and_then(Result::ok)
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|member| {
+                                let id = member.id;
+                                view! {
+                                    <option
+                                        value=id.to_string()
+                                        selected=move || assignee_id.get() == Some(id)
+                                    >
+                                        {member.name}
+                                    </option>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </select>
+            </div>
+
+            <div>
+                <label class="block text-sm font-medium text-gray-300 dark:text-gray-300 mb-2">
+                    "Estimate"
+                </label>
+                <input
+                    type="text"
+                    prop:value=estimate
+                    on:input=move |ev| set_estimate.set(event_target_value(&ev))
+                    class="w-full px-3 py-2 border border-gray-600 dark:border-gray-600 bg-gray-800 dark:bg-gray-800 text-gray-100 dark:text-gray-100 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500 focus:border-transparent"
+                    placeholder="e.g. 1h 30m (optional)"
+                />
+            </div>
+
+            {move || {
+                fields
+                    .get()
+                    .and_then(Result::ok)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|field| custom_field_input(field, custom_values, set_custom_values))
+                    .collect_view()
+            }}
+
             {move || error.get().map(|err| view! {
                 <div class="text-red-300 text-sm bg-red-900 border border-red-700 rounded-md p-3">
                     {err}
@@ -141,3 +241,108 @@ where
         </form>
     }
 }
+
+/// The labelled input for one of the project's custom fields, writing into
+/// `values` as the user types.
+fn custom_field_input(
+    field: CustomField,
+    values: ReadSignal<CustomFieldValues>,
+    set_values: WriteSignal<CustomFieldValues>,
+) -> View {
+    let id = field.id;
+    let set = move |value: Value| {
+        set_values.update(|values| {
+            values.insert(id, value);
+        });
+    };
+
+    let input = match field.field_type {
+        CustomFieldType::Text | CustomFieldType::Number | CustomFieldType::Date => {
+            let kind = match field.field_type {
+                CustomFieldType::Number => "number",
+                CustomFieldType::Date => "date",
+                _ => "text",
+            };
+            view! {
+                <input
+                    type=kind
+                    step="any"
+                    prop:value=move || input_text(values.get().get(&id))
+                    on:input=move |ev| set(Value::String(event_target_value(&ev)))
+                    class=INPUT_CLASS
+                />
+            }
+            .into_view()
+        }
+        CustomFieldType::SingleSelect => view! {
+            <select
+                on:change=move |ev| set(Value::String(event_target_value(&ev)))
+                class=INPUT_CLASS
+            >
+                <option value="" selected=move || !values.get().contains_key(&id)>"None"</option>
+                {field
+                    .options
+                    .iter()
+                    .map(|option| {
+                        let picked = Value::String(option.clone());
+                        view! {
+                            <option
+                                value=option.clone()
+                                selected=move || values.get().get(&id) == Some(&picked)
+                            >
+                                {option.clone()}
+                            </option>
+                        }
+                    })
+                    .collect_view()}
+            </select>
+        }
+        .into_view(),
+        CustomFieldType::MultiSelect => field
+            .options
+            .iter()
+            .map(|option| {
+                let (shown, ticked) = (option.clone(), option.clone());
+                let (field, option) = (field.clone(), option.clone());
+                view! {
+                    <label class="inline-flex items-center gap-1 mr-3 text-sm text-gray-300">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || {
+                                values.get().get(&id).is_some_and(|value| value_lists(value, &ticked))
+                            }
+                            on:change=move |ev| {
+                                let value = toggle_option(
+                                    &field,
+                                    values.get_untracked().get(&id),
+                                    &option,
+                                    event_target_checked(&ev),
+                                );
+                                set(value);
+                            }
+                        />
+                        {shown}
+                    </label>
+                }
+            })
+            .collect_view(),
+        CustomFieldType::Checkbox => view! {
+            <input
+                type="checkbox"
+                prop:checked=move || values.get().get(&id) == Some(&Value::Bool(true))
+                on:change=move |ev| set(Value::Bool(event_target_checked(&ev)))
+            />
+        }
+        .into_view(),
+    };
+
+    view! {
+        <div>
+            <label class="block text-sm font-medium text-gray-300 dark:text-gray-300 mb-2">
+                {field.name.clone()}
+            </label>
+            {input}
+        </div>
+    }
+    .into_view()
+}
@@ -1,11 +1,15 @@
-use crate::api;
-use common::{Task, TaskStatus, UpdateTaskRequest};
+use crate::api::{self, WriteError};
+use crate::logic::dependency_logic::{needs_blocker_override, BLOCKED_MOVE_PROMPT};
+use crate::logic::workflow_logic::{badge_color, move_choices, state_label};
+use common::{Patch, Task, TaskStatus, UpdateTaskRequest, Workflow};
 use leptos::*;
 
 #[component]
 #[allow(non_snake_case)]
 pub fn TaskItem<F>(
     task: Task,
+    /// Workflow of the task's project, whose allowed moves the card offers.
+    workflow: Workflow,
     on_update: F,
     #[prop(optional)] set_dragging_task_id: Option<WriteSignal<Option<uuid::Uuid>>>,
 ) -> impl IntoView
@@ -15,35 +19,49 @@ where
     let (is_updating, set_is_updating) = create_signal(false);
     let (is_deleting, set_is_deleting) = create_signal(false);
 
-    let update_task = create_action(move |(id, request): &(uuid::Uuid, UpdateTaskRequest)| {
-        let id = *id;
-        let request = request.clone();
-        async move { api::update_task(id, request).await }
-    });
+    let version = task.version;
+
+    let update_task = create_action(
+        move |(id, request, override_blockers): &(uuid::Uuid, UpdateTaskRequest, bool)| {
+            let id = *id;
+            let request = request.clone();
+            let override_blockers = *override_blockers;
+            async move { api::update_task(id, request, version, override_blockers).await }
+        },
+    );
 
     let delete_task = create_action(move |id: &uuid::Uuid| {
         let id = *id;
-        async move { api::delete_task(id).await }
+        async move { api::delete_task(id, version).await }
     });
 
-    let toggle_status = move |_| {
-        let new_status = match task.status {
-            TaskStatus::Todo => TaskStatus::InProgress,
-            TaskStatus::InProgress => TaskStatus::Completed,
-            TaskStatus::Completed => TaskStatus::Todo,
-            TaskStatus::Backlog => TaskStatus::Todo, // Backlog tasks go to Todo when toggled
+    // Only the moves the workflow allows from here are offered
+    let moves = move_choices(&workflow, &task);
+    let has_moves = !moves.is_empty();
+    let before = task.clone();
+    let move_to = move |ev: ev::Event| {
+        let Some(next) = workflow
+            .state(&TaskStatus::new(event_target_value(&ev)))
+            .cloned()
+        else {
+            return;
         };
 
+        let override_blockers = needs_blocker_override(&before, &next);
+        if override_blockers
+            && !window()
+                .confirm_with_message(BLOCKED_MOVE_PROMPT)
+                .unwrap_or(false)
+        {
+            return;
+        }
         let request = UpdateTaskRequest {
-            title: None,
-            description: None,
-            status: Some(new_status),
-            priority: None,
-            due_date: None,
+            status: Patch::Value(next.name),
+            ..Default::default()
         };
 
         set_is_updating.set(true);
-        update_task.dispatch((task.id, request));
+        update_task.dispatch((task.id, request, override_blockers));
     };
 
     let delete_task_handler = move |_| {
@@ -54,7 +72,11 @@ where
     create_effect(move |_| {
         if let Some(result) = update_task.value().get() {
             set_is_updating.set(false);
-            if result.is_ok() {
+            if let Err(WriteError::Rejected(message)) = &result {
+                let _ = window().alert_with_message(message);
+            }
+            // A conflict means this card is stale, so reload it either way
+            if matches!(result, Ok(_) | Err(WriteError::Conflict)) {
                 on_update();
             }
         }
@@ -63,18 +85,13 @@ where
     create_effect(move |_| {
         if let Some(result) = delete_task.value().get() {
             set_is_deleting.set(false);
-            if result.is_ok() {
+            if matches!(result, Ok(_) | Err(WriteError::Conflict)) {
                 on_update();
             }
         }
     });
 
-    let status_color = match task.status {
-        TaskStatus::Todo => "bg-gray-100 text-gray-800",
-        TaskStatus::InProgress => "bg-blue-100 text-blue-800",
-        TaskStatus::Completed => "bg-green-100 text-green-800",
-        TaskStatus::Backlog => "bg-purple-100 text-purple-800",
-    };
+    let status_color = badge_color(task.status_category);
 
     let priority_color = match task.priority {
         common::TaskPriority::Low => "bg-gray-100 text-gray-800",
@@ -120,7 +137,7 @@ where
             <div class="w-24 p-3 flex flex-col justify-between items-center border-l border-gray-100 bg-gray-50">
                 <div class="flex flex-col gap-1 items-center">
                     <span class={format!("px-2 py-1 text-xs font-medium rounded-full {status_color}")}>
-                        {format!("{:?}", task.status)}
+                        {state_label(&task.status)}
                     </span>
                     <span class={format!("px-2 py-1 text-xs font-medium rounded-full {priority_color}")}>
                         {format!("{:?}", task.priority)}
@@ -128,13 +145,19 @@ where
                 </div>
 
                 <div class="flex flex-col gap-1 w-full">
-                    <button
-                        on:click=toggle_status
-                        disabled=move || is_updating.get()
-                        class="px-2 py-1 text-xs font-medium bg-blue-100 text-blue-800 rounded hover:bg-blue-200 focus:outline-none focus:ring-1 focus:ring-blue-500 disabled:opacity-50 transition-colors w-full"
+                    <select
+                        on:change=move_to
+                        disabled=move || is_updating.get() || !has_moves
+                        title="Move to another state"
+                        class="px-1 py-1 text-xs font-medium bg-blue-100 text-blue-800 rounded hover:bg-blue-200 focus:outline-none focus:ring-1 focus:ring-blue-500 disabled:opacity-50 transition-colors w-full"
                     >
-                        {move || if is_updating.get() { "..." } else { "Toggle" }}
-                    </button>
+                        <option value="" selected=true>
+                            {move || if is_updating.get() { "..." } else { "Move" }}
+                        </option>
+                        {moves.into_iter().map(|(name, label)| view! {
+                            <option value=name.to_string()>{label}</option>
+                        }).collect_view()}
+                    </select>
 
                     <button
                         on:click=delete_task_handler
@@ -1,7 +1,19 @@
-use crate::components::Card;
-use crate::logic::task_list_logic::filter_and_group_tasks;
-use crate::logic::task_list_signals::{use_update_task_action, TaskListSignals};
-use common::{Task, TaskStatus, UpdateTaskRequest};
+use crate::api::{self, WriteError};
+use crate::components::{Card, Modal, TaskDetail};
+use crate::logic::board_state::BoardState;
+use crate::logic::dependency_logic::{needs_blocker_override, BLOCKED_MOVE_PROMPT};
+use crate::logic::recurrence_logic::spawned_occurrence;
+use crate::logic::saved_view_logic::{lane_of, order_by_lane, sort_cards, visible_states};
+use crate::logic::task_list_logic::{
+    apply_move, apply_server_task, drop_neighbours, filter_and_group_tasks, set_comment_count,
+};
+use crate::logic::task_list_signals::{use_move_task_action, TaskListSignals};
+use crate::logic::workflow_logic::{
+    check_drop, column_color, over_wip_limit, state_label, wip_label,
+};
+use common::{
+    saved_views::ViewGrouping, MoveTaskRequest, Task, TaskStatus, Workflow, WorkflowState,
+};
 use leptos::*;
 
 #[component]
@@ -10,20 +22,52 @@ pub fn TaskList(
     tasks: ReadSignal<Vec<Task>>,
     set_tasks: WriteSignal<Vec<Task>>,
     refresh_tasks: impl Fn() + 'static + Copy,
-    filter_priority: ReadSignal<Option<common::TaskPriority>>,
+    /// Filter, card order, lanes and shown columns of the board.
+    board: BoardState,
+    /// The project's workflow, one column per state.
+    workflow: Signal<Workflow>,
 ) -> impl IntoView {
     let signals = TaskListSignals::new();
-    let (update_task_action, on_success) = use_update_task_action();
+    // Names the lanes of a board grouped by assignee
+    let members = create_local_resource(|| (), |_| api::fetch_members());
+    let member_list = move || members.get().and_then(Result::ok).unwrap_or_default();
+    let (move_task_action, on_success) = use_move_task_action();
 
     // Create effect to handle server response and revert on failure
     create_effect(move |_| {
         on_success();
-        if let Some(result) = update_task_action.value().get() {
+        if let Some(result) = move_task_action.value().get() {
             match result {
-                Ok(_) => {
+                Ok(task) => {
                     leptos::logging::log!(
                         "Task updated successfully on server - optimistic update confirmed"
                     );
+                    let spawned = tasks.with_untracked(|tasks| {
+                        tasks
+                            .iter()
+                            .find(|t| t.id == task.id)
+                            .is_some_and(|before| spawned_occurrence(before, &task))
+                    });
+                    // Keep the server's version for the next drop
+                    set_tasks.update(|tasks| apply_server_task(tasks, task));
+                    if spawned {
+                        refresh_tasks();
+                    }
+                }
+                Err(WriteError::Conflict) => {
+                    leptos::logging::log!(
+                        "Task changed on server since it was loaded, reverting optimistic update"
+                    );
+                    signals.conflict.set(Some(
+                        "Someone else changed this task while you were moving it. The board now shows their changes."
+                            .to_string(),
+                    ));
+                    refresh_tasks();
+                }
+                Err(WriteError::Rejected(message)) => {
+                    leptos::logging::log!("Task move refused by server: {}", message);
+                    signals.conflict.set(Some(message));
+                    refresh_tasks();
                 }
                 Err(e) => {
                     leptos::logging::log!(
@@ -36,84 +80,114 @@ pub fn TaskList(
             }
         }
     });
+    // Cards of each column in the order shown, lane by lane
     let filtered_and_grouped_tasks = create_memo(move |_| {
         let tasks = tasks.get();
-        let priority_filter = filter_priority.get();
-        filter_and_group_tasks(&tasks, priority_filter)
+        let priority_filter = board.filter_priority.get();
+        let mut grouped = workflow
+            .with(|workflow| filter_and_group_tasks(&tasks, priority_filter, &workflow.states));
+        let sort = board.sort.get();
+        let grouping = board.grouping.get();
+        let members = member_list();
+        for column in grouped.values_mut() {
+            if let Some(sort) = sort {
+                sort_cards(column, sort);
+            }
+            order_by_lane(column, grouping, &members);
+        }
+        grouped
     });
 
     let handle_drop = move |status: TaskStatus, task_id: uuid::Uuid| {
-        leptos::logging::log!("Dropping task {} to status {:?}", task_id, status);
-
-        // Optimistic update: immediately update the local task list
-        set_tasks.update(|tasks| {
-            if let Some(task) = tasks.iter_mut().find(|t| t.id == task_id) {
-                task.status = status;
-                leptos::logging::log!(
-                    "Optimistically updated task {} to status {:?}",
-                    task_id,
-                    status
-                );
-            }
-        });
-
-        // Then send the update to the server
-        let request = UpdateTaskRequest {
-            title: None,
-            description: None,
-            status: Some(status),
-            priority: None,
-            due_date: None,
+        leptos::logging::log!("Dropping task {} to status {}", task_id, status);
+
+        let target = signals.drop_target.get_untracked();
+        signals.drop_target.set(None);
+        let Some((after_id, before_id)) = filtered_and_grouped_tasks.with_untracked(|grouped| {
+            let column = grouped.get(&status).map(Vec::as_slice).unwrap_or_default();
+            drop_neighbours(column, task_id, target)
+        }) else {
+            return;
         };
-        update_task_action.dispatch((task_id, request));
-    };
+        let Some(state) = workflow.with_untracked(|workflow| workflow.state(&status).cloned())
+        else {
+            return;
+        };
+        let Some((version, override_blockers, allowed)) = tasks.with_untracked(|tasks| {
+            let task = tasks.iter().find(|t| t.id == task_id)?;
+            let allowed = workflow.with_untracked(|workflow| check_drop(workflow, task, &state));
+            Some((task.version, needs_blocker_override(task, &state), allowed))
+        }) else {
+            return;
+        };
+        if let Err(message) = allowed {
+            signals.conflict.set(Some(message));
+            return;
+        }
+        if override_blockers
+            && !window()
+                .confirm_with_message(BLOCKED_MOVE_PROMPT)
+                .unwrap_or(false)
+        {
+            return;
+        }
+        signals.conflict.set(None);
 
-    let render_status_column = move |status: TaskStatus| {
-        let status_name = match status {
-            TaskStatus::Todo => "Todo",
-            TaskStatus::InProgress => "In Progress",
-            TaskStatus::Completed => "Completed",
-            TaskStatus::Backlog => "Backlog",
+        // Optimistic update: immediately move the card in the local task list
+        set_tasks.update(|tasks| apply_move(tasks, task_id, &state, after_id, before_id));
+        leptos::logging::log!("Optimistically moved task {} to status {}", task_id, status);
+
+        // Then send the move to the server
+        let request = MoveTaskRequest {
+            status,
+            after_id,
+            before_id,
         };
+        move_task_action.dispatch((task_id, request, version, override_blockers));
+    };
 
-        let status_color = match status {
-            TaskStatus::Todo => "border-gray-600 bg-gray-800",
-            TaskStatus::InProgress => "border-blue-600 bg-blue-900",
-            TaskStatus::Completed => "border-green-600 bg-green-900",
-            TaskStatus::Backlog => "border-purple-600 bg-purple-900",
+    let render_status_column = move |state: WorkflowState| {
+        let status_name = state_label(&state.name);
+        let category = state.category;
+        let wip_limit = state.wip_limit;
+        // Shared by the column's handlers, which each need the name
+        let status = store_value(state.name);
+
+        // Live cards of the column whatever the filter, as the limit counts
+        let card_count = move || {
+            tasks.with(|tasks| {
+                status.with_value(|status| tasks.iter().filter(|t| t.status == *status).count())
+            })
         };
+        let over_limit = move || over_wip_limit(card_count(), wip_limit);
 
-        let is_drag_over = move || signals.drag_over_status.get() == Some(status);
+        let is_drag_over = move || {
+            signals
+                .drag_over_status
+                .with(|over| status.with_value(|status| over.as_ref() == Some(status)))
+        };
 
         view! {
             <div
-                class={move || format!("min-h-96 p-4 rounded-lg border-2 transition-colors {}",
-                    if is_drag_over() {
-                        match status {
-                            TaskStatus::Todo => "border-gray-400 bg-gray-700",
-                            TaskStatus::InProgress => "border-blue-400 bg-blue-800",
-                            TaskStatus::Completed => "border-green-400 bg-green-800",
-                            TaskStatus::Backlog => "border-purple-400 bg-purple-800",
-                        }
-                    } else {
-                        status_color
-                    }
+                class={move || format!("min-h-96 p-4 rounded-lg border-2 transition-colors {}{}",
+                    column_color(category, is_drag_over()),
+                    if over_limit() { " ring-2 ring-red-500" } else { "" }
                 )}
                 on:dragover=move |ev| {
                     ev.prevent_default();
                     ev.stop_propagation();
-                    leptos::logging::log!("Drag over status {:?}", status);
-                    signals.drag_over_status.set(Some(status));
+                    leptos::logging::log!("Drag over status {}", status.get_value());
+                    signals.drag_over_status.set(Some(status.get_value()));
                 }
                 on:dragleave=move |ev| {
                     ev.stop_propagation();
-                    leptos::logging::log!("Drag leave status {:?}", status);
+                    leptos::logging::log!("Drag leave status {}", status.get_value());
 
                     // Use a longer timeout to prevent issues with fast dragging
                     set_timeout(
                         move || {
                             // Only clear if we're still on the same status
-                            if signals.drag_over_status.get() == Some(status) {
+                            if is_drag_over() {
                                 signals.drag_over_status.set(None);
                             }
                         },
@@ -123,33 +197,45 @@ pub fn TaskList(
                 on:drop=move |ev| {
                     ev.prevent_default();
                     ev.stop_propagation();
-                    leptos::logging::log!("Drop event on status {:?}", status);
+                    leptos::logging::log!("Drop event on status {}", status.get_value());
                     signals.drag_over_status.set(None);
                     if let Some(dragged_id) = signals.dragging_task_id.get() {
-                        leptos::logging::log!("Processing drop for task {} to status {:?}", dragged_id, status);
-                        handle_drop(status, dragged_id);
+                        leptos::logging::log!("Processing drop for task {} to status {}", dragged_id, status.get_value());
+                        handle_drop(status.get_value(), dragged_id);
                         signals.dragging_task_id.set(None);
                     } else {
                         leptos::logging::log!("No dragging task ID found during drop");
                     }
                 }
             >
-                <h3 class="text-lg font-semibold mb-4 text-center text-white">{status_name}</h3>
+                <h3 class="text-lg font-semibold mb-4 text-center text-white">
+                    {status_name}
+                    <span
+                        class={move || if over_limit() {
+                            "ml-2 text-sm font-normal text-red-400"
+                        } else {
+                            "ml-2 text-sm font-normal text-gray-400"
+                        }}
+                        title={move || if over_limit() { "Over the WIP limit" } else { "" }}
+                    >
+                        {move || wip_label(card_count(), wip_limit)}
+                    </span>
+                </h3>
                 <div class="space-y-3 min-h-80 relative"
                     on:dragover=move |ev| {
                         ev.prevent_default();
                         ev.stop_propagation();
-                        leptos::logging::log!("Drag over task area for status {:?}", status);
-                        signals.drag_over_status.set(Some(status));
+                        leptos::logging::log!("Drag over task area for status {}", status.get_value());
+                        signals.drag_over_status.set(Some(status.get_value()));
                     }
                     on:dragleave=move |ev| {
                         ev.stop_propagation();
-                        leptos::logging::log!("Drag leave task area for status {:?}", status);
+                        leptos::logging::log!("Drag leave task area for status {}", status.get_value());
 
                         // Use timeout for inner area as well
                         set_timeout(
                             move || {
-                                if signals.drag_over_status.get() == Some(status) {
+                                if is_drag_over() {
                                     signals.drag_over_status.set(None);
                                 }
                             },
@@ -159,11 +245,11 @@ pub fn TaskList(
                     on:drop=move |ev| {
                         ev.prevent_default();
                         ev.stop_propagation();
-                        leptos::logging::log!("Drop event on task area for status {:?}", status);
+                        leptos::logging::log!("Drop event on task area for status {}", status.get_value());
                         signals.drag_over_status.set(None);
                         if let Some(dragged_id) = signals.dragging_task_id.get() {
-                            leptos::logging::log!("Processing drop in task area for task {} to status {:?}", dragged_id, status);
-                            handle_drop(status, dragged_id);
+                            leptos::logging::log!("Processing drop in task area for task {} to status {}", dragged_id, status.get_value());
+                            handle_drop(status.get_value(), dragged_id);
                             signals.dragging_task_id.set(None);
                         } else {
                             leptos::logging::log!("No dragging task ID found during drop in task area");
@@ -172,7 +258,7 @@ pub fn TaskList(
                 >
                     {move || {
                         let grouped = filtered_and_grouped_tasks.get();
-                        if let Some(status_tasks) = grouped.get(&status) {
+                        if let Some(status_tasks) = status.with_value(|status| grouped.get(status)) {
                             if status_tasks.is_empty() {
                                 view! {
                                     <div class="text-center py-8 text-gray-400">
@@ -181,9 +267,31 @@ pub fn TaskList(
                                     </div>
                                 }.into_view()
                             } else {
+                                let grouping = board.grouping.get();
+                                let members = member_list();
+                                // Heads each lane with its priority or assignee
+                                let mut lane = None;
                                 status_tasks.iter().map(|task| {
+                                    let (_, label) = lane_of(task, grouping, &members);
+                                    let heading = (grouping != ViewGrouping::None
+                                        && lane.as_ref() != Some(&label))
+                                    .then(|| {
+                                        lane = Some(label.clone());
+                                        view! {
+                                            <div class="pt-2 text-xs font-semibold uppercase tracking-wide text-gray-400">
+                                                {label}
+                                            </div>
+                                        }
+                                    });
                                     view! {
-                                        <Card task=task.clone() set_dragging_task_id=signals.dragging_task_id.write_only() />
+                                        {heading}
+                                        <Card
+                                            task=task.clone()
+                                            set_dragging_task_id=signals.dragging_task_id.write_only()
+                                            set_selected_task_id=signals.selected_task_id.write_only()
+                                            set_drop_target=signals.drop_target.write_only()
+                                            on_timer_change=Callback::new(move |_| refresh_tasks())
+                                        />
                                     }
                                 }).collect_view()
                             }
@@ -201,14 +309,54 @@ pub fn TaskList(
         }
     };
 
+    // Untracked so edits to the board do not reload the open detail
+    let selected_task = move || {
+        let id = signals.selected_task_id.get()?;
+        tasks.with_untracked(|tasks| tasks.iter().find(|task| task.id == id).cloned())
+    };
+    let close_detail = move || signals.selected_task_id.set(None);
+
     view! {
         <div class="space-y-6">
-            // Status columns layout - 3 columns without Backlog
-            <div class="grid grid-cols-1 lg:grid-cols-3 gap-6">
-                {render_status_column(TaskStatus::Todo)}
-                {render_status_column(TaskStatus::InProgress)}
-                {render_status_column(TaskStatus::Completed)}
+            {move || signals.conflict.get().map(|message| view! {
+                <div class="flex items-center justify-between p-3 rounded-lg border border-yellow-600 bg-yellow-900 text-sm text-yellow-100">
+                    <span>{message}</span>
+                    <button
+                        class="ml-4 font-medium hover:text-white"
+                        on:click=move |_| signals.conflict.set(None)
+                    >
+                        "Dismiss"
+                    </button>
+                </div>
+            })}
+
+            // One column per shown workflow state, side by side on wide screens
+            <div class="grid grid-cols-1 lg:grid-flow-col lg:auto-cols-fr gap-6">
+                {move || {
+                    let states = board
+                        .columns
+                        .with(|columns| workflow.with(|workflow| visible_states(&workflow.states, columns)));
+                    states.into_iter().map(render_status_column).collect_view()
+                }}
             </div>
+
+            <Modal
+                show=Signal::derive(move || signals.selected_task_id.get().is_some())
+                on_close=close_detail
+                title="Task Details".to_string()
+            >
+                {move || selected_task().map(|task| {
+                    let id = task.id;
+                    view! {
+                        <TaskDetail
+                            task=task
+                            on_comment_count=move |count| {
+                                set_tasks.update(|tasks| set_comment_count(tasks, id, count));
+                            }
+                        />
+                    }
+                })}
+            </Modal>
         </div>
     }
 }
@@ -11,12 +11,14 @@ mod pages;
 mod tests;
 
 use components::*;
+use logic::board_state::BoardState;
 use pages::*;
 
 #[component]
 #[allow(non_snake_case)]
 fn App() -> impl IntoView {
     provide_meta_context();
+    provide_context(BoardState::new());
 
     view! {
         <Stylesheet id="leptos" href="/pkg/frontend.css"/>
@@ -28,6 +30,8 @@ fn App() -> impl IntoView {
                 <div class="flex-1 container mx-auto px-4 py-8 ml-64">
                     <Routes>
                         <Route path="" view=HomePage/>
+                        <Route path="/projects/:id/board" view=HomePage/>
+                        <Route path="/trash" view=TrashPage/>
                         <Route path="/*any" view=NotFound/>
                     </Routes>
                 </div>
@@ -0,0 +1,25 @@
+use common::Attachment;
+
+/// A file size in the largest unit that keeps it at least one, such as
+/// "512 B", "1.5 KB" or "10 MB".
+#[must_use]
+pub fn format_size(bytes: i64) -> String {
+    const UNITS: [&str; 3] = ["KB", "MB", "GB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    let size = format!("{size:.1}");
+    format!("{} {}", size.trim_end_matches(".0"), UNITS[unit])
+}
+
+/// Whether an attachment can be previewed inline as an image.
+#[must_use]
+pub fn is_image(attachment: &Attachment) -> bool {
+    attachment.content_type.starts_with("image/")
+}
@@ -0,0 +1,69 @@
+use crate::logic::saved_view_logic::BoardView;
+use common::{saved_views::ViewGrouping, TaskPriority, TaskSort, TaskStatus};
+use leptos::*;
+use uuid::Uuid;
+
+/// What the board shows, shared through the context so the sidebar can
+/// restore a saved view on whichever page is open.
+#[derive(Clone, Copy)]
+pub struct BoardState {
+    pub filter_priority: RwSignal<Option<TaskPriority>>,
+    pub search_query: RwSignal<String>,
+    pub filter_tags: RwSignal<Vec<String>>,
+    pub filter_assignee: RwSignal<Option<Uuid>>,
+    pub sort: RwSignal<Option<TaskSort>>,
+    pub grouping: RwSignal<ViewGrouping>,
+    /// Shown columns; empty shows them all.
+    pub columns: RwSignal<Vec<TaskStatus>>,
+    /// Fired after a view is saved so the sidebar lists it.
+    pub views_changed: Trigger,
+}
+
+impl Default for BoardState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BoardState {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            filter_priority: create_rw_signal(None::<TaskPriority>),
+            search_query: create_rw_signal(String::new()),
+            filter_tags: create_rw_signal(Vec::<String>::new()),
+            filter_assignee: create_rw_signal(None::<Uuid>),
+            sort: create_rw_signal(None::<TaskSort>),
+            grouping: create_rw_signal(ViewGrouping::None),
+            columns: create_rw_signal(Vec::<TaskStatus>::new()),
+            views_changed: create_trigger(),
+        }
+    }
+
+    /// The board as it is now, for saving.
+    #[must_use]
+    pub fn snapshot(&self) -> BoardView {
+        BoardView {
+            priority: self.filter_priority.get_untracked(),
+            search: self.search_query.get_untracked(),
+            tags: self.filter_tags.get_untracked(),
+            assignee: self.filter_assignee.get_untracked(),
+            sort: self.sort.get_untracked(),
+            grouping: self.grouping.get_untracked(),
+            columns: self.columns.get_untracked(),
+        }
+    }
+
+    /// Puts back a saved board, in one batch so tasks load once.
+    pub fn restore(&self, view: BoardView) {
+        batch(|| {
+            self.filter_priority.set(view.priority);
+            self.search_query.set(view.search);
+            self.filter_tags.set(view.tags);
+            self.filter_assignee.set(view.assignee);
+            self.sort.set(view.sort);
+            self.grouping.set(view.grouping);
+            self.columns.set(view.columns);
+        });
+    }
+}
@@ -0,0 +1,52 @@
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+
+/// Renders a comment's Markdown body to HTML. Raw HTML in the comment is
+/// shown as text and only web and mail links are kept, so a comment cannot
+/// inject markup or scripts into the page.
+#[must_use]
+pub fn render_markdown(body: &str) -> String {
+    let options =
+        Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS;
+    let events = Parser::new_ext(body, options).map(|event| match event {
+        Event::Html(html) | Event::InlineHtml(html) => Event::Text(html),
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        }),
+        event => event,
+    });
+
+    let mut output = String::new();
+    html::push_html(&mut output, events);
+    output
+}
+
+fn safe_url(url: CowStr<'_>) -> CowStr<'_> {
+    let lowercase = url.trim().to_ascii_lowercase();
+    if ["http://", "https://", "mailto:"]
+        .iter()
+        .any(|scheme| lowercase.starts_with(scheme))
+    {
+        url
+    } else {
+        CowStr::Borrowed("")
+    }
+}
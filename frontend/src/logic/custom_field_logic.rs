@@ -0,0 +1,98 @@
+use common::custom_fields::{check_values, CustomField, CustomFieldType, CustomFieldValues};
+use serde_json::Value;
+
+/// Text to put in a field's input for a stored value. Inputs of checkbox and
+/// multi-select fields read the value itself, so they start out empty.
+#[must_use]
+pub fn input_text(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Number(number)) => number.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Ticks or unticks `option` in a multi-select value, keeping the field's
+/// option order.
+#[must_use]
+pub fn toggle_option(
+    field: &CustomField,
+    value: Option<&Value>,
+    option: &str,
+    ticked: bool,
+) -> Value {
+    let picked = |known: &String| {
+        if known == option {
+            ticked
+        } else {
+            value.is_some_and(|value| value_lists(value, known))
+        }
+    };
+    Value::Array(
+        field
+            .options
+            .iter()
+            .filter(|known| picked(known))
+            .map(|known| Value::String(known.clone()))
+            .collect(),
+    )
+}
+
+/// Whether a multi-select value lists `option`.
+#[must_use]
+pub fn value_lists(value: &Value, option: &str) -> bool {
+    matches!(value, Value::Array(items) if items.iter().any(|item| item == option))
+}
+
+/// The values the form sends, checked as the server checks them. Number
+/// inputs hold text until then, so they are read as numbers here.
+pub fn form_values(
+    fields: &[CustomField],
+    values: &CustomFieldValues,
+) -> Result<CustomFieldValues, String> {
+    let mut checked = CustomFieldValues::new();
+    for field in fields {
+        let Some(value) = values.get(&field.id) else {
+            continue;
+        };
+        let value = match (field.field_type, value) {
+            (CustomFieldType::Number, Value::String(text)) if !text.trim().is_empty() => {
+                field.parse_value(text)?
+            }
+            _ => value.clone(),
+        };
+        checked.insert(field.id, value);
+    }
+    check_values(&mut checked, fields)?;
+    Ok(checked)
+}
+
+/// A stored value as the task detail shows it, such as "API, UI" or "Yes".
+#[must_use]
+pub fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Bool(true) => "Yes".to_string(),
+        Value::Bool(false) => "No".to_string(),
+        Value::Array(items) => items
+            .iter()
+            .map(display_value)
+            .collect::<Vec<_>>()
+            .join(", "),
+        value => value.to_string(),
+    }
+}
+
+/// Name and shown value of each field the task has a value for, in the
+/// project's field order.
+#[must_use]
+pub fn field_rows(fields: &[CustomField], values: &CustomFieldValues) -> Vec<(String, String)> {
+    fields
+        .iter()
+        .filter_map(|field| {
+            values
+                .get(&field.id)
+                .map(|value| (field.name.clone(), display_value(value)))
+        })
+        .collect()
+}
@@ -0,0 +1,11 @@
+use common::{StatusCategory, Task, WorkflowState};
+
+/// Asked before moving a blocked task where the server would refuse it.
+pub const BLOCKED_MOVE_PROMPT: &str = "This task is blocked by unfinished tasks. Move it anyway?";
+
+/// Whether moving `task` to `target` needs its blockers overridden, as the
+/// server refuses to start or complete a blocked task otherwise.
+#[must_use]
+pub fn needs_blocker_override(task: &Task, target: &WorkflowState) -> bool {
+    task.is_blocked && target.name != task.status && target.category != StatusCategory::Todo
+}
@@ -1,2 +1,16 @@
+pub mod attachment_logic;
+pub mod board_state;
+pub mod comment_logic;
+pub mod custom_field_logic;
+pub mod dependency_logic;
+pub mod project_logic;
+pub mod query_logic;
+pub mod recurrence_logic;
+pub mod saved_view_logic;
+pub mod subtask_logic;
+pub mod tag_logic;
+pub mod task_history_logic;
 pub mod task_list_logic;
 pub mod task_list_signals;
+pub mod time_logic;
+pub mod workflow_logic;
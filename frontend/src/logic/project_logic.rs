@@ -0,0 +1,17 @@
+use common::DEFAULT_PROJECT_ID;
+use uuid::Uuid;
+
+/// Where the board of a project lives.
+#[must_use]
+pub fn board_path(project_id: Uuid) -> String {
+    format!("/projects/{project_id}/board")
+}
+
+/// The project named by the `:id` route parameter. The home page and
+/// malformed ids show the default project.
+#[must_use]
+pub fn project_from_param(param: Option<&str>) -> Uuid {
+    param
+        .and_then(|id| Uuid::parse_str(id).ok())
+        .unwrap_or(DEFAULT_PROJECT_ID)
+}
@@ -0,0 +1,55 @@
+use common::{
+    query::{QueryError, TaskQuery},
+    TaskFilter,
+};
+
+/// The filter the search box text asks for: a task query when it parses,
+/// otherwise plain search text, as views saved before queries hold it.
+/// Blank text filters nothing.
+#[must_use]
+pub fn search_filter(text: &str) -> TaskFilter {
+    let text = text.trim();
+    if text.is_empty() {
+        return TaskFilter::default();
+    }
+    match text.parse::<TaskQuery>() {
+        Ok(query) => TaskFilter {
+            query: Some(query),
+            ..Default::default()
+        },
+        Err(_) => TaskFilter {
+            q: Some(text.to_string()),
+            ..Default::default()
+        },
+    }
+}
+
+/// The search box text of a filter, the inverse of [`search_filter`].
+#[must_use]
+pub fn search_text(filter: &TaskFilter) -> String {
+    let parts: Vec<String> = filter
+        .query
+        .iter()
+        .map(ToString::to_string)
+        .chain(filter.q.clone())
+        .collect();
+    parts.join(" ")
+}
+
+/// Splits `text` around the word `error` points at, for highlighting: the
+/// text before it, the word, and the rest. The word is empty when the error
+/// is at the end.
+#[must_use]
+pub fn split_at_error(text: &str, error: &QueryError) -> (String, String, String) {
+    let chars: Vec<char> = text.chars().collect();
+    let start = error.position.min(chars.len());
+    let end = chars[start..]
+        .iter()
+        .position(|c| c.is_whitespace())
+        .map_or(chars.len(), |length| start + length);
+    (
+        chars[..start].iter().collect(),
+        chars[start..end].iter().collect(),
+        chars[end..].iter().collect(),
+    )
+}
@@ -0,0 +1,25 @@
+use common::{recurrence::Recurrence, StatusCategory, Task};
+
+/// A recurrence rule in words, such as "Every 2 weeks on Mon, Thu". Rules
+/// this client cannot read are shown as stored.
+#[must_use]
+pub fn describe_rule(rule: &str) -> String {
+    rule.parse::<Recurrence>()
+        .map_or_else(|_| rule.to_string(), |recurrence| recurrence.describe())
+}
+
+/// How a recurring task repeats, for the card's repeat indicator.
+#[must_use]
+pub fn recurrence_label(task: &Task) -> Option<String> {
+    task.recurrence.as_deref().map(describe_rule)
+}
+
+/// Whether the server answered a write by completing a recurring task, which
+/// hands its rule on to a new occurrence the board has not loaded yet.
+/// `before` may already show the optimistic status.
+#[must_use]
+pub fn spawned_occurrence(before: &Task, after: &Task) -> bool {
+    before.recurrence.is_some()
+        && after.recurrence.is_none()
+        && after.status_category == StatusCategory::Done
+}
@@ -0,0 +1,178 @@
+use crate::logic::query_logic::{search_filter, search_text};
+use common::{
+    saved_views::{SavedView, SavedViewRequest, ViewGrouping},
+    Member, SortDirection, Task, TaskFilter, TaskPriority, TaskSort, TaskSortField, TaskStatus,
+    WorkflowState, DEFAULT_PROJECT_ID,
+};
+use std::cmp::Ordering;
+use uuid::Uuid;
+
+/// Sorts the board offers, with their labels; board order comes first.
+pub const SORT_CHOICES: [(&str, &str); 6] = [
+    ("", "Board order"),
+    ("-priority", "Most urgent first"),
+    ("due_date", "Due soonest"),
+    ("-updated_at", "Recently updated"),
+    ("-created_at", "Newest first"),
+    ("title", "Title"),
+];
+
+/// Everything the board shows apart from the tasks, as a saved view keeps it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BoardView {
+    pub priority: Option<TaskPriority>,
+    pub search: String,
+    pub tags: Vec<String>,
+    pub assignee: Option<Uuid>,
+    pub sort: Option<TaskSort>,
+    pub grouping: ViewGrouping,
+    /// Shown columns; empty shows them all.
+    pub columns: Vec<TaskStatus>,
+}
+
+/// The request saving `board` of `project` under `name`.
+#[must_use]
+pub fn view_request(name: &str, board: &BoardView, project: Uuid) -> SavedViewRequest {
+    SavedViewRequest {
+        name: name.trim().to_string(),
+        filter: TaskFilter {
+            priority: board.priority.into_iter().collect(),
+            all_tags: board.tags.clone(),
+            assignee: board.assignee,
+            project: Some(project),
+            ..search_filter(&board.search)
+        },
+        sort: board.sort,
+        grouping: board.grouping,
+        columns: board.columns.clone(),
+    }
+}
+
+/// The board a saved view restores. The board filters on one priority, so
+/// only the first of a view's is kept.
+#[must_use]
+pub fn board_from_view(view: &SavedView) -> BoardView {
+    BoardView {
+        priority: view.filter.priority.first().copied(),
+        search: search_text(&view.filter),
+        tags: view.filter.all_tags.clone(),
+        assignee: view.filter.assignee,
+        sort: view.sort,
+        grouping: view.grouping,
+        columns: view.columns.clone(),
+    }
+}
+
+/// The project whose board a view opens.
+#[must_use]
+pub fn view_project(view: &SavedView) -> Uuid {
+    view.filter.project.unwrap_or(DEFAULT_PROJECT_ID)
+}
+
+/// The view saving under `name` overwrites, if one already has it.
+#[must_use]
+pub fn existing_view(views: &[SavedView], name: &str) -> Option<Uuid> {
+    let name = name.trim();
+    views
+        .iter()
+        .find(|view| view.name == name)
+        .map(|view| view.id)
+}
+
+/// The workflow states whose columns are shown, in board order.
+#[must_use]
+pub fn visible_states(states: &[WorkflowState], columns: &[TaskStatus]) -> Vec<WorkflowState> {
+    states
+        .iter()
+        .filter(|state| columns.is_empty() || columns.contains(&state.name))
+        .cloned()
+        .collect()
+}
+
+/// Shows or hides the column of `status`. Hiding one while all are shown
+/// keeps the others, and showing the last hidden one goes back to all.
+#[must_use]
+pub fn toggle_column(
+    states: &[WorkflowState],
+    columns: &[TaskStatus],
+    status: &TaskStatus,
+    shown: bool,
+) -> Vec<TaskStatus> {
+    let picked: Vec<TaskStatus> = states
+        .iter()
+        .map(|state| &state.name)
+        .filter(|name| {
+            if *name == status {
+                shown
+            } else {
+                columns.is_empty() || columns.contains(name)
+            }
+        })
+        .cloned()
+        .collect();
+    if picked.len() == states.len() {
+        Vec::new()
+    } else {
+        picked
+    }
+}
+
+/// Orders the cards of a column by `sort`, as the server orders a listing.
+/// Cards without a due date go last either way; ties keep board order.
+pub fn sort_cards(cards: &mut [Task], sort: TaskSort) {
+    let ordered = |ordering: Ordering| match sort.direction {
+        SortDirection::Asc => ordering,
+        SortDirection::Desc => ordering.reverse(),
+    };
+    cards.sort_by(|a, b| match sort.field {
+        TaskSortField::CreatedAt => ordered(a.created_at.cmp(&b.created_at)),
+        TaskSortField::UpdatedAt => ordered(a.updated_at.cmp(&b.updated_at)),
+        TaskSortField::Priority => ordered(a.priority.cmp(&b.priority)),
+        TaskSortField::Title => ordered(a.title.cmp(&b.title)),
+        TaskSortField::DueDate => match (a.due_date, b.due_date) {
+            (Some(a), Some(b)) => ordered(a.cmp(&b)),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        },
+        // There is no search rank on the board
+        TaskSortField::Relevance => Ordering::Equal,
+    });
+}
+
+/// The lane of a card: where the lane goes among the column's lanes, then
+/// its heading. Without grouping every card shares one unnamed lane, and
+/// cards of a member not among `members` go with the unassigned ones.
+#[must_use]
+pub fn lane_of(task: &Task, grouping: ViewGrouping, members: &[Member]) -> (u8, String) {
+    match grouping {
+        ViewGrouping::None => (0, String::new()),
+        ViewGrouping::Priority => {
+            let place = match task.priority {
+                TaskPriority::Urgent => 0,
+                TaskPriority::High => 1,
+                TaskPriority::Medium => 2,
+                TaskPriority::Low => 3,
+            };
+            (place, format!("{:?}", task.priority))
+        }
+        ViewGrouping::Assignee => {
+            let name = task.assignee_id.and_then(|id| {
+                members
+                    .iter()
+                    .find(|member| member.id == id)
+                    .map(|member| member.name.clone())
+            });
+            match name {
+                Some(name) => (0, name),
+                None => (1, "Unassigned".to_string()),
+            }
+        }
+    }
+}
+
+/// Gathers the cards of a column into lanes, keeping their order within
+/// each lane. Lanes of assignees go by name.
+pub fn order_by_lane(cards: &mut [Task], grouping: ViewGrouping, members: &[Member]) {
+    cards.sort_by_cached_key(|task| lane_of(task, grouping, members));
+}
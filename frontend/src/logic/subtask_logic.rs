@@ -0,0 +1,19 @@
+use common::Task;
+
+/// How many subtasks are done, such as "3/5". Tasks without subtasks show
+/// nothing.
+#[must_use]
+pub fn progress_label(task: &Task) -> Option<String> {
+    (task.subtask_count > 0)
+        .then(|| format!("{}/{}", task.completed_subtask_count, task.subtask_count))
+}
+
+/// The share of subtasks that are done, from 0 to 100.
+#[must_use]
+pub fn progress_percent(task: &Task) -> i64 {
+    if task.subtask_count == 0 {
+        0
+    } else {
+        task.completed_subtask_count * 100 / task.subtask_count
+    }
+}
@@ -0,0 +1,23 @@
+use common::Tag;
+
+/// Tags offered while typing in the tag filter: those not already picked
+/// whose name contains the input, ignoring case.
+#[must_use]
+pub fn tag_suggestions<'a>(tags: &'a [Tag], picked: &[String], input: &str) -> Vec<&'a Tag> {
+    let needle = input.trim().to_lowercase();
+    tags.iter()
+        .filter(|tag| !picked.contains(&tag.name))
+        .filter(|tag| tag.name.to_lowercase().contains(&needle))
+        .collect()
+}
+
+/// The tag named by the input, once it matches an existing tag that is not
+/// already picked.
+#[must_use]
+pub fn pick_tag(tags: &[Tag], picked: &[String], input: &str) -> Option<String> {
+    let input = input.trim();
+    tags.iter()
+        .find(|tag| tag.name == input)
+        .filter(|tag| !picked.contains(&tag.name))
+        .map(|tag| tag.name.clone())
+}
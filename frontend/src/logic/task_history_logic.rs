@@ -0,0 +1,68 @@
+use crate::logic::recurrence_logic::describe_rule;
+use crate::logic::time_logic::format_minutes;
+use common::{TaskEvent, TaskEventKind};
+
+/// One line of the history timeline, such as "Changed priority from Low to
+/// High". Dates are shown as the day they fall on, and estimates in hours
+/// and minutes.
+#[must_use]
+pub fn describe_event(event: &TaskEvent) -> String {
+    let field = event.field.as_deref().unwrap_or_default();
+    let label = match field {
+        "estimate_minutes" => "estimate".to_string(),
+        field => field.replace('_', " "),
+    };
+    let value = |value: &Option<String>| {
+        value.as_deref().map(|value| match field {
+            "due_date" => value.get(..10).unwrap_or(value).to_string(),
+            "estimate_minutes" => value
+                .parse()
+                .map_or_else(|_| value.to_string(), format_minutes),
+            _ => value.to_string(),
+        })
+    };
+
+    match event.kind {
+        TaskEventKind::Created => "Created the task".to_string(),
+        TaskEventKind::Deleted => "Moved the task to the trash".to_string(),
+        TaskEventKind::Restored => "Restored the task from the trash".to_string(),
+        // Members, projects, parents and custom fields are stored by id,
+        // which means nothing to the reader
+        TaskEventKind::Updated if field == "project_id" => {
+            "Moved the task to another project".to_string()
+        }
+        TaskEventKind::Updated if field == "custom_fields" => "Changed custom fields".to_string(),
+        TaskEventKind::Updated if field == "parent_id" => {
+            match (&event.old_value, &event.new_value) {
+                (None, _) => "Made the task a subtask".to_string(),
+                (Some(_), None) => "Made the task a top-level task".to_string(),
+                (Some(_), Some(_)) => "Moved the task to another parent".to_string(),
+            }
+        }
+        TaskEventKind::Updated if field == "recurrence" => {
+            match (&event.old_value, &event.new_value) {
+                (_, None) => "Stopped the task repeating".to_string(),
+                (None, Some(new)) => format!("Made the task repeat: {}", describe_rule(new)),
+                (Some(_), Some(new)) => {
+                    format!("Changed how the task repeats: {}", describe_rule(new))
+                }
+            }
+        }
+        TaskEventKind::Updated if field == "assignee_id" => {
+            match (&event.old_value, &event.new_value) {
+                (None, _) => "Assigned the task".to_string(),
+                (Some(_), None) => "Unassigned the task".to_string(),
+                (Some(_), Some(_)) => "Reassigned the task".to_string(),
+            }
+        }
+        TaskEventKind::Updated => {
+            match (value(&event.old_value), value(&event.new_value)) {
+                (_, None) => format!("Cleared {label}"),
+                // Descriptions are too long to repeat in the timeline
+                (_, Some(_)) if field == "description" => format!("Edited {label}"),
+                (None, Some(new)) => format!("Set {label} to {new}"),
+                (Some(old), Some(new)) => format!("Changed {label} from {old} to {new}"),
+            }
+        }
+    }
+}
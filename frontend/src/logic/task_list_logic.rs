@@ -1,35 +1,114 @@
-use common::{Task, TaskPriority, TaskStatus};
+use common::{rank, Task, TaskPriority, TaskStatus, WorkflowState};
 use std::collections::HashMap;
+use uuid::Uuid;
 
+/// Replaces the local copy of a task with the server's after a write, so the
+/// next write sends the task's new version.
+pub fn apply_server_task(tasks: &mut [Task], updated: Task) {
+    if let Some(task) = tasks.iter_mut().find(|task| task.id == updated.id) {
+        *task = updated;
+    }
+}
+
+/// Keeps a card's comment count in step with the thread open in its detail.
+pub fn set_comment_count(tasks: &mut [Task], id: Uuid, count: usize) {
+    if let Some(task) = tasks.iter_mut().find(|task| task.id == id) {
+        task.comment_count = count as i64;
+    }
+}
+
+/// Groups the tasks matching `priority_filter` into one column per state of
+/// the workflow, each empty one included.
 #[must_use]
 pub fn filter_and_group_tasks(
     tasks: &[Task],
     priority_filter: Option<TaskPriority>,
+    states: &[WorkflowState],
 ) -> HashMap<TaskStatus, Vec<Task>> {
     let filtered: Vec<Task> = tasks
         .iter()
-        .filter(|&task| {
-            let priority_match =
-                priority_filter.is_none() || priority_filter == Some(task.priority);
-            let not_backlog = task.status != TaskStatus::Backlog;
-            priority_match && not_backlog
-        })
+        .filter(|&task| priority_filter.is_none() || priority_filter == Some(task.priority))
         .cloned()
         .collect();
 
     let mut grouped: HashMap<TaskStatus, Vec<Task>> = HashMap::new();
     for task in filtered {
-        grouped.entry(task.status).or_default().push(task);
+        grouped.entry(task.status.clone()).or_default().push(task);
     }
+    // Cards keep the order they were dropped in, as the server ranks them
     for tasks_list in grouped.values_mut() {
-        tasks_list.sort_by(|a, b| b.priority.cmp(&a.priority));
+        tasks_list.sort_by(|a, b| a.rank.cmp(&b.rank).then(a.id.cmp(&b.id)));
     }
-    for status in [
-        TaskStatus::Todo,
-        TaskStatus::InProgress,
-        TaskStatus::Completed,
-    ] {
-        grouped.entry(status).or_default();
+    for state in states {
+        grouped.entry(state.name.clone()).or_default();
     }
     grouped
 }
+
+/// The card a dragged card is over, and whether it is over that card's
+/// lower half.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DropTarget {
+    pub task_id: Uuid,
+    pub below: bool,
+}
+
+/// Where a card dropped into `column` lands, as the ids of the cards that end
+/// up directly above and below it: next to `target` when that is one of the
+/// column's cards, otherwise at the bottom. `None` when the card would stay
+/// where it is.
+#[must_use]
+pub fn drop_neighbours(
+    column: &[Task],
+    dragged_id: Uuid,
+    target: Option<DropTarget>,
+) -> Option<(Option<Uuid>, Option<Uuid>)> {
+    // A target left over from another column does not count
+    let target = target.filter(|target| column.iter().any(|task| task.id == target.task_id));
+    if target.is_some_and(|target| target.task_id == dragged_id) {
+        return None;
+    }
+
+    let others: Vec<Uuid> = column
+        .iter()
+        .map(|task| task.id)
+        .filter(|id| *id != dragged_id)
+        .collect();
+    let index = target
+        .and_then(|target| {
+            let position = others.iter().position(|id| *id == target.task_id)?;
+            Some(if target.below { position + 1 } else { position })
+        })
+        .unwrap_or(others.len());
+    if column.iter().position(|task| task.id == dragged_id) == Some(index) {
+        return None;
+    }
+
+    let after_id = index.checked_sub(1).map(|above| others[above]);
+    Some((after_id, others.get(index).copied()))
+}
+
+/// Moves a card locally ahead of the server's answer, ranking it between
+/// its new neighbours.
+pub fn apply_move(
+    tasks: &mut [Task],
+    id: Uuid,
+    state: &WorkflowState,
+    after_id: Option<Uuid>,
+    before_id: Option<Uuid>,
+) {
+    let rank_of = |id: Option<Uuid>| {
+        let id = id?;
+        tasks
+            .iter()
+            .find(|task| task.id == id)
+            .map(|task| task.rank.clone())
+    };
+    let after = rank_of(after_id);
+    let before = rank_of(before_id);
+    if let Some(task) = tasks.iter_mut().find(|task| task.id == id) {
+        task.status = state.name.clone();
+        task.status_category = state.category;
+        task.rank = rank::between(after.as_deref(), before.as_deref());
+    }
+}
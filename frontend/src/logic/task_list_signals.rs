@@ -1,10 +1,18 @@
-use common::{TaskPriority, TaskStatus, UpdateTaskRequest};
+use crate::api::WriteError;
+use crate::logic::task_list_logic::DropTarget;
+use common::{MoveTaskRequest, TaskPriority, TaskStatus};
 use leptos::*;
 
 pub struct TaskListSignals {
     pub filter_priority: RwSignal<Option<TaskPriority>>,
     pub drag_over_status: RwSignal<Option<TaskStatus>>,
     pub dragging_task_id: RwSignal<Option<uuid::Uuid>>,
+    /// Card the dragged card was last over, which decides where it lands.
+    pub drop_target: RwSignal<Option<DropTarget>>,
+    /// Message shown when a drop lost to someone else's edit of the task.
+    pub conflict: RwSignal<Option<String>>,
+    /// Task whose details and history are open.
+    pub selected_task_id: RwSignal<Option<uuid::Uuid>>,
 }
 
 impl Default for TaskListSignals {
@@ -20,35 +28,45 @@ impl TaskListSignals {
             filter_priority: create_rw_signal(None::<TaskPriority>),
             drag_over_status: create_rw_signal(None::<TaskStatus>),
             dragging_task_id: create_rw_signal(None::<uuid::Uuid>),
+            drop_target: create_rw_signal(None::<DropTarget>),
+            conflict: create_rw_signal(None::<String>),
+            selected_task_id: create_rw_signal(None::<uuid::Uuid>),
         }
     }
 }
 
-type UpdateTaskAction = Action<(uuid::Uuid, UpdateTaskRequest), Result<common::Task, String>>;
+/// Dispatched with the task id, where it was dropped, the version being
+/// changed and whether to override the task's blockers.
+type MoveTaskAction =
+    Action<(uuid::Uuid, MoveTaskRequest, i64, bool), Result<common::Task, WriteError>>;
 
 #[must_use]
-pub fn use_update_task_action() -> (UpdateTaskAction, Box<dyn Fn()>) {
-    let update_task_action = create_action(|(id, request): &(uuid::Uuid, UpdateTaskRequest)| {
-        let id = *id;
-        let request = request.clone();
-        async move {
-            leptos::logging::log!("Updating task {} with request: {:?}", id, request);
-            let result = crate::api::update_task(id, request).await;
-            leptos::logging::log!("Update result: {:?}", result);
-            result
-        }
-    });
+pub fn use_move_task_action() -> (MoveTaskAction, Box<dyn Fn()>) {
+    let move_task_action = create_action(
+        |(id, request, version, override_blockers): &(uuid::Uuid, MoveTaskRequest, i64, bool)| {
+            let id = *id;
+            let request = request.clone();
+            let version = *version;
+            let override_blockers = *override_blockers;
+            async move {
+                leptos::logging::log!("Moving task {} with request: {:?}", id, request);
+                let result = crate::api::move_task(id, request, version, override_blockers).await;
+                leptos::logging::log!("Move result: {:?}", result);
+                result
+            }
+        },
+    );
     let on_success = move || {
-        if let Some(result) = update_task_action.value().get() {
+        if let Some(result) = move_task_action.value().get() {
             match result {
                 Ok(_) => {
-                    leptos::logging::log!("Task update successful");
+                    leptos::logging::log!("Task move successful");
                 }
                 Err(e) => {
-                    leptos::logging::log!("Task update failed: {:?}", e);
+                    leptos::logging::log!("Task move failed: {:?}", e);
                 }
             }
         }
     };
-    (update_task_action, Box::new(on_success))
+    (move_task_action, Box::new(on_success))
 }
@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use common::Task;
+
+/// A duration in hours and minutes, such as "1h 30m", "2h" or "45m".
+#[must_use]
+pub fn format_minutes(minutes: i64) -> String {
+    let (hours, minutes) = (minutes / 60, minutes % 60);
+    match (hours, minutes) {
+        (0, minutes) => format!("{minutes}m"),
+        (hours, 0) => format!("{hours}h"),
+        (hours, minutes) => format!("{hours}h {minutes}m"),
+    }
+}
+
+/// Reads a duration typed as hours and minutes, such as "1h 30m", "2h" or
+/// "45m". A bare number counts as minutes.
+#[must_use]
+pub fn parse_minutes(text: &str) -> Option<u32> {
+    let text = text.trim();
+    if let Ok(minutes) = text.parse() {
+        return Some(minutes);
+    }
+    let mut total: u32 = 0;
+    let mut parts = 0;
+    for part in text.split_whitespace() {
+        let (value, scale) = if let Some(hours) = part.strip_suffix('h') {
+            (hours, 60)
+        } else if let Some(minutes) = part.strip_suffix('m') {
+            (minutes, 1)
+        } else {
+            return None;
+        };
+        let value: u32 = value.parse().ok()?;
+        total = total.checked_add(value.checked_mul(scale)?)?;
+        parts += 1;
+    }
+    (parts > 0).then_some(total)
+}
+
+/// Time logged against the estimate, such as "1h 30m / 2h". Tasks with
+/// neither show nothing.
+#[must_use]
+pub fn effort_label(task: &Task) -> Option<String> {
+    match task.estimate_minutes {
+        Some(estimate) => Some(format!(
+            "{} / {}",
+            format_minutes(task.logged_minutes),
+            format_minutes(i64::from(estimate))
+        )),
+        None => (task.logged_minutes > 0).then(|| format_minutes(task.logged_minutes)),
+    }
+}
+
+/// Whether more time is logged than the task was estimated at.
+#[must_use]
+pub fn over_estimate(task: &Task) -> bool {
+    task.estimate_minutes
+        .is_some_and(|estimate| task.logged_minutes > i64::from(estimate))
+}
+
+/// Whole minutes the task's running timer has been going at `now`.
+#[must_use]
+pub fn running_minutes(task: &Task, now: DateTime<Utc>) -> Option<i64> {
+    task.timer_started_at
+        .map(|started_at| (now - started_at).num_minutes().max(0))
+}
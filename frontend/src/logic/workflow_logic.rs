@@ -0,0 +1,77 @@
+use common::{transition::FilledFields, StatusCategory, Task, TaskStatus, Workflow, WorkflowState};
+
+/// A state's name for column headers and badges, with a space before each
+/// inner capital so `InProgress` reads "In Progress".
+#[must_use]
+pub fn state_label(status: &TaskStatus) -> String {
+    let mut label = String::new();
+    let mut previous = None::<char>;
+    for c in status.as_str().chars() {
+        if c.is_uppercase() && previous.is_some_and(char::is_lowercase) {
+            label.push(' ');
+        }
+        label.push(c);
+        previous = Some(c);
+    }
+    label
+}
+
+/// The states `task` can move to, as state names and labels in board
+/// order: those its workflow allows from its status whose guards it meets.
+#[must_use]
+pub fn move_choices(workflow: &Workflow, task: &Task) -> Vec<(TaskStatus, String)> {
+    workflow
+        .allowed_next(task)
+        .into_iter()
+        .map(|state| (state.name.clone(), state_label(&state.name)))
+        .collect()
+}
+
+/// Refuses a card dropped into a column its workflow does not allow, before
+/// the move is sent, with the reason the server would give.
+pub fn check_drop(workflow: &Workflow, task: &Task, target: &WorkflowState) -> Result<(), String> {
+    workflow
+        .check_transition(Some(&task.status), target, FilledFields::of(task))
+        .map_err(|error| error.to_string())
+}
+
+/// Border and background of a board column, lighter while a card is dragged
+/// over it. Columns are coloured by category, as their names are the
+/// project's own.
+#[must_use]
+pub fn column_color(category: StatusCategory, drag_over: bool) -> &'static str {
+    match (category, drag_over) {
+        (StatusCategory::Todo, false) => "border-gray-600 bg-gray-800",
+        (StatusCategory::Doing, false) => "border-blue-600 bg-blue-900",
+        (StatusCategory::Done, false) => "border-green-600 bg-green-900",
+        (StatusCategory::Todo, true) => "border-gray-400 bg-gray-700",
+        (StatusCategory::Doing, true) => "border-blue-400 bg-blue-800",
+        (StatusCategory::Done, true) => "border-green-400 bg-green-800",
+    }
+}
+
+/// Card count of a column header, out of the WIP limit when it has one.
+#[must_use]
+pub fn wip_label(count: usize, limit: Option<u32>) -> String {
+    match limit {
+        Some(limit) => format!("{count}/{limit}"),
+        None => count.to_string(),
+    }
+}
+
+/// Whether a column holds more cards than its WIP limit, which the server
+/// only lets happen when it warns rather than refuses.
+#[must_use]
+pub fn over_wip_limit(count: usize, limit: Option<u32>) -> bool {
+    limit.is_some_and(|limit| count > limit as usize)
+}
+
+/// Colours of a status badge.
+#[must_use]
+pub fn badge_color(category: StatusCategory) -> &'static str {
+    match category {
+        StatusCategory::Todo => "bg-gray-100 text-gray-800",
+        StatusCategory::Doing => "bg-blue-100 text-blue-800",
+        StatusCategory::Done => "bg-green-100 text-green-800",
+    }
+}
@@ -1,9 +1,13 @@
 use crate::{
     api,
     components::{Modal, Navbar, TaskForm, TaskList},
+    logic::{
+        board_state::BoardState, project_logic::project_from_param, query_logic::search_filter,
+    },
 };
-use common::{Task, TaskPriority};
+use common::{default_workflow, Task, TaskFilter, TaskSort, Workflow};
 use leptos::*;
+use leptos_router::use_params_map;
 
 #[component]
 #[allow(non_snake_case)]
@@ -12,17 +16,59 @@ pub fn HomePage() -> impl IntoView {
     let (is_loading, set_is_loading) = create_signal(true);
     let (error, set_error) = create_signal(None::<String>);
     let (show_modal, set_show_modal) = create_signal(false);
-    let (filter_priority, set_filter_priority) = create_signal(None::<TaskPriority>);
+    // Kept above the page so a saved view picked in the sidebar applies
+    let board = expect_context::<BoardState>();
     let (refresh_debounce, set_refresh_debounce) = create_signal(false);
 
-    let load_tasks = create_action(move |_: &()| async move { api::fetch_tasks(None).await });
+    // The board of `/projects/:id/board`, or the default project's at `/`
+    let params = use_params_map();
+    let project_id = Signal::derive(move || {
+        params.with(|params| project_from_param(params.get("id").map(String::as_str)))
+    });
+
+    // Columns of the board; until the project's workflow loads, or if it
+    // fails to, the states every project starts with
+    let workflow_resource = create_local_resource(move || project_id.get(), api::fetch_workflow);
+    let workflow = Signal::derive(move || {
+        workflow_resource
+            .get()
+            .and_then(Result::ok)
+            .unwrap_or_else(|| Workflow {
+                project_id: project_id.get(),
+                states: default_workflow(),
+                priority_rules: Vec::new(),
+            })
+    });
+
+    // The filters applied on the server; priority is filtered on the board
+    let current_filter = move || TaskFilter {
+        all_tags: board.filter_tags.get(),
+        assignee: board.filter_assignee.get(),
+        project: Some(project_id.get()),
+        ..board.search_query.with(|text| search_filter(text))
+    };
+
+    // Responses can arrive out of order, so each load is numbered and only
+    // the latest one is shown
+    let load_generation = store_value(0_u32);
+    let load_tasks = create_action(move |(generation, filter): &(u32, TaskFilter)| {
+        let (generation, filter) = (*generation, filter.clone());
+        async move {
+            let result = api::fetch_all_tasks(Some(filter), TaskSort::default()).await;
+            (generation, result)
+        }
+    });
+    let dispatch_load = move |filter: TaskFilter| {
+        load_generation.update_value(|generation| *generation += 1);
+        load_tasks.dispatch((load_generation.get_value(), filter));
+    };
 
     let refresh_tasks = move || {
         if !refresh_debounce.get() {
             set_refresh_debounce.set(true);
             set_is_loading.set(true);
             set_error.set(None);
-            load_tasks.dispatch(());
+            dispatch_load(untrack(current_filter));
 
             // Reset debounce after a delay
             set_timeout(
@@ -34,14 +80,17 @@ pub fn HomePage() -> impl IntoView {
         }
     };
 
-    // Load tasks on mount
+    // Load tasks on mount and again whenever a server-side filter changes
     create_effect(move |_| {
-        load_tasks.dispatch(());
+        dispatch_load(current_filter());
     });
 
     // Handle task loading results
     create_effect(move |_| {
-        if let Some(result) = load_tasks.value().get() {
+        if let Some((generation, result)) = load_tasks.value().get() {
+            if generation != load_generation.get_value() {
+                return;
+            }
             set_is_loading.set(false);
             match result {
                 Ok(loaded_tasks) => {
@@ -58,8 +107,9 @@ pub fn HomePage() -> impl IntoView {
     view! {
         <div class="min-h-screen bg-gray-900">
             <Navbar
-                filter_priority=filter_priority
-                set_filter_priority=set_filter_priority
+                board=board
+                project_id=project_id
+                workflow=workflow
                 on_add_task=move || set_show_modal.set(true)
             />
 
@@ -94,7 +144,7 @@ pub fn HomePage() -> impl IntoView {
                         }.into_view()
                     } else {
                         view! {
-                            <TaskList tasks=tasks set_tasks=set_tasks refresh_tasks=refresh_tasks filter_priority=filter_priority />
+                            <TaskList tasks=tasks set_tasks=set_tasks refresh_tasks=refresh_tasks board=board workflow=workflow />
                         }.into_view()
                     }
                 }}
@@ -105,6 +155,7 @@ pub fn HomePage() -> impl IntoView {
                     title="Add New Task".to_string()
                 >
                     <TaskForm
+                        project_id=project_id
                         on_submit=refresh_tasks
                         on_close=Some(move || set_show_modal.set(false))
                     />
@@ -1,3 +1,5 @@
 pub mod home;
+pub mod trash;
 
 pub use home::*;
+pub use trash::*;
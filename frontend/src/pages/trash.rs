@@ -0,0 +1,132 @@
+use crate::api;
+use common::Task;
+use leptos::*;
+
+#[component]
+#[allow(non_snake_case)]
+pub fn TrashPage() -> impl IntoView {
+    let (tasks, set_tasks) = create_signal(Vec::<Task>::new());
+    let (is_loading, set_is_loading) = create_signal(true);
+    let (error, set_error) = create_signal(None::<String>);
+
+    let load_trash = create_action(|_: &()| async move { api::fetch_trash().await });
+
+    let restore_task = create_action(|id: &uuid::Uuid| {
+        let id = *id;
+        async move { api::restore_task(id).await.map(|task| task.id) }
+    });
+
+    let purge_task = create_action(|id: &uuid::Uuid| {
+        let id = *id;
+        async move { api::purge_task(id).await.map(|()| id) }
+    });
+
+    // Load the trash on mount
+    load_trash.dispatch(());
+
+    create_effect(move |_| {
+        if let Some(result) = load_trash.value().get() {
+            set_is_loading.set(false);
+            match result {
+                Ok(trashed) => {
+                    set_tasks.set(trashed);
+                    set_error.set(None);
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        }
+    });
+
+    // Restored and purged tasks both leave the trash
+    let remove_from_trash = move |result: Result<uuid::Uuid, String>| match result {
+        Ok(id) => set_tasks.update(|tasks| tasks.retain(|task| task.id != id)),
+        Err(e) => set_error.set(Some(e)),
+    };
+    create_effect(move |_| {
+        if let Some(result) = restore_task.value().get() {
+            remove_from_trash(result);
+        }
+    });
+    create_effect(move |_| {
+        if let Some(result) = purge_task.value().get() {
+            remove_from_trash(result);
+        }
+    });
+
+    let confirm_purge = move |task: &Task| {
+        window()
+            .confirm_with_message(&format!(
+                "Permanently delete \"{}\"? This cannot be undone.",
+                task.title
+            ))
+            .unwrap_or(false)
+    };
+
+    view! {
+        <div class="min-h-screen bg-gray-900">
+            <div class="max-w-7xl mx-auto px-6">
+                <h1 class="text-2xl font-bold text-white mb-2">"Trash"</h1>
+                <p class="text-sm text-gray-400 mb-6">
+                    "Deleted tasks stay here until you restore them or delete them forever."
+                </p>
+
+                {move || error.get().map(|err| view! {
+                    <div class="bg-red-900 border border-red-700 rounded-lg p-4 mb-6">
+                        <p class="text-sm text-red-300">{err}</p>
+                    </div>
+                })}
+
+                {move || {
+                    if is_loading.get() {
+                        view! {
+                            <div class="text-center py-12">
+                                <div class="inline-block animate-spin rounded-full h-8 w-8 border-b-2 border-blue-400"></div>
+                                <p class="mt-2 text-gray-300">"Loading trash..."</p>
+                            </div>
+                        }.into_view()
+                    } else if tasks.with(Vec::is_empty) {
+                        view! {
+                            <div class="text-center py-12 text-gray-400">
+                                <p class="text-sm">"The trash is empty"</p>
+                            </div>
+                        }.into_view()
+                    } else {
+                        tasks.get().into_iter().map(|task| {
+                            let id = task.id;
+                            view! {
+                                <div class="flex items-center justify-between p-4 mb-3 rounded-lg border border-gray-700 bg-gray-800">
+                                    <div class="min-w-0">
+                                        <h3 class="font-medium text-white truncate">{task.title.clone()}</h3>
+                                        {task.deleted_at.map(|deleted| view! {
+                                            <p class="text-xs text-gray-400 mt-1">
+                                                "Deleted " {deleted.format("%m/%d/%Y %H:%M").to_string()}
+                                            </p>
+                                        })}
+                                    </div>
+                                    <div class="flex gap-2 ml-4">
+                                        <button
+                                            on:click=move |_| restore_task.dispatch(id)
+                                            class="px-3 py-1 text-sm font-medium bg-blue-100 text-blue-800 rounded hover:bg-blue-200 transition-colors"
+                                        >
+                                            "Restore"
+                                        </button>
+                                        <button
+                                            on:click=move |_| {
+                                                if confirm_purge(&task) {
+                                                    purge_task.dispatch(id);
+                                                }
+                                            }
+                                            class="px-3 py-1 text-sm font-medium bg-red-100 text-red-800 rounded hover:bg-red-200 transition-colors"
+                                        >
+                                            "Delete forever"
+                                        </button>
+                                    </div>
+                                </div>
+                            }
+                        }).collect_view()
+                    }
+                }}
+            </div>
+        </div>
+    }
+}
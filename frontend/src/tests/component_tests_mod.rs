@@ -1,7 +1,10 @@
 #[cfg(test)]
 mod component_tests {
     use chrono::Utc;
-    use common::{CreateTaskRequest, Task, TaskPriority, TaskStatus, UpdateTaskRequest};
+    use common::{
+        CreateTaskRequest, Patch, StatusCategory, Task, TaskPriority, TaskStatus,
+        UpdateTaskRequest, DEFAULT_PROJECT_ID,
+    };
     use uuid::Uuid;
 
     // Component interaction tests
@@ -20,6 +23,7 @@ mod component_tests {
                 description: description.map(ToString::to_string),
                 priority,
                 due_date,
+                ..Default::default()
             };
 
             assert_eq!(request.title, "New Task Title");
@@ -31,43 +35,43 @@ mod component_tests {
         #[test]
         fn test_task_item_update_interaction() {
             let mut task = create_test_task();
-            let new_status = TaskStatus::Completed;
+            let new_status = TaskStatus::new("Completed");
 
             // Simulate updating task status
             task.status = new_status;
 
-            assert_eq!(task.status, TaskStatus::Completed);
+            assert_eq!(task.status, TaskStatus::new("Completed"));
         }
 
         #[test]
         fn test_task_list_drag_drop_interaction() {
-            let target_status = TaskStatus::InProgress;
+            let target_status = TaskStatus::new("InProgress");
 
             // Create update request for drag and drop
             let update_request = UpdateTaskRequest {
-                title: None,
-                description: None,
-                status: Some(target_status),
-                priority: None,
-                due_date: None,
+                status: Patch::Value(target_status),
+                ..Default::default()
             };
 
-            assert_eq!(update_request.status, Some(TaskStatus::InProgress));
-            assert!(update_request.title.is_none());
-            assert!(update_request.description.is_none());
+            assert_eq!(
+                update_request.status,
+                Patch::Value(TaskStatus::new("InProgress"))
+            );
+            assert!(update_request.title.is_absent());
+            assert!(update_request.description.is_absent());
         }
 
         #[test]
         fn test_optimistic_task_status_update() {
             let mut task = create_test_task();
             let original_status = task.status;
-            let new_status = TaskStatus::Completed;
+            let new_status = TaskStatus::new("Completed");
 
             // Simulate optimistic update
             task.status = new_status;
 
             // Verify the optimistic update
-            assert_eq!(task.status, TaskStatus::Completed);
+            assert_eq!(task.status, TaskStatus::new("Completed"));
             assert_ne!(task.status, original_status);
         }
 
@@ -75,14 +79,14 @@ mod component_tests {
         fn test_optimistic_update_revert_on_failure() {
             let mut task = create_test_task();
             let original_status = task.status;
-            let attempted_status = TaskStatus::InProgress;
+            let attempted_status = TaskStatus::new("InProgress");
 
             // Simulate optimistic update
             task.status = attempted_status;
-            assert_eq!(task.status, TaskStatus::InProgress);
+            assert_eq!(task.status, TaskStatus::new("InProgress"));
 
             // Simulate server failure - revert to original status
-            task.status = original_status;
+            task.status = original_status.clone();
             assert_eq!(task.status, original_status);
         }
 
@@ -93,12 +97,12 @@ mod component_tests {
 
             // Find and update task optimistically
             if let Some(task) = tasks.iter_mut().find(|t| t.id == task_id) {
-                task.status = TaskStatus::Completed;
+                task.status = TaskStatus::new("Completed");
             }
 
             // Verify the task was updated in the list
             let updated_task = tasks.iter().find(|t| t.id == task_id).unwrap();
-            assert_eq!(updated_task.status, TaskStatus::Completed);
+            assert_eq!(updated_task.status, TaskStatus::new("Completed"));
         }
     }
 
@@ -147,14 +151,14 @@ mod component_tests {
         #[test]
         fn test_task_status_validation() {
             let statuses = [
-                TaskStatus::Todo,
-                TaskStatus::InProgress,
-                TaskStatus::Completed,
-                TaskStatus::Backlog,
+                TaskStatus::new("Todo"),
+                TaskStatus::new("InProgress"),
+                TaskStatus::new("Completed"),
+                TaskStatus::new("Backlog"),
             ];
 
             assert_eq!(statuses.len(), 4);
-            assert!(statuses.contains(&TaskStatus::InProgress));
+            assert!(statuses.contains(&TaskStatus::new("InProgress")));
         }
     }
 
@@ -194,66 +198,64 @@ mod component_tests {
 
         #[test]
         fn test_task_item_status_display() {
-            let todo_task = Task {
-                status: TaskStatus::Todo,
-                ..create_test_task()
-            };
+            use crate::logic::workflow_logic::{badge_color, state_label};
+
+            let todo_task = create_test_task();
             let completed_task = Task {
-                status: TaskStatus::Completed,
+                status: TaskStatus::new("Completed"),
+                status_category: StatusCategory::Done,
                 ..create_test_task()
             };
 
-            // Test status-based styling logic
-            let todo_style = match todo_task.status {
-                TaskStatus::Todo => "border-gray-300",
-                TaskStatus::InProgress => "border-blue-300",
-                TaskStatus::Completed => "border-green-300",
-                TaskStatus::Backlog => "border-purple-300",
-            };
-
-            let completed_style = match completed_task.status {
-                TaskStatus::Todo => "border-gray-300",
-                TaskStatus::InProgress => "border-blue-300",
-                TaskStatus::Completed => "border-green-300",
-                TaskStatus::Backlog => "border-purple-300",
-            };
-
-            assert_eq!(todo_style, "border-gray-300");
-            assert_eq!(completed_style, "border-green-300");
+            // Badges are coloured by category and labelled by state name
+            assert_eq!(
+                badge_color(todo_task.status_category),
+                "bg-gray-100 text-gray-800"
+            );
+            assert_eq!(
+                badge_color(completed_task.status_category),
+                "bg-green-100 text-green-800"
+            );
+            assert_eq!(state_label(&TaskStatus::new("InProgress")), "In Progress");
+            assert_eq!(state_label(&TaskStatus::new("Code review")), "Code review");
         }
 
         #[test]
         fn test_task_list_grouping_by_status() {
-            let tasks = vec![
+            let tasks = [
                 Task {
-                    status: TaskStatus::Todo,
+                    status: TaskStatus::new("Todo"),
+                    status_category: StatusCategory::Todo,
                     ..create_test_task()
                 },
                 Task {
-                    status: TaskStatus::InProgress,
+                    status: TaskStatus::new("InProgress"),
+                    status_category: StatusCategory::Doing,
                     ..create_test_task()
                 },
                 Task {
-                    status: TaskStatus::Completed,
+                    status: TaskStatus::new("Completed"),
+                    status_category: StatusCategory::Done,
                     ..create_test_task()
                 },
                 Task {
-                    status: TaskStatus::Todo,
+                    status: TaskStatus::new("Todo"),
+                    status_category: StatusCategory::Todo,
                     ..create_test_task()
                 },
             ];
 
             let todo_count = tasks
                 .iter()
-                .filter(|t| t.status == TaskStatus::Todo)
+                .filter(|t| t.status == TaskStatus::new("Todo"))
                 .count();
             let in_progress_count = tasks
                 .iter()
-                .filter(|t| t.status == TaskStatus::InProgress)
+                .filter(|t| t.status == TaskStatus::new("InProgress"))
                 .count();
             let completed_count = tasks
                 .iter()
-                .filter(|t| t.status == TaskStatus::Completed)
+                .filter(|t| t.status == TaskStatus::new("Completed"))
                 .count();
 
             assert_eq!(todo_count, 2);
@@ -268,11 +270,29 @@ mod component_tests {
             id: Uuid::new_v4(),
             title: "Test Task".to_string(),
             description: Some("Test Description".to_string()),
-            status: TaskStatus::Todo,
+            status: TaskStatus::new("Todo"),
+            status_category: StatusCategory::Todo,
             priority: TaskPriority::Medium,
             due_date: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            version: 1,
+            deleted_at: None,
+            comment_count: 0,
+            search: None,
+            tags: Vec::new(),
+            assignee_id: None,
+            project_id: DEFAULT_PROJECT_ID,
+            parent_id: None,
+            subtask_count: 0,
+            completed_subtask_count: 0,
+            is_blocked: false,
+            recurrence: None,
+            rank: String::new(),
+            estimate_minutes: None,
+            logged_minutes: 0,
+            timer_started_at: None,
+            custom_fields: Default::default(),
         }
     }
 
@@ -283,31 +303,85 @@ mod component_tests {
                 id: Uuid::new_v4(),
                 title: "Test Task 1".to_string(),
                 description: Some("Test Description 1".to_string()),
-                status: TaskStatus::Todo,
+                status: TaskStatus::new("Todo"),
+                status_category: StatusCategory::Todo,
                 priority: TaskPriority::Low,
                 due_date: None,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
+                version: 1,
+                deleted_at: None,
+                comment_count: 0,
+                search: None,
+                tags: Vec::new(),
+                assignee_id: None,
+                project_id: DEFAULT_PROJECT_ID,
+                parent_id: None,
+                subtask_count: 0,
+                completed_subtask_count: 0,
+                is_blocked: false,
+                recurrence: None,
+                rank: String::new(),
+                estimate_minutes: None,
+                logged_minutes: 0,
+                timer_started_at: None,
+                custom_fields: Default::default(),
             },
             Task {
                 id: Uuid::new_v4(),
                 title: "Test Task 2".to_string(),
                 description: Some("Test Description 2".to_string()),
-                status: TaskStatus::InProgress,
+                status: TaskStatus::new("InProgress"),
+                status_category: StatusCategory::Doing,
                 priority: TaskPriority::Medium,
                 due_date: None,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
+                version: 1,
+                deleted_at: None,
+                comment_count: 0,
+                search: None,
+                tags: Vec::new(),
+                assignee_id: None,
+                project_id: DEFAULT_PROJECT_ID,
+                parent_id: None,
+                subtask_count: 0,
+                completed_subtask_count: 0,
+                is_blocked: false,
+                recurrence: None,
+                rank: String::new(),
+                estimate_minutes: None,
+                logged_minutes: 0,
+                timer_started_at: None,
+                custom_fields: Default::default(),
             },
             Task {
                 id: Uuid::new_v4(),
                 title: "Test Task 3".to_string(),
                 description: Some("Test Description 3".to_string()),
-                status: TaskStatus::Completed,
+                status: TaskStatus::new("Completed"),
+                status_category: StatusCategory::Done,
                 priority: TaskPriority::High,
                 due_date: None,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
+                version: 1,
+                deleted_at: None,
+                comment_count: 0,
+                search: None,
+                tags: Vec::new(),
+                assignee_id: None,
+                project_id: DEFAULT_PROJECT_ID,
+                parent_id: None,
+                subtask_count: 0,
+                completed_subtask_count: 0,
+                is_blocked: false,
+                recurrence: None,
+                rank: String::new(),
+                estimate_minutes: None,
+                logged_minutes: 0,
+                timer_started_at: None,
+                custom_fields: Default::default(),
             },
         ]
     }
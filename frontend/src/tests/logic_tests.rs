@@ -1,9 +1,21 @@
 #[cfg(test)]
 mod frontend_logic_tests {
     use chrono::Utc;
-    use common::{CreateTaskRequest, TaskFilter, TaskPriority, TaskStatus, UpdateTaskRequest};
+    use common::{
+        default_workflow, CreateTaskRequest, Patch, SortDirection, StatusCategory, TaskFilter,
+        TaskPriority, TaskSort, TaskSortField, TaskStatus, UpdateTaskRequest, Workflow,
+        WorkflowState, MAX_PAGE_SIZE,
+    };
     use uuid::Uuid;
 
+    /// Category of `status` in the workflow every project starts with.
+    fn category_of(status: &TaskStatus) -> StatusCategory {
+        default_workflow()
+            .into_iter()
+            .find(|state| state.name == *status)
+            .map_or(StatusCategory::Todo, |state| state.category)
+    }
+
     // Configure tests to run silently
     #[allow(unused_imports)]
     use wasm_bindgen_test::*;
@@ -61,10 +73,11 @@ mod frontend_logic_tests {
         #[test]
         fn test_empty_filter_no_params() {
             let filter = TaskFilter {
-                status: None,
-                priority: None,
+                status: Vec::new(),
+                priority: Vec::new(),
                 due_before: None,
                 due_after: None,
+                ..Default::default()
             };
 
             let params = build_query_params(&filter);
@@ -74,10 +87,11 @@ mod frontend_logic_tests {
         #[test]
         fn test_status_filter_param() {
             let filter = TaskFilter {
-                status: Some(TaskStatus::InProgress),
-                priority: None,
+                status: vec![TaskStatus::new("InProgress")],
+                priority: Vec::new(),
                 due_before: None,
                 due_after: None,
+                ..Default::default()
             };
 
             let params = build_query_params(&filter);
@@ -87,10 +101,11 @@ mod frontend_logic_tests {
         #[test]
         fn test_priority_filter_param() {
             let filter = TaskFilter {
-                status: None,
-                priority: Some(TaskPriority::High),
+                status: Vec::new(),
+                priority: vec![TaskPriority::High],
                 due_before: None,
                 due_after: None,
+                ..Default::default()
             };
 
             let params = build_query_params(&filter);
@@ -100,10 +115,11 @@ mod frontend_logic_tests {
         #[test]
         fn test_multiple_filter_params() {
             let filter = TaskFilter {
-                status: Some(TaskStatus::Todo),
-                priority: Some(TaskPriority::Medium),
+                status: vec![TaskStatus::new("Todo")],
+                priority: vec![TaskPriority::Medium],
                 due_before: None,
                 due_after: None,
+                ..Default::default()
             };
 
             let params = build_query_params(&filter);
@@ -118,10 +134,11 @@ mod frontend_logic_tests {
             let due_after = Utc::now() - chrono::Duration::days(1);
 
             let filter = TaskFilter {
-                status: None,
-                priority: None,
+                status: Vec::new(),
+                priority: Vec::new(),
                 due_before: Some(due_before),
                 due_after: Some(due_after),
+                ..Default::default()
             };
 
             let params = build_query_params(&filter);
@@ -136,10 +153,11 @@ mod frontend_logic_tests {
             let due_after = Utc::now() - chrono::Duration::days(1);
 
             let filter = TaskFilter {
-                status: Some(TaskStatus::Completed),
-                priority: Some(TaskPriority::Urgent),
+                status: vec![TaskStatus::new("Completed")],
+                priority: vec![TaskPriority::Urgent],
                 due_before: Some(due_before),
                 due_after: Some(due_after),
+                ..Default::default()
             };
 
             let params = build_query_params(&filter);
@@ -150,16 +168,61 @@ mod frontend_logic_tests {
             assert_eq!(params.matches("&").count(), 3); // 3 ampersands for 4 params
         }
 
+        #[test]
+        fn test_multi_value_filter_params() {
+            let filter = TaskFilter {
+                status: vec![TaskStatus::new("Todo"), TaskStatus::new("InProgress")],
+                priority: vec![TaskPriority::High, TaskPriority::Urgent],
+                ..Default::default()
+            };
+
+            let params = build_query_params(&filter);
+            assert_eq!(params, "?status=Todo,InProgress&priority=High,Urgent");
+        }
+
+        #[test]
+        fn test_status_names_are_encoded() {
+            let filter = TaskFilter {
+                status: vec![TaskStatus::new("Q&A"), TaskStatus::new("In Review")],
+                ..Default::default()
+            };
+
+            let params = build_query_params(&filter);
+            assert_eq!(params, "?status=Q%26A,In%20Review");
+        }
+
+        #[test]
+        fn test_sort_and_page_params() {
+            let sort = TaskSort::new(TaskSortField::DueDate, SortDirection::Desc);
+            let params = [format!("sort={sort}"), format!("limit={MAX_PAGE_SIZE}")].join("&");
+            assert_eq!(params, "sort=-due_date&limit=200");
+            assert_eq!(TaskSort::default().to_string(), "-created_at");
+        }
+
+        #[test]
+        fn test_search_param_encoding() {
+            let q = "\"login page\" deploy* -bug";
+            let param = format!("q={}", urlencoding::encode(q));
+            assert_eq!(param, "q=%22login%20page%22%20deploy%2A%20-bug");
+        }
+
         // Helper function to build query parameters
         fn build_query_params(filter: &TaskFilter) -> String {
             let mut params = Vec::new();
 
-            if let Some(status) = &filter.status {
-                params.push(format!("status={status:?}"));
+            if !filter.status.is_empty() {
+                let statuses: Vec<String> = filter
+                    .status
+                    .iter()
+                    .map(|s| urlencoding::encode(s.as_str()).to_string())
+                    .collect();
+                params.push(format!("status={}", statuses.join(",")));
             }
 
-            if let Some(priority) = &filter.priority {
-                params.push(format!("priority={priority:?}"));
+            if !filter.priority.is_empty() {
+                let priorities: Vec<String> =
+                    filter.priority.iter().map(|p| p.to_string()).collect();
+                params.push(format!("priority={}", priorities.join(",")));
             }
 
             if let Some(due_before) = &filter.due_before {
@@ -190,6 +253,7 @@ mod frontend_logic_tests {
                 description: Some("Test Description".to_string()),
                 priority: TaskPriority::High,
                 due_date: None,
+                ..Default::default()
             };
 
             assert_eq!(request.title, "Test Task");
@@ -205,6 +269,7 @@ mod frontend_logic_tests {
                 description: None,
                 priority: TaskPriority::Low,
                 due_date: None,
+                ..Default::default()
             };
 
             assert_eq!(request.title, "Minimal Task");
@@ -221,6 +286,7 @@ mod frontend_logic_tests {
                 description: None,
                 priority: TaskPriority::Medium,
                 due_date: Some(due_date),
+                ..Default::default()
             };
 
             assert_eq!(request.title, "Task with Due Date");
@@ -231,53 +297,57 @@ mod frontend_logic_tests {
         #[test]
         fn test_update_task_request_partial() {
             let request = UpdateTaskRequest {
-                title: Some("Updated Title".to_string()),
-                description: None,
-                status: Some(TaskStatus::InProgress),
-                priority: None,
-                due_date: None,
+                title: Patch::Value("Updated Title".to_string()),
+                status: Patch::Value(TaskStatus::new("InProgress")),
+                ..Default::default()
             };
 
-            assert_eq!(request.title, Some("Updated Title".to_string()));
-            assert_eq!(request.description, None);
-            assert_eq!(request.status, Some(TaskStatus::InProgress));
-            assert_eq!(request.priority, None);
-            assert_eq!(request.due_date, None);
+            assert_eq!(request.title, Patch::Value("Updated Title".to_string()));
+            assert_eq!(request.description, Patch::Absent);
+            assert_eq!(request.status, Patch::Value(TaskStatus::new("InProgress")));
+            assert_eq!(request.priority, Patch::Absent);
+            assert_eq!(request.due_date, Patch::Absent);
         }
 
         #[test]
         fn test_update_task_request_complete() {
             let due_date = Utc::now() + chrono::Duration::days(2);
             let request = UpdateTaskRequest {
-                title: Some("Complete Update".to_string()),
-                description: Some("Updated Description".to_string()),
-                status: Some(TaskStatus::Completed),
-                priority: Some(TaskPriority::Urgent),
-                due_date: Some(due_date),
+                title: Patch::Value("Complete Update".to_string()),
+                description: Patch::Value("Updated Description".to_string()),
+                status: Patch::Value(TaskStatus::new("Completed")),
+                priority: Patch::Value(TaskPriority::Urgent),
+                due_date: Patch::Value(due_date),
+                tags: Patch::Absent,
+                assignee_id: Patch::Absent,
+                project_id: Patch::Absent,
+                parent_id: Patch::Absent,
+                recurrence: Patch::Absent,
+                estimate_minutes: Patch::Absent,
+                custom_fields: Patch::Absent,
             };
 
-            assert_eq!(request.title, Some("Complete Update".to_string()));
-            assert_eq!(request.description, Some("Updated Description".to_string()));
-            assert_eq!(request.status, Some(TaskStatus::Completed));
-            assert_eq!(request.priority, Some(TaskPriority::Urgent));
-            assert_eq!(request.due_date, Some(due_date));
+            assert_eq!(request.title, Patch::Value("Complete Update".to_string()));
+            assert_eq!(
+                request.description,
+                Patch::Value("Updated Description".to_string())
+            );
+            assert_eq!(request.status, Patch::Value(TaskStatus::new("Completed")));
+            assert_eq!(request.priority, Patch::Value(TaskPriority::Urgent));
+            assert_eq!(request.due_date, Patch::Value(due_date));
         }
 
         #[test]
         fn test_update_task_request_empty() {
             let request = UpdateTaskRequest {
-                title: None,
-                description: None,
-                status: None,
-                priority: None,
-                due_date: None,
+                ..Default::default()
             };
 
-            assert_eq!(request.title, None);
-            assert_eq!(request.description, None);
-            assert_eq!(request.status, None);
-            assert_eq!(request.priority, None);
-            assert_eq!(request.due_date, None);
+            assert_eq!(request.title, Patch::Absent);
+            assert_eq!(request.description, Patch::Absent);
+            assert_eq!(request.status, Patch::Absent);
+            assert_eq!(request.priority, Patch::Absent);
+            assert_eq!(request.due_date, Patch::Absent);
         }
     }
 
@@ -322,16 +392,16 @@ mod frontend_logic_tests {
         fn test_status_transitions() {
             // Valid transitions
             assert!(is_valid_status_transition(
-                TaskStatus::Todo,
-                TaskStatus::InProgress
+                TaskStatus::new("Todo"),
+                TaskStatus::new("InProgress")
             ));
             assert!(is_valid_status_transition(
-                TaskStatus::InProgress,
-                TaskStatus::Completed
+                TaskStatus::new("InProgress"),
+                TaskStatus::new("Completed")
             ));
             assert!(is_valid_status_transition(
-                TaskStatus::InProgress,
-                TaskStatus::Todo
+                TaskStatus::new("InProgress"),
+                TaskStatus::new("Todo")
             ));
 
             // Invalid transitions (if any business rules apply)
@@ -587,6 +657,7 @@ mod frontend_logic_tests {
                 },
                 priority: state.priority,
                 due_date: state.due_date,
+                ..Default::default()
             }
         }
     }
@@ -599,25 +670,25 @@ mod frontend_logic_tests {
         fn test_optimistic_task_update() {
             let mut task = create_test_task();
             let original_status = task.status;
-            let new_status = TaskStatus::InProgress;
+            let new_status = TaskStatus::new("InProgress");
 
             // Perform optimistic update
             task.status = new_status;
 
-            assert_eq!(task.status, TaskStatus::InProgress);
+            assert_eq!(task.status, TaskStatus::new("InProgress"));
             assert_ne!(task.status, original_status);
         }
 
         #[test]
         fn test_task_list_optimistic_update() {
-            let mut tasks = vec![
-                create_test_task_with_status(TaskStatus::Todo),
-                create_test_task_with_status(TaskStatus::InProgress),
-                create_test_task_with_status(TaskStatus::Completed),
+            let mut tasks = [
+                create_test_task_with_status(TaskStatus::new("Todo")),
+                create_test_task_with_status(TaskStatus::new("InProgress")),
+                create_test_task_with_status(TaskStatus::new("Completed")),
             ];
 
             let target_id = tasks[0].id;
-            let new_status = TaskStatus::Completed;
+            let new_status = TaskStatus::new("Completed");
 
             // Find and update task optimistically (simulate UI update)
             if let Some(task) = tasks.iter_mut().find(|t| t.id == target_id) {
@@ -626,61 +697,1174 @@ mod frontend_logic_tests {
 
             // Verify optimistic update
             let updated_task = tasks.iter().find(|t| t.id == target_id).unwrap();
-            assert_eq!(updated_task.status, TaskStatus::Completed);
+            assert_eq!(updated_task.status, TaskStatus::new("Completed"));
         }
 
         #[test]
         fn test_optimistic_update_request_generation() {
-            let new_status = TaskStatus::InProgress;
+            let new_status = TaskStatus::new("InProgress");
 
             // Create update request for optimistic update
             let request = UpdateTaskRequest {
-                title: None,
-                description: None,
-                status: Some(new_status),
-                priority: None,
-                due_date: None,
+                status: Patch::Value(new_status),
+                ..Default::default()
             };
 
-            assert_eq!(request.status, Some(TaskStatus::InProgress));
-            assert!(request.title.is_none());
-            assert!(request.description.is_none());
-            assert!(request.priority.is_none());
-            assert!(request.due_date.is_none());
+            assert_eq!(request.status, Patch::Value(TaskStatus::new("InProgress")));
+            assert!(request.title.is_absent());
+            assert!(request.description.is_absent());
+            assert!(request.priority.is_absent());
+            assert!(request.due_date.is_absent());
         }
 
         #[test]
         fn test_optimistic_update_failure_revert() {
             let mut task = create_test_task();
             let original_status = task.status;
-            let attempted_status = TaskStatus::Completed;
+            let attempted_status = TaskStatus::new("Completed");
 
             // Simulate optimistic update
             task.status = attempted_status;
-            assert_eq!(task.status, TaskStatus::Completed);
+            assert_eq!(task.status, TaskStatus::new("Completed"));
 
             // Simulate server failure - revert to original status
-            task.status = original_status;
+            task.status = original_status.clone();
             assert_eq!(task.status, original_status);
         }
 
+        #[test]
+        fn test_server_task_replaces_optimistic_copy() {
+            let mut tasks = vec![create_test_task(), create_test_task()];
+            tasks[0].status = TaskStatus::new("InProgress");
+
+            // The server echoes the task with its bumped version
+            let mut from_server = tasks[0].clone();
+            from_server.version = 2;
+            crate::logic::task_list_logic::apply_server_task(&mut tasks, from_server);
+
+            assert_eq!(tasks[0].version, 2);
+            assert_eq!(tasks[0].status, TaskStatus::new("InProgress"));
+            assert_eq!(tasks[1].version, 1);
+        }
+
         // Helper function to create test task with specific status
         fn create_test_task_with_status(status: TaskStatus) -> common::Task {
             common::Task {
                 id: Uuid::new_v4(),
                 title: "Test Task".to_string(),
                 description: Some("Test Description".to_string()),
+                status_category: category_of(&status),
                 status,
                 priority: TaskPriority::Medium,
                 due_date: None,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
+                version: 1,
+                deleted_at: None,
+                comment_count: 0,
+                search: None,
+                tags: Vec::new(),
+                assignee_id: None,
+                project_id: common::DEFAULT_PROJECT_ID,
+                parent_id: None,
+                subtask_count: 0,
+                completed_subtask_count: 0,
+                is_blocked: false,
+                recurrence: None,
+                rank: String::new(),
+                estimate_minutes: None,
+                logged_minutes: 0,
+                timer_started_at: None,
+                custom_fields: Default::default(),
             }
         }
 
         // Helper function to create basic test task
         fn create_test_task() -> common::Task {
-            create_test_task_with_status(TaskStatus::Todo)
+            create_test_task_with_status(TaskStatus::new("Todo"))
+        }
+    }
+
+    // Test the history timeline wording
+    mod task_history_tests {
+        use super::*;
+        use crate::logic::task_history_logic::describe_event;
+        use common::{TaskEvent, TaskEventKind};
+
+        fn event(field: Option<&str>, old: Option<&str>, new: Option<&str>) -> TaskEvent {
+            TaskEvent {
+                id: 1,
+                task_id: Uuid::new_v4(),
+                kind: TaskEventKind::Updated,
+                field: field.map(str::to_string),
+                old_value: old.map(str::to_string),
+                new_value: new.map(str::to_string),
+                actor: None,
+                created_at: Utc::now(),
+            }
+        }
+
+        #[test]
+        fn test_field_changes_are_described() {
+            let changed = event(Some("priority"), Some("Low"), Some("High"));
+            assert_eq!(
+                describe_event(&changed),
+                "Changed priority from Low to High"
+            );
+
+            let set = event(Some("due_date"), None, Some("2030-01-02T03:04:05+00:00"));
+            assert_eq!(describe_event(&set), "Set due date to 2030-01-02");
+
+            let estimated = event(Some("estimate_minutes"), Some("90"), Some("120"));
+            assert_eq!(
+                describe_event(&estimated),
+                "Changed estimate from 1h 30m to 2h"
+            );
+
+            let cleared = event(Some("description"), Some("Old"), None);
+            assert_eq!(describe_event(&cleared), "Cleared description");
+
+            let edited = event(Some("description"), Some("Old"), Some("New"));
+            assert_eq!(describe_event(&edited), "Edited description");
+        }
+
+        #[test]
+        fn test_assignee_changes_are_described() {
+            let sarah = Uuid::new_v4().to_string();
+            let marko = Uuid::new_v4().to_string();
+
+            let assigned = event(Some("assignee_id"), None, Some(&sarah));
+            assert_eq!(describe_event(&assigned), "Assigned the task");
+
+            let reassigned = event(Some("assignee_id"), Some(&sarah), Some(&marko));
+            assert_eq!(describe_event(&reassigned), "Reassigned the task");
+
+            let unassigned = event(Some("assignee_id"), Some(&marko), None);
+            assert_eq!(describe_event(&unassigned), "Unassigned the task");
+
+            let moved = event(
+                Some("project_id"),
+                Some(&common::DEFAULT_PROJECT_ID.to_string()),
+                Some(&sarah),
+            );
+            assert_eq!(describe_event(&moved), "Moved the task to another project");
+
+            let values = format!("{{\"{sarah}\":\"S1\"}}");
+            let custom = event(Some("custom_fields"), None, Some(&values));
+            assert_eq!(describe_event(&custom), "Changed custom fields");
+        }
+
+        #[test]
+        fn test_parent_changes_are_described() {
+            let epic = Uuid::new_v4().to_string();
+            let story = Uuid::new_v4().to_string();
+
+            let nested = event(Some("parent_id"), None, Some(&epic));
+            assert_eq!(describe_event(&nested), "Made the task a subtask");
+
+            let moved = event(Some("parent_id"), Some(&epic), Some(&story));
+            assert_eq!(describe_event(&moved), "Moved the task to another parent");
+
+            let lifted = event(Some("parent_id"), Some(&story), None);
+            assert_eq!(describe_event(&lifted), "Made the task a top-level task");
+        }
+
+        #[test]
+        fn test_recurrence_changes_are_described() {
+            let started = event(Some("recurrence"), None, Some("FREQ=WEEKLY;BYDAY=MO"));
+            assert_eq!(
+                describe_event(&started),
+                "Made the task repeat: Every week on Mon"
+            );
+
+            let changed = event(
+                Some("recurrence"),
+                Some("FREQ=WEEKLY;BYDAY=MO"),
+                Some("FREQ=DAILY;INTERVAL=2"),
+            );
+            assert_eq!(
+                describe_event(&changed),
+                "Changed how the task repeats: Every 2 days"
+            );
+
+            let stopped = event(Some("recurrence"), Some("FREQ=DAILY"), None);
+            assert_eq!(describe_event(&stopped), "Stopped the task repeating");
+        }
+
+        #[test]
+        fn test_task_events_are_described() {
+            let mut deleted = event(None, None, None);
+            deleted.kind = TaskEventKind::Deleted;
+            assert_eq!(describe_event(&deleted), "Moved the task to the trash");
+        }
+    }
+
+    // Test comment rendering and counts
+    mod comment_tests {
+        use super::*;
+        use crate::logic::comment_logic::render_markdown;
+        use crate::logic::task_list_logic::set_comment_count;
+
+        #[test]
+        fn test_markdown_is_rendered() {
+            let html = render_markdown("Ship it **today**, see [the plan](https://example.com)");
+            assert!(html.contains("<strong>today</strong>"));
+            assert!(html.contains("<a href=\"https://example.com\">the plan</a>"));
+        }
+
+        #[test]
+        fn test_markdown_cannot_inject_markup() {
+            let html =
+                render_markdown("<script>alert(1)</script>\n\nHi <b onclick=\"x()\">there</b>");
+            assert!(!html.contains("<script>"));
+            assert!(!html.contains("<b "));
+            assert!(html.contains("&lt;script&gt;"));
+
+            let html = render_markdown("[click](javascript:alert(1)) ![img](data:text/html,x)");
+            assert!(!html.contains("javascript:"));
+            assert!(!html.contains("data:"));
+        }
+
+        #[test]
+        fn test_comment_count_follows_thread() {
+            let mut tasks = vec![common::Task {
+                id: Uuid::new_v4(),
+                title: "Discussed".to_string(),
+                description: None,
+                status: TaskStatus::new("Todo"),
+                status_category: StatusCategory::Todo,
+                priority: TaskPriority::Medium,
+                due_date: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                version: 1,
+                deleted_at: None,
+                comment_count: 0,
+                search: None,
+                tags: Vec::new(),
+                assignee_id: None,
+                project_id: common::DEFAULT_PROJECT_ID,
+                parent_id: None,
+                subtask_count: 0,
+                completed_subtask_count: 0,
+                is_blocked: false,
+                recurrence: None,
+                rank: String::new(),
+                estimate_minutes: None,
+                logged_minutes: 0,
+                timer_started_at: None,
+                custom_fields: Default::default(),
+            }];
+            let id = tasks[0].id;
+
+            set_comment_count(&mut tasks, id, 3);
+            assert_eq!(tasks[0].comment_count, 3);
+            set_comment_count(&mut tasks, Uuid::new_v4(), 5);
+            assert_eq!(tasks[0].comment_count, 3);
+        }
+    }
+
+    // Test the navbar's tag filter autocomplete
+    mod tag_filter_tests {
+        use super::*;
+        use crate::logic::tag_logic::{pick_tag, tag_suggestions};
+        use common::Tag;
+
+        fn tags(names: &[&str]) -> Vec<Tag> {
+            names
+                .iter()
+                .map(|name| Tag {
+                    id: Uuid::new_v4(),
+                    name: name.to_string(),
+                    color: "#3b82f6".to_string(),
+                })
+                .collect()
+        }
+
+        #[test]
+        fn test_suggestions_skip_picked_tags() {
+            let tags = tags(&["backend", "Bug", "ui"]);
+            let picked = vec!["ui".to_string()];
+
+            let names = |input: &str| -> Vec<String> {
+                tag_suggestions(&tags, &picked, input)
+                    .into_iter()
+                    .map(|tag| tag.name.clone())
+                    .collect()
+            };
+            assert_eq!(names(""), ["backend", "Bug"]);
+            assert_eq!(names("b"), ["backend", "Bug"]);
+            assert_eq!(names("BU"), ["Bug"]);
+            assert!(names("u").iter().all(|name| name != "ui"));
+        }
+
+        #[test]
+        fn test_only_existing_tags_are_picked() {
+            let tags = tags(&["bug", "ui"]);
+            let picked = vec!["ui".to_string()];
+
+            assert_eq!(pick_tag(&tags, &picked, " bug "), Some("bug".to_string()));
+            assert_eq!(pick_tag(&tags, &picked, "bu"), None);
+            assert_eq!(pick_tag(&tags, &picked, "ui"), None);
+        }
+    }
+
+    // Test project board routing
+    mod project_tests {
+        use super::*;
+        use crate::logic::project_logic::{board_path, project_from_param};
+        use common::DEFAULT_PROJECT_ID;
+
+        #[test]
+        fn test_board_route_names_the_project() {
+            let id = Uuid::new_v4();
+            assert_eq!(board_path(id), format!("/projects/{id}/board"));
+            assert_eq!(project_from_param(Some(&id.to_string())), id);
+        }
+
+        #[test]
+        fn test_missing_or_malformed_project_falls_back_to_default() {
+            assert_eq!(project_from_param(None), DEFAULT_PROJECT_ID);
+            assert_eq!(project_from_param(Some("not-a-uuid")), DEFAULT_PROJECT_ID);
+        }
+    }
+
+    // Test when a move needs a blocked task's blockers overridden
+    mod dependency_tests {
+        use super::*;
+        use crate::logic::dependency_logic::needs_blocker_override;
+
+        fn blocked_task(status: TaskStatus) -> common::Task {
+            common::Task {
+                id: Uuid::new_v4(),
+                title: "Client".to_string(),
+                description: None,
+                status_category: category_of(&status),
+                status,
+                priority: TaskPriority::Medium,
+                due_date: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                version: 1,
+                deleted_at: None,
+                comment_count: 0,
+                search: None,
+                tags: Vec::new(),
+                assignee_id: None,
+                project_id: common::DEFAULT_PROJECT_ID,
+                parent_id: None,
+                subtask_count: 0,
+                completed_subtask_count: 0,
+                is_blocked: true,
+                recurrence: None,
+                rank: String::new(),
+                estimate_minutes: None,
+                logged_minutes: 0,
+                timer_started_at: None,
+                custom_fields: Default::default(),
+            }
+        }
+
+        /// The state called `name` in the workflow every project starts with.
+        fn state(name: &str) -> WorkflowState {
+            default_workflow()
+                .into_iter()
+                .find(|state| state.name == TaskStatus::new(name))
+                .unwrap()
+        }
+
+        #[test]
+        fn test_starting_or_completing_a_blocked_task_needs_override() {
+            let task = blocked_task(TaskStatus::new("Todo"));
+            assert!(needs_blocker_override(&task, &state("InProgress")));
+            assert!(needs_blocker_override(&task, &state("Completed")));
+            assert!(!needs_blocker_override(&task, &state("Backlog")));
+
+            // Custom states count by their category
+            let review = WorkflowState {
+                name: TaskStatus::new("Review"),
+                category: StatusCategory::Doing,
+                wip_limit: None,
+                next: None,
+                requires: Vec::new(),
+            };
+            assert!(needs_blocker_override(&task, &review));
+        }
+
+        #[test]
+        fn test_unblocked_or_unchanged_tasks_move_freely() {
+            let started = blocked_task(TaskStatus::new("InProgress"));
+            assert!(!needs_blocker_override(&started, &state("InProgress")));
+            assert!(needs_blocker_override(&started, &state("Completed")));
+
+            let mut task = blocked_task(TaskStatus::new("Todo"));
+            task.is_blocked = false;
+            assert!(!needs_blocker_override(&task, &state("Completed")));
+        }
+    }
+
+    // Test the subtask roll-up shown on cards
+    mod subtask_tests {
+        use super::*;
+        use crate::logic::subtask_logic::{progress_label, progress_percent};
+
+        fn task_with_subtasks(completed: i64, total: i64) -> common::Task {
+            common::Task {
+                id: Uuid::new_v4(),
+                title: "Launch".to_string(),
+                description: None,
+                status: TaskStatus::new("InProgress"),
+                status_category: StatusCategory::Doing,
+                priority: TaskPriority::Medium,
+                due_date: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                version: 1,
+                deleted_at: None,
+                comment_count: 0,
+                search: None,
+                tags: Vec::new(),
+                assignee_id: None,
+                project_id: common::DEFAULT_PROJECT_ID,
+                parent_id: None,
+                subtask_count: total,
+                completed_subtask_count: completed,
+                is_blocked: false,
+                recurrence: None,
+                rank: String::new(),
+                estimate_minutes: None,
+                logged_minutes: 0,
+                timer_started_at: None,
+                custom_fields: Default::default(),
+            }
+        }
+
+        #[test]
+        fn test_progress_counts_completed_subtasks() {
+            let task = task_with_subtasks(3, 5);
+            assert_eq!(progress_label(&task).as_deref(), Some("3/5"));
+            assert_eq!(progress_percent(&task), 60);
+            assert_eq!(progress_percent(&task_with_subtasks(2, 2)), 100);
+        }
+
+        #[test]
+        fn test_tasks_without_subtasks_show_no_progress() {
+            let task = task_with_subtasks(0, 0);
+            assert_eq!(progress_label(&task), None);
+            assert_eq!(progress_percent(&task), 0);
+        }
+    }
+
+    // Test repeat labels and reloading after a recurring task completes
+    mod recurrence_tests {
+        use super::*;
+        use crate::logic::recurrence_logic::{describe_rule, recurrence_label, spawned_occurrence};
+
+        fn recurring_task(status: TaskStatus, recurrence: Option<&str>) -> common::Task {
+            common::Task {
+                id: Uuid::new_v4(),
+                title: "Water plants".to_string(),
+                description: None,
+                status_category: category_of(&status),
+                status,
+                priority: TaskPriority::Medium,
+                due_date: Some(Utc::now()),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                version: 1,
+                deleted_at: None,
+                comment_count: 0,
+                search: None,
+                tags: Vec::new(),
+                assignee_id: None,
+                project_id: common::DEFAULT_PROJECT_ID,
+                parent_id: None,
+                subtask_count: 0,
+                completed_subtask_count: 0,
+                is_blocked: false,
+                recurrence: recurrence.map(str::to_string),
+                rank: String::new(),
+                estimate_minutes: None,
+                logged_minutes: 0,
+                timer_started_at: None,
+                custom_fields: Default::default(),
+            }
+        }
+
+        #[test]
+        fn test_repeat_label_describes_the_rule() {
+            let task = recurring_task(TaskStatus::new("Todo"), Some("FREQ=MONTHLY;COUNT=3"));
+            assert_eq!(
+                recurrence_label(&task).as_deref(),
+                Some("Every month, 3 more")
+            );
+            assert_eq!(
+                recurrence_label(&recurring_task(TaskStatus::new("Todo"), None)),
+                None
+            );
+            assert_eq!(describe_rule("FREQ=SECONDLY"), "FREQ=SECONDLY");
+        }
+
+        #[test]
+        fn test_completing_a_recurring_task_spawns_an_occurrence() {
+            // The board has already moved the card when the server answers
+            let before = recurring_task(TaskStatus::new("Completed"), Some("FREQ=DAILY"));
+            let after = recurring_task(TaskStatus::new("Completed"), None);
+            assert!(spawned_occurrence(&before, &after));
+
+            let last = recurring_task(TaskStatus::new("Completed"), Some("FREQ=DAILY;COUNT=1"));
+            assert!(!spawned_occurrence(&last, &last));
+            let plain = recurring_task(TaskStatus::new("Completed"), None);
+            assert!(!spawned_occurrence(&plain, &after));
+        }
+    }
+
+    // Test the board columns a project's workflow gives
+    mod workflow_tests {
+        use super::*;
+        use crate::logic::task_list_logic::filter_and_group_tasks;
+        use crate::logic::workflow_logic::{
+            check_drop, move_choices, over_wip_limit, state_label, wip_label,
+        };
+        use common::transition::TaskField;
+
+        fn support_workflow() -> Workflow {
+            let state = |name: &str, category| WorkflowState {
+                name: TaskStatus::new(name),
+                category,
+                wip_limit: None,
+                next: None,
+                requires: Vec::new(),
+            };
+            Workflow {
+                project_id: Uuid::new_v4(),
+                states: vec![
+                    state("Triage", StatusCategory::Todo),
+                    state("Fixing", StatusCategory::Doing),
+                    state("Shipped", StatusCategory::Done),
+                ],
+                priority_rules: Vec::new(),
+            }
+        }
+
+        fn triage_task() -> common::Task {
+            common::Task {
+                id: Uuid::new_v4(),
+                title: "Crash report".to_string(),
+                description: None,
+                status: TaskStatus::new("Triage"),
+                status_category: StatusCategory::Todo,
+                priority: TaskPriority::Medium,
+                due_date: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                version: 1,
+                deleted_at: None,
+                comment_count: 0,
+                search: None,
+                tags: Vec::new(),
+                assignee_id: None,
+                project_id: common::DEFAULT_PROJECT_ID,
+                parent_id: None,
+                subtask_count: 0,
+                completed_subtask_count: 0,
+                is_blocked: false,
+                recurrence: None,
+                rank: String::new(),
+                estimate_minutes: None,
+                logged_minutes: 0,
+                timer_started_at: None,
+                custom_fields: Default::default(),
+            }
+        }
+
+        #[test]
+        fn test_every_state_gets_a_column() {
+            let workflow = support_workflow();
+            let grouped = filter_and_group_tasks(&[], None, &workflow.states);
+            assert_eq!(grouped.len(), 3);
+            assert!(grouped[&TaskStatus::new("Shipped")].is_empty());
+
+            // Backlog is a column like any other
+            let grouped = filter_and_group_tasks(&[], None, &default_workflow());
+            assert!(grouped.contains_key(&TaskStatus::new("Backlog")));
+        }
+
+        #[test]
+        fn test_cards_offer_only_allowed_moves() {
+            let mut workflow = support_workflow();
+            workflow.states[0].next = Some(vec![TaskStatus::new("Fixing")]);
+            workflow.states[1].requires = vec![TaskField::Description];
+            let mut task = triage_task();
+
+            // Fixing needs a description, so nothing is offered yet
+            assert!(move_choices(&workflow, &task).is_empty());
+            assert_eq!(
+                check_drop(&workflow, &task, &workflow.states[2]),
+                Err("Cannot move a task from Triage to Shipped".to_string())
+            );
+            assert_eq!(
+                check_drop(&workflow, &task, &workflow.states[1]),
+                Err("Moving to Fixing requires: description".to_string())
+            );
+
+            task.description = Some("Crashes on start".to_string());
+            assert_eq!(
+                move_choices(&workflow, &task),
+                vec![(TaskStatus::new("Fixing"), "Fixing".to_string())]
+            );
+            assert!(check_drop(&workflow, &task, &workflow.states[0]).is_ok());
+        }
+
+        #[test]
+        fn test_state_names_are_spaced_for_display() {
+            assert_eq!(state_label(&TaskStatus::new("InProgress")), "In Progress");
+            assert_eq!(state_label(&TaskStatus::new("QA")), "QA");
+        }
+
+        #[test]
+        fn test_column_headers_count_against_wip_limits() {
+            assert_eq!(wip_label(3, None), "3");
+            assert_eq!(wip_label(3, Some(4)), "3/4");
+
+            // Only going past the limit is flagged; a full column is fine
+            assert!(!over_wip_limit(4, Some(4)));
+            assert!(over_wip_limit(5, Some(4)));
+            assert!(!over_wip_limit(50, None));
+        }
+    }
+
+    // Test where dropped cards land and how columns are ordered
+    mod card_order_tests {
+        use super::*;
+        use crate::logic::task_list_logic::{
+            apply_move, drop_neighbours, filter_and_group_tasks, DropTarget,
+        };
+
+        fn ranked_task(rank: &str, priority: TaskPriority) -> common::Task {
+            common::Task {
+                id: Uuid::new_v4(),
+                title: format!("Card {rank}"),
+                description: None,
+                status: TaskStatus::new("Todo"),
+                status_category: StatusCategory::Todo,
+                priority,
+                due_date: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                version: 1,
+                deleted_at: None,
+                comment_count: 0,
+                search: None,
+                tags: Vec::new(),
+                assignee_id: None,
+                project_id: common::DEFAULT_PROJECT_ID,
+                parent_id: None,
+                subtask_count: 0,
+                completed_subtask_count: 0,
+                is_blocked: false,
+                recurrence: None,
+                rank: rank.to_string(),
+                estimate_minutes: None,
+                logged_minutes: 0,
+                timer_started_at: None,
+                custom_fields: Default::default(),
+            }
+        }
+
+        fn column() -> Vec<common::Task> {
+            ["A", "B", "C"]
+                .into_iter()
+                .map(|rank| ranked_task(rank, TaskPriority::Medium))
+                .collect()
+        }
+
+        #[test]
+        fn test_columns_follow_rank_not_priority() {
+            let tasks = [
+                ranked_task("C", TaskPriority::Urgent),
+                ranked_task("A", TaskPriority::Low),
+                ranked_task("B", TaskPriority::High),
+            ];
+            let grouped = filter_and_group_tasks(&tasks, None, &default_workflow());
+            let ranks: Vec<&str> = grouped[&TaskStatus::new("Todo")]
+                .iter()
+                .map(|task| task.rank.as_str())
+                .collect();
+            assert_eq!(ranks, ["A", "B", "C"]);
+        }
+
+        #[test]
+        fn test_drop_lands_beside_the_target_card() {
+            let column = column();
+            let dragged = Uuid::new_v4();
+            let (a, b, c) = (column[0].id, column[1].id, column[2].id);
+
+            let over = |task_id, below| Some(DropTarget { task_id, below });
+            assert_eq!(
+                drop_neighbours(&column, dragged, over(b, false)),
+                Some((Some(a), Some(b)))
+            );
+            assert_eq!(
+                drop_neighbours(&column, dragged, over(b, true)),
+                Some((Some(b), Some(c)))
+            );
+            assert_eq!(
+                drop_neighbours(&column, dragged, over(a, false)),
+                Some((None, Some(a)))
+            );
+
+            // Without a card of this column under it, the card goes last
+            assert_eq!(
+                drop_neighbours(&column, dragged, over(Uuid::new_v4(), false)),
+                Some((Some(c), None))
+            );
+            assert_eq!(drop_neighbours(&[], dragged, None), Some((None, None)));
+        }
+
+        #[test]
+        fn test_drop_in_place_is_ignored() {
+            let column = column();
+            let (a, b, c) = (column[0].id, column[1].id, column[2].id);
+            let over = |task_id, below| Some(DropTarget { task_id, below });
+
+            assert_eq!(drop_neighbours(&column, b, over(b, true)), None);
+            assert_eq!(drop_neighbours(&column, b, over(a, true)), None);
+            assert_eq!(drop_neighbours(&column, b, over(c, false)), None);
+            assert_eq!(drop_neighbours(&column, c, None), None);
+            assert_eq!(
+                drop_neighbours(&column, a, over(c, true)),
+                Some((Some(c), None))
+            );
+        }
+
+        #[test]
+        fn test_optimistic_move_ranks_between_neighbours() {
+            let mut tasks = column();
+            let (a, b, c) = (tasks[0].id, tasks[1].id, tasks[2].id);
+
+            let states = default_workflow();
+            apply_move(&mut tasks, c, &states[0], Some(a), Some(b));
+            let grouped = filter_and_group_tasks(&tasks, None, &states);
+            let order: Vec<Uuid> = grouped[&TaskStatus::new("Todo")]
+                .iter()
+                .map(|task| task.id)
+                .collect();
+            assert_eq!(order, [a, c, b]);
+
+            apply_move(&mut tasks, a, &states[1], None, None);
+            let moved = tasks.iter().find(|task| task.id == a).unwrap();
+            assert_eq!(moved.status, TaskStatus::new("InProgress"));
+            assert_eq!(moved.status_category, StatusCategory::Doing);
+        }
+    }
+
+    // Test effort labels and the running timer
+    mod time_tests {
+        use super::*;
+        use crate::logic::time_logic::{
+            effort_label, format_minutes, over_estimate, parse_minutes, running_minutes,
+        };
+        use chrono::Duration;
+
+        fn task_with_effort(estimate: Option<u32>, logged: i64) -> common::Task {
+            common::Task {
+                id: Uuid::new_v4(),
+                title: "Invoice export".to_string(),
+                description: None,
+                status: TaskStatus::new("InProgress"),
+                status_category: StatusCategory::Doing,
+                priority: TaskPriority::Medium,
+                due_date: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                version: 1,
+                deleted_at: None,
+                comment_count: 0,
+                search: None,
+                tags: Vec::new(),
+                assignee_id: None,
+                project_id: common::DEFAULT_PROJECT_ID,
+                parent_id: None,
+                subtask_count: 0,
+                completed_subtask_count: 0,
+                is_blocked: false,
+                recurrence: None,
+                rank: String::new(),
+                estimate_minutes: estimate,
+                logged_minutes: logged,
+                timer_started_at: None,
+                custom_fields: Default::default(),
+            }
+        }
+
+        #[test]
+        fn test_minutes_read_as_hours_and_minutes() {
+            assert_eq!(format_minutes(0), "0m");
+            assert_eq!(format_minutes(45), "45m");
+            assert_eq!(format_minutes(120), "2h");
+            assert_eq!(format_minutes(90), "1h 30m");
+        }
+
+        #[test]
+        fn test_typed_durations_are_read_as_minutes() {
+            assert_eq!(parse_minutes("90"), Some(90));
+            assert_eq!(parse_minutes(" 1h 30m "), Some(90));
+            assert_eq!(parse_minutes("2h"), Some(120));
+            assert_eq!(parse_minutes("45m"), Some(45));
+            assert_eq!(parse_minutes(""), None);
+            assert_eq!(parse_minutes("1.5h"), None);
+            assert_eq!(parse_minutes("soon"), None);
+        }
+
+        #[test]
+        fn test_effort_compares_logged_time_to_estimate() {
+            let task = task_with_effort(Some(120), 90);
+            assert_eq!(effort_label(&task).as_deref(), Some("1h 30m / 2h"));
+            assert!(!over_estimate(&task));
+            assert!(over_estimate(&task_with_effort(Some(60), 61)));
+
+            assert_eq!(effort_label(&task_with_effort(None, 0)), None);
+            let unestimated = task_with_effort(None, 30);
+            assert_eq!(effort_label(&unestimated).as_deref(), Some("30m"));
+            assert!(!over_estimate(&unestimated));
+        }
+
+        #[test]
+        fn test_running_timer_counts_whole_minutes() {
+            let now = Utc::now();
+            let mut task = task_with_effort(None, 0);
+            assert_eq!(running_minutes(&task, now), None);
+
+            task.timer_started_at = Some(now - Duration::seconds(150));
+            assert_eq!(running_minutes(&task, now), Some(2));
+        }
+    }
+
+    mod attachment_tests {
+        use super::*;
+        use crate::logic::attachment_logic::{format_size, is_image};
+
+        fn attachment(content_type: &str) -> common::Attachment {
+            common::Attachment {
+                id: Uuid::new_v4(),
+                task_id: Uuid::new_v4(),
+                filename: "screenshot.png".to_string(),
+                content_type: content_type.to_string(),
+                size_bytes: 2048,
+                sha256: "a".repeat(64),
+                created_at: Utc::now(),
+            }
+        }
+
+        #[test]
+        fn test_sizes_read_in_the_largest_whole_unit() {
+            assert_eq!(format_size(0), "0 B");
+            assert_eq!(format_size(1023), "1023 B");
+            assert_eq!(format_size(1024), "1 KB");
+            assert_eq!(format_size(1536), "1.5 KB");
+            assert_eq!(format_size(10 * 1024 * 1024), "10 MB");
+            assert_eq!(format_size(5 * 1024 * 1024 * 1024 * 1024), "5120 GB");
+        }
+
+        #[test]
+        fn test_only_images_are_previewed() {
+            assert!(is_image(&attachment("image/png")));
+            assert!(!is_image(&attachment("text/plain; charset=utf-8")));
+            assert!(!is_image(&attachment("application/pdf")));
+        }
+    }
+
+    mod custom_field_tests {
+        use super::*;
+        use crate::logic::custom_field_logic::{
+            display_value, field_rows, form_values, input_text, toggle_option,
+        };
+        use common::custom_fields::{CustomField, CustomFieldType, CustomFieldValues};
+        use serde_json::json;
+
+        fn field(name: &str, field_type: CustomFieldType, options: &[&str]) -> CustomField {
+            CustomField {
+                id: Uuid::new_v4(),
+                project_id: common::DEFAULT_PROJECT_ID,
+                name: name.to_string(),
+                field_type,
+                options: options.iter().map(|option| option.to_string()).collect(),
+            }
+        }
+
+        #[test]
+        fn test_form_values_are_checked_like_the_server() {
+            let points = field("Story points", CustomFieldType::Number, &[]);
+            let customer = field("Customer", CustomFieldType::Text, &[]);
+            let fields = [points.clone(), customer.clone()];
+
+            let values: CustomFieldValues =
+                [(points.id, json!("3")), (customer.id, json!(" "))].into();
+            let checked = form_values(&fields, &values).unwrap();
+            assert_eq!(checked, [(points.id, json!(3))].into());
+
+            let values: CustomFieldValues = [(points.id, json!("lots"))].into();
+            assert_eq!(
+                form_values(&fields, &values),
+                Err("Story points must be a number".to_string())
+            );
+
+            // Values of another project's fields are left out
+            let values: CustomFieldValues = [(Uuid::new_v4(), json!("Acme"))].into();
+            assert!(form_values(&fields, &values).unwrap().is_empty());
+        }
+
+        #[test]
+        fn test_multi_select_keeps_option_order() {
+            let components = field("Components", CustomFieldType::MultiSelect, &["API", "UI"]);
+
+            let value = toggle_option(&components, None, "UI", true);
+            assert_eq!(value, json!(["UI"]));
+            let value = toggle_option(&components, Some(&value), "API", true);
+            assert_eq!(value, json!(["API", "UI"]));
+            let value = toggle_option(&components, Some(&value), "UI", false);
+            assert_eq!(value, json!(["API"]));
+        }
+
+        #[test]
+        fn test_values_are_shown_in_field_order() {
+            let severity = field("Severity", CustomFieldType::SingleSelect, &["S1"]);
+            let billable = field("Billable", CustomFieldType::Checkbox, &[]);
+            let components = field("Components", CustomFieldType::MultiSelect, &["API", "UI"]);
+            let values: CustomFieldValues = [
+                (components.id, json!(["API", "UI"])),
+                (billable.id, json!(true)),
+            ]
+            .into();
+
+            assert_eq!(
+                field_rows(&[severity, billable, components], &values),
+                [
+                    ("Billable".to_string(), "Yes".to_string()),
+                    ("Components".to_string(), "API, UI".to_string()),
+                ]
+            );
+            assert_eq!(display_value(&json!(2.5)), "2.5");
+            assert_eq!(input_text(Some(&json!(8))), "8");
+            assert_eq!(input_text(None), "");
+        }
+    }
+
+    mod saved_view_tests {
+        use super::*;
+        use crate::logic::saved_view_logic::{
+            board_from_view, existing_view, order_by_lane, sort_cards, toggle_column, view_project,
+            view_request, visible_states, BoardView,
+        };
+        use chrono::{Duration, TimeZone};
+        use common::{
+            saved_views::{SavedView, ViewGrouping},
+            Member, Task,
+        };
+
+        fn card(title: &str, priority: TaskPriority) -> Task {
+            Task {
+                id: Uuid::new_v4(),
+                title: title.to_string(),
+                description: None,
+                status: TaskStatus::new("Todo"),
+                status_category: StatusCategory::Todo,
+                priority,
+                due_date: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                version: 1,
+                deleted_at: None,
+                comment_count: 0,
+                search: None,
+                tags: Vec::new(),
+                assignee_id: None,
+                project_id: common::DEFAULT_PROJECT_ID,
+                parent_id: None,
+                subtask_count: 0,
+                completed_subtask_count: 0,
+                is_blocked: false,
+                recurrence: None,
+                rank: "m".to_string(),
+                estimate_minutes: None,
+                logged_minutes: 0,
+                timer_started_at: None,
+                custom_fields: Default::default(),
+            }
+        }
+
+        fn titles(cards: &[Task]) -> Vec<&str> {
+            cards.iter().map(|card| card.title.as_str()).collect()
+        }
+
+        #[test]
+        fn test_board_survives_a_round_trip() {
+            let project = Uuid::new_v4();
+            let board = BoardView {
+                priority: Some(TaskPriority::High),
+                search: " login page ".to_string(),
+                tags: vec!["bug".to_string()],
+                assignee: Some(Uuid::new_v4()),
+                sort: Some("-due_date".parse().unwrap()),
+                grouping: ViewGrouping::Assignee,
+                columns: vec![TaskStatus::new("Todo")],
+            };
+
+            let request = view_request(" Triage ", &board, project);
+            assert_eq!(request.name, "Triage");
+            assert_eq!(request.filter.priority, [TaskPriority::High]);
+            assert_eq!(request.filter.project, Some(project));
+
+            let view = SavedView {
+                id: Uuid::new_v4(),
+                name: request.name,
+                filter: request.filter,
+                sort: request.sort,
+                grouping: request.grouping,
+                columns: request.columns,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            };
+            assert_eq!(
+                board_from_view(&view),
+                BoardView {
+                    search: "login page".to_string(),
+                    ..board
+                }
+            );
+            assert_eq!(view_project(&view), project);
+            let views = [view];
+            assert_eq!(existing_view(&views, "Triage "), Some(views[0].id));
+            assert_eq!(existing_view(&views, "triage"), None);
+
+            assert_eq!(
+                views[0].filter.query.as_ref().map(ToString::to_string),
+                Some("login page".to_string())
+            );
+
+            // An empty search is not saved
+            let request = view_request("All", &BoardView::default(), project);
+            assert_eq!(request.filter.q, None);
+            assert_eq!(request.filter.query, None);
+        }
+
+        #[test]
+        fn test_hiding_and_showing_columns() {
+            let states = default_workflow();
+            let todo = TaskStatus::new("Todo");
+            let done = TaskStatus::new("Completed");
+
+            let columns = toggle_column(&states, &[], &done, false);
+            assert!(!columns.contains(&done));
+            assert_eq!(columns.len(), states.len() - 1);
+            let shown = visible_states(&states, &columns);
+            assert!(shown.iter().all(|state| state.name != done));
+
+            let columns = toggle_column(&states, &columns, &todo, false);
+            assert_eq!(visible_states(&states, &columns).len(), states.len() - 2);
+            let columns = toggle_column(&states, &columns, &todo, true);
+            let columns = toggle_column(&states, &columns, &done, true);
+            assert!(columns.is_empty());
+            assert_eq!(visible_states(&states, &columns).len(), states.len());
+        }
+
+        #[test]
+        fn test_due_date_sort_puts_undated_cards_last() {
+            let day = |day| Some(Utc.with_ymd_and_hms(2026, 11, day, 0, 0, 0).unwrap());
+            let mut cards = vec![
+                card("Undated", TaskPriority::Low),
+                card("Later", TaskPriority::Low),
+                card("Sooner", TaskPriority::Low),
+            ];
+            cards[1].due_date = day(20);
+            cards[2].due_date = day(2);
+
+            sort_cards(&mut cards, "due_date".parse().unwrap());
+            assert_eq!(titles(&cards), ["Sooner", "Later", "Undated"]);
+            sort_cards(&mut cards, "-due_date".parse().unwrap());
+            assert_eq!(titles(&cards), ["Later", "Sooner", "Undated"]);
+
+            cards[0].created_at -= Duration::days(1);
+            sort_cards(&mut cards, "-created_at".parse().unwrap());
+            assert_eq!(titles(&cards)[2], "Later");
+        }
+
+        #[test]
+        fn test_lanes_keep_the_order_within_them() {
+            let ada = Member {
+                id: Uuid::new_v4(),
+                name: "Ada".to_string(),
+            };
+            let mut cards = vec![
+                card("Typo", TaskPriority::Low),
+                card("Outage", TaskPriority::Urgent),
+                card("Crash", TaskPriority::Urgent),
+                card("Docs", TaskPriority::Medium),
+            ];
+            cards[2].assignee_id = Some(ada.id);
+
+            order_by_lane(&mut cards, ViewGrouping::Priority, &[]);
+            assert_eq!(titles(&cards), ["Outage", "Crash", "Docs", "Typo"]);
+
+            order_by_lane(&mut cards, ViewGrouping::Assignee, &[ada]);
+            assert_eq!(titles(&cards), ["Crash", "Outage", "Docs", "Typo"]);
+
+            // Without lanes the cards stay as they are
+            order_by_lane(&mut cards, ViewGrouping::None, &[]);
+            assert_eq!(titles(&cards), ["Crash", "Outage", "Docs", "Typo"]);
+        }
+    }
+
+    mod query_tests {
+        use super::*;
+        use crate::logic::query_logic::{search_filter, search_text, split_at_error};
+        use common::query::TaskQuery;
+
+        #[test]
+        fn test_search_box_text_becomes_a_query() {
+            let filter = search_filter(" tag:bug login ");
+            assert_eq!(filter.q, None);
+            assert_eq!(
+                filter.query,
+                Some("tag:bug login".parse::<TaskQuery>().unwrap())
+            );
+            assert_eq!(search_text(&filter), "tag:bug login");
+
+            assert_eq!(search_filter("  "), TaskFilter::default());
+            assert_eq!(search_text(&TaskFilter::default()), "");
+        }
+
+        #[test]
+        fn test_old_searches_still_search() {
+            // A view saved before queries may hold text that is no query
+            let filter = TaskFilter {
+                q: Some("log\"in".to_string()),
+                ..Default::default()
+            };
+            assert_eq!(search_text(&filter), "log\"in");
+            assert_eq!(search_filter(&search_text(&filter)), filter);
+        }
+
+        #[test]
+        fn test_errors_split_out_the_offending_word() {
+            let text = "tag:bug status>Todo login";
+            let err = text.parse::<TaskQuery>().unwrap_err();
+            assert_eq!(
+                split_at_error(text, &err),
+                (
+                    "tag:bug ".to_string(),
+                    "status>Todo".to_string(),
+                    " login".to_string()
+                )
+            );
+
+            let text = "café priority:";
+            let err = text.parse::<TaskQuery>().unwrap_err();
+            assert_eq!(
+                split_at_error(text, &err),
+                ("café priority:".to_string(), String::new(), String::new())
+            );
         }
     }
 }